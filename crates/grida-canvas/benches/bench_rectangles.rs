@@ -19,6 +19,8 @@ fn create_rectangles(count: usize, with_effects: bool) -> Scene {
                 id: id.clone(),
                 name: format!("Rectangle {}", i),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: AffineTransform::identity(),
             size: Size {
@@ -26,6 +28,7 @@ fn create_rectangles(count: usize, with_effects: bool) -> Scene {
                 height: 100.0,
             },
             corner_radius: RectangularCornerRadius::zero(),
+            corner_smoothing: 0.0,
             fill: Paint::Solid(SolidPaint {
                 color: Color(255, 0, 0, 255),
                 opacity: 1.0,
@@ -37,18 +40,21 @@ fn create_rectangles(count: usize, with_effects: bool) -> Scene {
             stroke_width: 1.0,
             stroke_align: StrokeAlign::Inside,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
-            effect: if with_effects {
-                Some(FilterEffect::DropShadow(FeDropShadow {
+            effects: if with_effects {
+                vec![FilterEffect::DropShadow(FeDropShadow {
                     dx: 2.0,
                     dy: 2.0,
                     blur: 4.0,
+                    spread: 0.0,
                     color: Color(0, 0, 0, 128),
-                }))
+                })]
             } else {
-                None
+                vec![]
             },
+            trim: None,
         };
 
         repository.insert(Node::Rectangle(rect));
@@ -60,6 +66,8 @@ fn create_rectangles(count: usize, with_effects: bool) -> Scene {
             id: "root".to_string(),
             name: "Root Group".to_string(),
             active: true,
+            locked: false,
+            render_order: None,
         },
         transform: AffineTransform::identity(),
         children: ids.clone(),
@@ -70,12 +78,9 @@ fn create_rectangles(count: usize, with_effects: bool) -> Scene {
     repository.insert(Node::Group(root_group));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Test Scene".to_string(),
-        transform: AffineTransform::identity(),
         children: vec!["root".to_string()],
         nodes: repository,
-        background_color: None,
+        ..Scene::new("scene", "Test Scene")
     }
 }
 
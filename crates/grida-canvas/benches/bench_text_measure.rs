@@ -0,0 +1,63 @@
+use cg::cache::text_measure::TextMeasureCache;
+use cg::node::schema::*;
+use cg::runtime::repository::FontRepository;
+use cg::text::measure::measure_text;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn style() -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: vec!["Geist".to_string()],
+        font_size: 14.0,
+        font_weight: FontWeight::new(400),
+        italic: false,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
+    }
+}
+
+/// A table column where most cells repeat a handful of values.
+fn cells() -> Vec<String> {
+    let values = ["Pending", "Approved", "Rejected", "In review"];
+    (0..1000)
+        .map(|i| values[i % values.len()].to_string())
+        .collect()
+}
+
+fn bench_text_measure(c: &mut Criterion) {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    let style = style();
+    let cells = cells();
+
+    let mut group = c.benchmark_group("text_measure");
+
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            for text in &cells {
+                black_box(measure_text(text, &style, 120.0, &fonts));
+            }
+        })
+    });
+
+    group.bench_function("cached", |b| {
+        let mut cache = TextMeasureCache::new();
+        b.iter(|| {
+            for text in &cells {
+                black_box(cache.measure(text, &style, 120.0, &fonts));
+            }
+        });
+        // only the distinct values are ever laid out
+        assert_eq!(cache.misses(), 4);
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_text_measure);
+criterion_main!(benches);
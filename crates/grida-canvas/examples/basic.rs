@@ -22,12 +22,13 @@ async fn demo_basic() -> Scene {
     };
     image_node.corner_radius = RectangularCornerRadius::all(20.0);
     image_node.stroke_width = 2.0;
-    image_node.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    image_node.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 4.0,
         dy: 4.0,
         blur: 8.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 77),
-    }));
+    })];
     image_node._ref = demo_image_id.to_string();
 
     // Create a test rectangle node with linear gradient
@@ -44,12 +45,13 @@ async fn demo_basic() -> Scene {
         opacity: 1.0,
     });
     rect_node.stroke_width = 2.0;
-    rect_node.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    rect_node.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 4.0,
         dy: 4.0,
         blur: 8.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 77),
-    }));
+    })];
 
     // Create a test ellipse node with radial gradient and a visible stroke
     let mut ellipse_node = nf.create_ellipse_node();
@@ -77,6 +79,7 @@ async fn demo_basic() -> Scene {
             },
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     ellipse_node.stroke_width = 6.0;
 
@@ -134,13 +137,14 @@ async fn demo_basic() -> Scene {
     text_span_node.text = "Grida Canvas SKIA Bindings Backend".to_string();
     text_span_node.text_style = TextStyle {
         text_decoration: TextDecoration::LineThrough,
-        font_family: font_caveat_family.clone(),
+        font_families: vec![font_caveat_family.clone()],
         font_size: 32.0,
         font_weight: FontWeight::new(900),
         italic: false,
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     text_span_node.text_align = TextAlign::Center;
     text_span_node.text_align_vertical = TextAlignVertical::Center;
@@ -223,12 +227,10 @@ async fn demo_basic() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Demo")
     }
 }
 
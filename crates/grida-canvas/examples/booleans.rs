@@ -71,6 +71,8 @@ async fn demo_booleans() -> Scene {
                 id: "bool_union_1".to_string(),
                 name: "Union Operation".to_string(),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: AffineTransform::new(start_x + spacing * 2.0, y_offset, 0.0),
             op: BooleanPathOperation::Union,
@@ -86,9 +88,10 @@ async fn demo_booleans() -> Scene {
             stroke_width: 2.0,
             stroke_align: StrokeAlign::Center,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
         };
 
         // Collect IDs before moving nodes
@@ -154,6 +157,8 @@ async fn demo_booleans() -> Scene {
                 id: "bool_intersection_1".to_string(),
                 name: "Intersection Operation".to_string(),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: AffineTransform::new(start_x + spacing * 2.0, y_offset, 0.0),
             op: BooleanPathOperation::Intersection,
@@ -169,9 +174,10 @@ async fn demo_booleans() -> Scene {
             stroke_width: 2.0,
             stroke_align: StrokeAlign::Center,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
         };
 
         // Collect IDs before moving nodes
@@ -237,6 +243,8 @@ async fn demo_booleans() -> Scene {
                 id: "bool_difference_1".to_string(),
                 name: "Difference Operation".to_string(),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: AffineTransform::new(start_x + spacing * 2.0, y_offset, 0.0),
             op: BooleanPathOperation::Difference,
@@ -252,9 +260,10 @@ async fn demo_booleans() -> Scene {
             stroke_width: 2.0,
             stroke_align: StrokeAlign::Center,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
         };
 
         // Collect IDs before moving nodes
@@ -320,6 +329,8 @@ async fn demo_booleans() -> Scene {
                 id: "bool_xor_1".to_string(),
                 name: "XOR Operation".to_string(),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: AffineTransform::new(start_x + spacing * 2.0, y_offset, 0.0),
             op: BooleanPathOperation::Xor,
@@ -335,9 +346,10 @@ async fn demo_booleans() -> Scene {
             stroke_width: 2.0,
             stroke_align: StrokeAlign::Center,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: 1.0,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
         };
 
         // Collect IDs before moving nodes
@@ -359,12 +371,10 @@ async fn demo_booleans() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Boolean Operations Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Boolean Operations Demo")
     }
 }
 
@@ -50,6 +50,8 @@ fn create_static_scene() -> Scene {
             id: "root".to_string(),
             name: "Root Group".to_string(),
             active: true,
+            locked: false,
+            render_order: None,
         },
         transform: AffineTransform::identity(),
         children: ids,
@@ -60,12 +62,10 @@ fn create_static_scene() -> Scene {
     repository.insert(Node::Group(root_group));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Test Scene".to_string(),
-        transform: AffineTransform::identity(),
         children: vec!["root".to_string()],
         nodes: repository,
         background_color: Some(Color(255, 255, 255, 255)),
+        ..Scene::new("scene", "Test Scene")
     }
 }
 
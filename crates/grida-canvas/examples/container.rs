@@ -25,12 +25,13 @@ async fn demo_clip() -> Scene {
         color: Color(200, 50, 50, 255), // Darker red
         opacity: 1.0,
     }));
-    container.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    container.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 0.0,
         dy: 0.0,
         blur: 10.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 255),
-    }));
+    })];
     container.clip = true;
     container.stroke_width = 2.0;
 
@@ -63,12 +64,9 @@ async fn demo_clip() -> Scene {
     repository.insert(Node::Container(container));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Simple Container Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![container_id],
         nodes: repository,
-        background_color: None,
+        ..Scene::new("scene", "Simple Container Demo")
     }
 }
 
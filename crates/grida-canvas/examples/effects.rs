@@ -37,12 +37,13 @@ async fn demo_effects() -> Scene {
                 color: Color(255, 255, 255, 255), // White
                 opacity: 1.0,
             });
-            rect.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+            rect.effects = vec![FilterEffect::DropShadow(FeDropShadow {
                 dx: 4.0,
                 dy: 4.0,
                 blur: 4.0 * (i + 1) as f32,
+                spread: 0.0,
                 color: Color(0, 0, 0, 128),
-            }));
+            })];
             all_effect_ids.push(rect.base.id.clone());
             repository.insert(Node::Rectangle(rect));
         } else {
@@ -59,12 +60,13 @@ async fn demo_effects() -> Scene {
                 color: Color(255, 255, 255, 255), // White
                 opacity: 1.0,
             });
-            polygon.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+            polygon.effects = vec![FilterEffect::DropShadow(FeDropShadow {
                 dx: 4.0,
                 dy: 4.0,
                 blur: 4.0 * (i + 1) as f32,
+                spread: 0.0,
                 color: Color(0, 0, 0, 128),
-            }));
+            })];
             all_effect_ids.push(polygon.base.id.clone());
             repository.insert(Node::RegularPolygon(polygon));
         }
@@ -86,9 +88,9 @@ async fn demo_effects() -> Scene {
                 color: Color(200, 200, 200, 255), // White
                 opacity: 1.0,
             });
-            rect.effect = Some(FilterEffect::GaussianBlur(FeGaussianBlur {
+            rect.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur {
                 radius: 4.0 * (i + 1) as f32,
-            }));
+            })];
             all_effect_ids.push(rect.base.id.clone());
             repository.insert(Node::Rectangle(rect));
         } else {
@@ -105,9 +107,9 @@ async fn demo_effects() -> Scene {
                 color: Color(200, 200, 200, 255), // White
                 opacity: 1.0,
             });
-            polygon.effect = Some(FilterEffect::GaussianBlur(FeGaussianBlur {
+            polygon.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur {
                 radius: 4.0 * (i + 1) as f32,
-            }));
+            })];
             all_effect_ids.push(polygon.base.id.clone());
             repository.insert(Node::RegularPolygon(polygon));
         }
@@ -139,6 +141,7 @@ async fn demo_effects() -> Scene {
             }, // Yellow
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     let vivid_gradient_rect_id = vivid_gradient_rect.base.id.clone();
     repository.insert(Node::Rectangle(vivid_gradient_rect));
@@ -158,9 +161,9 @@ async fn demo_effects() -> Scene {
                 color: Color(255, 255, 255, 128), // Semi-transparent white
                 opacity: 1.0,
             });
-            blur_rect.effect = Some(FilterEffect::BackdropBlur(FeBackdropBlur {
+            blur_rect.effects = vec![FilterEffect::BackdropBlur(FeBackdropBlur {
                 radius: 8.0 * (i + 1) as f32,
-            }));
+            })];
             all_effect_ids.push(blur_rect.base.id.clone());
             repository.insert(Node::Rectangle(blur_rect));
         } else {
@@ -177,9 +180,9 @@ async fn demo_effects() -> Scene {
                 color: Color(255, 255, 255, 128), // Semi-transparent white
                 opacity: 1.0,
             });
-            blur_polygon.effect = Some(FilterEffect::BackdropBlur(FeBackdropBlur {
+            blur_polygon.effects = vec![FilterEffect::BackdropBlur(FeBackdropBlur {
                 radius: 8.0 * (i + 1) as f32,
-            }));
+            })];
             all_effect_ids.push(blur_polygon.base.id.clone());
             repository.insert(Node::RegularPolygon(blur_polygon));
         }
@@ -192,12 +195,10 @@ async fn demo_effects() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Effects Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Effects Demo")
     }
 }
 
@@ -45,6 +45,7 @@ async fn demo_gradients() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         ids.push(rect.base.id.clone());
         repository.insert(Node::Rectangle(rect));
@@ -76,6 +77,7 @@ async fn demo_gradients() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         ids.push(rect.base.id.clone());
         repository.insert(Node::Rectangle(rect));
@@ -109,6 +111,7 @@ async fn demo_gradients() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 8.0;
         ids.push(rect.base.id.clone());
@@ -145,6 +148,7 @@ async fn demo_gradients() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 8.0;
         ids.push(rect.base.id.clone());
@@ -156,12 +160,10 @@ async fn demo_gradients() -> Scene {
     repository.insert(Node::Container(root));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Gradients Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Gradients Demo")
     }
 }
 
@@ -32,6 +32,7 @@ async fn demo_image() -> Scene {
         opacity: 1.0,
         transform: AffineTransform::identity(),
         fit: BoxFit::Cover,
+        repeat: ImageRepeat::NoRepeat,
     });
     rect1.stroke = Paint::Solid(SolidPaint {
         color: Color(255, 0, 0, 255),
@@ -50,12 +51,10 @@ async fn demo_image() -> Scene {
     repository.insert(Node::Container(root));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Images Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Images Demo")
     }
 }
 
@@ -29,6 +29,7 @@ async fn demo_images() -> Scene {
         opacity: 1.0,
         transform: AffineTransform::identity(),
         fit: BoxFit::Cover,
+        repeat: ImageRepeat::NoRepeat,
     });
     rect1.stroke = Paint::Solid(SolidPaint {
         color: Color(255, 0, 0, 255),
@@ -49,12 +50,14 @@ async fn demo_images() -> Scene {
         opacity: 1.0,
         transform: AffineTransform::identity(),
         fit: BoxFit::Cover,
+        repeat: ImageRepeat::NoRepeat,
     });
     rect2.stroke = Paint::Image(ImagePaint {
         _ref: image_url.clone(),
         opacity: 1.0,
         transform: AffineTransform::identity(),
         fit: BoxFit::Cover,
+        repeat: ImageRepeat::NoRepeat,
     });
     rect2.stroke_width = 10.0;
 
@@ -76,6 +79,7 @@ async fn demo_images() -> Scene {
         opacity: 1.0,
         transform: AffineTransform::identity(),
         fit: BoxFit::Cover,
+        repeat: ImageRepeat::NoRepeat,
     });
     rect3.stroke_width = 10.0;
 
@@ -95,6 +99,7 @@ async fn demo_images() -> Scene {
             matrix: [[0.7071, -0.7071, 100.0], [0.7071, 0.7071, 0.0]],
         },
         fit: BoxFit::None,
+        repeat: ImageRepeat::NoRepeat,
     });
 
     let mut repository = NodeRepository::new();
@@ -114,12 +119,10 @@ async fn demo_images() -> Scene {
     repository.insert(Node::Container(root));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Images Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Images Demo")
     }
 }
 
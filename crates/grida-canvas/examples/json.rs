@@ -2,7 +2,6 @@ use cg::io::io_json::parse;
 use cg::node::schema::*;
 use cg::window;
 use clap::Parser;
-use math2::transform::AffineTransform;
 use std::fs;
 
 #[derive(Parser)]
@@ -15,26 +14,12 @@ struct Cli {
 async fn load_scene_from_file(file_path: &str) -> Scene {
     let file: String = fs::read_to_string(file_path).expect("failed to read file");
     let canvas_file = parse(&file).expect("failed to parse file");
-    let nodes = canvas_file.document.nodes;
-    // entry_scene_id or scenes[0]
-    let scene_id = canvas_file.document.entry_scene_id.unwrap_or(
-        canvas_file
-            .document
-            .scenes
-            .keys()
-            .next()
-            .unwrap()
-            .to_string(),
-    );
-    let scene = canvas_file.document.scenes.get(&scene_id).unwrap();
-    Scene {
-        nodes: nodes.into_iter().map(|(k, v)| (k, v.into())).collect(),
-        id: scene_id,
-        name: scene.name.clone(),
-        transform: AffineTransform::identity(),
-        children: scene.children.clone(),
-        background_color: Some(Color(230, 230, 230, 255)),
-    }
+    let mut scene = canvas_file
+        .document
+        .into_entry_scene()
+        .expect("failed to load the entry scene");
+    scene.background_color = Some(Color(230, 230, 230, 255));
+    scene
 }
 
 #[tokio::main]
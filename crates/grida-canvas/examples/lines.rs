@@ -93,6 +93,7 @@ async fn demo_lines() -> Scene {
             },
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     line_gradient.stroke_width = 6.0;
     ids.push(line_gradient.base.id.clone());
@@ -121,12 +122,10 @@ async fn demo_lines() -> Scene {
     repo.insert(Node::Container(root));
 
     Scene {
-        id: "scene".to_string(),
-        name: "LineNode Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_id],
         nodes: repo,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "LineNode Demo")
     }
 }
 
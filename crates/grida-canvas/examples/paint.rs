@@ -73,6 +73,7 @@ async fn demo_paints() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         all_shape_ids.push(rect.base.id.clone());
         repository.insert(Node::Rectangle(rect));
@@ -107,6 +108,7 @@ async fn demo_paints() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         all_shape_ids.push(rect.base.id.clone());
         repository.insert(Node::Rectangle(rect));
@@ -179,6 +181,7 @@ async fn demo_paints() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 4.0; // Consistent stroke width
 
@@ -221,6 +224,7 @@ async fn demo_paints() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 4.0; // Consistent stroke width
 
@@ -234,12 +238,10 @@ async fn demo_paints() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Paints Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Paints Demo")
     }
 }
 
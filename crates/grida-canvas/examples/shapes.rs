@@ -164,7 +164,7 @@ async fn demo_shapes() -> Scene {
             height: base_size,
         };
         star.point_count = 3 + i; // 3 to 12 points
-        star.inner_radius = 0.7 - (i as f32 * 0.05); // 0.3 to 0.75 inner radius
+        star.set_inner_radius(0.7 - (i as f32 * 0.05)); // 0.3 to 0.75 inner radius
         star.fill = Paint::Solid(SolidPaint {
             color: Color(
                 200 - (i * 20) as u8,
@@ -184,12 +184,10 @@ async fn demo_shapes() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Shapes Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Shapes Demo")
     }
 }
 
@@ -200,15 +200,16 @@ async fn demo_strokes() -> Scene {
         rect.stroke_width = 4.0;
 
         // Add different effects
-        rect.effect = match i {
-            0 => Some(FilterEffect::DropShadow(FeDropShadow {
+        rect.effects = match i {
+            0 => vec![FilterEffect::DropShadow(FeDropShadow {
                 dx: 4.0,
                 dy: 4.0,
                 blur: 4.0,
+                spread: 0.0,
                 color: Color(0, 0, 0, 128),
-            })),
-            1 => Some(FilterEffect::GaussianBlur(FeGaussianBlur { radius: 2.0 })),
-            2 => Some(FilterEffect::BackdropBlur(FeBackdropBlur { radius: 4.0 })),
+            })],
+            1 => vec![FilterEffect::GaussianBlur(FeGaussianBlur { radius: 2.0 })],
+            2 => vec![FilterEffect::BackdropBlur(FeBackdropBlur { radius: 4.0 })],
             _ => unreachable!(),
         };
 
@@ -281,6 +282,7 @@ async fn demo_strokes() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 8.0;
         all_shape_ids.push(rect.base.id.clone());
@@ -312,6 +314,7 @@ async fn demo_strokes() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 8.0;
         all_shape_ids.push(rect.base.id.clone());
@@ -347,6 +350,7 @@ async fn demo_strokes() -> Scene {
                 },
             ],
             opacity: 1.0,
+            spread: GradientSpread::Pad,
         });
         rect.stroke_width = 8.0;
         all_shape_ids.push(rect.base.id.clone());
@@ -381,12 +385,10 @@ async fn demo_strokes() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Strokes Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Strokes Demo")
     }
 }
 
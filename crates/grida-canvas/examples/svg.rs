@@ -33,13 +33,14 @@ async fn demo_scene() -> Scene {
     title_text.text = "Grida Canvas SVG Demo".to_string();
     title_text.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Arial".to_string(),
+        font_families: vec!["Arial".to_string()],
         font_size: 36.0,
         font_weight: FontWeight::new(700),
         italic: false,
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     title_text.text_align = TextAlign::Center;
     title_text.text_align_vertical = TextAlignVertical::Center;
@@ -62,13 +63,14 @@ async fn demo_scene() -> Scene {
         "Rich content demonstration with shapes, gradients, and effects".to_string();
     subtitle_text.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Arial".to_string(),
+        font_families: vec!["Arial".to_string()],
         font_size: 18.0,
         font_weight: FontWeight::new(400),
         italic: true,
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     subtitle_text.text_align = TextAlign::Center;
     subtitle_text.text_align_vertical = TextAlignVertical::Center;
@@ -105,18 +107,20 @@ async fn demo_scene() -> Scene {
             },
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     rect_gradient.stroke_width = 3.0;
     rect_gradient.stroke = Paint::Solid(SolidPaint {
         color: Color(0, 0, 0, 255),
         opacity: 1.0,
     });
-    rect_gradient.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    rect_gradient.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 5.0,
         dy: 5.0,
         blur: 10.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 100),
-    }));
+    })];
     all_node_ids.push(rect_gradient.base.id.clone());
     repo.insert(Node::Rectangle(rect_gradient));
 
@@ -145,6 +149,7 @@ async fn demo_scene() -> Scene {
             },
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     ellipse_radial.stroke_width = 4.0;
     ellipse_radial.stroke = Paint::Solid(SolidPaint {
@@ -178,12 +183,13 @@ async fn demo_scene() -> Scene {
         color: Color(255, 255, 255, 255),
         opacity: 1.0,
     });
-    hexagon.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    hexagon.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 3.0,
         dy: 3.0,
         blur: 8.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 150),
-    }));
+    })];
     all_node_ids.push(hexagon.base.id.clone());
     repo.insert(Node::Polygon(hexagon));
 
@@ -251,6 +257,7 @@ async fn demo_scene() -> Scene {
             },
         ],
         opacity: 1.0,
+        spread: GradientSpread::Pad,
     });
     line.stroke_width = 8.0;
     all_node_ids.push(line.base.id.clone());
@@ -288,13 +295,14 @@ async fn demo_scene() -> Scene {
     description_text.text = "This PDF demonstrates various rendering capabilities including gradients, shapes, text, and effects.".to_string();
     description_text.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Arial".to_string(),
+        font_families: vec!["Arial".to_string()],
         font_size: 14.0,
         font_weight: FontWeight::new(400),
         italic: false,
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     description_text.text_align = TextAlign::Center;
     description_text.text_align_vertical = TextAlignVertical::Center;
@@ -311,12 +319,10 @@ async fn demo_scene() -> Scene {
     repo.insert(Node::Container(root_container));
 
     Scene {
-        id: "scene".into(),
-        name: "SVG Demo".into(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repo,
         background_color: Some(Color(255, 255, 255, 255)),
+        ..Scene::new("scene", "SVG Demo")
     }
 }
 
@@ -29,13 +29,14 @@ async fn demo_texts() -> Scene {
     word_text_node.text = "Grida Canvas".to_string();
     word_text_node.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Arial".to_string(),
+        font_families: vec!["Arial".to_string()],
         font_size: 48.0,
         italic: false,                     // TODO: add italic to text style
         font_weight: FontWeight::new(700), // Bold
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::Uppercase,
+        direction: TextDirection::Ltr,
     };
     word_text_node.stroke = Some(Paint::Solid(SolidPaint {
         color: Color(255, 255, 255, 255),
@@ -58,13 +59,14 @@ async fn demo_texts() -> Scene {
             .to_string();
     sentence_text_node.text_style = TextStyle {
         text_decoration: TextDecoration::Underline,
-        font_family: "Caveat".to_string(),
+        font_families: vec!["Caveat".to_string()],
         font_size: 32.0,
         italic: false,                     // TODO: add italic to text style
         font_weight: FontWeight::new(400), // Regular
-        letter_spacing: None,
-        line_height: None,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     sentence_text_node.text_align = TextAlign::Left;
     sentence_text_node.text_align_vertical = TextAlignVertical::Center;
@@ -80,13 +82,14 @@ async fn demo_texts() -> Scene {
     paragraph_text_node.text = LOREM.to_string();
     paragraph_text_node.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Arial".to_string(),
+        font_families: vec!["Arial".to_string()],
         font_size: 16.0,
         italic: false,                     // TODO: add italic to text style
         font_weight: FontWeight::new(400), // Regular
-        letter_spacing: None,
-        line_height: Some(1.5), // 1.5 line height for better readability
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Multiplier(1.5), // 1.5 line height for better readability
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     paragraph_text_node.text_align = TextAlign::Left;
     paragraph_text_node.text_align_vertical = TextAlignVertical::Top;
@@ -102,13 +105,14 @@ async fn demo_texts() -> Scene {
     second_paragraph_text_node.text = LOREM_SHORT.to_string();
     second_paragraph_text_node.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "VT323".to_string(),
+        font_families: vec!["VT323".to_string()],
         font_size: 16.0,
         italic: false,                     // TODO: add italic to text style
         font_weight: FontWeight::new(400), // Regular
-        letter_spacing: None,
-        line_height: Some(1.5), // 1.5 line height for better readability
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Multiplier(1.5), // 1.5 line height for better readability
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     second_paragraph_text_node.text_align = TextAlign::Left;
     second_paragraph_text_node.text_align_vertical = TextAlignVertical::Top;
@@ -151,12 +155,10 @@ async fn demo_texts() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Text Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Text Demo")
     }
 }
 
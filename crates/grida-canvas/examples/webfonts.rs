@@ -25,13 +25,14 @@ async fn demo_webfonts() -> Scene {
     heading_node.text = "Web fonts demo".to_string();
     heading_node.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Playfair Display".to_string(),
+        font_families: vec!["Playfair Display".to_string()],
         font_size: 64.0,
         font_weight: FontWeight::new(700), // Bold
-        letter_spacing: None,
+        letter_spacing: LetterSpacing::default(),
         italic: false,
-        line_height: None,
+        line_height: LineHeight::Normal,
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     heading_node.text_align = TextAlign::Left;
     heading_node.text_align_vertical = TextAlignVertical::Top;
@@ -47,13 +48,14 @@ async fn demo_webfonts() -> Scene {
     description_node.text = PARAGRAPH.to_string();
     description_node.text_style = TextStyle {
         text_decoration: TextDecoration::None,
-        font_family: "Playfair Display".to_string(),
+        font_families: vec!["Playfair Display".to_string()],
         font_size: 14.0,
         font_weight: FontWeight::new(400), // Regular
-        letter_spacing: None,
+        letter_spacing: LetterSpacing::default(),
         italic: false,
-        line_height: Some(1.5), // 1.5 line height for better readability
+        line_height: LineHeight::Multiplier(1.5), // 1.5 line height for better readability
         text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
     };
     description_node.text_align = TextAlign::Left;
     description_node.text_align_vertical = TextAlignVertical::Top;
@@ -91,13 +93,14 @@ async fn demo_webfonts() -> Scene {
         text_node.text = format!("AlbertSans {}", variant);
         text_node.text_style = TextStyle {
             text_decoration: TextDecoration::None,
-            font_family: "Albert Sans".to_string(),
+            font_families: vec!["Albert Sans".to_string()],
             font_size: 24.0,
             font_weight: FontWeight::new(*weight),
-            letter_spacing: None,
+            letter_spacing: LetterSpacing::default(),
             italic: *is_italic,
-            line_height: None,
+            line_height: LineHeight::Normal,
             text_transform: TextTransform::None,
+            direction: TextDirection::Ltr,
         };
         text_node.text_align = TextAlign::Left;
         text_node.text_align_vertical = TextAlignVertical::Top;
@@ -138,12 +141,10 @@ async fn demo_webfonts() -> Scene {
     repository.insert(Node::Container(root_container_node));
 
     Scene {
-        id: "scene".to_string(),
-        name: "Webfonts Demo".to_string(),
-        transform: AffineTransform::identity(),
         children: vec![root_container_id],
         nodes: repository,
         background_color: Some(Color(250, 250, 250, 255)),
+        ..Scene::new("scene", "Webfonts Demo")
     }
 }
 
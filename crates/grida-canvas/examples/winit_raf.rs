@@ -19,7 +19,6 @@ fn pseudo_random_u32(max: u32) -> u32 {
 // Main application struct holding the window and rendering loop state
 struct App {
     window: Window,
-    frame_count: u32,
     start_time: Instant,
     scheduler: scheduler::FrameScheduler,
 }
@@ -52,13 +51,14 @@ impl ApplicationHandler for App {
 
                 self.scheduler.sleep_to_maintain_fps(); // Apply pacing (no-op on wasm)
 
-                self.frame_count += 1;
-
-                // Log FPS every second
+                // Log the frame timing every second
                 let elapsed = self.start_time.elapsed();
                 if elapsed >= Duration::from_secs(1) {
-                    println!("Hz in last second: {}", self.frame_count);
-                    self.frame_count = 0;
+                    let stats = self.scheduler.stats();
+                    println!(
+                        "fps: {:.0} | frame: {:.1}ms - {:.1}ms | dropped: {}",
+                        stats.avg_fps, stats.min_frame_ms, stats.max_frame_ms, stats.dropped
+                    );
                     self.start_time = Instant::now();
                 }
 
@@ -86,7 +86,6 @@ fn main() {
     // Initialize application with both a target and max FPS
     let mut app = App {
         window,
-        frame_count: 0,
         start_time: now,
         scheduler: scheduler::FrameScheduler::new(u32::MAX).with_max_fps(u32::MAX),
     };
@@ -1,5 +1,6 @@
 use crate::node::repository::NodeRepository;
 use crate::node::schema::{FilterEffect, IntrinsicSizeNode, Node, NodeId, Scene, StrokeAlign};
+use crate::painter::geometry::has_open_contour;
 use math2::rect;
 use math2::rect::Rectangle;
 use math2::transform::AffineTransform;
@@ -51,6 +52,19 @@ impl GeometryCache {
         cache
     }
 
+    /// Builds a cache for a single subtree rooted at `id`, placed under `parent_world`.
+    ///
+    /// Useful for measuring a subtree in its parent's local space (pass identity).
+    pub fn from_subtree(
+        id: &NodeId,
+        repo: &NodeRepository,
+        parent_world: &AffineTransform,
+    ) -> Self {
+        let mut cache = Self::new();
+        Self::build_recursive(id, repo, parent_world, None, &mut cache);
+        cache
+    }
+
     fn build_recursive(
         id: &NodeId,
         repo: &NodeRepository,
@@ -164,7 +178,7 @@ impl GeometryCache {
                         0.0
                     },
                     n.stroke_align,
-                    n.effect.as_ref(),
+                    &n.effects,
                 );
 
                 let entry = GeometryEntry {
@@ -195,7 +209,7 @@ impl GeometryCache {
                         0.0
                     },
                     n.stroke_align,
-                    n.effect.as_ref(),
+                    &n.effects,
                 );
 
                 for child_id in &n.children {
@@ -224,23 +238,9 @@ impl GeometryCache {
                 union_world_bounds
             }
             _ => {
-                let intrinsic_node = Box::new(match node {
-                    Node::Path(n) => IntrinsicSizeNode::Path(n.clone()),
-                    Node::Rectangle(n) => IntrinsicSizeNode::Rectangle(n.clone()),
-                    Node::Ellipse(n) => IntrinsicSizeNode::Ellipse(n.clone()),
-                    Node::Polygon(n) => IntrinsicSizeNode::Polygon(n.clone()),
-                    Node::RegularPolygon(n) => IntrinsicSizeNode::RegularPolygon(n.clone()),
-                    Node::RegularStarPolygon(n) => IntrinsicSizeNode::RegularStarPolygon(n.clone()),
-                    Node::Line(n) => IntrinsicSizeNode::Line(n.clone()),
-                    Node::TextSpan(n) => IntrinsicSizeNode::TextSpan(n.clone()),
-                    Node::Image(n) => IntrinsicSizeNode::Image(n.clone()),
-                    Node::Container(n) => IntrinsicSizeNode::Container(n.clone()),
-                    Node::Error(n) => IntrinsicSizeNode::Error(n.clone()),
-                    Node::Group(_) | Node::BooleanOperation(_) => panic!("Unsupported node type"),
-                });
-                let intrinsic = intrinsic_node.as_ref();
+                let intrinsic = intrinsic_node(node).expect("Unsupported node type");
 
-                let (local_transform, local_bounds) = node_geometry(intrinsic);
+                let (local_transform, local_bounds) = node_geometry(&intrinsic);
                 let world_transform = parent_world.compose(&local_transform);
                 let world_bounds = transform_rect(&local_bounds, &world_transform);
                 let render_bounds = compute_render_bounds(node, world_bounds);
@@ -301,6 +301,40 @@ impl GeometryCache {
     }
 }
 
+/// The node as an [`IntrinsicSizeNode`], `None` for groups and boolean operations whose
+/// geometry is derived from their children.
+fn intrinsic_node(node: &Node) -> Option<IntrinsicSizeNode> {
+    Some(match node {
+        Node::Path(n) => IntrinsicSizeNode::Path(n.clone()),
+        Node::Rectangle(n) => IntrinsicSizeNode::Rectangle(n.clone()),
+        Node::Ellipse(n) => IntrinsicSizeNode::Ellipse(n.clone()),
+        Node::Polygon(n) => IntrinsicSizeNode::Polygon(n.clone()),
+        Node::RegularPolygon(n) => IntrinsicSizeNode::RegularPolygon(n.clone()),
+        Node::RegularStarPolygon(n) => IntrinsicSizeNode::RegularStarPolygon(n.clone()),
+        Node::Line(n) => IntrinsicSizeNode::Line(n.clone()),
+        Node::TextSpan(n) => IntrinsicSizeNode::TextSpan(n.clone()),
+        Node::RichText(n) => IntrinsicSizeNode::RichText(n.clone()),
+        Node::Image(n) => IntrinsicSizeNode::Image(n.clone()),
+        Node::Container(n) => IntrinsicSizeNode::Container(n.clone()),
+        Node::Error(n) => IntrinsicSizeNode::Error(n.clone()),
+        Node::Group(_) | Node::BooleanOperation(_) => return None,
+    })
+}
+
+/// See [`Node::ink_bounds`].
+pub(crate) fn ink_bounds(node: &Node) -> Rectangle {
+    let bounds = match intrinsic_node(node) {
+        Some(intrinsic) => node_geometry(&intrinsic).1,
+        None => Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        },
+    };
+    compute_render_bounds(node, bounds)
+}
+
 fn node_geometry(node: &IntrinsicSizeNode) -> (AffineTransform, Rectangle) {
     match node {
         IntrinsicSizeNode::Error(n) => (n.transform, n.rect()),
@@ -328,6 +362,15 @@ fn node_geometry(node: &IntrinsicSizeNode) -> (AffineTransform, Rectangle) {
                 height: n.size.height,
             },
         ),
+        IntrinsicSizeNode::RichText(n) => (
+            n.transform,
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: n.size.width,
+                height: n.size.height,
+            },
+        ),
         IntrinsicSizeNode::Path(n) => (n.transform, path_bounds(&n.data)),
         IntrinsicSizeNode::Image(n) => (n.transform, n.rect()),
     }
@@ -365,6 +408,14 @@ fn polygon_bounds(points: &[crate::node::schema::Point]) -> Rectangle {
     }
 }
 
+/// Whether the svg path `data` only has closed contours, invalid data counts as closed.
+fn path_is_closed(data: &str) -> bool {
+    match skia_safe::path::Path::from_svg(data) {
+        Some(path) => !has_open_contour(&path),
+        None => true,
+    }
+}
+
 fn path_bounds(data: &str) -> Rectangle {
     if let Some(path) = skia_safe::path::Path::from_svg(data) {
         let b = path.compute_tight_bounds();
@@ -404,22 +455,27 @@ fn stroke_outset(align: StrokeAlign, width: f32) -> f32 {
     }
 }
 
+/// How far a gaussian blur with standard deviation `sigma` spreads past the blurred
+/// content. Past three standard deviations the remaining coverage rounds to zero.
+fn gaussian_blur_extent(sigma: f32) -> f32 {
+    3.0 * sigma
+}
+
 fn compute_render_bounds_from_style(
     world_bounds: Rectangle,
     stroke_width: f32,
     stroke_align: StrokeAlign,
-    effect: Option<&FilterEffect>,
+    effects: &[FilterEffect],
 ) -> Rectangle {
     let mut bounds = inflate_rect(world_bounds, stroke_outset(stroke_align, stroke_width));
 
-    if let Some(effect) = effect {
+    for effect in effects {
         match effect {
             FilterEffect::GaussianBlur(blur) => {
-                bounds = inflate_rect(bounds, blur.radius);
-            }
-            FilterEffect::BackdropBlur(blur) => {
-                bounds = inflate_rect(bounds, blur.radius);
+                bounds = inflate_rect(bounds, gaussian_blur_extent(blur.radius));
             }
+            // the blurred backdrop is drawn inside the shape
+            FilterEffect::BackdropBlur(_) => {}
             FilterEffect::DropShadow(shadow) => {
                 let shadow_rect = inflate_rect(
                     Rectangle {
@@ -428,10 +484,12 @@ fn compute_render_bounds_from_style(
                         width: world_bounds.width,
                         height: world_bounds.height,
                     },
-                    shadow.blur,
+                    gaussian_blur_extent(shadow.blur) + shadow.spread,
                 );
                 bounds = rect::union(&[bounds, shadow_rect]);
             }
+            // inner shadows are clipped to the shape and never extend the bounds
+            FilterEffect::InnerShadow(_) => {}
         }
     }
 
@@ -444,55 +502,60 @@ fn compute_render_bounds(node: &Node, world_bounds: Rectangle) -> Rectangle {
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::Ellipse(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::Polygon(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::RegularPolygon(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::RegularStarPolygon(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::Path(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
-            n.stroke_align,
-            n.effect.as_ref(),
+            // open paths are stroked centered regardless of the alignment
+            if path_is_closed(&n.data) {
+                n.stroke_align
+            } else {
+                StrokeAlign::Center
+            },
+            &n.effects,
         ),
         Node::Image(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
         Node::Line(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width,
             n.get_stroke_align(),
-            None,
+            &[],
         ),
         Node::TextSpan(n) => compute_render_bounds_from_style(
             world_bounds,
             n.stroke_width.unwrap_or(0.0),
             n.stroke_align,
-            None,
+            &[],
         ),
         Node::Container(n) => compute_render_bounds_from_style(
             world_bounds,
@@ -502,8 +565,9 @@ fn compute_render_bounds(node: &Node, world_bounds: Rectangle) -> Rectangle {
                 0.0
             },
             n.stroke_align,
-            n.effect.as_ref(),
+            &n.effects,
         ),
+        Node::RichText(_) => world_bounds,
         Node::Error(_) => world_bounds,
         Node::Group(_) | Node::BooleanOperation(_) => world_bounds,
     }
@@ -1,3 +1,5 @@
+use crate::node::schema::{ImageNode, ImagePaint, ImageRepeat};
+use math2::box_fit::BoxFit;
 use skia_safe::{surfaces, Image, Paint as SkPaint, Rect};
 
 /// Strategy for generating the scale levels for mipmaps.
@@ -82,6 +84,12 @@ impl ImageMipmaps {
         self.levels.len()
     }
 
+    /// Returns the image for the largest mip level, the original image unless the
+    /// configured levels skip it.
+    pub fn first_level_image(&self) -> Option<&Image> {
+        self.levels.first().map(|(_, img)| img)
+    }
+
     /// Returns the image for the smallest mip level.
     pub fn last_level_image(&self) -> Option<&Image> {
         self.levels.last().map(|(_, img)| img)
@@ -119,6 +127,50 @@ impl ImageMipmaps {
     }
 }
 
+/// Returns the dimensions to decode an image of `source` size at, when drawn by `node` at
+/// render `scale`.
+///
+/// Image nodes cover their bounds, see [`paint_target_decode_size`].
+pub fn target_decode_size(node: &ImageNode, scale: f32, source: (u32, u32)) -> (u32, u32) {
+    let fitted = BoxFit::Cover.calculate_transform(
+        (source.0 as f32, source.1 as f32),
+        (node.size.width, node.size.height),
+    );
+    decode_size(source, fitted.get_scale_x() * scale)
+}
+
+/// Returns the dimensions to decode an image of `source` size at, when drawn by `paint`
+/// into a box of `container` size at render `scale`.
+///
+/// The result keeps the aspect ratio of the source and never exceeds it, so images are only
+/// ever decoded at a lower resolution.
+pub fn paint_target_decode_size(
+    paint: &ImagePaint,
+    container: (f32, f32),
+    scale: f32,
+    source: (u32, u32),
+) -> (u32, u32) {
+    // mirrors the painter, the paint transform only applies without a fit or when repeating
+    let image_transform = match paint.fit {
+        _ if paint.repeat != ImageRepeat::NoRepeat => paint.transform,
+        BoxFit::None => paint.transform,
+        fit => fit.calculate_transform((source.0 as f32, source.1 as f32), container),
+    };
+    let image_scale = image_transform
+        .get_scale_x()
+        .max(image_transform.get_scale_y());
+    decode_size(source, image_scale * scale)
+}
+
+/// Scales `source` down by `factor`, rounding up so no displayed pixel is lost.
+fn decode_size(source: (u32, u32), factor: f32) -> (u32, u32) {
+    if !factor.is_finite() || factor >= 1.0 {
+        return source;
+    }
+    let scaled = |dim: u32| ((dim as f32 * factor.max(0.0)).ceil() as u32).clamp(1, dim.max(1));
+    (scaled(source.0), scaled(source.1))
+}
+
 fn scale_image(image: &Image, scale: f32) -> Image {
     let width = ((image.width() as f32 * scale).round() as i32).max(1);
     let height = ((image.height() as f32 * scale).round() as i32).max(1);
@@ -2,6 +2,8 @@ pub mod geometry;
 pub mod mipmap;
 pub mod paragraph;
 pub mod picture;
+pub mod polygon_path;
 pub mod scene;
+pub mod text_measure;
 pub mod tile;
 pub mod vector_path;
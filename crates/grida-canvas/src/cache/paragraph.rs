@@ -1,6 +1,12 @@
-use crate::node::schema::{NodeId, Paint, Size, TextAlign, TextAlignVertical, TextStyle};
+use crate::node::schema::{
+    LetterSpacing, LineHeight, NodeId, Paint, Size, TextAlign, TextAlignVertical, TextOverflow,
+    TextSpan, TextStyle,
+};
 use crate::painter::{cvt, make_textstyle};
 use crate::runtime::repository::FontRepository;
+use crate::text::measure::text_block_bounds;
+use crate::text::overflow::layout_within_line_limit;
+use crate::text::rich_text::{build_rich_paragraph, spans_text_block};
 use skia_safe::textlayout;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
@@ -31,25 +37,62 @@ impl ParagraphCache {
         style: &TextStyle,
         align: &TextAlign,
         valign: &TextAlignVertical,
+        max_lines: Option<usize>,
+        overflow: TextOverflow,
         size: &Size,
     ) -> u64 {
         let mut h = DefaultHasher::new();
         text.hash(&mut h);
-        style.text_decoration.hash(&mut h);
-        style.font_family.hash(&mut h);
-        style.font_size.to_bits().hash(&mut h);
-        style.font_weight.0.hash(&mut h);
-        style.italic.hash(&mut h);
-        style.letter_spacing.map(|v| v.to_bits()).hash(&mut h);
-        style.line_height.map(|v| v.to_bits()).hash(&mut h);
-        style.text_transform.hash(&mut h);
+        Self::style_hash(style, &mut h);
         (*align as u8).hash(&mut h);
         (*valign as u8).hash(&mut h);
+        max_lines.hash(&mut h);
+        overflow.hash(&mut h);
         size.width.to_bits().hash(&mut h);
         size.height.to_bits().hash(&mut h);
         h.finish()
     }
 
+    fn spans_hash(
+        spans: &[TextSpan],
+        align: &TextAlign,
+        valign: &TextAlignVertical,
+        size: &Size,
+    ) -> u64 {
+        let mut h = DefaultHasher::new();
+        for span in spans {
+            span.text.hash(&mut h);
+            Self::style_hash(&span.text_style, &mut h);
+        }
+        (*align as u8).hash(&mut h);
+        (*valign as u8).hash(&mut h);
+        size.width.to_bits().hash(&mut h);
+        size.height.to_bits().hash(&mut h);
+        h.finish()
+    }
+
+    pub(crate) fn style_hash(style: &TextStyle, h: &mut DefaultHasher) {
+        style.text_decoration.hash(h);
+        style.font_families.hash(h);
+        style.font_size.to_bits().hash(h);
+        style.font_weight.0.hash(h);
+        style.italic.hash(h);
+        match style.letter_spacing {
+            LetterSpacing::Px(v) => (0u8, v.to_bits()).hash(h),
+            LetterSpacing::Em(v) => (1u8, v.to_bits()).hash(h),
+        }
+        match style.line_height {
+            LineHeight::Normal => (0u8, 0u32).hash(h),
+            LineHeight::Px(v) => (1u8, v.to_bits()).hash(h),
+            LineHeight::Multiplier(v) => (2u8, v.to_bits()).hash(h),
+        }
+        style.text_transform.hash(h);
+        style.direction.hash(h);
+    }
+
+    /// Returns the laid out paragraph of a text node, cut off at `max_lines`, see
+    /// [`layout_within_line_limit`].
+    #[allow(clippy::too_many_arguments)]
     pub fn get_or_create(
         &mut self,
         id: &NodeId,
@@ -58,32 +101,79 @@ impl ParagraphCache {
         fill: &Paint,
         align: &TextAlign,
         valign: &TextAlignVertical,
+        max_lines: Option<usize>,
+        overflow: TextOverflow,
         style: &TextStyle,
         fonts: &FontRepository,
     ) -> Rc<textlayout::Paragraph> {
         let fonts_gen = fonts.generation();
-        let hash = Self::text_hash(text, style, align, valign, size);
+        let hash = Self::text_hash(text, style, align, valign, max_lines, overflow, size);
         if let Some(entry) = self.entries.get(id) {
             if entry.hash == hash && entry.font_generation == fonts_gen {
                 return entry.paragraph.clone();
             }
         }
-        let fill_paint = cvt::sk_paint(fill, 1.0, (size.width, size.height));
         let mut paragraph_style = textlayout::ParagraphStyle::new();
-        paragraph_style.set_text_direction(textlayout::TextDirection::LTR);
-        paragraph_style.set_text_align(align.clone().into());
-
-        let mut para_builder =
-            textlayout::ParagraphBuilder::new(&paragraph_style, &fonts.font_collection());
-        let mut ts = make_textstyle(style);
-        ts.set_foreground_paint(&fill_paint);
-        para_builder.push_style(&ts);
+        paragraph_style.set_text_direction(style.direction.into());
+        paragraph_style.set_text_align(align.resolve(style.direction).into());
         let transformed_text =
             crate::text::text_transform::transform_text(text, style.text_transform);
-        para_builder.add_text(&transformed_text);
-        let mut paragraph = para_builder.build();
-        para_builder.pop();
-        paragraph.layout(size.width);
+
+        let build = |paragraph_style: &textlayout::ParagraphStyle,
+                     fill_paint: &skia_safe::Paint| {
+            let mut para_builder =
+                textlayout::ParagraphBuilder::new(paragraph_style, &fonts.font_collection());
+            let mut ts = make_textstyle(style);
+            ts.set_foreground_paint(fill_paint);
+            para_builder.push_style(&ts);
+            para_builder.add_text(&transformed_text);
+            let mut paragraph = para_builder.build();
+            para_builder.pop();
+            paragraph.layout(size.width);
+            paragraph
+        };
+        let fill_paint = cvt::sk_paint(fill, 1.0, (size.width, size.height));
+        let mut paragraph =
+            layout_within_line_limit(&mut paragraph_style, max_lines, overflow, size, |ps| {
+                build(ps, &fill_paint)
+            });
+        if spans_text_block(fill) {
+            // the laid out lines are only known now, rebuild with the paint fitted to them
+            let bounds = text_block_bounds(&paragraph);
+            paragraph = build(&paragraph_style, &cvt::sk_paint_in_rect(fill, 1.0, bounds));
+        }
+
+        let rc = Rc::new(paragraph);
+        self.entries.insert(
+            id.clone(),
+            ParagraphCacheEntry {
+                hash,
+                font_generation: fonts_gen,
+                paragraph: rc.clone(),
+            },
+        );
+        rc
+    }
+
+    /// Same as [`Self::get_or_create`], for multi-style text laid out from spans.
+    pub fn get_or_create_rich(
+        &mut self,
+        id: &NodeId,
+        spans: &[TextSpan],
+        size: &Size,
+        fill: &Paint,
+        align: &TextAlign,
+        valign: &TextAlignVertical,
+        fonts: &FontRepository,
+    ) -> Rc<textlayout::Paragraph> {
+        let fonts_gen = fonts.generation();
+        let hash = Self::spans_hash(spans, align, valign, size);
+        if let Some(entry) = self.entries.get(id) {
+            if entry.hash == hash && entry.font_generation == fonts_gen {
+                return entry.paragraph.clone();
+            }
+        }
+        let paragraph = build_rich_paragraph(spans, fill, align, size, fonts);
 
         let rc = Rc::new(paragraph);
         self.entries.insert(
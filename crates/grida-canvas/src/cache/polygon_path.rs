@@ -0,0 +1,126 @@
+use crate::node::schema::{NodeId, RegularPolygonNode, RegularStarPolygonNode};
+use skia_safe::Path;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The inputs a polygon-derived path is generated from.
+///
+/// Floats are compared by their bits, so any change (including between `0.0` and `-0.0`)
+/// invalidates the entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolygonPathKey {
+    width: u32,
+    height: u32,
+    point_count: usize,
+    /// `None` for regular polygons.
+    inner_radius: Option<u32>,
+    corner_radius: u32,
+    angle_offset: u32,
+}
+
+impl PolygonPathKey {
+    pub fn from_regular_polygon(node: &RegularPolygonNode) -> Self {
+        Self {
+            width: node.size.width.to_bits(),
+            height: node.size.height.to_bits(),
+            point_count: node.point_count,
+            inner_radius: None,
+            corner_radius: node.corner_radius.to_bits(),
+            angle_offset: node.angle_offset.to_bits(),
+        }
+    }
+
+    pub fn from_regular_star(node: &RegularStarPolygonNode) -> Self {
+        Self {
+            width: node.size.width.to_bits(),
+            height: node.size.height.to_bits(),
+            point_count: node.point_count,
+            inner_radius: Some(node.inner_radius.to_bits()),
+            corner_radius: node.corner_radius.to_bits(),
+            angle_offset: node.angle_offset.to_bits(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PathCacheEntry {
+    pub key: PolygonPathKey,
+    pub path: Rc<Path>,
+}
+
+/// Opt-in memoization of the paths generated by `to_polygon().to_path()` on regular polygon
+/// and star nodes.
+///
+/// The nodes themselves stay plain data and regenerate their geometry on every call, callers
+/// that redraw static scenes can keep a `PathCache` around instead. An entry is reused as long
+/// as its [`PolygonPathKey`] matches, and regenerated when any of the inputs change.
+#[derive(Default, Clone, Debug)]
+pub struct PathCache {
+    entries: HashMap<NodeId, PathCacheEntry>,
+}
+
+impl PathCache {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn get_or_create_regular_polygon(&mut self, node: &RegularPolygonNode) -> Rc<Path> {
+        self.get_or_create(
+            &node.base.id,
+            PolygonPathKey::from_regular_polygon(node),
+            || node.to_polygon().to_path(),
+        )
+    }
+
+    pub fn get_or_create_regular_star(&mut self, node: &RegularStarPolygonNode) -> Rc<Path> {
+        self.get_or_create(
+            &node.base.id,
+            PolygonPathKey::from_regular_star(node),
+            || node.to_polygon().to_path(),
+        )
+    }
+
+    fn get_or_create(
+        &mut self,
+        id: &NodeId,
+        key: PolygonPathKey,
+        create: impl FnOnce() -> Path,
+    ) -> Rc<Path> {
+        if let Some(entry) = self.entries.get(id) {
+            if entry.key == key {
+                return entry.path.clone();
+            }
+        }
+        let rc = Rc::new(create());
+        self.entries.insert(
+            id.clone(),
+            PathCacheEntry {
+                key,
+                path: rc.clone(),
+            },
+        );
+        rc
+    }
+
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn remove(&mut self, id: &NodeId) {
+        self.entries.remove(id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, id: &NodeId) -> Option<&PathCacheEntry> {
+        self.entries.get(id)
+    }
+}
@@ -1,5 +1,6 @@
-use crate::node::schema::{NodeId, Scene};
+use crate::node::schema::{Node, NodeId, Point, Scene};
 use crate::runtime::camera::Camera2D;
+use crate::runtime::repository::FontRepository;
 use crate::{
     cache::{
         geometry::GeometryCache,
@@ -10,17 +11,26 @@ use crate::{
     },
     painter::layer::{Layer, LayerList},
 };
-use math2::{rect::Rectangle, vector2::Vector2};
+use math2::transform::AffineTransform;
+use math2::{
+    rect::{self, Rectangle},
+    vector2::Vector2,
+};
 use rstar::{RTree, RTreeObject, AABB};
 use skia_safe::{Picture, Surface};
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct IndexedLayer {
-    pub index: usize,
+/// A node in the [`SceneCache`] spatial index, bounded by its ink bounds grown to the
+/// render bounds of its layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedNode {
+    pub id: NodeId,
+    /// Index of the node's layer in [`SceneCache::layers`], `None` for nodes without one.
+    pub layer: Option<usize>,
     pub bounds: AABB<[f32; 2]>,
 }
 
-impl RTreeObject for IndexedLayer {
+impl RTreeObject for IndexedNode {
     type Envelope = AABB<[f32; 2]>;
 
     fn envelope(&self) -> Self::Envelope {
@@ -28,7 +38,31 @@ impl RTreeObject for IndexedLayer {
     }
 }
 
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    /// Position in paint order.
+    order: usize,
+    layer: Option<usize>,
+    parent: Option<NodeId>,
+    world: AffineTransform,
+    /// Ink bounds in scene space, `None` for groups and boolean operations.
+    bounds: Option<Rectangle>,
+    /// Envelope of the node in the tree, `None` when the node is not in it.
+    envelope: Option<AABB<[f32; 2]>>,
+    /// Whether the node is a clipping container.
+    clip: bool,
+    /// Whether the node and all of its ancestors are active.
+    active: bool,
+    /// Whether the node is listed by [`SceneCache::visible_nodes`], operands of boolean
+    /// operations are drawn as part of the operation and are not.
+    listed: bool,
+}
+
 /// A unified cache storing geometry information and recorded pictures for a scene.
+///
+/// The spatial index serves both the painter layers and the node queries
+/// [`SceneCache::visible_nodes`] and [`SceneCache::node_at`], which give the same
+/// results as the linear [`Scene::visible_nodes`] and [`Scene::node_at`].
 #[derive(Debug, Clone)]
 pub struct SceneCache {
     pub layers: LayerList,
@@ -37,7 +71,9 @@ pub struct SceneCache {
     pub tile: ImageTileCache,
     pub paragraph: std::cell::RefCell<ParagraphCache>,
     pub path: std::cell::RefCell<VectorPathCache>,
-    pub layer_index: RTree<IndexedLayer>,
+    pub layer_index: RTree<IndexedNode>,
+    index_entries: HashMap<NodeId, IndexEntry>,
+    next_order: usize,
 }
 
 impl SceneCache {
@@ -51,6 +87,8 @@ impl SceneCache {
             paragraph: std::cell::RefCell::new(ParagraphCache::new()),
             path: std::cell::RefCell::new(VectorPathCache::new()),
             layer_index: RTree::new(),
+            index_entries: HashMap::new(),
+            next_order: 0,
         }
     }
 
@@ -59,15 +97,168 @@ impl SceneCache {
         self.geometry = GeometryCache::from_scene(scene);
     }
 
-    pub fn update_layers(&mut self, scene: &Scene) {
-        self.layers = LayerList::from_scene(scene, &self.geometry);
+    /// Rebuild the layer list and its spatial index from the provided scene.
+    ///
+    /// `fonts` is used to outline text children of boolean operations.
+    pub fn update_layers(&mut self, scene: &Scene, fonts: &FontRepository) {
+        self.layers = LayerList::from_scene(scene, &self.geometry, fonts);
         self.layers.layers.sort_by_key(|l| l.z_index());
+
+        let layers: HashMap<NodeId, usize> = self
+            .layers
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| (layer.id().clone(), i))
+            .collect();
         self.layer_index = RTree::new();
-        for (i, layer) in self.layers.layers.iter().enumerate() {
-            if let Some(rb) = self.geometry.get_render_bounds(&layer.id()) {
-                let bounds = AABB::from_corners([rb.x, rb.y], [rb.x + rb.width, rb.y + rb.height]);
-                self.layer_index.insert(IndexedLayer { index: i, bounds });
+        self.index_entries.clear();
+        self.next_order = 0;
+
+        let mut visited = HashSet::new();
+        for id in scene.nodes.in_render_order(&scene.children) {
+            self.index_node(
+                scene,
+                id,
+                None,
+                &scene.transform,
+                true,
+                true,
+                Some(&layers),
+                &mut visited,
+            );
+        }
+    }
+
+    /// Refreshes the spatial index for `id` and its subtree after the node changed.
+    ///
+    /// Layer bounds are read from the geometry cache, update it first. Nodes that were not
+    /// part of the scene when the layers were built trigger a full [`Self::update_layers`]
+    /// with `fonts`.
+    pub fn update_node(&mut self, scene: &Scene, id: &NodeId, fonts: &FontRepository) {
+        let Some(entry) = self.index_entries.get(id) else {
+            self.update_layers(scene, fonts);
+            return;
+        };
+        let parent = entry.parent.clone();
+        let listed = entry.listed;
+        let (parent_world, parent_active) =
+            match parent.as_ref().and_then(|p| self.index_entries.get(p)) {
+                Some(parent) => (parent.world, parent.active),
+                None => (scene.transform, true),
+            };
+
+        let mut visited = HashSet::new();
+        self.index_node(
+            scene,
+            id,
+            parent,
+            &parent_world,
+            parent_active,
+            listed,
+            None,
+            &mut visited,
+        );
+    }
+
+    /// Indexes `id` and its subtree. Layers are looked up in `layers`, or kept from the
+    /// previous entry when `None`.
+    #[allow(clippy::too_many_arguments)]
+    fn index_node<'a>(
+        &mut self,
+        scene: &'a Scene,
+        id: &'a NodeId,
+        parent: Option<NodeId>,
+        parent_world: &AffineTransform,
+        parent_active: bool,
+        listed: bool,
+        layers: Option<&HashMap<NodeId, usize>>,
+        visited: &mut HashSet<&'a NodeId>,
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+        let Some(node) = scene.nodes.get(id) else {
+            return;
+        };
+
+        let world = parent_world.compose(&node.transform());
+        let active = parent_active && node.active();
+        let (bounds, clip, children) = match node {
+            Node::Group(n) => (None, false, n.children.as_slice()),
+            Node::BooleanOperation(n) => (None, false, n.children.as_slice()),
+            Node::Container(n) => (
+                Some(rect::transform(node.ink_bounds(), &world)),
+                n.clip,
+                n.children.as_slice(),
+            ),
+            _ => (
+                Some(rect::transform(node.ink_bounds(), &world)),
+                false,
+                &[][..],
+            ),
+        };
+
+        let previous = self.index_entries.remove(id);
+        if let Some(envelope) = previous.as_ref().and_then(|p| p.envelope) {
+            self.layer_index.remove(&IndexedNode {
+                id: id.clone(),
+                layer: previous.as_ref().and_then(|p| p.layer),
+                bounds: envelope,
+            });
+        }
+        let (order, layer) = match (previous, layers) {
+            (Some(previous), None) => (previous.order, previous.layer),
+            (previous, layers) => {
+                let order = previous.map(|p| p.order).unwrap_or_else(|| {
+                    self.next_order += 1;
+                    self.next_order - 1
+                });
+                (order, layers.and_then(|layers| layers.get(id).copied()))
             }
+        };
+
+        let render_bounds = layer.and_then(|_| self.geometry.get_render_bounds(id));
+        let envelope = match (bounds, render_bounds) {
+            (Some(ink), Some(render)) => Some(rect::union(&[ink, render])),
+            (ink, render) => ink.or(render),
+        }
+        .filter(|_| active)
+        .map(|bounds| envelope(&bounds));
+        if let Some(bounds) = envelope {
+            self.layer_index.insert(IndexedNode {
+                id: id.clone(),
+                layer,
+                bounds,
+            });
+        }
+        self.index_entries.insert(
+            id.clone(),
+            IndexEntry {
+                order,
+                layer,
+                parent,
+                world,
+                bounds,
+                envelope,
+                clip,
+                active,
+                listed,
+            },
+        );
+
+        let operands = matches!(node, Node::BooleanOperation(_));
+        for child in scene.nodes.in_render_order(children) {
+            self.index_node(
+                scene,
+                child,
+                Some(id.clone()),
+                &world,
+                active,
+                listed && !operands,
+                layers,
+                visited,
+            );
         }
     }
 
@@ -119,13 +310,9 @@ impl SceneCache {
     /// - Partially overlapping with the rectangle
     /// - Touching the rectangle's edges
     pub fn intersects(&self, rect: Rectangle) -> Vec<usize> {
-        let env = AABB::from_corners(
-            [rect.x, rect.y],
-            [rect.x + rect.width, rect.y + rect.height],
-        );
         self.layer_index
-            .locate_in_envelope_intersecting(&env)
-            .map(|il| il.index)
+            .locate_in_envelope_intersecting(&envelope(&rect))
+            .filter_map(|node| node.layer)
             .collect()
     }
 
@@ -133,13 +320,9 @@ impl SceneCache {
     /// This only includes layers that are completely inside the rectangle, not touching its edges.
     pub fn contains(&self, rect: &Rectangle) -> Vec<usize> {
         // Get layers that are fully contained
-        let env = AABB::from_corners(
-            [rect.x, rect.y],
-            [rect.x + rect.width, rect.y + rect.height],
-        );
         self.layer_index
-            .locate_in_envelope(&env)
-            .map(|il| il.index)
+            .locate_in_envelope(&envelope(rect))
+            .filter_map(|node| node.layer)
             .collect()
     }
 
@@ -148,23 +331,85 @@ impl SceneCache {
         let env = AABB::from_point([point[0], point[1]]);
         self.layer_index
             .locate_in_envelope_intersecting(&env)
-            .map(|il| il.index)
+            .filter_map(|node| node.layer)
             .collect()
     }
 
+    /// Indexed [`Scene::visible_nodes`] of the scene the layers were built from.
+    pub fn visible_nodes(&self, viewport: Rectangle) -> Vec<NodeId> {
+        let mut shown: HashSet<&NodeId> = HashSet::new();
+        'candidates: for candidate in self
+            .layer_index
+            .locate_in_envelope_intersecting(&envelope(&viewport))
+        {
+            let entry = &self.index_entries[&candidate.id];
+            if !entry.bounds.is_some_and(|b| b.intersects(&viewport)) {
+                continue;
+            }
+            // off-screen clipping ancestors cull their whole subtree
+            let mut ancestors = vec![&candidate.id];
+            let mut parent = entry.parent.as_ref();
+            while let Some(id) = parent {
+                let entry = &self.index_entries[id];
+                if entry.clip && !entry.bounds.is_some_and(|b| b.intersects(&viewport)) {
+                    continue 'candidates;
+                }
+                ancestors.push(id);
+                parent = entry.parent.as_ref();
+            }
+            shown.extend(ancestors);
+        }
+
+        let mut visible: Vec<(usize, &NodeId)> = shown
+            .into_iter()
+            .filter_map(|id| {
+                let entry = &self.index_entries[id];
+                entry.listed.then_some((entry.order, id))
+            })
+            .collect();
+        visible.sort_unstable_by_key(|(order, _)| *order);
+        visible.into_iter().map(|(_, id)| id.clone()).collect()
+    }
+
+    /// Indexed [`Scene::node_at`], `scene` must be the scene the layers were built from.
+    ///
+    /// Only nodes whose bounds contain `point`, and their ancestors, are hit tested.
+    pub fn node_at(&self, scene: &Scene, point: Point) -> Option<NodeId> {
+        let mut candidates = HashSet::new();
+        for node in self
+            .layer_index
+            .locate_in_envelope_intersecting(&AABB::from_point([point.x, point.y]))
+        {
+            let mut parent = self.index_entries[&node.id].parent.clone();
+            candidates.insert(node.id.clone());
+            while let Some(id) = parent {
+                parent = self.index_entries[&id].parent.clone();
+                if !candidates.insert(id) {
+                    break;
+                }
+            }
+        }
+        scene.node_at_among(point, Some(&candidates))
+    }
+
     /// Update raster tile cache using the given camera and surface.
     pub fn update_tiles(&mut self, camera: &Camera2D, surface: &mut Surface, partial: bool) {
         let width = surface.width() as f32;
         let height = surface.height() as f32;
         let index = &self.layer_index;
         let intersects = |rect: Rectangle| {
-            let env = AABB::from_corners(
-                [rect.x, rect.y],
-                [rect.x + rect.width, rect.y + rect.height],
-            );
-            index.locate_in_envelope_intersecting(&env).next().is_some()
+            index
+                .locate_in_envelope_intersecting(&envelope(&rect))
+                .any(|node| node.layer.is_some())
         };
         self.tile
             .update_tiles(camera, width, height, surface, partial, intersects);
     }
 }
+
+fn envelope(rect: &Rectangle) -> AABB<[f32; 2]> {
+    AABB::from_corners(
+        [rect.x, rect.y],
+        [rect.x + rect.width, rect.y + rect.height],
+    )
+}
@@ -0,0 +1,83 @@
+use crate::cache::paragraph::ParagraphCache;
+use crate::node::schema::TextStyle;
+use crate::runtime::repository::FontRepository;
+use crate::text::measure::{measure_text, TextMetrics};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Cache key: (content hash, style hash, layout width bits).
+type TextMeasureKey = (u64, u64, u32);
+
+/// Caches [`measure_text`] results by content, so repeated strings (e.g. table cells)
+/// are laid out once.
+///
+/// Unlike [`ParagraphCache`], entries are not tied to a node. Every entry is dropped
+/// when the font repository generation changes.
+#[derive(Default, Clone, Debug)]
+pub struct TextMeasureCache {
+    entries: HashMap<TextMeasureKey, TextMetrics>,
+    font_generation: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl TextMeasureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(text: &str, style: &TextStyle, width: f32) -> TextMeasureKey {
+        let mut content = DefaultHasher::new();
+        text.hash(&mut content);
+        let mut style_hash = DefaultHasher::new();
+        ParagraphCache::style_hash(style, &mut style_hash);
+        (content.finish(), style_hash.finish(), width.to_bits())
+    }
+
+    /// Returns the metrics of `text`, measuring it only if it is not cached yet.
+    pub fn measure(
+        &mut self,
+        text: &str,
+        style: &TextStyle,
+        width: f32,
+        fonts: &FontRepository,
+    ) -> TextMetrics {
+        if self.font_generation != fonts.generation() {
+            self.entries.clear();
+            self.font_generation = fonts.generation();
+        }
+
+        let key = Self::key(text, style, width);
+        if let Some(metrics) = self.entries.get(&key) {
+            self.hits += 1;
+            return *metrics;
+        }
+        self.misses += 1;
+        let metrics = measure_text(text, style, width, fonts);
+        self.entries.insert(key, metrics);
+        metrics
+    }
+
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of measurements served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of measurements that required a layout.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
@@ -1,13 +1,13 @@
 use crate::cache::scene::SceneCache;
 use crate::fonts::geistmono::geistmono;
-use crate::node::schema::NodeId;
+use crate::node::schema::{NodeId, Size};
 use crate::painter::{
     cvt,
     layer::{Layer, PainterPictureTextLayer},
 };
 use crate::runtime::camera::Camera2D;
 use crate::runtime::repository::FontRepository;
-use skia_safe::{textlayout, Color, Font, Paint, PaintStyle, Path, Point, Rect, Surface};
+use skia_safe::{Color, Font, Paint, PaintStyle, Path, Point, Rect, Surface};
 
 thread_local! {
     static BG_PAINT: Paint = {
@@ -82,6 +82,7 @@ impl HitOverlay {
                     let base = match layer {
                         crate::painter::layer::PainterPictureLayer::Shape(s) => &s.base,
                         crate::painter::layer::PainterPictureLayer::Text(t) => &t.base,
+                        crate::painter::layer::PainterPictureLayer::RichText(t) => &t.base,
                     };
                     let mut path = if let Some(entry) = cache.path.borrow().get(id) {
                         (*entry.path).clone()
@@ -149,48 +150,18 @@ impl HitOverlay {
     }
 
     fn text_layer_path(fonts: &FontRepository, layer: &PainterPictureTextLayer) -> Path {
-        let size = crate::node::schema::Size {
-            width: layer.base.shape.rect.width(),
-            height: layer.base.shape.rect.height(),
-        };
-        let fill = layer
-            .base
-            .fills
-            .first()
-            .cloned()
-            .unwrap_or(crate::node::schema::Paint::Solid(
-                crate::node::schema::SolidPaint {
-                    color: crate::node::schema::Color(0, 0, 0, 255),
-                    opacity: 1.0,
-                },
-            ));
-        let fill_paint = cvt::sk_paint(&fill, 1.0, (size.width, size.height));
-
-        let mut paragraph_style = textlayout::ParagraphStyle::new();
-        paragraph_style.set_text_direction(textlayout::TextDirection::LTR);
-        paragraph_style.set_text_align(layer.text_align.clone().into());
-
-        let mut builder =
-            textlayout::ParagraphBuilder::new(&paragraph_style, &fonts.font_collection());
-
-        let mut ts = crate::painter::make_textstyle(&layer.text_style);
-        ts.set_foreground_paint(&fill_paint);
-        builder.push_style(&ts);
-        let transformed_text = crate::text::text_transform::transform_text(
+        crate::text::outline::text_outline(
             &layer.text,
-            layer.text_style.text_transform,
-        );
-        builder.add_text(&transformed_text);
-        let mut paragraph = builder.build();
-        builder.pop();
-        paragraph.layout(size.width);
-
-        let mut path = Path::new();
-        let lines = paragraph.line_number();
-        for i in 0..lines {
-            let (_, line_path) = paragraph.get_path_at(i);
-            path.add_path(&line_path, (0.0, 0.0), None);
-        }
-        path
+            &layer.text_style,
+            layer.text_align,
+            layer.text_align_vertical,
+            &Size {
+                width: layer.base.shape.rect.width(),
+                height: layer.base.shape.rect.height(),
+            },
+            layer.max_lines,
+            layer.overflow,
+            fonts,
+        )
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    cache::geometry::GeometryCache,
+    node::schema::{NodeId, Scene},
+    painter::{cvt, Painter},
+    runtime::repository::{FontRepository, ImageRepository},
+};
+use math2::Rectangle;
+use skia_safe::{surfaces, EncodedImageFormat};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Gap in pixels kept between packed sprites, so filtering never bleeds a sprite into its
+/// neighbours.
+const ATLAS_PADDING: i32 = 1;
+
+/// Where a single node landed in an [`Atlas`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasEntry {
+    pub node_id: NodeId,
+    /// The sprite rect in atlas pixels.
+    pub rect_in_atlas: Rectangle,
+}
+
+/// A PNG holding several node rasterizations, with the layout of each sprite.
+#[derive(Debug, Clone)]
+pub struct Atlas {
+    pub png: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+    pub entries: Vec<AtlasEntry>,
+}
+
+/// Packs `sizes` into shelves no wider than `max_width`, tallest first.
+///
+/// Returns the top-left corner of each size (in input order) and the total packed
+/// `(width, height)`. Sizes wider than `max_width` get a shelf of their own.
+pub fn pack_shelves(
+    sizes: &[(i32, i32)],
+    max_width: i32,
+    padding: i32,
+) -> (Vec<(i32, i32)>, (i32, i32)) {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].1.cmp(&sizes[*a].1));
+
+    let mut positions = vec![(0, 0); sizes.len()];
+    let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+    let (mut width, mut height) = (0, 0);
+
+    for i in order {
+        let (w, h) = sizes[i];
+        if x > 0 && x + w > max_width {
+            // start a new shelf below the current one
+            y += shelf_height + padding;
+            x = 0;
+            shelf_height = 0;
+        }
+        positions[i] = (x, y);
+        x += w + padding;
+        shelf_height = shelf_height.max(h);
+        width = width.max(x - padding);
+        height = height.max(y + h);
+    }
+
+    (positions, (width, height))
+}
+
+/// Rasterizes each of `node_ids` at `scale` and packs the results into a single PNG atlas.
+///
+/// Each node is drawn in isolation (siblings and the scene background are left out) at its
+/// render bounds, including the transforms of its ancestors. Nodes missing from `geometry`
+/// or with empty bounds are skipped. Text and images are drawn without loaded resources.
+/// Returns `None` if there is nothing to pack or the surface cannot be created.
+pub fn export_nodes_as_atlas(
+    scene: &Scene,
+    geometry: &GeometryCache,
+    node_ids: &[NodeId],
+    scale: f32,
+) -> Option<Atlas> {
+    let mut sprites = Vec::new();
+    for id in node_ids {
+        let Some(bounds) = geometry.get_render_bounds(id) else {
+            continue;
+        };
+        let size = (
+            (bounds.width * scale).ceil() as i32,
+            (bounds.height * scale).ceil() as i32,
+        );
+        if size.0 <= 0 || size.1 <= 0 {
+            continue;
+        }
+        sprites.push((id, bounds, size));
+    }
+    if sprites.is_empty() {
+        return None;
+    }
+
+    // aim for a roughly square atlas, but never narrower than the widest sprite
+    let sizes: Vec<(i32, i32)> = sprites.iter().map(|(_, _, size)| *size).collect();
+    let area: i64 = sizes
+        .iter()
+        .map(|(w, h)| (*w + ATLAS_PADDING) as i64 * (*h + ATLAS_PADDING) as i64)
+        .sum();
+    let widest = sizes.iter().map(|(w, _)| *w).max().unwrap_or(0);
+    let max_width = widest.max((area as f64).sqrt().ceil() as i32);
+    let (positions, (width, height)) = pack_shelves(&sizes, max_width, ATLAS_PADDING);
+
+    let mut surface = surfaces::raster_n32_premul((width, height))?;
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+
+    let mut entries = Vec::with_capacity(sprites.len());
+    for ((id, bounds, (w, h)), (x, y)) in sprites.into_iter().zip(positions) {
+        let Some(node) = scene.nodes.get(id) else {
+            continue;
+        };
+        let parent_transform = geometry
+            .get_parent(id)
+            .and_then(|parent| geometry.get_world_transform(&parent))
+            .unwrap_or(scene.transform);
+        let rect = Rectangle {
+            x: x as f32,
+            y: y as f32,
+            width: w as f32,
+            height: h as f32,
+        };
+
+        canvas.save();
+        canvas.clip_rect(
+            skia_safe::Rect::from_xywh(rect.x, rect.y, rect.width, rect.height),
+            None,
+            None,
+        );
+        canvas.translate((rect.x, rect.y));
+        canvas.scale((scale, scale));
+        canvas.translate((-bounds.x, -bounds.y));
+        canvas.concat(&cvt::sk_matrix(parent_transform.matrix));
+        painter.draw_node_recursively(node, &scene.nodes, geometry);
+        canvas.restore();
+
+        entries.push(AtlasEntry {
+            node_id: id.clone(),
+            rect_in_atlas: rect,
+        });
+    }
+
+    let png = surface
+        .image_snapshot()
+        .encode(None, EncodedImageFormat::PNG, None)?
+        .to_vec();
+
+    Some(Atlas {
+        png,
+        width,
+        height,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shelves_do_not_exceed_max_width() {
+        let (positions, (width, height)) = pack_shelves(&[(40, 10), (40, 30), (40, 20)], 90, 1);
+
+        // tallest first, the third sprite wraps onto a second shelf
+        assert_eq!(positions, vec![(0, 31), (0, 0), (41, 0)]);
+        assert_eq!((width, height), (81, 41));
+    }
+
+    #[test]
+    fn oversized_sprite_gets_its_own_shelf() {
+        let (positions, (width, _)) = pack_shelves(&[(10, 10), (200, 5)], 50, 0);
+        assert_eq!(positions, vec![(0, 0), (0, 10)]);
+        assert_eq!(width, 200);
+    }
+}
@@ -1,13 +1,13 @@
 use crate::{
     export::{ExportAsImage, ExportSize, Exported},
-    node::schema::Scene,
+    node::schema::{Scene, Size},
     runtime::{
         camera::Camera2D,
         scene::{Backend, Renderer},
     },
 };
 use math2::Rectangle;
-use skia_safe::EncodedImageFormat;
+use skia_safe::{surfaces, EncodedImageFormat};
 
 impl Into<EncodedImageFormat> for ExportAsImage {
     fn into(self) -> EncodedImageFormat {
@@ -57,3 +57,33 @@ pub fn export_node_as_image(
 
     exported
 }
+
+/// Renders the whole scene into a `width` x `height` PNG.
+///
+/// The scene origin is placed at the top-left corner and scaled by `scale` (e.g. `2.0` for
+/// a 2x export). The scene transform and `background_color` are honored, the image is
+/// transparent where nothing is painted. Returns an empty buffer if the surface cannot be
+/// created or encoding fails.
+pub fn render_scene_to_png(scene: &Scene, width: i32, height: i32, scale: f32) -> Vec<u8> {
+    let Some(mut surface) = surfaces::raster_n32_premul((width, height)) else {
+        return Vec::new();
+    };
+
+    let mut camera = Camera2D::new(Size {
+        width: width as f32,
+        height: height as f32,
+    });
+    camera.set_zoom(scale);
+    camera.set_center(width as f32 / scale / 2.0, height as f32 / scale / 2.0);
+
+    let mut r = Renderer::new(Backend::new_from_raster(width, height), None, camera);
+    r.load_scene(scene.clone());
+    r.render_to_canvas(surface.canvas(), width as f32, height as f32);
+    r.free();
+
+    surface
+        .image_snapshot()
+        .encode(None, EncodedImageFormat::PNG, None)
+        .map(|data| data.to_vec())
+        .unwrap_or_default()
+}
@@ -1,5 +1,6 @@
 use crate::{
     export::{ExportAsSVG, Exported},
+    helpers::precision::round_numbers_in_markup,
     node::schema::Scene,
     runtime::{
         camera::Camera2D,
@@ -12,7 +13,7 @@ use skia_safe::{svg, Rect as SkRect};
 pub fn export_node_as_svg(
     scene: &Scene,
     rect: Rectangle,
-    _options: ExportAsSVG,
+    options: ExportAsSVG,
 ) -> Option<Exported> {
     let width = rect.width;
     let height = rect.height;
@@ -39,5 +40,8 @@ pub fn export_node_as_svg(
 
     renderer.free();
 
-    Some(Exported::SVG(data.as_bytes().to_vec()))
+    let svg = String::from_utf8_lossy(data.as_bytes());
+    let svg = round_numbers_in_markup(&svg, options.precision);
+
+    Some(Exported::SVG(svg.into_bytes()))
 }
@@ -1,3 +1,4 @@
+pub mod export_as_atlas;
 pub mod export_as_image;
 pub mod export_as_pdf;
 pub mod export_as_svg;
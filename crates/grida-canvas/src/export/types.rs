@@ -1,3 +1,4 @@
+use crate::helpers::precision::DEFAULT_PRECISION;
 use serde::Deserialize;
 
 #[derive(Clone, Deserialize)]
@@ -49,6 +50,27 @@ pub struct ExportAsPDF {
 #[derive(Clone, Deserialize)]
 pub struct ExportAsSVG {
     // svg export does not support constraints
+    /// Number of decimal places kept for coordinates in the output.
+    #[serde(default = "default_precision")]
+    pub(crate) precision: usize,
+}
+
+fn default_precision() -> usize {
+    DEFAULT_PRECISION
+}
+
+impl Default for ExportAsSVG {
+    fn default() -> Self {
+        Self {
+            precision: DEFAULT_PRECISION,
+        }
+    }
+}
+
+impl ExportAsSVG {
+    pub fn with_precision(precision: usize) -> Self {
+        Self { precision }
+    }
 }
 
 #[derive(Clone, Deserialize)]
@@ -115,7 +137,7 @@ impl ExportAs {
     }
 
     pub fn svg() -> Self {
-        Self::SVG(ExportAsSVG {})
+        Self::SVG(ExportAsSVG::default())
     }
 
     pub fn is_format_image(&self) -> bool {
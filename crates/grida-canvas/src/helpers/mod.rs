@@ -1 +1,2 @@
+pub mod precision;
 pub mod webfont_helper;
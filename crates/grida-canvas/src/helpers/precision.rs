@@ -0,0 +1,189 @@
+use serde_json::Value;
+
+/// Default number of decimal places kept for exported coordinates.
+pub const DEFAULT_PRECISION: usize = 3;
+
+/// Rounds `value` to `precision` decimal places.
+pub fn round(value: f64, precision: usize) -> f64 {
+    let factor = 10f64.powi(precision as i32);
+    let rounded = (value * factor).round() / factor;
+    // avoid emitting `-0`
+    if rounded == 0.0 {
+        0.0
+    } else {
+        rounded
+    }
+}
+
+/// Formats `value` with at most `precision` decimal places, without trailing zeros.
+///
+/// ```
+/// use cg::helpers::precision::format_number;
+/// assert_eq!(format_number(12.3456, 2), "12.35");
+/// assert_eq!(format_number(3.0, 2), "3");
+/// ```
+pub fn format_number(value: f32, precision: usize) -> String {
+    format!("{}", round(value as f64, precision))
+}
+
+/// Recursively rounds every floating point number in a JSON value.
+///
+/// Integers are left untouched.
+pub fn round_json_value(value: &mut Value, precision: usize) {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            if let Some(rounded) = n
+                .as_f64()
+                .and_then(|v| serde_json::Number::from_f64(round(v, precision)))
+            {
+                *n = rounded;
+            }
+        }
+        Value::Array(arr) => {
+            for v in arr.iter_mut() {
+                round_json_value(v, precision);
+            }
+        }
+        Value::Object(map) => {
+            for (_, v) in map.iter_mut() {
+                round_json_value(v, precision);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Attributes whose values are never treated as numeric.
+const NON_NUMERIC_ATTRIBUTES: [&str; 5] = ["id", "class", "version", "href", "xlink:href"];
+
+/// Rounds the decimal numbers found in the attribute values of an XML/SVG document.
+///
+/// Only tokens with a fractional part are rewritten. Identifier-like attributes
+/// (`id`, `href`, ...), numbers in exponent notation and text content between
+/// tags are left as-is.
+pub fn round_numbers_in_markup(markup: &str, precision: usize) -> String {
+    let mut out = String::with_capacity(markup.len());
+    let mut in_tag = false;
+    let mut rest = markup;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            '"' | '\'' if in_tag => {
+                let Some(close) = rest[1..].find(c) else {
+                    break;
+                };
+                let value = &rest[1..close + 1];
+                let numeric = out
+                    .trim_end()
+                    .strip_suffix('=')
+                    .map(|s| s.trim_end())
+                    .and_then(|s| s.rsplit(|ch: char| ch.is_whitespace()).next())
+                    .map_or(true, |name| !NON_NUMERIC_ATTRIBUTES.contains(&name));
+
+                out.push(c);
+                if !numeric {
+                    out.push_str(value);
+                } else {
+                    out.push_str(&round_numbers_in_value(value, precision));
+                }
+                out.push(c);
+                rest = &rest[close + 2..];
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Rounds the decimal numbers in a single attribute value, e.g. SVG path data.
+///
+/// ```
+/// use cg::helpers::precision::round_numbers_in_value;
+/// assert_eq!(round_numbers_in_value("M0.12345 1L2.5 3.14159", 2), "M0.12 1L2.5 3.14");
+/// ```
+pub fn round_numbers_in_value(value: &str, precision: usize) -> String {
+    let bytes = value.as_bytes();
+    let mut out = String::with_capacity(value.len());
+    let mut last = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let prev = if i > 0 { bytes[i - 1] } else { b' ' };
+        let starts_number = c.is_ascii_digit() || c == b'.' || c == b'-';
+        let continues_token = prev.is_ascii_digit() || prev == b'.' || prev == b'_' || prev == b'#';
+        if !starts_number || continues_token {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut end = i;
+        if bytes[end] == b'-' {
+            end += 1;
+        }
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        let mut has_fraction = false;
+        if end < bytes.len() && bytes[end] == b'.' {
+            end += 1;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+                has_fraction = true;
+            }
+        }
+
+        let exponent = end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E');
+        if has_fraction && !exponent {
+            if let Ok(v) = value[start..end].parse::<f64>() {
+                out.push_str(&value[last..start]);
+                out.push_str(&format!("{}", round(v, precision)));
+                last = end;
+            }
+        }
+        i = end.max(start + 1);
+    }
+
+    out.push_str(&value[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_to_precision() {
+        assert_eq!(round(12.3456, 2), 12.35);
+        assert_eq!(round(-0.0004, 3), 0.0);
+        assert_eq!(format_number(12.3456, 2), "12.35");
+    }
+
+    #[test]
+    fn rounds_numbers_in_svg_attributes() {
+        let svg = r#"<path id="p1.2345" d="M12.3456 -7.891011L0.5 1e-7" transform="matrix(1 0 0 1 12.3456 4)"/><text>1.23456</text>"#;
+        let rounded = round_numbers_in_markup(svg, 2);
+        assert_eq!(
+            rounded,
+            r#"<path id="p1.2345" d="M12.35 -7.89L0.5 1e-7" transform="matrix(1 0 0 1 12.35 4)"/><text>1.23456</text>"#
+        );
+    }
+
+    #[test]
+    fn rounds_json_floats_only() {
+        let mut value = serde_json::json!({ "x": 12.3456, "n": 7, "list": [0.12345] });
+        round_json_value(&mut value, 2);
+        assert_eq!(
+            value,
+            serde_json::json!({ "x": 12.35, "n": 7, "list": [0.12] })
+        );
+    }
+}
@@ -10,7 +10,7 @@ use math2::{rect, rect::Rectangle, vector2::Vector2};
 /// identifiers that intersect a screen point.
 ///
 /// Hit testing happens in a few steps:
-/// 1. Filter nodes whose render bounds contain the point
+/// 1. Filter nodes whose render bounds contain the point, skipping locked layers
 /// 2. Sort the filtered nodes by z-index (which reflects tree order)
 /// 3. Return the first match (path level checks TBD)
 ///
@@ -34,6 +34,9 @@ impl<'a> HitTester<'a> {
         indices.sort();
         for idx in indices.into_iter().rev() {
             let layer = &self.cache.layers.layers[idx];
+            if layer.base().locked {
+                continue;
+            }
             if let Some(bounds) = self.cache.geometry.get_render_bounds(layer.id()) {
                 if rect::contains_point(&bounds, point) {
                     return Some(layer.id().clone());
@@ -50,6 +53,9 @@ impl<'a> HitTester<'a> {
         let mut out = Vec::with_capacity(indices.len());
         for idx in indices.into_iter().rev() {
             let layer = &self.cache.layers.layers[idx];
+            if layer.base().locked {
+                continue;
+            }
             if let Some(bounds) = self.cache.geometry.get_render_bounds(layer.id()) {
                 if rect::contains_point(&bounds, point) {
                     out.push(layer.id().clone());
@@ -78,11 +84,15 @@ impl<'a> HitTester<'a> {
         indices.sort();
         for idx in indices.into_iter().rev() {
             let layer = &self.cache.layers.layers[idx];
+            if layer.base().locked {
+                continue;
+            }
             if let Some(bounds) = self.cache.geometry.get_render_bounds(layer.id()) {
                 if rect::contains_point(&bounds, point) {
                     let base = match layer {
                         crate::painter::layer::PainterPictureLayer::Shape(s) => &s.base,
                         crate::painter::layer::PainterPictureLayer::Text(t) => &t.base,
+                        crate::painter::layer::PainterPictureLayer::RichText(t) => &t.base,
                     };
                     let mut path = if let Some(entry) = self.cache.path.borrow().get(layer.id()) {
                         (*entry.path).clone()
@@ -109,11 +119,15 @@ impl<'a> HitTester<'a> {
         let mut out = Vec::with_capacity(indices.len());
         for idx in indices.into_iter().rev() {
             let layer = &self.cache.layers.layers[idx];
+            if layer.base().locked {
+                continue;
+            }
             if let Some(bounds) = self.cache.geometry.get_render_bounds(layer.id()) {
                 if rect::contains_point(&bounds, point) {
                     let base = match layer {
                         crate::painter::layer::PainterPictureLayer::Shape(s) => &s.base,
                         crate::painter::layer::PainterPictureLayer::Text(t) => &t.base,
+                        crate::painter::layer::PainterPictureLayer::RichText(t) => &t.base,
                     };
                     let mut path = if let Some(entry) = self.cache.path.borrow().get(layer.id()) {
                         (*entry.path).clone()
@@ -137,6 +151,7 @@ impl<'a> HitTester<'a> {
             let base = match layer {
                 crate::painter::layer::PainterPictureLayer::Shape(s) => &s.base,
                 crate::painter::layer::PainterPictureLayer::Text(t) => &t.base,
+                crate::painter::layer::PainterPictureLayer::RichText(t) => &t.base,
             };
             let mut path = if let Some(entry) = self.cache.path.borrow().get(id) {
                 (*entry.path).clone()
@@ -160,6 +175,9 @@ impl<'a> HitTester<'a> {
         let mut out = Vec::with_capacity(indices.len());
         for idx in indices.into_iter().rev() {
             let layer = &self.cache.layers.layers[idx];
+            if layer.base().locked {
+                continue;
+            }
             if let Some(bounds) = self.cache.geometry.get_render_bounds(layer.id()) {
                 if rect::intersects(&bounds, rect) {
                     out.push(layer.id().clone());
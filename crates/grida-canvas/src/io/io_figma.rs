@@ -2,16 +2,20 @@ use crate::helpers::webfont_helper;
 use crate::node::repository::NodeRepository;
 use crate::node::schema::{
     BaseNode, BlendMode, BooleanPathOperation, BooleanPathOperationNode, Color, ContainerNode,
-    EllipseNode, ErrorNode, FeBackdropBlur, FeDropShadow, FeGaussianBlur, FilterEffect, FontWeight,
-    GradientStop, ImagePaint, LineNode, LinearGradientPaint, Node, NodeId, Paint, PathNode,
-    RadialGradientPaint, RectangleNode, RectangularCornerRadius, RegularPolygonNode,
-    RegularStarPolygonNode, Scene, Size, SolidPaint, StrokeAlign, TextAlign, TextAlignVertical,
-    TextDecoration, TextSpanNode, TextStyle, TextTransform,
+    EdgeInsets, EllipseNode, ErrorNode, FeBackdropBlur, FeDropShadow, FeGaussianBlur,
+    FeInnerShadow, FillRule, FilterEffect, FontWeight, GradientStop, ImagePaint, LetterSpacing,
+    LineHeight, LineNode, LinearGradientPaint, Node, NodeId, Paint, PathNode, RadialGradientPaint,
+    RectangleNode, RectangularCornerRadius, RegularPolygonNode, RegularStarPolygonNode, Scene,
+    Size, SolidPaint, StrokeAlign, StrokeCap, TextAlign, TextAlignVertical, TextDecoration,
+    TextDirection, TextOverflow, TextSizingMode, TextSpanNode, TextStyle, TextTransform,
 };
+use figma_api::models::line_node::StrokeCap as FigmaStrokeCap;
 use figma_api::models::minimal_strokes_trait::StrokeAlign as FigmaStrokeAlign;
+use figma_api::models::path::WindingRule as FigmaWindingRule;
 use figma_api::models::type_style::{
     TextAlignHorizontal as FigmaTextAlignHorizontal, TextAlignVertical as FigmaTextAlignVertical,
-    TextDecoration as FigmaTextDecoration,
+    TextAutoResize as FigmaTextAutoResize, TextDecoration as FigmaTextDecoration,
+    TextTruncation as FigmaTextTruncation,
 };
 use figma_api::models::vector::Vector;
 use figma_api::models::{
@@ -37,22 +41,22 @@ const BLACK: Paint = Paint::Solid(SolidPaint {
 // Map implementations
 impl From<&Rgba> for Color {
     fn from(color: &Rgba) -> Self {
-        Color(
-            (color.r * 255.0) as u8,
-            (color.g * 255.0) as u8,
-            (color.b * 255.0) as u8,
-            (color.a * 255.0) as u8,
+        Color::from_rgba_f32(
+            color.r as f32,
+            color.g as f32,
+            color.b as f32,
+            color.a as f32,
         )
     }
 }
 
 impl From<&Box<Rgba>> for Color {
     fn from(color: &Box<Rgba>) -> Self {
-        Color(
-            (color.r * 255.0) as u8,
-            (color.g * 255.0) as u8,
-            (color.b * 255.0) as u8,
-            (color.a * 255.0) as u8,
+        Color::from_rgba_f32(
+            color.r as f32,
+            color.g as f32,
+            color.b as f32,
+            color.a as f32,
         )
     }
 }
@@ -87,11 +91,21 @@ impl From<&FigmaPaint> for Paint {
                     }
                 };
 
+                let repeat = if matches!(
+                    image.scale_mode,
+                    figma_api::models::image_paint::ScaleMode::Tile
+                ) {
+                    ImageRepeat::Repeat
+                } else {
+                    ImageRepeat::NoRepeat
+                };
+
                 Paint::Image(ImagePaint {
                     transform,
                     _ref: image.image_ref.clone(),
                     fit,
                     opacity: image.opacity.unwrap_or(1.0) as f32,
+                    repeat,
                 })
             }
             FigmaPaint::GradientPaint(gradient) => {
@@ -112,6 +126,7 @@ impl From<&FigmaPaint> for Paint {
                             ),
                             stops,
                             opacity: gradient.opacity.unwrap_or(1.0) as f32,
+                            spread: GradientSpread::Pad,
                         })
                     }
                     figma_api::models::gradient_paint::Type::GradientRadial => {
@@ -121,6 +136,7 @@ impl From<&FigmaPaint> for Paint {
                             ),
                             stops,
                             opacity: gradient.opacity.unwrap_or(1.0) as f32,
+                            spread: GradientSpread::Pad,
                         })
                     }
                     _ => Paint::Solid(SolidPaint {
@@ -147,6 +163,17 @@ impl From<&FigmaStrokeAlign> for StrokeAlign {
     }
 }
 
+impl From<&FigmaStrokeCap> for StrokeCap {
+    fn from(cap: &FigmaStrokeCap) -> Self {
+        match cap {
+            FigmaStrokeCap::Round => StrokeCap::Round,
+            FigmaStrokeCap::Square => StrokeCap::Square,
+            // arrow and marker caps are not supported, the stroke ends flat
+            _ => StrokeCap::Butt,
+        }
+    }
+}
+
 impl From<&FigmaTextAlignHorizontal> for TextAlign {
     fn from(align: &FigmaTextAlignHorizontal) -> Self {
         match align {
@@ -178,6 +205,16 @@ impl From<&FigmaTextDecoration> for TextDecoration {
     }
 }
 
+impl From<&FigmaTextAutoResize> for TextSizingMode {
+    fn from(resize: &FigmaTextAutoResize) -> Self {
+        match resize {
+            FigmaTextAutoResize::WidthAndHeight => TextSizingMode::Auto,
+            FigmaTextAutoResize::Height => TextSizingMode::AutoHeight,
+            FigmaTextAutoResize::None | FigmaTextAutoResize::Truncate => TextSizingMode::Fixed,
+        }
+    }
+}
+
 fn map_option<'a, T, U>(value: Option<&'a T>) -> Option<U>
 where
     U: From<&'a T>,
@@ -314,11 +351,21 @@ impl FigmaConverter {
                     }
                 };
 
+                let repeat = if matches!(
+                    image.scale_mode,
+                    figma_api::models::image_paint::ScaleMode::Tile
+                ) {
+                    ImageRepeat::Repeat
+                } else {
+                    ImageRepeat::NoRepeat
+                };
+
                 Paint::Image(ImagePaint {
                     transform,
                     _ref: url,
                     fit,
                     opacity: image.opacity.unwrap_or(1.0) as f32,
+                    repeat,
                 })
             }
             FigmaPaint::GradientPaint(gradient) => {
@@ -339,6 +386,7 @@ impl FigmaConverter {
                             ),
                             stops,
                             opacity: gradient.opacity.unwrap_or(1.0) as f32,
+                            spread: GradientSpread::Pad,
                         })
                     }
                     figma_api::models::gradient_paint::Type::GradientRadial => {
@@ -348,6 +396,7 @@ impl FigmaConverter {
                             ),
                             stops,
                             opacity: gradient.opacity.unwrap_or(1.0) as f32,
+                            spread: GradientSpread::Pad,
                         })
                     }
                     _ => Paint::Solid(SolidPaint {
@@ -426,6 +475,14 @@ impl FigmaConverter {
         visible.unwrap_or(true).then_some(1.0).unwrap_or(0.0)
     }
 
+    /// Convert Figma's path winding rule to our FillRule
+    fn convert_winding_rule(rule: FigmaWindingRule) -> FillRule {
+        match rule {
+            FigmaWindingRule::Nonzero => FillRule::NonZero,
+            FigmaWindingRule::Evenodd => FillRule::EvenOdd,
+        }
+    }
+
     /// Convert Figma's text decoration to our TextDecoration
     fn convert_text_decoration(decoration: Option<&FigmaTextDecoration>) -> TextDecoration {
         map_option(decoration).unwrap_or(TextDecoration::None)
@@ -441,52 +498,46 @@ impl FigmaConverter {
         map_option(align).unwrap_or(TextAlignVertical::Top)
     }
 
-    /// Convert Figma's effects to our FilterEffect
-    fn convert_effects(effects: Option<&Vec<Effect>>) -> Option<FilterEffect> {
-        // If no effects, return None
-        let effects = effects?;
-        if effects.is_empty() {
-            return None;
-        }
+    /// Convert Figma's effects to our FilterEffects
+    fn convert_effects(effects: Option<&Vec<Effect>>) -> Vec<FilterEffect> {
+        let Some(effects) = effects else {
+            return vec![];
+        };
 
-        // Filter visible effects first
-        let visible_effects: Vec<&Effect> = effects
+        effects
             .iter()
-            .filter(|effect| match effect {
-                Effect::DropShadow(drop_shadow) => drop_shadow.visible,
-                Effect::LayerBlur(blur) => blur.visible,
-                Effect::BackgroundBlur(blur) => blur.visible,
-                Effect::InnerShadow(inner_shadow) => inner_shadow.visible,
-                _ => true,
-            })
-            .collect();
-
-        // Find the first valid effect
-        for effect in visible_effects {
-            match effect {
-                Effect::DropShadow(drop_shadow) => {
-                    return Some(FilterEffect::DropShadow(FeDropShadow {
+            .filter_map(|effect| match effect {
+                Effect::DropShadow(drop_shadow) if drop_shadow.visible => {
+                    Some(FilterEffect::DropShadow(FeDropShadow {
                         dx: drop_shadow.offset.x as f32,
                         dy: drop_shadow.offset.y as f32,
                         blur: drop_shadow.radius as f32,
+                        spread: drop_shadow.spread.unwrap_or(0.0) as f32,
                         color: Self::convert_color(&drop_shadow.color),
-                    }));
+                    }))
                 }
-                Effect::LayerBlur(blur) => {
-                    return Some(FilterEffect::GaussianBlur(FeGaussianBlur {
+                Effect::InnerShadow(inner_shadow) if inner_shadow.visible => {
+                    Some(FilterEffect::InnerShadow(FeInnerShadow {
+                        dx: inner_shadow.offset.x as f32,
+                        dy: inner_shadow.offset.y as f32,
+                        blur: inner_shadow.radius as f32,
+                        spread: inner_shadow.spread.unwrap_or(0.0) as f32,
+                        color: Self::convert_color(&inner_shadow.color),
+                    }))
+                }
+                Effect::LayerBlur(blur) if blur.visible => {
+                    Some(FilterEffect::GaussianBlur(FeGaussianBlur {
                         radius: blur.radius as f32,
-                    }));
+                    }))
                 }
-                Effect::BackgroundBlur(blur) => {
-                    return Some(FilterEffect::BackdropBlur(FeBackdropBlur {
+                Effect::BackgroundBlur(blur) if blur.visible => {
+                    Some(FilterEffect::BackdropBlur(FeBackdropBlur {
                         radius: blur.radius as f32,
-                    }));
+                    }))
                 }
-                _ => continue, // Skip unsupported effects
-            }
-        }
-
-        None // No valid effects found
+                _ => None, // Skip hidden and unsupported effects
+            })
+            .collect()
     }
 
     /// Convert Figma's slice to our SliceNode
@@ -496,6 +547,8 @@ impl FigmaConverter {
                 id: slice.id.clone(),
                 name: format!("[Slice] {}", slice.name),
                 active: slice.visible.unwrap_or(true),
+                locked: slice.locked.unwrap_or(false),
+                render_order: None,
             },
             transform: AffineTransform::identity(),
             size: Size {
@@ -526,6 +579,8 @@ impl FigmaConverter {
                 id: component.id.clone(),
                 name: component.name.clone(),
                 active: component.visible.unwrap_or(true),
+                locked: component.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: Self::convert_blend_mode(component.blend_mode),
             transform,
@@ -534,6 +589,7 @@ impl FigmaConverter {
                 component.corner_radius,
                 component.rectangle_corner_radii.as_ref(),
             ),
+            corner_smoothing: component.corner_smoothing.unwrap_or(0.0) as f32,
             fill: self
                 .convert_fills(Some(&component.fills.as_ref()))
                 .unwrap_or(TRANSPARENT),
@@ -550,9 +606,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
-            effect: Self::convert_effects(Some(&component.effects)),
+            stroke_dash_offset: None,
+            effects: Self::convert_effects(Some(&component.effects)),
             children,
             opacity: Self::convert_opacity(component.visible),
+            padding: EdgeInsets::zero(),
             clip: component.clips_content,
         }))
     }
@@ -567,6 +625,8 @@ impl FigmaConverter {
                 id: component_set.id.clone(),
                 name: format!("[ComponentSet] {}", component_set.name),
                 active: component_set.visible.unwrap_or(true),
+                locked: component_set.locked.unwrap_or(false),
+                render_order: None,
             },
             transform: Self::convert_transform(component_set.relative_transform.as_ref()),
             size: Self::convert_size(component_set.size.as_ref()),
@@ -625,6 +685,8 @@ impl FigmaConverter {
                 id: instance.id.clone(),
                 name: instance.name.clone(),
                 active: instance.visible.unwrap_or(true),
+                locked: instance.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: Self::convert_blend_mode(instance.blend_mode),
             transform,
@@ -633,6 +695,7 @@ impl FigmaConverter {
                 instance.corner_radius,
                 instance.rectangle_corner_radii.as_ref(),
             ),
+            corner_smoothing: instance.corner_smoothing.unwrap_or(0.0) as f32,
             fill: self
                 .convert_fills(Some(&instance.fills.as_ref()))
                 .unwrap_or(TRANSPARENT),
@@ -649,9 +712,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
-            effect: Self::convert_effects(Some(&instance.effects)),
+            stroke_dash_offset: None,
+            effects: Self::convert_effects(Some(&instance.effects)),
             children,
             opacity: Self::convert_opacity(instance.visible),
+            padding: EdgeInsets::zero(),
             clip: instance.clips_content,
         }))
     }
@@ -669,11 +734,14 @@ impl FigmaConverter {
                 id: section.id.clone(),
                 name: format!("[Section] {}", section.name),
                 active: section.visible.unwrap_or(true),
+                locked: section.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
             transform: Self::convert_transform(section.relative_transform.as_ref()),
             size: Self::convert_size(section.size.as_ref()),
             corner_radius: RectangularCornerRadius::zero(),
+            corner_smoothing: 0.0,
             children,
             fill: self
                 .convert_fills(Some(&section.fills.as_ref()))
@@ -682,8 +750,10 @@ impl FigmaConverter {
             stroke_width: 0.0,
             stroke_align: StrokeAlign::Inside,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(section.visible),
-            effect: None,
+            effects: vec![],
+            padding: EdgeInsets::zero(),
             clip: false,
         }))
     }
@@ -695,6 +765,8 @@ impl FigmaConverter {
                 id: link.id.clone(),
                 name: format!("[Link] {}", link.name),
                 active: link.visible.unwrap_or(true),
+                locked: link.locked.unwrap_or(false),
+                render_order: None,
             },
             transform: AffineTransform::identity(),
             size: Size {
@@ -764,12 +836,10 @@ impl FigmaConverter {
             .collect::<Result<Vec<_>, _>>()?;
         // canvas.background_color
         Ok(Scene {
-            id: canvas.id.clone(),
-            name: canvas.name.clone(),
-            transform: AffineTransform::identity(),
             children,
             nodes: self.repository.clone(),
             background_color: Some(Color::from(&canvas.background_color)),
+            ..Scene::new(canvas.id.clone(), canvas.name.clone())
         })
     }
 
@@ -788,6 +858,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
             transform,
@@ -796,6 +868,7 @@ impl FigmaConverter {
                 origin.corner_radius,
                 origin.rectangle_corner_radii.as_ref(),
             ),
+            corner_smoothing: origin.corner_smoothing.unwrap_or(0.0) as f32,
             fill: self
                 .convert_fills(Some(&origin.fills.as_ref()))
                 .unwrap_or(TRANSPARENT),
@@ -812,9 +885,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            stroke_dash_offset: None,
+            effects: Self::convert_effects(Some(&origin.effects)),
             children,
             opacity: Self::convert_opacity(origin.visible),
+            padding: EdgeInsets::zero(),
             clip: origin.clips_content,
         }))
     }
@@ -838,7 +913,7 @@ impl FigmaConverter {
             figma_api::models::BlendMode::Saturation => BlendMode::Saturation,
             figma_api::models::BlendMode::Color => BlendMode::Color,
             figma_api::models::BlendMode::Luminosity => BlendMode::Luminosity,
-            figma_api::models::BlendMode::PassThrough => BlendMode::Normal,
+            figma_api::models::BlendMode::PassThrough => BlendMode::PassThrough,
             figma_api::models::BlendMode::LinearBurn => BlendMode::ColorBurn,
             figma_api::models::BlendMode::LinearDodge => BlendMode::ColorDodge,
         }
@@ -856,29 +931,36 @@ impl FigmaConverter {
             );
         }
 
+        let truncated = style.text_truncation == Some(FigmaTextTruncation::Ending);
+
         Ok(Node::TextSpan(TextSpanNode {
             base: BaseNode {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform: Self::convert_transform(origin.relative_transform.as_ref()),
             size: Size {
                 width: origin.size.as_ref().map_or(0.0, |size| size.x as f32),
                 height: origin.size.as_ref().map_or(0.0, |size| size.y as f32),
             },
+            sizing: map_option(style.text_auto_resize.as_ref()).unwrap_or_default(),
             text: origin.characters.clone(),
             text_style: TextStyle {
                 text_decoration: Self::convert_text_decoration(style.text_decoration.as_ref()),
-                font_family: style
+                font_families: vec![style
                     .font_family
                     .clone()
-                    .unwrap_or_else(|| "Inter".to_string()),
+                    .unwrap_or_else(|| "Inter".to_string())],
                 font_size: style.font_size.unwrap_or(14.0) as f32,
                 font_weight: FontWeight::new(style.font_weight.unwrap_or(400.0) as u32),
-                letter_spacing: style.letter_spacing.map(|v| v as f32),
+                letter_spacing: LetterSpacing::Px(style.letter_spacing.unwrap_or(0.0) as f32),
                 italic: style.italic.unwrap_or(false),
-                line_height: style.line_height_px.map(|v| v as f32),
+                line_height: style
+                    .line_height_px
+                    .map_or(LineHeight::Normal, |v| LineHeight::Px(v as f32)),
                 text_transform: match origin.style.text_case.as_ref() {
                     Some(figma_api::models::type_style::TextCase::Upper) => {
                         TextTransform::Uppercase
@@ -895,11 +977,21 @@ impl FigmaConverter {
                     }
                     None => TextTransform::None,
                 },
+                direction: TextDirection::Ltr,
             },
             text_align: Self::convert_text_align(style.text_align_horizontal.as_ref()),
             text_align_vertical: Self::convert_text_align_vertical(
                 style.text_align_vertical.as_ref(),
             ),
+            max_lines: truncated
+                .then_some(style.max_lines)
+                .flatten()
+                .map(|lines| lines as usize),
+            overflow: if truncated {
+                TextOverflow::Ellipsis
+            } else {
+                TextOverflow::Clip
+            },
             fill: self.convert_fills(Some(&origin.fills)).unwrap_or(BLACK),
             stroke: self.convert_strokes(Some(&origin.strokes)),
             stroke_width: Some(origin.stroke_weight.unwrap_or(0.0) as f32),
@@ -921,6 +1013,8 @@ impl FigmaConverter {
                         id: format!("{}-path-{}", origin.id, path_index),
                         name: format!("{}-path-{}", origin.name, path_index),
                         active: origin.visible.unwrap_or(true),
+                        locked: origin.locked.unwrap_or(false),
+                        render_order: None,
                     },
                     transform: AffineTransform::identity(),
                     fill: self
@@ -933,10 +1027,14 @@ impl FigmaConverter {
                     }),
                     stroke_width: 0.0,
                     stroke_align: StrokeAlign::Inside,
+                    stroke_cap: StrokeCap::Butt,
                     stroke_dash_array: None,
+                    stroke_dash_offset: None,
                     opacity: Self::convert_opacity(origin.visible),
                     blend_mode: Self::convert_blend_mode(origin.blend_mode),
-                    effect: Self::convert_effects(Some(&origin.effects)),
+                    effects: Self::convert_effects(Some(&origin.effects)),
+                    trim: None,
+                    fill_rule: Self::convert_winding_rule(geometry.winding_rule),
                 });
                 children.push(self.repository.insert(path_node));
                 path_index += 1;
@@ -952,6 +1050,8 @@ impl FigmaConverter {
                         id: format!("{}-path-{}", origin.id, path_index),
                         name: format!("{}-path-{}", origin.name, path_index),
                         active: origin.visible.unwrap_or(true),
+                        locked: origin.locked.unwrap_or(false),
+                        render_order: None,
                     },
                     transform: AffineTransform::identity(),
                     fill: self
@@ -961,10 +1061,14 @@ impl FigmaConverter {
                     stroke: TRANSPARENT,
                     stroke_width: 0.0,
                     stroke_align: StrokeAlign::Inside,
+                    stroke_cap: StrokeCap::Butt,
                     stroke_dash_array: None,
+                    stroke_dash_offset: None,
                     opacity: Self::convert_opacity(origin.visible),
                     blend_mode: Self::convert_blend_mode(origin.blend_mode),
-                    effect: Self::convert_effects(Some(&origin.effects)),
+                    effects: Self::convert_effects(Some(&origin.effects)),
+                    trim: None,
+                    fill_rule: Self::convert_winding_rule(geometry.winding_rule),
                 });
                 children.push(self.repository.insert(path_node));
                 path_index += 1;
@@ -977,19 +1081,24 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
             transform: Self::convert_transform(origin.relative_transform.as_ref()),
             size: Self::convert_size(origin.size.as_ref()),
             corner_radius: RectangularCornerRadius::zero(),
+            corner_smoothing: 0.0,
             fill: TRANSPARENT,
             stroke: None,
             stroke_width: 0.0,
             stroke_align: StrokeAlign::Inside,
             stroke_dash_array: None,
-            effect: None,
+            stroke_dash_offset: None,
+            effects: vec![],
             children,
             opacity: Self::convert_opacity(origin.visible),
+            padding: EdgeInsets::zero(),
             clip: false,
         }))
     }
@@ -1026,6 +1135,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             op: op,
@@ -1047,7 +1158,8 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            stroke_dash_offset: None,
+            effects: Self::convert_effects(Some(&origin.effects)),
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
         }))
@@ -1062,6 +1174,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             size,
@@ -1087,9 +1201,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            effects: Self::convert_effects(Some(&origin.effects)),
+            angle_offset: 0.0,
         }))
     }
 
@@ -1103,6 +1219,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             size,
@@ -1117,12 +1235,15 @@ impl FigmaConverter {
                     .map(|a| serde_json::to_string(a).unwrap_or_default())
                     .unwrap_or_else(|| "CENTER".to_string()),
             ),
+            stroke_cap: map_option(origin.stroke_cap.as_ref()).unwrap_or_default(),
             stroke_dash_array: origin
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
+            trim: None,
         }))
     }
 
@@ -1139,6 +1260,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             size,
@@ -1160,9 +1283,14 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            effects: Self::convert_effects(Some(&origin.effects)),
+            start_angle: origin.arc_data.starting_angle as f32,
+            end_angle: origin.arc_data.ending_angle as f32,
+            inner_radius_ratio: origin.arc_data.inner_radius as f32,
+            trim: None,
         }))
     }
 
@@ -1177,6 +1305,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             size,
@@ -1201,9 +1331,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            effects: Self::convert_effects(Some(&origin.effects)),
+            angle_offset: 0.0,
         }))
     }
 
@@ -1216,6 +1348,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             transform,
             size,
@@ -1223,6 +1357,7 @@ impl FigmaConverter {
                 origin.corner_radius,
                 origin.rectangle_corner_radii.as_ref(),
             ),
+            corner_smoothing: origin.corner_smoothing.unwrap_or(0.0) as f32,
             fill: self
                 .convert_fills(Some(&origin.fills))
                 .unwrap_or(TRANSPARENT),
@@ -1241,9 +1376,11 @@ impl FigmaConverter {
                 .stroke_dashes
                 .clone()
                 .map(|v| v.into_iter().map(|x| x as f32).collect()),
+            stroke_dash_offset: None,
             opacity: Self::convert_opacity(origin.visible),
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
-            effect: Self::convert_effects(Some(&origin.effects)),
+            effects: Self::convert_effects(Some(&origin.effects)),
+            trim: None,
         }))
     }
 
@@ -1262,6 +1399,8 @@ impl FigmaConverter {
                 id: origin.id.clone(),
                 name: origin.name.clone(),
                 active: origin.visible.unwrap_or(true),
+                locked: origin.locked.unwrap_or(false),
+                render_order: None,
             },
             blend_mode: Self::convert_blend_mode(origin.blend_mode),
             transform,
@@ -1270,14 +1409,17 @@ impl FigmaConverter {
                 origin.corner_radius,
                 origin.rectangle_corner_radii.as_ref(),
             ),
+            corner_smoothing: origin.corner_smoothing.unwrap_or(0.0) as f32,
             fill: self.convert_fills(None).unwrap_or(TRANSPARENT),
             stroke: None,
             stroke_width: 0.0,
             stroke_align: StrokeAlign::Inside,
             stroke_dash_array: None,
-            effect: None,
+            stroke_dash_offset: None,
+            effects: vec![],
             children,
             opacity: 1.0,
+            padding: EdgeInsets::zero(),
             clip: origin.clips_content,
         }))
     }
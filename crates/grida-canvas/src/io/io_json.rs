@@ -1,16 +1,25 @@
+use crate::helpers::precision::round_json_value;
+use crate::node::repository::NodeRepository;
 use crate::node::schema::*;
-use math2::transform::AffineTransform;
-use serde::Deserialize;
+use math2::box_fit::BoxFit;
+use math2::transform::{AffineTransform, TransformDecomposition};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOCanvasFile {
     pub version: String,
     pub document: IODocument,
+    /// Additional top-level fields (e.g. `author`, `created_at`), preserved for round-tripping.
+    #[serde(flatten)]
+    pub metadata: HashMap<String, Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IODocument {
     pub bitmaps: HashMap<String, serde_json::Value>,
     pub properties: HashMap<String, serde_json::Value>,
@@ -19,7 +28,7 @@ pub struct IODocument {
     pub entry_scene_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOScene {
     pub id: String,
     pub name: String,
@@ -28,11 +37,20 @@ pub struct IOScene {
     pub children: Vec<String>,
     #[serde(rename = "backgroundColor")]
     pub background_color: Option<RGBA>,
+    /// Image ref of the scene background, cover-fit to the viewport.
+    #[serde(
+        rename = "backgroundImage",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub background_image: Option<String>,
     pub guides: Option<Vec<serde_json::Value>>,
-    pub constraints: Option<HashMap<String, String>>,
+    /// Scene settings, and the layout constraints of nodes as
+    /// `"<node id>": { "horizontal": "stretch", "vertical": "top" }` entries.
+    pub constraints: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum IONode {
     #[serde(rename = "container")]
@@ -43,6 +61,8 @@ pub enum IONode {
     Vector(IOVectorNode),
     #[serde(rename = "path")]
     Path(IOPathNode),
+    #[serde(rename = "line")]
+    Line(IOLineNode),
     #[serde(rename = "ellipse")]
     Ellipse(IOEllipseNode),
     #[serde(rename = "rectangle")]
@@ -51,7 +71,74 @@ pub enum IONode {
     Unknown,
 }
 
-#[derive(Debug, Deserialize)]
+/// A width or height: a number of pixels, a percentage of the parent like `"50%"`, or
+/// `"auto"`.
+///
+/// Numeric strings are read as pixels. Anything else that cannot be read, such as `null`,
+/// becomes `Px(0.0)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dimension {
+    Auto,
+    Px(f32),
+    Percent(f32),
+}
+
+impl Dimension {
+    /// Resolves the dimension to pixels, percentages are relative to `parent`.
+    ///
+    /// Returns `None` for `Auto`, and for percentages without a parent.
+    pub fn resolve(&self, parent: Option<f32>) -> Option<f32> {
+        match self {
+            Dimension::Auto => None,
+            Dimension::Px(px) => Some(*px),
+            Dimension::Percent(percent) => parent.map(|parent| parent * percent / 100.0),
+        }
+    }
+}
+
+impl From<&Value> for Dimension {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Number(n) => Dimension::Px(n.as_f64().unwrap_or(0.0) as f32),
+            Value::String(s) => {
+                let s = s.trim();
+                if s == "auto" {
+                    Dimension::Auto
+                } else if let Some(percent) = s.strip_suffix('%') {
+                    Dimension::Percent(percent.trim().parse().unwrap_or(0.0))
+                } else {
+                    Dimension::Px(s.parse().unwrap_or(0.0))
+                }
+            }
+            _ => Dimension::Px(0.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Dimension {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Ok(Dimension::from(&value))
+    }
+}
+
+impl Serialize for Dimension {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Dimension::Auto => serializer.serialize_str("auto"),
+            Dimension::Px(px) => serializer.serialize_f32(*px),
+            Dimension::Percent(percent) => serializer.serialize_str(&format!("{percent}%")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOContainerNode {
     pub id: String,
     pub name: String,
@@ -63,13 +150,21 @@ pub struct IOContainerNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
     pub left: f32,
     pub top: f32,
-    pub width: serde_json::Value,
-    pub height: serde_json::Value,
+    pub width: Dimension,
+    pub height: Dimension,
     pub children: Vec<String>,
     pub expanded: Option<bool>,
     pub fill: Option<Fill>,
@@ -78,10 +173,18 @@ pub struct IOContainerNode {
     #[serde(
         rename = "cornerRadius",
         deserialize_with = "deserialize_corner_radius",
+        serialize_with = "serialize_corner_radius",
         default = "default_corner_radius"
     )]
     pub corner_radius: Option<RectangularCornerRadius>,
-    pub padding: Option<serde_json::Value>,
+    #[serde(rename = "cornerSmoothing")]
+    pub corner_smoothing: Option<f32>,
+    #[serde(
+        deserialize_with = "deserialize_padding",
+        serialize_with = "serialize_padding",
+        default
+    )]
+    pub padding: Option<EdgeInsets>,
     pub layout: Option<String>,
     pub direction: Option<String>,
     #[serde(rename = "mainAxisAlignment")]
@@ -130,7 +233,64 @@ where
     }
 }
 
-#[derive(Debug, Deserialize)]
+fn serialize_corner_radius<S>(
+    value: &Option<RectangularCornerRadius>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        None => serializer.serialize_none(),
+        Some(radius) if radius.is_uniform() => serializer.serialize_f32(radius.tl),
+        Some(radius) => [radius.tl, radius.tr, radius.bl, radius.br].serialize(serializer),
+    }
+}
+
+/// Reads padding as a single number for all sides, a `[vertical, horizontal]` pair, or
+/// `[top, right, bottom, left]`, like the CSS shorthand.
+fn deserialize_padding<'de, D>(deserializer: D) -> Result<Option<EdgeInsets>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    let as_f32 = |v: &serde_json::Value| v.as_f64().unwrap_or(0.0) as f32;
+
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => {
+            Some(EdgeInsets::all(n.as_f64().unwrap_or(0.0) as f32))
+        }
+        Some(serde_json::Value::Array(arr)) => match arr.as_slice() {
+            [vertical, horizontal] => {
+                Some(EdgeInsets::symmetric(as_f32(vertical), as_f32(horizontal)))
+            }
+            [top, right, bottom, left] => Some(EdgeInsets {
+                top: as_f32(top),
+                right: as_f32(right),
+                bottom: as_f32(bottom),
+                left: as_f32(left),
+            }),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn serialize_padding<S>(value: &Option<EdgeInsets>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        None => serializer.serialize_none(),
+        Some(p) if p.top == p.right && p.top == p.bottom && p.top == p.left => {
+            serializer.serialize_f32(p.top)
+        }
+        Some(p) if p.top == p.bottom && p.left == p.right => [p.top, p.right].serialize(serializer),
+        Some(p) => [p.top, p.right, p.bottom, p.left].serialize(serializer),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOTextNode {
     pub id: String,
     pub name: String,
@@ -142,6 +302,14 @@ pub struct IOTextNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
@@ -149,8 +317,10 @@ pub struct IOTextNode {
     pub top: f32,
     pub right: Option<f32>,
     pub bottom: Option<f32>,
-    pub width: serde_json::Value,
-    pub height: serde_json::Value,
+    /// `"auto"` hugs the content.
+    pub width: Dimension,
+    /// `"auto"` hugs the content.
+    pub height: Dimension,
     pub fill: Option<Fill>,
     pub style: Option<HashMap<String, serde_json::Value>>,
     pub text: String,
@@ -158,10 +328,18 @@ pub struct IOTextNode {
     pub text_align: TextAlign,
     #[serde(rename = "textAlignVertical", default = "default_text_align_vertical")]
     pub text_align_vertical: TextAlignVertical,
+    #[serde(rename = "maxLines")]
+    pub max_lines: Option<usize>,
+    #[serde(rename = "textOverflow", default)]
+    pub text_overflow: TextOverflow,
     #[serde(rename = "textDecoration", default = "default_text_decoration")]
     pub text_decoration: TextDecoration,
+    #[serde(default)]
+    pub direction: TextDirection,
+    /// Unitless, a multiple of the font size.
     #[serde(rename = "lineHeight")]
     pub line_height: Option<f32>,
+    /// In logical pixels.
     #[serde(rename = "letterSpacing")]
     pub letter_spacing: Option<f32>,
     #[serde(rename = "fontSize")]
@@ -172,7 +350,7 @@ pub struct IOTextNode {
     pub font_weight: FontWeight,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOVectorNode {
     pub id: String,
     pub name: String,
@@ -184,6 +362,14 @@ pub struct IOVectorNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
@@ -193,14 +379,23 @@ pub struct IOVectorNode {
     pub height: f32,
     pub fill: Option<Fill>,
     pub paths: Option<Vec<IOPath>>,
+    pub stroke: Option<Fill>,
+    #[serde(rename = "strokeWidth")]
+    pub stroke_width: Option<f32>,
+    #[serde(rename = "strokeAlign")]
+    pub stroke_align: Option<String>,
+    #[serde(rename = "strokeCap")]
+    pub stroke_cap: Option<String>,
+    #[serde(rename = "strokeDashArray")]
+    pub stroke_dash_array: Option<Vec<f32>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOVectorNetworkVertex {
     pub p: [f32; 2],
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOVectorNetworkSegment {
     pub a: usize,
     pub b: usize,
@@ -208,7 +403,7 @@ pub struct IOVectorNetworkSegment {
     pub tb: [f32; 2],
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOVectorNetwork {
     #[serde(default)]
     pub vertices: Vec<IOVectorNetworkVertex>,
@@ -216,7 +411,7 @@ pub struct IOVectorNetwork {
     pub segments: Vec<IOVectorNetworkSegment>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOPathNode {
     pub id: String,
     pub name: String,
@@ -228,6 +423,14 @@ pub struct IOPathNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
@@ -238,11 +441,55 @@ pub struct IOPathNode {
     #[serde(rename = "vectorNetwork")]
     pub vector_network: Option<IOVectorNetwork>,
     pub fill: Option<Fill>,
+    #[serde(rename = "fillRule")]
+    pub fill_rule: Option<String>,
+    pub stroke: Option<Fill>,
+    #[serde(rename = "strokeWidth")]
+    pub stroke_width: Option<f32>,
+    #[serde(rename = "strokeAlign")]
+    pub stroke_align: Option<String>,
+    #[serde(rename = "strokeCap")]
+    pub stroke_cap: Option<String>,
+    #[serde(rename = "strokeDashArray")]
+    pub stroke_dash_array: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IOLineNode {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default = "default_locked")]
+    pub locked: bool,
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    #[serde(default = "default_rotation")]
+    pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
+    #[serde(rename = "zIndex", default = "default_z_index")]
+    pub z_index: i32,
+    pub position: Option<String>,
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub stroke: Option<Fill>,
     #[serde(rename = "strokeWidth")]
     pub stroke_width: Option<f32>,
+    #[serde(rename = "strokeCap")]
+    pub stroke_cap: Option<String>,
+    #[serde(rename = "strokeDashArray")]
+    pub stroke_dash_array: Option<Vec<f32>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOEllipseNode {
     pub id: String,
     pub name: String,
@@ -254,6 +501,14 @@ pub struct IOEllipseNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
@@ -262,14 +517,19 @@ pub struct IOEllipseNode {
     pub width: f32,
     pub height: f32,
     pub fill: Option<Fill>,
+    pub stroke: Option<Fill>,
     #[serde(rename = "strokeWidth")]
     pub stroke_width: Option<f32>,
+    #[serde(rename = "strokeAlign")]
+    pub stroke_align: Option<String>,
     #[serde(rename = "strokeCap")]
     pub stroke_cap: Option<String>,
-    pub effects: Option<Vec<serde_json::Value>>,
+    #[serde(rename = "strokeDashArray")]
+    pub stroke_dash_array: Option<Vec<f32>>,
+    pub effects: Option<Vec<IOFilterEffect>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IORectangleNode {
     pub id: String,
     pub name: String,
@@ -281,6 +541,14 @@ pub struct IORectangleNode {
     pub opacity: f32,
     #[serde(default = "default_rotation")]
     pub rotation: f32,
+    #[serde(rename = "scaleX", default = "default_scale")]
+    pub scale_x: f32,
+    #[serde(rename = "scaleY", default = "default_scale")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub skew: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[[f32; 3]; 2]>,
     #[serde(rename = "zIndex", default = "default_z_index")]
     pub z_index: i32,
     pub position: Option<String>,
@@ -289,20 +557,28 @@ pub struct IORectangleNode {
     pub width: f32,
     pub height: f32,
     pub fill: Option<Fill>,
+    pub stroke: Option<Fill>,
     #[serde(rename = "strokeWidth")]
     pub stroke_width: Option<f32>,
+    #[serde(rename = "strokeAlign")]
+    pub stroke_align: Option<String>,
     #[serde(rename = "strokeCap")]
     pub stroke_cap: Option<String>,
-    pub effects: Option<Vec<serde_json::Value>>,
+    #[serde(rename = "strokeDashArray")]
+    pub stroke_dash_array: Option<Vec<f32>>,
+    pub effects: Option<Vec<IOFilterEffect>>,
     #[serde(
         rename = "cornerRadius",
         deserialize_with = "deserialize_corner_radius",
+        serialize_with = "serialize_corner_radius",
         default = "default_corner_radius"
     )]
     pub corner_radius: Option<RectangularCornerRadius>,
+    #[serde(rename = "cornerSmoothing")]
+    pub corner_smoothing: Option<f32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOGradientStop {
     pub offset: f32,
     pub color: RGBA,
@@ -317,26 +593,149 @@ impl From<IOGradientStop> for GradientStop {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Fill {
     #[serde(rename = "solid")]
-    Solid { color: Option<RGBA> },
+    Solid {
+        /// The source alpha of a solid fill lives in `color.a`.
+        color: Option<RGBA>,
+        /// Multiplies the alpha of `color`.
+        #[serde(default = "default_opacity")]
+        opacity: f32,
+    },
     #[serde(rename = "linear_gradient")]
     LinearGradient {
         id: Option<String>,
         transform: Option<[[f32; 3]; 2]>,
         stops: Vec<IOGradientStop>,
+        /// Multiplies the alpha of every stop.
+        #[serde(default = "default_opacity")]
+        opacity: f32,
+        #[serde(rename = "spreadMethod", default)]
+        spread: IOGradientSpread,
     },
     #[serde(rename = "radial_gradient")]
     RadialGradient {
         id: Option<String>,
         transform: Option<[[f32; 3]; 2]>,
         stops: Vec<IOGradientStop>,
+        /// Multiplies the alpha of every stop.
+        #[serde(default = "default_opacity")]
+        opacity: f32,
+        #[serde(rename = "spreadMethod", default)]
+        spread: IOGradientSpread,
+    },
+    #[serde(rename = "image")]
+    Image {
+        /// Image ref, a key of the document `bitmaps` or the image url itself.
+        src: String,
+        #[serde(default)]
+        fit: IOBoxFit,
+        transform: Option<[[f32; 3]; 2]>,
+        #[serde(default)]
+        repeat: IOImageRepeat,
+        #[serde(default = "default_opacity")]
+        opacity: f32,
+    },
+}
+
+/// Spread mode of a gradient fill, named after the SVG `spreadMethod` values.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum IOGradientSpread {
+    #[default]
+    #[serde(rename = "pad")]
+    Pad,
+    #[serde(rename = "repeat")]
+    Repeat,
+    #[serde(rename = "reflect")]
+    Reflect,
+}
+
+impl From<IOGradientSpread> for GradientSpread {
+    fn from(spread: IOGradientSpread) -> Self {
+        match spread {
+            IOGradientSpread::Pad => GradientSpread::Pad,
+            IOGradientSpread::Repeat => GradientSpread::Repeat,
+            IOGradientSpread::Reflect => GradientSpread::Reflect,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum IOBoxFit {
+    #[default]
+    #[serde(rename = "cover")]
+    Cover,
+    #[serde(rename = "contain")]
+    Contain,
+    #[serde(rename = "none")]
+    None,
+}
+
+impl From<IOBoxFit> for BoxFit {
+    fn from(fit: IOBoxFit) -> Self {
+        match fit {
+            IOBoxFit::Cover => BoxFit::Cover,
+            IOBoxFit::Contain => BoxFit::Contain,
+            IOBoxFit::None => BoxFit::None,
+        }
+    }
+}
+
+/// Repeat mode of an image fill, named after the CSS `background-repeat` values.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum IOImageRepeat {
+    #[default]
+    #[serde(rename = "no-repeat")]
+    NoRepeat,
+    #[serde(rename = "repeat")]
+    Repeat,
+    #[serde(rename = "repeat-x")]
+    RepeatX,
+    #[serde(rename = "repeat-y")]
+    RepeatY,
+}
+
+impl From<IOImageRepeat> for ImageRepeat {
+    fn from(repeat: IOImageRepeat) -> Self {
+        match repeat {
+            IOImageRepeat::NoRepeat => ImageRepeat::NoRepeat,
+            IOImageRepeat::Repeat => ImageRepeat::Repeat,
+            IOImageRepeat::RepeatX => ImageRepeat::RepeatX,
+            IOImageRepeat::RepeatY => ImageRepeat::RepeatY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum IOFilterEffect {
+    #[serde(rename = "shadow")]
+    Shadow {
+        #[serde(default)]
+        dx: f32,
+        #[serde(default)]
+        dy: f32,
+        #[serde(default)]
+        blur: f32,
+        #[serde(default)]
+        spread: f32,
+        #[serde(default)]
+        inset: bool,
+        #[serde(default = "default_shadow_color")]
+        color: RGBA,
     },
+    #[serde(rename = "filter-blur")]
+    LayerBlur { blur: f32 },
+    #[serde(rename = "backdrop-filter-blur")]
+    BackdropBlur { blur: f32 },
+    /// Effects not (yet) supported by the renderer.
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Border {
     #[serde(rename = "borderWidth")]
     pub border_width: Option<f32>,
@@ -346,7 +745,7 @@ pub struct Border {
     pub border_style: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOPath {
     pub d: String,
     #[serde(rename = "fillRule")]
@@ -354,7 +753,7 @@ pub struct IOPath {
     pub fill: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RGBA {
     pub r: u8,
     pub g: u8,
@@ -375,6 +774,9 @@ fn default_opacity() -> f32 {
 fn default_rotation() -> f32 {
     0.0
 }
+fn default_scale() -> f32 {
+    1.0
+}
 fn default_z_index() -> i32 {
     0
 }
@@ -395,47 +797,600 @@ fn default_corner_radius() -> Option<RectangularCornerRadius> {
     None
 }
 
+fn default_shadow_color() -> RGBA {
+    RGBA {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 0.25,
+    }
+}
+
+/// Builds a node transform from the layout fields of an IO node.
+///
+/// An explicit `[[a, c, tx], [b, d, ty]]` matrix is used as is. Otherwise the node is
+/// translated to `left`/`top` and rotated, then skewed horizontally by `skew` and scaled
+/// in its local space.
+///
+/// The JSON `rotation` and `skew` are in degrees like in the editor, positive rotations
+/// turn clockwise on screen. They are converted to the radians the transform math
+/// expects here.
+fn node_transform(
+    left: f32,
+    top: f32,
+    rotation_deg: f32,
+    scale: (f32, f32),
+    skew_deg: f32,
+    matrix: Option<[[f32; 3]; 2]>,
+) -> AffineTransform {
+    if let Some(matrix) = matrix {
+        return AffineTransform { matrix };
+    }
+    TransformDecomposition {
+        translation: [left, top],
+        rotation: rotation_deg.to_radians(),
+        scale,
+        skew: skew_deg.to_radians(),
+    }
+    .to_transform()
+}
+
 pub fn parse(file: &str) -> Result<IOCanvasFile, serde_json::Error> {
     serde_json::from_str(file)
 }
 
+/// Serializes a canvas file to JSON, rounding floats to `precision` decimal places.
+pub fn serialize(file: &IOCanvasFile, precision: usize) -> Result<String, serde_json::Error> {
+    let mut value = serde_json::to_value(file)?;
+    round_json_value(&mut value, precision);
+    serde_json::to_string(&value)
+}
+
+impl IODocument {
+    /// The id of the scene shown when the document is opened.
+    ///
+    /// This is `entry_scene_id` when it names a scene of the document, otherwise the
+    /// smallest scene id, so documents without an entry scene open the same scene every
+    /// time. `None` when the document has no scenes.
+    pub fn entry_scene_id(&self) -> Option<&str> {
+        self.entry_scene_id
+            .as_deref()
+            .filter(|id| self.scenes.contains_key(*id))
+            .or_else(|| self.scenes.keys().min().map(String::as_str))
+    }
+
+    /// The scene shown when the document is opened, see [`IODocument::entry_scene_id`].
+    pub fn entry_scene(&self) -> Option<&IOScene> {
+        self.scenes.get(self.entry_scene_id()?)
+    }
+
+    /// Converts the entry scene into a renderable [`Scene`], see
+    /// [`IODocument::into_scene`].
+    pub fn into_entry_scene(self) -> Result<Scene, LoadError> {
+        let id = self.entry_scene_id().ok_or(LoadError::NoScenes)?;
+        self.into_scene(id)
+    }
+
+    /// Assembles the scene `scene_id` into a renderable [`Scene`].
+    ///
+    /// The repository holds the scene roots and their descendants, other nodes of the
+    /// document are left out. Roots are sorted by z-index and the scene is placed at the
+    /// origin. Fails on the first child id without a node and on nodes of unknown type.
+    // borrows the document, so several scenes can be loaded from it
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_scene(&self, scene_id: &str) -> Result<Scene, LoadError> {
+        let scene = self
+            .scenes
+            .get(scene_id)
+            .ok_or_else(|| LoadError::MissingScene(scene_id.to_string()))?;
+
+        let missing = |parent: Option<&String>, children: &[String]| match children
+            .iter()
+            .find(|id| !self.nodes.contains_key(*id))
+        {
+            Some(id) => Err(LoadError::MissingNode {
+                parent: parent.cloned(),
+                id: id.clone(),
+            }),
+            None => Ok(()),
+        };
+
+        missing(None, &scene.children)?;
+        let mut nodes = HashMap::new();
+        for id in collect_scene_nodes(self, scene) {
+            // children are checked with their parent, before they are reached
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            match node {
+                IONode::Unknown => return Err(LoadError::UnknownNodeType(id.clone())),
+                IONode::Container(container) => missing(Some(id), &container.children)?,
+                _ => {}
+            }
+            nodes.insert(id.clone(), node.clone());
+        }
+
+        let children = sort_by_z_index(&scene.children, &self.nodes);
+        Ok(Scene {
+            id: scene_id.to_string(),
+            nodes: convert_nodes(nodes),
+            ..scene.to_scene(children)
+        })
+    }
+}
+
+/// A problem assembling a [`Scene`] from a canvas file, see [`IOCanvasFile::into_scene`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The document has no scenes at all.
+    NoScenes,
+    /// The document has no scene with this id.
+    MissingScene(String),
+    /// `parent` lists `id` as a child, but the document has no node with that id.
+    /// `parent` is `None` for the scene roots.
+    MissingNode { parent: Option<String>, id: String },
+    /// The node with this id has a `type` the renderer does not know.
+    UnknownNodeType(String),
+}
+
+impl IOCanvasFile {
+    /// Assembles the scene `scene_id` into a renderable [`Scene`], see
+    /// [`IODocument::into_scene`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_scene(&self, scene_id: &str) -> Result<Scene, LoadError> {
+        self.document.into_scene(scene_id)
+    }
+}
+
+impl IOScene {
+    /// The scene with `children` as roots and an empty repository.
+    fn to_scene(&self, children: Vec<String>) -> Scene {
+        Scene {
+            children,
+            background_color: self.background_color.clone().map(Into::into),
+            background_image: self.background_image_paint(),
+            guides: self.guide_list(),
+            constraints: self.layout_constraints(),
+            ..Scene::new(self.id.clone(), self.name.clone())
+        }
+    }
+
+    /// Returns the background image of the scene as a cover-fit image paint.
+    pub fn background_image_paint(&self) -> Option<ImagePaint> {
+        self.background_image.as_ref().map(|src| ImagePaint {
+            transform: AffineTransform::identity(),
+            _ref: src.clone(),
+            fit: BoxFit::Cover,
+            opacity: 1.0,
+            repeat: ImageRepeat::NoRepeat,
+        })
+    }
+
+    /// Returns the guides of the scene, skipping entries that are not a valid
+    /// `{ "axis": "x" | "y", "offset": number }` guide.
+    pub fn guide_list(&self) -> Vec<Guide> {
+        self.guides
+            .iter()
+            .flatten()
+            .filter_map(|value| {
+                let axis = match value.get("axis")?.as_str()? {
+                    "x" => Axis::X,
+                    "y" => Axis::Y,
+                    _ => return None,
+                };
+                let offset = value.get("offset")?.as_f64()? as f32;
+                Some(Guide { axis, offset })
+            })
+            .collect()
+    }
+
+    /// Returns the layout constraints of the nodes, read from the object entries of
+    /// `constraints`. A missing or unknown axis value falls back to [`Constraint::Start`].
+    pub fn layout_constraints(&self) -> LayoutConstraints {
+        self.constraints
+            .iter()
+            .flatten()
+            .filter_map(|(id, value)| {
+                let value = value.as_object()?;
+                let axis = |key: &str| convert_constraint(value.get(key).and_then(Value::as_str));
+                let constraint = LayoutConstraint {
+                    horizontal: axis("horizontal"),
+                    vertical: axis("vertical"),
+                };
+                Some((id.clone(), constraint))
+            })
+            .collect()
+    }
+}
+
+/// File name of the manifest written by [`save_multi`].
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Index of a multi-file bundle, listing the scene files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IOManifest {
+    pub version: String,
+    pub entry_scene_id: Option<String>,
+    pub scenes: Vec<IOManifestScene>,
+    #[serde(flatten)]
+    pub metadata: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IOManifestScene {
+    pub id: String,
+    pub name: String,
+    /// Scene file name, relative to the manifest
+    pub file: String,
+}
+
+/// Writes each scene of `file` to its own canvas file in `dir`, plus a manifest listing them.
+///
+/// Every scene file is a standalone canvas file holding the scene and the nodes reachable
+/// from it, so scenes can be loaded individually with [`load_scene_file`].
+pub fn save_multi(file: &IOCanvasFile, dir: impl AsRef<Path>) -> io::Result<IOManifest> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let mut scene_ids: Vec<&String> = file.document.scenes.keys().collect();
+    scene_ids.sort();
+
+    let mut scenes = Vec::with_capacity(scene_ids.len());
+    for (index, scene_id) in scene_ids.into_iter().enumerate() {
+        let scene = &file.document.scenes[scene_id];
+        let nodes: HashMap<&String, &IONode> = collect_scene_nodes(&file.document, scene)
+            .into_iter()
+            .filter_map(|id| file.document.nodes.get_key_value(id))
+            .collect();
+
+        let value = serde_json::json!({
+            "version": file.version,
+            "document": {
+                "bitmaps": file.document.bitmaps,
+                "properties": file.document.properties,
+                "nodes": nodes,
+                "scenes": { scene_id: scene },
+                "entry_scene_id": scene_id,
+            },
+        });
+
+        let name = format!("scene-{}.json", index);
+        fs::write(dir.join(&name), serde_json::to_string(&value)?)?;
+        scenes.push(IOManifestScene {
+            id: scene_id.clone(),
+            name: scene.name.clone(),
+            file: name,
+        });
+    }
+
+    let manifest = IOManifest {
+        version: file.version.clone(),
+        entry_scene_id: file.document.entry_scene_id.clone(),
+        scenes,
+        metadata: file.metadata.clone(),
+    };
+    fs::write(
+        dir.join(MANIFEST_FILE_NAME),
+        serde_json::to_string(&manifest)?,
+    )?;
+    Ok(manifest)
+}
+
+/// Reads the manifest of a bundle written by [`save_multi`].
+pub fn load_manifest(dir: impl AsRef<Path>) -> io::Result<IOManifest> {
+    let data = fs::read_to_string(dir.as_ref().join(MANIFEST_FILE_NAME))?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+/// Loads a single scene file of a bundle.
+pub fn load_scene_file(dir: impl AsRef<Path>, scene: &IOManifestScene) -> io::Result<IOCanvasFile> {
+    let data = fs::read_to_string(dir.as_ref().join(&scene.file))?;
+    Ok(parse(&data)?)
+}
+
+/// Loads all scenes of a bundle written by [`save_multi`] back into a single canvas file.
+pub fn load_multi(dir: impl AsRef<Path>) -> io::Result<IOCanvasFile> {
+    let dir = dir.as_ref();
+    let manifest = load_manifest(dir)?;
+
+    let mut document = IODocument {
+        bitmaps: HashMap::new(),
+        properties: HashMap::new(),
+        nodes: HashMap::new(),
+        scenes: HashMap::new(),
+        entry_scene_id: manifest.entry_scene_id,
+    };
+    for scene in &manifest.scenes {
+        let part = load_scene_file(dir, scene)?.document;
+        document.bitmaps.extend(part.bitmaps);
+        document.properties.extend(part.properties);
+        document.nodes.extend(part.nodes);
+        document.scenes.extend(part.scenes);
+    }
+
+    Ok(IOCanvasFile {
+        version: manifest.version,
+        document,
+        metadata: manifest.metadata,
+    })
+}
+
+/// Collects the ids of all nodes reachable from the scene, including nested children.
+fn collect_scene_nodes<'a>(document: &'a IODocument, scene: &'a IOScene) -> Vec<&'a String> {
+    collect_subtree_nodes(document, &scene.children)
+}
+
+/// Collects `roots` and all their descendants, each id once.
+fn collect_subtree_nodes<'a>(document: &'a IODocument, roots: &'a [String]) -> Vec<&'a String> {
+    let mut ids = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<&String> = roots.iter().rev().collect();
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        ids.push(id);
+        if let Some(IONode::Container(container)) = document.nodes.get(id) {
+            stack.extend(container.children.iter().rev());
+        }
+    }
+    ids
+}
+
+/// A self-contained set of nodes cut out of a document, e.g. a clipboard payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IOFragment {
+    /// The top-level nodes of the fragment, in order.
+    pub roots: Vec<String>,
+    /// The roots and all their descendants.
+    pub nodes: HashMap<String, IONode>,
+}
+
+/// Serializes the nodes `ids` of `document` and all their descendants into a fragment,
+/// rounding floats to `precision` decimal places.
+///
+/// Ids that are descendants of other `ids` are included once, as part of their ancestor.
+pub fn serialize_fragment(
+    document: &IODocument,
+    ids: &[String],
+    precision: usize,
+) -> Result<String, serde_json::Error> {
+    let nodes: HashMap<&String, &IONode> = collect_subtree_nodes(document, ids)
+        .into_iter()
+        .filter_map(|id| document.nodes.get_key_value(id))
+        .collect();
+    let nested: HashSet<&String> = nodes
+        .values()
+        .filter_map(|node| match node {
+            IONode::Container(container) => Some(&container.children),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    let roots: Vec<&String> = ids
+        .iter()
+        .filter(|id| nodes.contains_key(id) && !nested.contains(id))
+        .collect();
+
+    let mut value = serde_json::json!({
+        "roots": roots,
+        "nodes": nodes,
+    });
+    round_json_value(&mut value, precision);
+    serde_json::to_string(&value)
+}
+
+pub fn parse_fragment(json: &str) -> Result<IOFragment, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+impl IOFragment {
+    /// Gives every node a fresh id, so the fragment can be pasted next to its source.
+    ///
+    /// Roots and children lists are updated to the new ids. Returns the mapping from each
+    /// original id to its new id.
+    pub fn remap_ids(&mut self) -> HashMap<String, String> {
+        let mapping: HashMap<String, String> = self
+            .nodes
+            .keys()
+            .map(|id| (id.clone(), uuid::Uuid::new_v4().to_string()))
+            .collect();
+        let remap = |id: &mut String| {
+            if let Some(new_id) = mapping.get(id) {
+                *id = new_id.clone();
+            }
+        };
+
+        self.nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .map(|(mut id, mut node)| {
+                remap(&mut id);
+                if let Some(node_id) = node.id_mut() {
+                    remap(node_id);
+                }
+                if let IONode::Container(container) = &mut node {
+                    container.children.iter_mut().for_each(remap);
+                }
+                (id, node)
+            })
+            .collect();
+        self.roots.iter_mut().for_each(remap);
+        mapping
+    }
+}
+
+/// Returns `children` sorted by the `zIndex` of their nodes, back to front.
+///
+/// The sort is stable, siblings with the same z-index keep their order in `children`.
+/// Unknown ids count as z-index `0`.
+pub fn sort_by_z_index(children: &[String], nodes: &HashMap<String, IONode>) -> Vec<String> {
+    let mut sorted = children.to_vec();
+    sorted.sort_by_key(|id| nodes.get(id).map_or(0, IONode::z_index));
+    sorted
+}
+
+/// Converts all document nodes, resolving percentage sizes against the size of each
+/// node's parent container.
+///
+/// Percentages of nodes outside any container resolve to `0.0`. The children of each
+/// container are sorted by z-index, see [`sort_by_z_index`], while `nodes` hold the
+/// document order.
+pub fn convert_nodes(mut nodes: HashMap<String, IONode>) -> NodeRepository {
+    let z_index: HashMap<String, i32> = nodes
+        .iter()
+        .map(|(id, node)| (id.clone(), node.z_index()))
+        .collect();
+    let nested: HashSet<&String> = nodes
+        .values()
+        .filter_map(|node| match node {
+            IONode::Container(container) => Some(&container.children),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    let mut stack: Vec<(String, Option<Size>)> = nodes
+        .keys()
+        .filter(|id| !nested.contains(id))
+        .map(|id| (id.clone(), None))
+        .collect();
+
+    let mut repo = NodeRepository::new();
+    while let Some((id, parent)) = stack.pop() {
+        let Some(node) = nodes.remove(&id) else {
+            continue;
+        };
+        let mut node = node.into_node(parent.as_ref());
+        if let Node::Container(container) = &mut node {
+            container
+                .children
+                .sort_by_key(|child| z_index.get(child).copied().unwrap_or(0));
+            stack.extend(
+                container
+                    .children
+                    .iter()
+                    .map(|child| (child.clone(), Some(container.size.clone()))),
+            );
+        }
+        repo.insert(node);
+    }
+    // nodes only reachable through a cycle
+    for (_, node) in nodes {
+        repo.insert(node.into());
+    }
+    repo
+}
+
 impl From<RGBA> for Color {
     fn from(color: RGBA) -> Self {
-        Color(color.r, color.g, color.b, (color.a * 255.0) as u8)
+        // the rgb channels are 8-bit already, only the alpha is normalized
+        let [r, g, b, _] = Color::from_rgba8(color.r, color.g, color.b, 255).rgba_f32();
+        Color::from_rgba_f32(r, g, b, color.a)
     }
 }
 
+/// Converts the IO effects into renderer filter effects, skipping unsupported ones.
+fn convert_effects(effects: Option<Vec<IOFilterEffect>>) -> Vec<FilterEffect> {
+    effects
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|effect| match effect {
+            IOFilterEffect::Shadow {
+                dx,
+                dy,
+                blur,
+                inset: true,
+                spread,
+                color,
+            } => Some(FilterEffect::InnerShadow(FeInnerShadow {
+                dx,
+                dy,
+                blur,
+                spread,
+                color: color.into(),
+            })),
+            IOFilterEffect::Shadow {
+                dx,
+                dy,
+                blur,
+                spread,
+                color,
+                ..
+            } => Some(FilterEffect::DropShadow(FeDropShadow {
+                dx,
+                dy,
+                blur,
+                spread,
+                color: color.into(),
+            })),
+            IOFilterEffect::LayerBlur { blur } => {
+                Some(FilterEffect::GaussianBlur(FeGaussianBlur { radius: blur }))
+            }
+            IOFilterEffect::BackdropBlur { blur } => {
+                Some(FilterEffect::BackdropBlur(FeBackdropBlur { radius: blur }))
+            }
+            IOFilterEffect::Unknown => None,
+        })
+        .collect()
+}
+
 impl From<Option<Fill>> for Paint {
     fn from(fill: Option<Fill>) -> Self {
         match fill {
-            Some(Fill::Solid { color }) => Paint::Solid(SolidPaint {
+            Some(Fill::Solid { color, opacity }) => Paint::Solid(SolidPaint {
                 color: color.map_or(Color(0, 0, 0, 0), |c| c.into()),
-                opacity: 1.0,
+                opacity,
             }),
             Some(Fill::LinearGradient {
-                transform, stops, ..
+                transform,
+                stops,
+                opacity,
+                spread,
+                ..
             }) => {
-                let stops = stops.into_iter().map(|s| s.into()).collect();
+                let mut stops = stops.into_iter().map(|s| s.into()).collect();
+                normalize_stops(&mut stops);
                 Paint::LinearGradient(LinearGradientPaint {
                     transform: transform
                         .map(|m| AffineTransform { matrix: m })
                         .unwrap_or_else(AffineTransform::identity),
                     stops,
-                    opacity: 1.0,
+                    opacity,
+                    spread: spread.into(),
                 })
             }
             Some(Fill::RadialGradient {
-                transform, stops, ..
+                transform,
+                stops,
+                opacity,
+                spread,
+                ..
             }) => {
-                let stops = stops.into_iter().map(|s| s.into()).collect();
+                let mut stops = stops.into_iter().map(|s| s.into()).collect();
+                normalize_stops(&mut stops);
                 Paint::RadialGradient(RadialGradientPaint {
                     transform: transform
                         .map(|m| AffineTransform { matrix: m })
                         .unwrap_or_else(AffineTransform::identity),
                     stops,
-                    opacity: 1.0,
+                    opacity,
+                    spread: spread.into(),
                 })
             }
+            Some(Fill::Image {
+                src,
+                fit,
+                transform,
+                repeat,
+                opacity,
+            }) => Paint::Image(ImagePaint {
+                transform: transform
+                    .map(|m| AffineTransform { matrix: m })
+                    .unwrap_or_else(AffineTransform::identity),
+                _ref: src,
+                fit: fit.into(),
+                opacity,
+                repeat: repeat.into(),
+            }),
             None => Paint::Solid(SolidPaint {
                 color: Color(0, 0, 0, 0),
                 opacity: 1.0,
@@ -444,91 +1399,170 @@ impl From<Option<Fill>> for Paint {
     }
 }
 
+/// Resolves `width` and `height` against the size of the parent, unresolved dimensions
+/// become `0.0`.
+fn resolve_size(width: &Dimension, height: &Dimension, parent: Option<&Size>) -> Size {
+    Size {
+        width: width.resolve(parent.map(|size| size.width)).unwrap_or(0.0),
+        height: height
+            .resolve(parent.map(|size| size.height))
+            .unwrap_or(0.0),
+    }
+}
+
 impl From<IOContainerNode> for ContainerNode {
     fn from(node: IOContainerNode) -> Self {
-        let width = match node.width {
-            Value::Number(n) => n.as_f64().unwrap_or(0.0) as f32,
-            _ => 0.0,
-        };
-        let height = match node.height {
-            Value::Number(n) => n.as_f64().unwrap_or(0.0) as f32,
-            _ => 0.0,
-        };
+        node.into_container_node(None)
+    }
+}
+
+impl IOContainerNode {
+    /// Converts the node, resolving percentage sizes against `parent`.
+    pub fn into_container_node(self, parent: Option<&Size>) -> ContainerNode {
+        let (stroke, stroke_width, stroke_dash_array) = convert_border(self.border);
         ContainerNode {
             base: BaseNode {
-                id: node.id,
-                name: node.name,
-                active: node.active,
+                id: self.id,
+                name: self.name,
+                active: self.active,
+                locked: self.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
-            transform: AffineTransform::new(node.left, node.top, node.rotation),
-            size: Size { width, height },
-            corner_radius: node
+            transform: node_transform(
+                self.left,
+                self.top,
+                self.rotation,
+                (self.scale_x, self.scale_y),
+                self.skew,
+                self.transform,
+            ),
+            size: resolve_size(&self.width, &self.height, parent),
+            corner_radius: self
                 .corner_radius
                 .unwrap_or(RectangularCornerRadius::zero()),
-            fill: node.fill.into(),
-            stroke: None,
-            stroke_width: 0.0,
+            corner_smoothing: self.corner_smoothing.unwrap_or(0.0),
+            fill: self.fill.into(),
+            stroke,
+            stroke_width,
             stroke_align: StrokeAlign::Inside,
-            stroke_dash_array: None,
-            effect: None,
-            children: node.children,
-            opacity: node.opacity,
+            stroke_dash_array,
+            stroke_dash_offset: None,
+            effects: vec![],
+            children: self.children,
+            opacity: self.opacity,
+            padding: self.padding.unwrap_or_default(),
             clip: true,
         }
     }
 }
 
+/// Converts a CSS-like border into a container stroke, drawn inside the box like a
+/// `border-box` border.
+///
+/// Returns the stroke paint, width and dash pattern. `dashed` and `dotted` dashes scale
+/// with the width, a missing color is black and a missing, zero width or `none` border
+/// has no stroke.
+fn convert_border(border: Option<Border>) -> (Option<Paint>, f32, Option<Vec<f32>>) {
+    let Some(border) = border else {
+        return (None, 0.0, None);
+    };
+    let width = border.border_width.unwrap_or(0.0);
+    let dash_array = match border.border_style.as_deref() {
+        Some("none") | Some("hidden") => return (None, 0.0, None),
+        Some("dashed") => Some(vec![width * 3.0, width * 3.0]),
+        Some("dotted") => Some(vec![width, width]),
+        _ => None,
+    };
+    if width <= 0.0 {
+        return (None, 0.0, None);
+    }
+    let color = border.border_color.map_or(Color(0, 0, 0, 255), Color::from);
+    let paint = Paint::Solid(SolidPaint {
+        color,
+        opacity: 1.0,
+    });
+    (Some(paint), width, dash_array)
+}
+
 impl From<IOTextNode> for TextSpanNode {
     fn from(node: IOTextNode) -> Self {
-        let width = match node.width {
-            Value::Number(n) => n.as_f64().unwrap_or(0.0) as f32,
-            _ => 0.0,
-        };
-        let height = match node.height {
-            Value::Number(n) => n.as_f64().unwrap_or(0.0) as f32,
-            _ => 0.0,
+        node.into_text_span_node(None)
+    }
+}
+
+impl IOTextNode {
+    /// Converts the node, resolving percentage sizes against `parent`.
+    pub fn into_text_span_node(self, parent: Option<&Size>) -> TextSpanNode {
+        let sizing = match (self.width, self.height) {
+            (Dimension::Auto, _) => TextSizingMode::Auto,
+            (_, Dimension::Auto) => TextSizingMode::AutoHeight,
+            _ => TextSizingMode::Fixed,
         };
         TextSpanNode {
             base: BaseNode {
-                id: node.id,
-                name: node.name,
-                active: node.active,
+                id: self.id,
+                name: self.name,
+                active: self.active,
+                locked: self.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
-            transform: AffineTransform::new(node.left, node.top, node.rotation),
-            size: Size { width, height },
-            text: node.text,
+            transform: node_transform(
+                self.left,
+                self.top,
+                self.rotation,
+                (self.scale_x, self.scale_y),
+                self.skew,
+                self.transform,
+            ),
+            size: resolve_size(&self.width, &self.height, parent),
+            sizing,
+            text: self.text,
             text_style: TextStyle {
-                text_decoration: node.text_decoration,
-                font_family: node.font_family.unwrap_or_else(|| "Inter".to_string()),
-                font_size: node.font_size.unwrap_or(14.0),
-                font_weight: node.font_weight,
+                text_decoration: self.text_decoration,
+                font_families: vec![self.font_family.unwrap_or_else(|| "Inter".to_string())],
+                font_size: self.font_size.unwrap_or(14.0),
+                font_weight: self.font_weight,
                 italic: false,
-                letter_spacing: node.letter_spacing,
-                line_height: node.line_height,
+                letter_spacing: LetterSpacing::Px(self.letter_spacing.unwrap_or(0.0)),
+                line_height: self
+                    .line_height
+                    .map_or(LineHeight::Normal, LineHeight::Multiplier),
                 text_transform: TextTransform::None,
+                direction: self.direction,
             },
-            text_align: node.text_align,
-            text_align_vertical: node.text_align_vertical,
-            fill: node.fill.into(),
+            text_align: self.text_align,
+            text_align_vertical: self.text_align_vertical,
+            max_lines: self.max_lines,
+            overflow: self.text_overflow,
+            fill: self.fill.into(),
             stroke: None,
             stroke_width: None,
             stroke_align: StrokeAlign::Inside,
-            opacity: node.opacity,
+            opacity: self.opacity,
         }
     }
 }
 
 impl From<IOEllipseNode> for Node {
     fn from(node: IOEllipseNode) -> Self {
-        let transform = AffineTransform::new(node.left, node.top, node.rotation);
+        let transform = node_transform(
+            node.left,
+            node.top,
+            node.rotation,
+            (node.scale_x, node.scale_y),
+            node.skew,
+            node.transform,
+        );
 
         Node::Ellipse(EllipseNode {
             base: BaseNode {
                 id: node.id,
                 name: node.name,
                 active: node.active,
+                locked: node.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
             transform,
@@ -537,28 +1571,39 @@ impl From<IOEllipseNode> for Node {
                 height: node.height,
             },
             fill: node.fill.into(),
-            stroke: Paint::Solid(SolidPaint {
-                color: Color(0, 0, 0, 255),
-                opacity: 1.0,
-            }),
+            stroke: node.stroke.into(),
             stroke_width: node.stroke_width.unwrap_or(0.0),
-            stroke_align: StrokeAlign::Inside,
-            stroke_dash_array: None,
-            effect: None,
+            stroke_align: convert_stroke_align(node.stroke_align.as_deref()),
+            stroke_dash_array: node.stroke_dash_array,
+            stroke_dash_offset: None,
+            effects: convert_effects(node.effects),
             opacity: node.opacity,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::TAU,
+            inner_radius_ratio: 0.0,
+            trim: None,
         })
     }
 }
 
 impl From<IORectangleNode> for Node {
     fn from(node: IORectangleNode) -> Self {
-        let transform = AffineTransform::new(node.left, node.top, node.rotation);
+        let transform = node_transform(
+            node.left,
+            node.top,
+            node.rotation,
+            (node.scale_x, node.scale_y),
+            node.skew,
+            node.transform,
+        );
 
         Node::Rectangle(RectangleNode {
             base: BaseNode {
                 id: node.id,
                 name: node.name,
                 active: node.active,
+                locked: node.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
             transform,
@@ -569,23 +1614,39 @@ impl From<IORectangleNode> for Node {
             corner_radius: node
                 .corner_radius
                 .unwrap_or(RectangularCornerRadius::zero()),
+            corner_smoothing: node.corner_smoothing.unwrap_or(0.0),
             fill: node.fill.into(),
-            stroke: Paint::Solid(SolidPaint {
-                color: Color(0, 0, 0, 255),
-                opacity: 1.0,
-            }),
+            stroke: node.stroke.into(),
             stroke_width: node.stroke_width.unwrap_or(0.0),
-            stroke_align: StrokeAlign::Inside,
-            stroke_dash_array: None,
-            effect: None,
+            stroke_align: convert_stroke_align(node.stroke_align.as_deref()),
+            stroke_dash_array: node.stroke_dash_array,
+            stroke_dash_offset: None,
+            effects: convert_effects(node.effects),
             opacity: node.opacity,
+            trim: None,
         })
     }
 }
 
 impl From<IOVectorNode> for Node {
     fn from(node: IOVectorNode) -> Self {
-        let transform = AffineTransform::new(node.left, node.top, node.rotation);
+        let transform = node_transform(
+            node.left,
+            node.top,
+            node.rotation,
+            (node.scale_x, node.scale_y),
+            node.skew,
+            node.transform,
+        );
+
+        // the paths are merged into one, an `evenodd` path makes the whole node even-odd
+        let fill_rule = node
+            .paths
+            .iter()
+            .flatten()
+            .map(|path| convert_fill_rule(Some(path.fill_rule.as_str())))
+            .find(|rule| *rule == FillRule::EvenOdd)
+            .unwrap_or_default();
 
         // For vector nodes, we'll create a path node with the path data
         Node::Path(PathNode {
@@ -593,6 +1654,8 @@ impl From<IOVectorNode> for Node {
                 id: node.id,
                 name: node.name,
                 active: node.active,
+                locked: node.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
             transform,
@@ -604,49 +1667,141 @@ impl From<IOVectorNode> for Node {
                     .collect::<Vec<String>>()
                     .join(" ")
             }),
-            stroke: Paint::Solid(SolidPaint {
-                color: Color(0, 0, 0, 255),
-                opacity: 1.0,
-            }),
-            stroke_width: 0.0,
-            stroke_align: StrokeAlign::Inside,
-            stroke_dash_array: None,
+            stroke: node.stroke.into(),
+            stroke_width: node.stroke_width.unwrap_or(0.0),
+            stroke_align: convert_stroke_align(node.stroke_align.as_deref()),
+            stroke_cap: convert_stroke_cap(node.stroke_cap.as_deref()),
+            stroke_dash_array: node.stroke_dash_array,
+            stroke_dash_offset: None,
             opacity: node.opacity,
-            effect: None,
+            effects: vec![],
+            trim: None,
+            fill_rule,
         })
     }
 }
 
+/// Converts a vector network to SVG path data.
+///
+/// Segments are grouped into connected components by their shared vertices, each chain of
+/// segments becomes a subpath starting with an `M`, closed when it returns to its first
+/// vertex. Segments without tangents are straight `L` lines, the others cubic `C`
+/// curves. Isolated vertices, degenerate segments and out of range indices draw nothing.
 fn vector_network_to_path(vn: &IOVectorNetwork) -> String {
-    if vn.vertices.is_empty() {
-        return String::new();
+    let len = vn.vertices.len();
+    let segments: Vec<&IOVectorNetworkSegment> = vn
+        .segments
+        .iter()
+        .filter(|seg| seg.a < len && seg.b < len)
+        .filter(|seg| seg.a != seg.b || !is_zero_tangent(seg.ta) || !is_zero_tangent(seg.tb))
+        .collect();
+
+    // union-find over the vertices, keyed by the root of each segment's component
+    let mut roots: Vec<usize> = (0..len).collect();
+    fn find(roots: &mut [usize], mut v: usize) -> usize {
+        while roots[v] != v {
+            roots[v] = roots[roots[v]];
+            v = roots[v];
+        }
+        v
+    }
+    for seg in &segments {
+        let (a, b) = (find(&mut roots, seg.a), find(&mut roots, seg.b));
+        roots[a] = b;
+    }
+    let mut components: Vec<(usize, Vec<&IOVectorNetworkSegment>)> = Vec::new();
+    for &seg in &segments {
+        let root = find(&mut roots, seg.a);
+        match components.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, segs)) => segs.push(seg),
+            None => components.push((root, vec![seg])),
+        }
     }
 
     let mut d = String::new();
-    let first = vn
-        .segments
-        .get(0)
-        .map(|s| vn.vertices[s.a].p)
-        .unwrap_or([0.0, 0.0]);
-    d.push_str(&format!("M{} {}", first[0], first[1]));
-
-    for seg in &vn.segments {
-        let a = vn.vertices[seg.a].p;
-        let b = vn.vertices[seg.b].p;
-        let c1 = [a[0] + seg.ta[0], a[1] + seg.ta[1]];
-        let c2 = [b[0] + seg.tb[0], b[1] + seg.tb[1]];
-        d.push_str(&format!(
-            " C{} {},{} {},{} {}",
-            c1[0], c1[1], c2[0], c2[1], b[0], b[1]
-        ));
+    for (_, segs) in components {
+        let mut used = vec![false; segs.len()];
+        // the first vertex of the open subpath and the current point
+        let mut subpath: Option<(usize, usize)> = None;
+        loop {
+            let next = subpath.and_then(|(_, pen)| {
+                (0..segs.len()).find(|&i| !used[i] && (segs[i].a == pen || segs[i].b == pen))
+            });
+            let Some(i) = next else {
+                // open chains start at a dead end, so they are not split in two
+                let unused: Vec<usize> = (0..segs.len()).filter(|&i| !used[i]).collect();
+                let degree = |v: usize| {
+                    unused
+                        .iter()
+                        .map(|&i| (segs[i].a == v) as usize + (segs[i].b == v) as usize)
+                        .sum::<usize>()
+                };
+                let Some(start) = unused
+                    .iter()
+                    .flat_map(|&i| [segs[i].a, segs[i].b])
+                    .find(|&v| degree(v) % 2 == 1)
+                    .or_else(|| unused.first().map(|&i| segs[i].a))
+                else {
+                    break;
+                };
+                let p = vn.vertices[start].p;
+                if !d.is_empty() {
+                    d.push(' ');
+                }
+                d.push_str(&format!("M{} {}", p[0], p[1]));
+                subpath = Some((start, start));
+                continue;
+            };
+            used[i] = true;
+
+            let (start, pen) = subpath.unwrap();
+            let seg = segs[i];
+            // walk the segment backwards when the chain reaches it from its end
+            let (a, b, ta, tb) = if seg.a == pen {
+                (seg.a, seg.b, seg.ta, seg.tb)
+            } else {
+                (seg.b, seg.a, seg.tb, seg.ta)
+            };
+            let (pa, pb) = (vn.vertices[a].p, vn.vertices[b].p);
+            if is_zero_tangent(ta) && is_zero_tangent(tb) {
+                d.push_str(&format!(" L{} {}", pb[0], pb[1]));
+            } else {
+                let c1 = [pa[0] + ta[0], pa[1] + ta[1]];
+                let c2 = [pb[0] + tb[0], pb[1] + tb[1]];
+                d.push_str(&format!(
+                    " C{} {},{} {},{} {}",
+                    c1[0], c1[1], c2[0], c2[1], pb[0], pb[1]
+                ));
+            }
+
+            if b == start {
+                d.push_str(" Z");
+                subpath = None;
+            } else {
+                subpath = Some((start, b));
+            }
+        }
     }
 
     d
 }
 
+/// Whether a segment tangent is short enough for the segment end to be a straight line.
+fn is_zero_tangent(t: [f32; 2]) -> bool {
+    const EPSILON: f32 = 0.0001;
+    t[0].abs() < EPSILON && t[1].abs() < EPSILON
+}
+
 impl From<IOPathNode> for Node {
     fn from(node: IOPathNode) -> Self {
-        let transform = AffineTransform::new(node.left, node.top, node.rotation);
+        let transform = node_transform(
+            node.left,
+            node.top,
+            node.rotation,
+            (node.scale_x, node.scale_y),
+            node.skew,
+            node.transform,
+        );
 
         let data = node
             .vector_network
@@ -659,31 +1814,147 @@ impl From<IOPathNode> for Node {
                 id: node.id,
                 name: node.name,
                 active: node.active,
+                locked: node.locked,
+                render_order: None,
             },
             blend_mode: BlendMode::Normal,
             transform,
             fill: node.fill.into(),
             data,
-            stroke: Paint::Solid(SolidPaint {
-                color: Color(0, 0, 0, 255),
-                opacity: 1.0,
-            }),
+            stroke: node.stroke.into(),
             stroke_width: node.stroke_width.unwrap_or(0.0),
-            stroke_align: StrokeAlign::Inside,
-            stroke_dash_array: None,
+            stroke_align: convert_stroke_align(node.stroke_align.as_deref()),
+            stroke_cap: convert_stroke_cap(node.stroke_cap.as_deref()),
+            stroke_dash_array: node.stroke_dash_array,
+            stroke_dash_offset: None,
+            opacity: node.opacity,
+            effects: vec![],
+            trim: None,
+            fill_rule: convert_fill_rule(node.fill_rule.as_deref()),
+        })
+    }
+}
+
+/// Parses a `strokeCap` value (`"butt"`, `"round"` or `"square"`), unknown values fall back
+/// to butt.
+fn convert_stroke_cap(cap: Option<&str>) -> StrokeCap {
+    match cap {
+        Some("round") => StrokeCap::Round,
+        Some("square") => StrokeCap::Square,
+        _ => StrokeCap::Butt,
+    }
+}
+
+/// Parses a constraint value, `"left"` / `"top"`, `"right"` / `"bottom"`, `"center"`,
+/// `"scale"` or `"stretch"`. Unknown values fall back to left / top.
+fn convert_constraint(constraint: Option<&str>) -> Constraint {
+    match constraint {
+        Some("right") | Some("bottom") => Constraint::End,
+        Some("center") => Constraint::Center,
+        Some("scale") => Constraint::Scale,
+        Some("stretch") => Constraint::Stretch,
+        _ => Constraint::Start,
+    }
+}
+
+/// Parses a `strokeAlign` value (`"inside"`, `"center"` or `"outside"`), unknown values
+/// fall back to inside.
+fn convert_stroke_align(align: Option<&str>) -> StrokeAlign {
+    match align {
+        Some("center") => StrokeAlign::Center,
+        Some("outside") => StrokeAlign::Outside,
+        _ => StrokeAlign::Inside,
+    }
+}
+
+/// Parses a `fillRule` value (`"nonzero"` or `"evenodd"`), unknown values fall back to
+/// nonzero.
+fn convert_fill_rule(rule: Option<&str>) -> FillRule {
+    match rule {
+        Some("evenodd") => FillRule::EvenOdd,
+        _ => FillRule::NonZero,
+    }
+}
+
+impl From<IOLineNode> for Node {
+    fn from(node: IOLineNode) -> Self {
+        Node::Line(LineNode {
+            base: BaseNode {
+                id: node.id,
+                name: node.name,
+                active: node.active,
+                locked: node.locked,
+                render_order: None,
+            },
+            transform: node_transform(
+                node.left,
+                node.top,
+                node.rotation,
+                (node.scale_x, node.scale_y),
+                node.skew,
+                node.transform,
+            ),
+            size: Size {
+                width: node.width,
+                height: 0.0,
+            },
+            stroke: node.stroke.into(),
+            stroke_width: node.stroke_width.unwrap_or(1.0),
+            _data_stroke_align: StrokeAlign::Center,
+            stroke_cap: convert_stroke_cap(node.stroke_cap.as_deref()),
+            stroke_dash_array: node.stroke_dash_array,
+            stroke_dash_offset: None,
             opacity: node.opacity,
-            effect: None,
+            blend_mode: BlendMode::Normal,
+            trim: None,
         })
     }
 }
 
 impl From<IONode> for Node {
     fn from(node: IONode) -> Self {
-        match node {
-            IONode::Container(container) => Node::Container(container.into()),
-            IONode::Text(text) => Node::TextSpan(text.into()),
+        node.into_node(None)
+    }
+}
+
+impl IONode {
+    /// Mutable access to the id of the node, `None` for unknown nodes.
+    fn id_mut(&mut self) -> Option<&mut String> {
+        match self {
+            IONode::Container(node) => Some(&mut node.id),
+            IONode::Text(node) => Some(&mut node.id),
+            IONode::Vector(node) => Some(&mut node.id),
+            IONode::Path(node) => Some(&mut node.id),
+            IONode::Line(node) => Some(&mut node.id),
+            IONode::Ellipse(node) => Some(&mut node.id),
+            IONode::Rectangle(node) => Some(&mut node.id),
+            IONode::Unknown => None,
+        }
+    }
+
+    /// The stacking order of the node among its siblings, `0` for unknown nodes.
+    pub fn z_index(&self) -> i32 {
+        match self {
+            IONode::Container(node) => node.z_index,
+            IONode::Text(node) => node.z_index,
+            IONode::Vector(node) => node.z_index,
+            IONode::Path(node) => node.z_index,
+            IONode::Line(node) => node.z_index,
+            IONode::Ellipse(node) => node.z_index,
+            IONode::Rectangle(node) => node.z_index,
+            IONode::Unknown => 0,
+        }
+    }
+
+    /// Converts the node, resolving percentage sizes against `parent`, the size of the
+    /// containing node.
+    pub fn into_node(self, parent: Option<&Size>) -> Node {
+        match self {
+            IONode::Container(container) => Node::Container(container.into_container_node(parent)),
+            IONode::Text(text) => Node::TextSpan(text.into_text_span_node(parent)),
             IONode::Vector(vector) => vector.into(),
             IONode::Path(path) => path.into(),
+            IONode::Line(line) => line.into(),
             IONode::Ellipse(ellipse) => ellipse.into(),
             IONode::Rectangle(rectangle) => rectangle.into(),
             IONode::Unknown => Node::Error(ErrorNode {
@@ -691,6 +1962,8 @@ impl From<IONode> for Node {
                     id: "unknown".to_string(),
                     name: "Unknown Node".to_string(),
                     active: false,
+                    locked: false,
+                    render_order: None,
                 },
                 transform: AffineTransform::identity(),
                 size: Size {
@@ -725,6 +1998,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entry_scene_of_canvas_json() {
+        let path = "../fixtures/local/document.json";
+        let Ok(data) = fs::read_to_string(path) else {
+            eprintln!("test resource not found: {}", path);
+            return;
+        };
+        let parsed: IOCanvasFile = serde_json::from_str(&data).expect("failed to parse JSON");
+        let entry = parsed.document.entry_scene_id().map(str::to_string);
+
+        let scene = parsed.document.into_entry_scene().expect("entry scene");
+        assert_eq!(Some(scene.id.clone()), entry);
+        for id in &scene.children {
+            assert!(scene.nodes.get(id).is_some(), "missing node {id}");
+        }
+    }
+
+    fn document(entry_scene_id: Option<&str>) -> IODocument {
+        let mut document: IODocument = serde_json::from_value(serde_json::json!({
+            "bitmaps": {},
+            "properties": {},
+            "nodes": {
+                "rect": {
+                    "type": "rectangle",
+                    "id": "rect",
+                    "name": "rect",
+                    "left": 0.0,
+                    "top": 0.0,
+                    "width": 10.0,
+                    "height": 10.0
+                }
+            },
+            "scenes": {
+                "b": { "id": "b", "name": "B", "type": "scene", "children": ["rect"] },
+                "a": { "id": "a", "name": "A", "type": "scene", "children": [] }
+            }
+        }))
+        .unwrap();
+        document.entry_scene_id = entry_scene_id.map(str::to_string);
+        document
+    }
+
+    #[test]
+    fn entry_scene_falls_back_to_the_first_scene_id() {
+        assert_eq!(document(Some("b")).entry_scene_id(), Some("b"));
+        assert_eq!(document(None).entry_scene_id(), Some("a"));
+        assert_eq!(document(Some("missing")).entry_scene_id(), Some("a"));
+
+        let scene = document(Some("b")).into_entry_scene().unwrap();
+        assert_eq!((scene.id.as_str(), scene.name.as_str()), ("b", "B"));
+        assert_eq!(scene.children, vec!["rect".to_string()]);
+        assert!(matches!(
+            scene.nodes.get(&scene.children[0]),
+            Some(Node::Rectangle(_))
+        ));
+    }
+
+    #[test]
+    fn entry_scene_load_errors_are_reported() {
+        let mut broken = document(Some("b"));
+        broken.nodes.insert(
+            "odd".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "type": "hologram",
+                "id": "odd",
+                "name": "odd"
+            }))
+            .unwrap(),
+        );
+        broken
+            .scenes
+            .get_mut("b")
+            .unwrap()
+            .children
+            .push("odd".to_string());
+        assert_eq!(
+            broken.into_entry_scene().unwrap_err(),
+            LoadError::UnknownNodeType("odd".to_string())
+        );
+
+        let mut empty = document(None);
+        empty.scenes.clear();
+        assert_eq!(empty.into_entry_scene().unwrap_err(), LoadError::NoScenes);
+    }
+
     #[test]
     fn corner_radius_optional_and_falls_back_to_zero() {
         // Test JSON without cornerRadius field
@@ -816,4 +2174,562 @@ mod tests {
             panic!("Expected rectangle node not found");
         }
     }
+
+    #[test]
+    fn serialize_rounds_to_precision() {
+        let json = r#"{
+            "version": "0.0.1-beta.1+20250303",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {
+                    "test-rect": {
+                        "type": "rectangle",
+                        "id": "test-rect",
+                        "name": "Test Rectangle",
+                        "left": 12.3456,
+                        "top": 0.0,
+                        "width": 100.0,
+                        "height": 100.0
+                    }
+                },
+                "scenes": {}
+            }
+        }"#;
+
+        let parsed = parse(json).expect("failed to parse JSON");
+        let serialized = serialize(&parsed, 2).expect("failed to serialize JSON");
+        assert!(serialized.contains("\"left\":12.35"));
+
+        let reparsed = parse(&serialized).expect("failed to parse serialized JSON");
+        if let Some(IONode::Rectangle(rect_node)) = reparsed.document.nodes.get("test-rect") {
+            assert_eq!(rect_node.left, 12.35);
+        } else {
+            panic!("Expected rectangle node not found");
+        }
+    }
+
+    #[test]
+    fn parse_rectangle_with_multiple_effects() {
+        let json = r#"{
+            "type": "rectangle",
+            "id": "test-rect",
+            "name": "Test Rectangle",
+            "left": 0.0,
+            "top": 0.0,
+            "width": 100.0,
+            "height": 100.0,
+            "effects": [
+                {
+                    "type": "shadow",
+                    "dx": 4.0,
+                    "dy": 8.0,
+                    "blur": 12.0,
+                    "color": { "r": 0, "g": 0, "b": 0, "a": 0.5 }
+                },
+                { "type": "filter-blur", "blur": 6.0 },
+                { "type": "noise" }
+            ]
+        }"#;
+
+        let io_node: IONode = serde_json::from_str(json).expect("failed to parse JSON");
+        let Node::Rectangle(rect) = Node::from(io_node) else {
+            panic!("Expected rectangle node");
+        };
+
+        assert_eq!(rect.effects.len(), 2);
+        match &rect.effects[0] {
+            FilterEffect::DropShadow(shadow) => {
+                assert_eq!((shadow.dx, shadow.dy, shadow.blur), (4.0, 8.0, 12.0));
+            }
+            _ => panic!("Expected drop shadow"),
+        }
+        match &rect.effects[1] {
+            FilterEffect::GaussianBlur(blur) => assert_eq!(blur.radius, 6.0),
+            _ => panic!("Expected gaussian blur"),
+        }
+    }
+
+    #[test]
+    fn preserves_unknown_top_level_fields() {
+        let json = r#"{
+            "version": "0.0.1-beta.1+20250303",
+            "author": "grida",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {},
+                "scenes": {}
+            }
+        }"#;
+
+        let parsed = parse(json).expect("failed to parse JSON");
+        assert_eq!(
+            parsed.metadata.get("author"),
+            Some(&Value::String("grida".to_string()))
+        );
+
+        let serialized = serialize(&parsed, 3).expect("failed to serialize JSON");
+        let reparsed = parse(&serialized).expect("failed to parse serialized JSON");
+        assert_eq!(reparsed.metadata, parsed.metadata);
+        assert!(!reparsed.metadata.contains_key("version"));
+    }
+
+    #[test]
+    fn save_multi_writes_scene_files_and_manifest() {
+        let json = r#"{
+            "version": "0.0.1-beta.1+20250303",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {
+                    "rect-1": {
+                        "type": "rectangle",
+                        "id": "rect-1",
+                        "name": "Rectangle 1",
+                        "left": 0.0,
+                        "top": 0.0,
+                        "width": 100.0,
+                        "height": 100.0
+                    },
+                    "rect-2": {
+                        "type": "rectangle",
+                        "id": "rect-2",
+                        "name": "Rectangle 2",
+                        "left": 10.0,
+                        "top": 10.0,
+                        "width": 50.0,
+                        "height": 50.0
+                    }
+                },
+                "scenes": {
+                    "scene-a": {
+                        "id": "scene-a",
+                        "name": "Scene A",
+                        "type": "scene",
+                        "children": ["rect-1"]
+                    },
+                    "scene-b": {
+                        "id": "scene-b",
+                        "name": "Scene B",
+                        "type": "scene",
+                        "children": ["rect-2"]
+                    }
+                },
+                "entry_scene_id": "scene-a"
+            }
+        }"#;
+        let file = parse(json).expect("failed to parse JSON");
+
+        let dir = std::env::temp_dir().join(format!("grida-save-multi-{}", std::process::id()));
+        let manifest = save_multi(&file, &dir).expect("failed to save bundle");
+        assert_eq!(manifest.scenes.len(), 2);
+        assert!(dir.join(MANIFEST_FILE_NAME).exists());
+        for scene in &manifest.scenes {
+            assert!(dir.join(&scene.file).exists());
+        }
+
+        // each scene file only holds the nodes of its scene
+        let scene_a = load_scene_file(&dir, &manifest.scenes[0]).expect("failed to load scene");
+        assert_eq!(scene_a.document.scenes.len(), 1);
+        assert!(scene_a.document.nodes.contains_key("rect-1"));
+        assert!(!scene_a.document.nodes.contains_key("rect-2"));
+
+        let reloaded = load_multi(&dir).expect("failed to load bundle");
+        assert_eq!(reloaded.version, file.version);
+        assert_eq!(reloaded.document.entry_scene_id.as_deref(), Some("scene-a"));
+        assert_eq!(reloaded.document.scenes.len(), 2);
+        assert_eq!(reloaded.document.nodes.len(), 2);
+        assert_eq!(reloaded.document.scenes["scene-b"].children, vec!["rect-2"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn parse_padding_shapes() {
+        let container = |padding: &str| -> ContainerNode {
+            let json = format!(
+                r#"{{
+                    "id": "container",
+                    "name": "container",
+                    "left": 0.0,
+                    "top": 0.0,
+                    "width": 100,
+                    "height": 100,
+                    "children": [],
+                    "padding": {padding}
+                }}"#
+            );
+            serde_json::from_str::<IOContainerNode>(&json)
+                .unwrap()
+                .into()
+        };
+
+        assert_eq!(container("8").padding, EdgeInsets::all(8.0));
+        assert_eq!(
+            container("[4, 12]").padding,
+            EdgeInsets {
+                top: 4.0,
+                right: 12.0,
+                bottom: 4.0,
+                left: 12.0,
+            }
+        );
+        let padded = container("[1, 2, 3, 4]");
+        assert_eq!(
+            padded.padding,
+            EdgeInsets {
+                top: 1.0,
+                right: 2.0,
+                bottom: 3.0,
+                left: 4.0,
+            }
+        );
+        let content = padded.content_rect();
+        assert_eq!(
+            (content.x, content.y, content.width, content.height),
+            (4.0, 1.0, 94.0, 96.0)
+        );
+
+        assert_eq!(container("null").padding, EdgeInsets::zero());
+    }
+
+    #[test]
+    fn parse_dimensions() {
+        let parse = |json: &str| serde_json::from_str::<Dimension>(json).unwrap();
+        assert_eq!(parse("120"), Dimension::Px(120.0));
+        assert_eq!(parse(r#""auto""#), Dimension::Auto);
+        assert_eq!(parse(r#""100%""#), Dimension::Percent(100.0));
+        assert_eq!(parse("null"), Dimension::Px(0.0));
+
+        assert_eq!(Dimension::Percent(50.0).resolve(Some(300.0)), Some(150.0));
+        assert_eq!(Dimension::Percent(50.0).resolve(None), None);
+        assert_eq!(Dimension::Auto.resolve(Some(300.0)), None);
+    }
+
+    #[test]
+    fn percentages_resolve_against_parent_container() {
+        let json = r#"{
+            "parent": {
+                "type": "container",
+                "id": "parent",
+                "name": "parent",
+                "left": 0.0,
+                "top": 0.0,
+                "width": 400,
+                "height": 200,
+                "children": ["child", "label"]
+            },
+            "child": {
+                "type": "container",
+                "id": "child",
+                "name": "child",
+                "left": 0.0,
+                "top": 0.0,
+                "width": "100%",
+                "height": "50%",
+                "children": []
+            },
+            "label": {
+                "type": "text",
+                "id": "label",
+                "name": "label",
+                "left": 0.0,
+                "top": 0.0,
+                "width": 120,
+                "height": "auto",
+                "text": "label"
+            }
+        }"#;
+        let nodes: HashMap<String, IONode> = serde_json::from_str(json).unwrap();
+        let repo = convert_nodes(nodes);
+
+        let Some(Node::Container(parent)) = repo.get(&"parent".to_string()) else {
+            panic!("expected the parent container");
+        };
+        assert_eq!((parent.size.width, parent.size.height), (400.0, 200.0));
+
+        let Some(Node::Container(child)) = repo.get(&"child".to_string()) else {
+            panic!("expected the child container");
+        };
+        assert_eq!((child.size.width, child.size.height), (400.0, 100.0));
+
+        let Some(Node::TextSpan(label)) = repo.get(&"label".to_string()) else {
+            panic!("expected the text node");
+        };
+        assert_eq!(label.size.width, 120.0);
+        assert_eq!(label.sizing, TextSizingMode::AutoHeight);
+    }
+
+    fn rectangle(extra: serde_json::Value) -> Node {
+        let mut json = serde_json::json!({
+            "id": "rect",
+            "name": "rect",
+            "left": 10.0,
+            "top": 20.0,
+            "width": 100.0,
+            "height": 50.0,
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value::<IORectangleNode>(json)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn explicit_matrix_is_used_as_is() {
+        let matrix = [[0.5, -0.25, 30.0], [0.25, 2.0, 40.0]];
+        let node = rectangle(serde_json::json!({ "transform": matrix, "scaleX": 3.0 }));
+        assert_eq!(node.transform().matrix, matrix);
+
+        // and survives a serialize/parse round trip
+        let io: IORectangleNode = serde_json::from_value(serde_json::json!({
+            "id": "rect",
+            "name": "rect",
+            "left": 0.0,
+            "top": 0.0,
+            "width": 1.0,
+            "height": 1.0,
+            "transform": matrix,
+        }))
+        .unwrap();
+        let json = serde_json::to_string(&io).unwrap();
+        let io: IORectangleNode = serde_json::from_str(&json).unwrap();
+        assert_eq!(io.transform, Some(matrix));
+    }
+
+    #[test]
+    fn scale_and_skew_compose_after_translation() {
+        let node = rectangle(serde_json::json!({ "scaleX": 2.0, "scaleY": 3.0 }));
+        assert_eq!(
+            node.transform().matrix,
+            [[2.0, 0.0, 10.0], [0.0, 3.0, 20.0]]
+        );
+
+        let node = rectangle(serde_json::json!({ "skew": 45.0 }));
+        let [[a, c, tx], [b, d, ty]] = node.transform().matrix;
+        assert_eq!((a, b, d, tx, ty), (1.0, 0.0, 1.0, 10.0, 20.0));
+        assert!((c - 1.0).abs() < 1e-6, "{c}");
+
+        // without scale or skew the transform is unchanged
+        let node = rectangle(serde_json::json!({ "rotation": 30.0 }));
+        assert_eq!(
+            node.transform(),
+            AffineTransform::new(10.0, 20.0, 30f32.to_radians())
+        );
+    }
+
+    #[test]
+    fn rotation_is_read_in_degrees() {
+        let node = rectangle(serde_json::json!({ "rotation": 90.0 }));
+        let [[a, _, tx], [b, _, ty]] = node.transform().matrix;
+        // the linear part maps (1, 0) to (0, 1)
+        assert!(a.abs() < 1e-6 && (b - 1.0).abs() < 1e-6, "{a} {b}");
+        assert_eq!((tx, ty), (10.0, 20.0));
+    }
+
+    fn network(vertices: &[[f32; 2]], segments: &[(usize, usize)]) -> IOVectorNetwork {
+        IOVectorNetwork {
+            vertices: vertices
+                .iter()
+                .map(|&p| IOVectorNetworkVertex { p })
+                .collect(),
+            segments: segments
+                .iter()
+                .map(|&(a, b)| IOVectorNetworkSegment {
+                    a,
+                    b,
+                    ta: [0.0, 0.0],
+                    tb: [0.0, 0.0],
+                })
+                .collect(),
+        }
+    }
+
+    fn subpaths(d: &str) -> (usize, usize) {
+        (d.matches('M').count(), d.matches('Z').count())
+    }
+
+    #[test]
+    fn vector_network_with_two_loops_is_a_donut() {
+        let vn = network(
+            &[
+                [0.0, 0.0],
+                [100.0, 0.0],
+                [100.0, 100.0],
+                [0.0, 100.0],
+                [25.0, 25.0],
+                [75.0, 25.0],
+                [75.0, 75.0],
+                [25.0, 75.0],
+            ],
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 0),
+                (4, 5),
+                (5, 6),
+                (6, 7),
+                (7, 4),
+            ],
+        );
+        let d = vector_network_to_path(&vn);
+        assert_eq!(subpaths(&d), (2, 2), "{d}");
+
+        let mut path = skia_safe::Path::from_svg(&d).expect("valid path data");
+        path.set_fill_type(skia_safe::PathFillType::EvenOdd);
+        assert!(path.contains((10.0, 10.0)));
+        assert!(!path.contains((50.0, 50.0)));
+    }
+
+    #[test]
+    fn vector_network_subpaths_follow_shared_vertices() {
+        // an open chain listed out of order, and a disconnected segment
+        let vn = network(
+            &[
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [20.0, 0.0],
+                [0.0, 50.0],
+                [10.0, 50.0],
+            ],
+            &[(1, 2), (0, 1), (3, 4)],
+        );
+        let d = vector_network_to_path(&vn);
+        assert_eq!(subpaths(&d), (2, 0), "{d}");
+        // the chain starts at a dead end instead of being split in two
+        assert!(d.starts_with("M20 0 L10 0 L0 0"), "{d}");
+
+        // segments walked against their direction still form one closed loop
+        let vn = network(
+            &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]],
+            &[(0, 1), (2, 1), (2, 0)],
+        );
+        assert_eq!(subpaths(&vector_network_to_path(&vn)), (1, 1));
+    }
+
+    #[test]
+    fn vector_network_degenerate_parts_draw_nothing() {
+        // isolated vertex, zero length segment to itself, out of range index
+        let vn = network(&[[0.0, 0.0], [10.0, 10.0]], &[(0, 0), (0, 5)]);
+        assert_eq!(vector_network_to_path(&vn), "");
+        assert_eq!(vector_network_to_path(&network(&[], &[])), "");
+
+        // a curved segment from a vertex back to itself is a closed loop
+        let mut vn = network(&[[0.0, 0.0]], &[(0, 0)]);
+        vn.segments[0].ta = [20.0, -20.0];
+        vn.segments[0].tb = [20.0, 20.0];
+        assert_eq!(vector_network_to_path(&vn), "M0 0 C20 -20,20 20,0 0 Z");
+    }
+
+    #[test]
+    fn vector_network_straight_segments_are_lines() {
+        let mut vn = network(
+            &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            &[(0, 1), (1, 2), (2, 3), (3, 0)],
+        );
+        assert_eq!(
+            vector_network_to_path(&vn),
+            "M0 0 L10 0 L10 10 L0 10 L0 0 Z"
+        );
+
+        // a tangent on either end keeps the segment a curve
+        vn.segments[1].tb = [5.0, 0.0];
+        assert_eq!(
+            vector_network_to_path(&vn),
+            "M0 0 L10 0 C10 0,15 10,10 10 L0 10 L0 0 Z"
+        );
+    }
+
+    #[test]
+    fn stroke_is_parsed_from_json() {
+        let node = rectangle(serde_json::json!({
+            "stroke": { "type": "solid", "color": { "r": 0, "g": 0, "b": 255, "a": 1.0 } },
+            "strokeWidth": 2.0,
+            "strokeAlign": "outside",
+            "strokeDashArray": [4.0, 2.0],
+        }));
+        let Node::Rectangle(rect) = node else {
+            panic!("expected a rectangle node");
+        };
+        assert_eq!(
+            rect.stroke,
+            Paint::Solid(SolidPaint {
+                color: Color(0, 0, 255, 255),
+                opacity: 1.0,
+            })
+        );
+        assert_eq!(rect.stroke_width, 2.0);
+        assert_eq!(rect.stroke_align, StrokeAlign::Outside);
+        assert_eq!(rect.stroke_dash_array, Some(vec![4.0, 2.0]));
+    }
+
+    #[test]
+    fn missing_stroke_is_transparent() {
+        let Node::Rectangle(rect) = rectangle(serde_json::json!({ "strokeWidth": 2.0 })) else {
+            panic!("expected a rectangle node");
+        };
+        let Paint::Solid(stroke) = rect.stroke else {
+            panic!("expected a solid stroke");
+        };
+        assert_eq!(stroke.color.3, 0);
+        assert_eq!(rect.stroke_align, StrokeAlign::Inside);
+        assert_eq!(rect.stroke_dash_array, None);
+    }
+
+    fn container(border: serde_json::Value) -> ContainerNode {
+        let io: IOContainerNode = serde_json::from_value(serde_json::json!({
+            "id": "container",
+            "name": "container",
+            "left": 0.0,
+            "top": 0.0,
+            "width": 100.0,
+            "height": 100.0,
+            "children": [],
+            "border": border,
+        }))
+        .unwrap();
+        io.into()
+    }
+
+    #[test]
+    fn container_border_becomes_the_stroke() {
+        let node = container(serde_json::json!({
+            "borderWidth": 3.0,
+            "borderColor": { "r": 255, "g": 0, "b": 0, "a": 1.0 },
+            "borderStyle": "dashed",
+        }));
+        assert_eq!(
+            node.stroke,
+            Some(Paint::Solid(SolidPaint {
+                color: Color(255, 0, 0, 255),
+                opacity: 1.0,
+            }))
+        );
+        assert_eq!(node.stroke_width, 3.0);
+        assert_eq!(node.stroke_align, StrokeAlign::Inside);
+        assert_eq!(node.stroke_dash_array, Some(vec![9.0, 9.0]));
+
+        let dotted = container(serde_json::json!({ "borderWidth": 2.0, "borderStyle": "dotted" }));
+        assert_eq!(dotted.stroke_dash_array, Some(vec![2.0, 2.0]));
+        let solid = container(serde_json::json!({ "borderWidth": 2.0, "borderStyle": "solid" }));
+        assert!(solid.stroke.is_some());
+        assert_eq!(solid.stroke_dash_array, None);
+    }
+
+    #[test]
+    fn container_without_border_has_no_stroke() {
+        for border in [
+            serde_json::Value::Null,
+            serde_json::json!({ "borderWidth": 0.0, "borderStyle": "solid" }),
+            serde_json::json!({ "borderWidth": 2.0, "borderStyle": "none" }),
+        ] {
+            let node = container(border);
+            assert_eq!(node.stroke, None);
+            assert_eq!(node.stroke_width, 0.0);
+        }
+    }
 }
@@ -0,0 +1,664 @@
+//! Structured SVG export.
+//!
+//! Unlike [`crate::export::export_as_svg`], which records the rendered output through skia's
+//! SVG canvas, this module maps the node tree to SVG elements one by one, so the result keeps
+//! the document structure (groups, shapes, text and gradients) and stays editable.
+//!
+//! Not every feature has an SVG equivalent: effects are dropped, strokes are always center
+//! aligned and image paints ignore their transform.
+
+use crate::cache::geometry::GeometryCache;
+use crate::helpers::precision::{format_number, round_numbers_in_value, DEFAULT_PRECISION};
+use crate::node::schema::*;
+use crate::painter::geometry::{
+    boolean_operation_path, build_container_clip_shape, build_smooth_rrect_path,
+};
+use crate::runtime::repository::FontRepository;
+use math2::box_fit::BoxFit;
+use math2::rect;
+use math2::transform::AffineTransform;
+
+/// Exports the scene as a standalone SVG document.
+///
+/// The document's `viewBox` is the union of the root nodes' bounds, and the scene
+/// background color (if any) is drawn as a full size `<rect>`. `fonts` is used to
+/// outline text operands of boolean operations.
+pub fn export_scene(scene: &Scene, fonts: &FontRepository) -> String {
+    let mut writer = SvgWriter::new(scene, fonts);
+
+    // the nodes are nested one level deeper when wrapped in the scene transform
+    let wrap = scene.transform != AffineTransform::identity();
+    let mut body = String::new();
+    for id in scene.nodes.in_render_order(&scene.children) {
+        writer.write_node(&mut body, id, if wrap { 2 } else { 1 });
+    }
+
+    let bounds = scene
+        .children
+        .iter()
+        .filter_map(|id| writer.cache.get_world_bounds(id))
+        .reduce(|a, b| rect::union(&[a, b]))
+        .unwrap_or(math2::rect::Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        });
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+        num(bounds.width),
+        num(bounds.height),
+        num(bounds.x),
+        num(bounds.y),
+        num(bounds.width),
+        num(bounds.height),
+    );
+    if !writer.defs.is_empty() {
+        svg.push_str("  <defs>\n");
+        svg.push_str(&writer.defs);
+        svg.push_str("  </defs>\n");
+    }
+    if let Some(color) = scene.background_color {
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{}/>\n",
+            num(bounds.x),
+            num(bounds.y),
+            num(bounds.width),
+            num(bounds.height),
+            color_attrs("fill", color, 1.0),
+        ));
+    }
+    if wrap {
+        svg.push_str(&format!("  <g{}>\n", transform_attr(&scene.transform)));
+        svg.push_str(&body);
+        svg.push_str("  </g>\n");
+    } else {
+        svg.push_str(&body);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Fill and stroke of a shape element.
+struct ShapeStyle<'a> {
+    fill: Option<&'a Paint>,
+    stroke: Option<&'a Paint>,
+    stroke_width: f32,
+    stroke_dash_array: Option<&'a Vec<f32>>,
+}
+
+struct SvgWriter<'a> {
+    scene: &'a Scene,
+    fonts: &'a FontRepository,
+    cache: GeometryCache,
+    defs: String,
+    next_def_id: usize,
+}
+
+impl<'a> SvgWriter<'a> {
+    fn new(scene: &'a Scene, fonts: &'a FontRepository) -> Self {
+        Self {
+            scene,
+            fonts,
+            cache: GeometryCache::from_scene(scene),
+            defs: String::new(),
+            next_def_id: 0,
+        }
+    }
+
+    fn def_id(&mut self, kind: &str) -> String {
+        let id = format!("{}-{}", kind, self.next_def_id);
+        self.next_def_id += 1;
+        id
+    }
+
+    fn write_node(&mut self, out: &mut String, id: &NodeId, depth: usize) {
+        let Some(node) = self.scene.nodes.get(id) else {
+            return;
+        };
+        if !node.active() {
+            return;
+        }
+        let pad = "  ".repeat(depth);
+
+        match node {
+            Node::Group(n) => {
+                out.push_str(&format!(
+                    "{pad}<g{}>\n",
+                    node_attrs(id, &n.transform, n.opacity, n.blend_mode)
+                ));
+                for child in self.scene.nodes.in_render_order(&n.children) {
+                    self.write_node(out, child, depth + 1);
+                }
+                out.push_str(&format!("{pad}</g>\n"));
+            }
+            Node::Container(n) => {
+                out.push_str(&format!(
+                    "{pad}<g{}>\n",
+                    node_attrs(id, &n.transform, n.opacity, n.blend_mode)
+                ));
+                let shape = rect_element(&n.size, &n.corner_radius, n.corner_smoothing);
+                let paint = self.paint_attrs("fill", &n.fill, &n.size);
+                let stroke = self.stroke_attrs(
+                    n.stroke.as_ref(),
+                    n.stroke_width,
+                    n.stroke_dash_array.as_ref(),
+                    &n.size,
+                );
+                out.push_str(&format!("{pad}  <{shape}{paint}{stroke}/>\n"));
+
+                if n.clip {
+                    let clip_id = self.def_id("clip");
+                    let clip_shape = if n.padding.is_zero() {
+                        shape
+                    } else {
+                        path_element(&build_container_clip_shape(n).to_path())
+                    };
+                    self.defs.push_str(&format!(
+                        "    <clipPath id=\"{clip_id}\">\n      <{clip_shape}/>\n    </clipPath>\n"
+                    ));
+                    out.push_str(&format!("{pad}  <g clip-path=\"url(#{clip_id})\">\n"));
+                    for child in self.scene.nodes.in_render_order(&n.children) {
+                        self.write_node(out, child, depth + 2);
+                    }
+                    out.push_str(&format!("{pad}  </g>\n"));
+                } else {
+                    for child in self.scene.nodes.in_render_order(&n.children) {
+                        self.write_node(out, child, depth + 1);
+                    }
+                }
+                out.push_str(&format!("{pad}</g>\n"));
+            }
+            Node::Rectangle(n) => {
+                let element = rect_element(&n.size, &n.corner_radius, n.corner_smoothing);
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_shape(out, &pad, &element, &attrs, style, &n.size);
+            }
+            Node::Image(n) => {
+                let element = rect_element(&n.size, &n.corner_radius, 0.0);
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_shape(out, &pad, &element, &attrs, style, &n.size);
+            }
+            Node::Ellipse(n) => {
+                let element = if n.is_full_ellipse() {
+                    format!(
+                        "ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"",
+                        num(n.size.width / 2.0),
+                        num(n.size.height / 2.0),
+                        num(n.size.width / 2.0),
+                        num(n.size.height / 2.0),
+                    )
+                } else {
+                    path_element(&n.to_path())
+                };
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_shape(out, &pad, &element, &attrs, style, &n.size);
+            }
+            Node::Line(n) => {
+                let element = format!(
+                    "line x1=\"0\" y1=\"0\" x2=\"{}\" y2=\"0\"",
+                    num(n.size.width)
+                );
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: None,
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_shape(out, &pad, &element, &attrs, style, &n.size);
+            }
+            Node::Path(n) => {
+                // keep the authored path data as-is
+                let element = format!("path d=\"{}\"", escape(&n.data));
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                let size = path_size(&n.to_path());
+                self.write_shape(out, &pad, &element, &attrs, style, &size);
+            }
+            Node::Polygon(n) => {
+                let path = n.to_path();
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_path(out, &pad, &path, &attrs, style);
+            }
+            Node::RegularPolygon(n) => {
+                let path = n.to_path();
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_path(out, &pad, &path, &attrs, style);
+            }
+            Node::RegularStarPolygon(n) => {
+                let path = n.to_path();
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: Some(&n.stroke),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_path(out, &pad, &path, &attrs, style);
+            }
+            Node::BooleanOperation(n) => {
+                let Some(path) = boolean_operation_path(n, &self.scene.nodes, self.fonts) else {
+                    return;
+                };
+                let attrs = node_attrs(id, &n.transform, n.opacity, n.blend_mode);
+                let style = ShapeStyle {
+                    fill: Some(&n.fill),
+                    stroke: n.stroke.as_ref(),
+                    stroke_width: n.stroke_width,
+                    stroke_dash_array: n.stroke_dash_array.as_ref(),
+                };
+                self.write_path(out, &pad, &path, &attrs, style);
+            }
+            Node::TextSpan(n) => {
+                let paint = self.paint_attrs("fill", &n.fill, &n.size);
+                let stroke = self.stroke_attrs(
+                    n.stroke.as_ref(),
+                    n.stroke_width.unwrap_or(0.0),
+                    None,
+                    &n.size,
+                );
+                out.push_str(&format!(
+                    "{pad}<text{}{}{}{paint}{stroke}>{}</text>\n",
+                    node_attrs(id, &n.transform, n.opacity, n.blend_mode),
+                    text_anchor_attrs(n.text_align.resolve(n.text_style.direction), &n.size),
+                    text_style_attrs(&n.text_style),
+                    escape(&n.text)
+                ));
+            }
+            Node::RichText(n) => {
+                let paint = self.paint_attrs("fill", &n.fill, &n.size);
+                out.push_str(&format!(
+                    "{pad}<text{}{}{paint}>",
+                    node_attrs(id, &n.transform, n.opacity, n.blend_mode),
+                    text_anchor_attrs(n.text_align.resolve(n.direction()), &n.size),
+                ));
+                for span in &n.spans {
+                    let fill = match &span.fill {
+                        Some(fill) => self.paint_attrs("fill", fill, &n.size),
+                        None => String::new(),
+                    };
+                    out.push_str(&format!(
+                        "<tspan{}{fill}>{}</tspan>",
+                        text_style_attrs(&span.text_style),
+                        escape(&span.text)
+                    ));
+                }
+                out.push_str("</text>\n");
+            }
+            Node::Error(_) => {}
+        }
+    }
+
+    fn write_shape(
+        &mut self,
+        out: &mut String,
+        pad: &str,
+        element: &str,
+        attrs: &str,
+        style: ShapeStyle,
+        size: &Size,
+    ) {
+        let fill = match style.fill {
+            Some(fill) => self.paint_attrs("fill", fill, size),
+            None => String::new(),
+        };
+        let stroke = self.stroke_attrs(
+            style.stroke,
+            style.stroke_width,
+            style.stroke_dash_array,
+            size,
+        );
+        out.push_str(&format!("{pad}<{element}{attrs}{fill}{stroke}/>\n"));
+    }
+
+    fn write_path(
+        &mut self,
+        out: &mut String,
+        pad: &str,
+        path: &skia_safe::Path,
+        attrs: &str,
+        style: ShapeStyle,
+    ) {
+        let size = path_size(path);
+        self.write_shape(out, pad, &path_element(path), attrs, style, &size);
+    }
+
+    /// Paint attributes for `name` (`fill` or `stroke`). Gradients and images are added to the defs.
+    fn paint_attrs(&mut self, name: &str, paint: &Paint, size: &Size) -> String {
+        match paint {
+            Paint::Solid(solid) => color_attrs(name, solid.color, solid.opacity),
+            Paint::LinearGradient(gradient) => {
+                let id = self.def_id("gradient");
+                self.defs.push_str(&format!(
+                    "    <linearGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" x1=\"0\" y1=\"0\" x2=\"{}\" y2=\"0\"{}{}>\n",
+                    num(size.width),
+                    gradient_transform_attr(&gradient.transform),
+                    spread_method_attr(gradient.spread),
+                ));
+                self.defs
+                    .push_str(&gradient_stops(&gradient.stops, gradient.opacity));
+                self.defs.push_str("    </linearGradient>\n");
+                format!(" {name}=\"url(#{id})\"")
+            }
+            Paint::RadialGradient(gradient) => {
+                let id = self.def_id("gradient");
+                self.defs.push_str(&format!(
+                    "    <radialGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" r=\"{}\"{}{}>\n",
+                    num(size.width / 2.0),
+                    num(size.height / 2.0),
+                    num(size.width.min(size.height) / 2.0),
+                    gradient_transform_attr(&gradient.transform),
+                    spread_method_attr(gradient.spread),
+                ));
+                self.defs
+                    .push_str(&gradient_stops(&gradient.stops, gradient.opacity));
+                self.defs.push_str("    </radialGradient>\n");
+                format!(" {name}=\"url(#{id})\"")
+            }
+            Paint::Image(image) => {
+                let id = self.def_id("image");
+                let aspect = match image.fit {
+                    BoxFit::Cover => "xMidYMid slice",
+                    BoxFit::Contain | BoxFit::None => "xMidYMid meet",
+                };
+                self.defs.push_str(&format!(
+                    concat!(
+                        "    <pattern id=\"{id}\" patternUnits=\"userSpaceOnUse\" width=\"{w}\" height=\"{h}\">\n",
+                        "      <image href=\"{}\" width=\"{w}\" height=\"{h}\" preserveAspectRatio=\"{aspect}\"/>\n",
+                        "    </pattern>\n",
+                    ),
+                    escape(&image._ref),
+                    w = num(size.width),
+                    h = num(size.height),
+                ));
+                let mut attrs = format!(" {name}=\"url(#{id})\"");
+                if image.opacity < 1.0 {
+                    attrs.push_str(&format!(" {name}-opacity=\"{}\"", num(image.opacity)));
+                }
+                attrs
+            }
+        }
+    }
+
+    fn stroke_attrs(
+        &mut self,
+        paint: Option<&Paint>,
+        width: f32,
+        dash_array: Option<&Vec<f32>>,
+        size: &Size,
+    ) -> String {
+        let Some(paint) = paint else {
+            return String::new();
+        };
+        if width <= 0.0 {
+            return String::new();
+        }
+        let mut attrs = self.paint_attrs("stroke", paint, size);
+        attrs.push_str(&format!(" stroke-width=\"{}\"", num(width)));
+        if let Some(dashes) = dash_array.filter(|d| !d.is_empty()) {
+            let dashes: Vec<String> = dashes.iter().map(|d| num(*d)).collect();
+            attrs.push_str(&format!(" stroke-dasharray=\"{}\"", dashes.join(" ")));
+        }
+        attrs
+    }
+}
+
+fn num(value: f32) -> String {
+    format_number(value, DEFAULT_PRECISION)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+fn color_attrs(name: &str, color: Color, opacity: f32) -> String {
+    let alpha = color.3 as f32 / 255.0 * opacity;
+    let mut attrs = format!(" {name}=\"{}\"", hex(color));
+    if alpha < 1.0 {
+        attrs.push_str(&format!(" {name}-opacity=\"{}\"", num(alpha)));
+    }
+    attrs
+}
+
+fn matrix(transform: &AffineTransform) -> String {
+    let m = transform.matrix;
+    format!(
+        "matrix({} {} {} {} {} {})",
+        num(m[0][0]),
+        num(m[1][0]),
+        num(m[0][1]),
+        num(m[1][1]),
+        num(m[0][2]),
+        num(m[1][2]),
+    )
+}
+
+fn transform_attr(transform: &AffineTransform) -> String {
+    if *transform == AffineTransform::identity() {
+        String::new()
+    } else {
+        format!(" transform=\"{}\"", matrix(transform))
+    }
+}
+
+fn gradient_transform_attr(transform: &AffineTransform) -> String {
+    if *transform == AffineTransform::identity() {
+        String::new()
+    } else {
+        format!(" gradientTransform=\"{}\"", matrix(transform))
+    }
+}
+
+fn spread_method_attr(spread: GradientSpread) -> &'static str {
+    match spread {
+        GradientSpread::Pad => "",
+        GradientSpread::Repeat => " spreadMethod=\"repeat\"",
+        GradientSpread::Reflect => " spreadMethod=\"reflect\"",
+    }
+}
+
+fn gradient_stops(stops: &[GradientStop], opacity: f32) -> String {
+    stops
+        .iter()
+        .map(|stop| {
+            let alpha = stop.color.3 as f32 / 255.0 * opacity;
+            let mut attrs = format!(
+                "      <stop offset=\"{}\" stop-color=\"{}\"",
+                num(stop.offset),
+                hex(stop.color)
+            );
+            if alpha < 1.0 {
+                attrs.push_str(&format!(" stop-opacity=\"{}\"", num(alpha)));
+            }
+            attrs.push_str("/>\n");
+            attrs
+        })
+        .collect()
+}
+
+/// The CSS `mix-blend-mode` keyword, `None` for modes that need no declaration.
+fn css_blend_mode(mode: BlendMode) -> Option<&'static str> {
+    match mode {
+        BlendMode::Normal | BlendMode::PassThrough => None,
+        BlendMode::Multiply => Some("multiply"),
+        BlendMode::Screen => Some("screen"),
+        BlendMode::Overlay => Some("overlay"),
+        BlendMode::Darken => Some("darken"),
+        BlendMode::Lighten => Some("lighten"),
+        BlendMode::ColorDodge => Some("color-dodge"),
+        BlendMode::ColorBurn => Some("color-burn"),
+        BlendMode::HardLight => Some("hard-light"),
+        BlendMode::SoftLight => Some("soft-light"),
+        BlendMode::Difference => Some("difference"),
+        BlendMode::Exclusion => Some("exclusion"),
+        BlendMode::Hue => Some("hue"),
+        BlendMode::Saturation => Some("saturation"),
+        BlendMode::Color => Some("color"),
+        BlendMode::Luminosity => Some("luminosity"),
+        // no CSS equivalent, the node is drawn as normal
+        BlendMode::DstOut => None,
+    }
+}
+
+/// `id`, `transform`, `opacity` and `mix-blend-mode` attributes shared by all nodes.
+fn node_attrs(
+    id: &NodeId,
+    transform: &AffineTransform,
+    opacity: f32,
+    blend_mode: BlendMode,
+) -> String {
+    let mut attrs = format!(" id=\"{}\"", escape(id));
+    attrs.push_str(&transform_attr(transform));
+    if opacity < 1.0 {
+        attrs.push_str(&format!(" opacity=\"{}\"", num(opacity)));
+    }
+    if let Some(mode) = css_blend_mode(blend_mode) {
+        attrs.push_str(&format!(" style=\"mix-blend-mode: {mode}\""));
+    }
+    attrs
+}
+
+/// A `<rect>` for uniform corner radii, a `<path>` for mixed or smoothed ones.
+fn rect_element(size: &Size, radius: &RectangularCornerRadius, smoothing: f32) -> String {
+    if !radius.is_zero() && smoothing > 0.0 {
+        let rect = skia_safe::Rect::from_wh(size.width, size.height);
+        return path_element(&build_smooth_rrect_path(rect, radius, smoothing));
+    }
+    let uniform = radius.tl == radius.tr && radius.tl == radius.bl && radius.tl == radius.br;
+    if !uniform {
+        let rrect = skia_safe::RRect::new_rect_radii(
+            skia_safe::Rect::from_wh(size.width, size.height),
+            &[
+                skia_safe::Point::new(radius.tl, radius.tl),
+                skia_safe::Point::new(radius.tr, radius.tr),
+                skia_safe::Point::new(radius.br, radius.br),
+                skia_safe::Point::new(radius.bl, radius.bl),
+            ],
+        );
+        let mut path = skia_safe::Path::new();
+        path.add_rrect(rrect, None);
+        return path_element(&path);
+    }
+    let mut element = format!(
+        "rect width=\"{}\" height=\"{}\"",
+        num(size.width),
+        num(size.height)
+    );
+    if radius.tl > 0.0 {
+        element.push_str(&format!(
+            " rx=\"{}\" ry=\"{}\"",
+            num(radius.tl),
+            num(radius.tl)
+        ));
+    }
+    element
+}
+
+fn path_element(path: &skia_safe::Path) -> String {
+    let mut element = format!(
+        "path d=\"{}\"",
+        round_numbers_in_value(&path.to_svg(), DEFAULT_PRECISION)
+    );
+    if path.fill_type() == skia_safe::PathFillType::EvenOdd {
+        element.push_str(" fill-rule=\"evenodd\"");
+    }
+    element
+}
+
+/// The paint box of a path shape, matching the size the painter uses for its gradients.
+fn path_size(path: &skia_safe::Path) -> Size {
+    let bounds = path.bounds();
+    Size {
+        width: bounds.width(),
+        height: bounds.height(),
+    }
+}
+
+/// `align` is expected to be resolved, see [`TextAlign::resolve`].
+fn text_anchor_attrs(align: TextAlign, size: &Size) -> String {
+    let (x, anchor) = match align {
+        TextAlign::Left | TextAlign::Justify | TextAlign::Start => (0.0, None),
+        TextAlign::Center => (size.width / 2.0, Some("middle")),
+        TextAlign::Right | TextAlign::End => (size.width, Some("end")),
+    };
+    let mut attrs = format!(" x=\"{}\" y=\"0\" dominant-baseline=\"hanging\"", num(x));
+    if let Some(anchor) = anchor {
+        attrs.push_str(&format!(" text-anchor=\"{anchor}\""));
+    }
+    attrs
+}
+
+fn text_style_attrs(style: &TextStyle) -> String {
+    let mut attrs = format!(
+        " font-family=\"{}\" font-size=\"{}\" font-weight=\"{}\"",
+        escape(&style.font_families.join(", ")),
+        num(style.font_size),
+        style.font_weight.value()
+    );
+    if style.italic {
+        attrs.push_str(" font-style=\"italic\"");
+    }
+    match style.letter_spacing {
+        LetterSpacing::Px(px) if px != 0.0 => {
+            attrs.push_str(&format!(" letter-spacing=\"{}\"", num(px)));
+        }
+        LetterSpacing::Em(em) if em != 0.0 => {
+            attrs.push_str(&format!(" letter-spacing=\"{}em\"", num(em)));
+        }
+        _ => {}
+    }
+    let decoration = match style.text_decoration {
+        TextDecoration::None => None,
+        TextDecoration::Underline => Some("underline"),
+        TextDecoration::Overline => Some("overline"),
+        TextDecoration::LineThrough => Some("line-through"),
+    };
+    if let Some(decoration) = decoration {
+        attrs.push_str(&format!(" text-decoration=\"{decoration}\""));
+    }
+    attrs
+}
@@ -1,2 +1,3 @@
 pub mod io_figma;
 pub mod io_json;
+pub mod io_svg;
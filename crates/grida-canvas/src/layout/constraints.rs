@@ -0,0 +1,70 @@
+use crate::node::schema::{Constraint, Node, NodeId, Scene, Size};
+
+/// Resizes the container `node_id` to `new_size` and lays out its children by their
+/// [`LayoutConstraint`](crate::node::schema::LayoutConstraint)s, like resizing a frame
+/// in Figma.
+///
+/// Children are placed by the translation of their transform and resized through their
+/// `size`. Nodes without a size, like paths and groups, only move. Child containers
+/// resized this way lay out their own children in turn. Does nothing when `node_id` is
+/// not a container.
+pub fn apply_constraints(scene: &mut Scene, node_id: &NodeId, new_size: Size) {
+    let Some(Node::Container(container)) = scene.nodes.get_mut(node_id) else {
+        return;
+    };
+    let old_size = std::mem::replace(&mut container.size, new_size.clone());
+    let children = container.children.clone();
+
+    for child_id in &children {
+        let constraint = scene.constraints.get(child_id).copied().unwrap_or_default();
+        let Some(child) = scene.nodes.get_mut(child_id) else {
+            continue;
+        };
+
+        let transform = child.transform();
+        let size = child.size_mut().cloned();
+        let (x, width) = resolve(
+            constraint.horizontal,
+            transform.x(),
+            size.as_ref().map_or(0.0, |s| s.width),
+            old_size.width,
+            new_size.width,
+        );
+        let (y, height) = resolve(
+            constraint.vertical,
+            transform.y(),
+            size.as_ref().map_or(0.0, |s| s.height),
+            old_size.height,
+            new_size.height,
+        );
+        child.transform_mut().set_translation(x, y);
+
+        let Some(size) = size else {
+            continue;
+        };
+        if width == size.width && height == size.height {
+            continue;
+        }
+        let resized = Size { width, height };
+        if let Node::Container(_) = child {
+            apply_constraints(scene, child_id, resized);
+        } else if let Some(child_size) = child.size_mut() {
+            *child_size = resized;
+        }
+    }
+}
+
+/// Places a child along one axis, returns its new position and length.
+fn resolve(constraint: Constraint, position: f32, length: f32, old: f32, new: f32) -> (f32, f32) {
+    let delta = new - old;
+    match constraint {
+        Constraint::Start => (position, length),
+        Constraint::End => (position + delta, length),
+        Constraint::Center => (position + delta / 2.0, length),
+        Constraint::Stretch => (position, (length + delta).max(0.0)),
+        Constraint::Scale => {
+            let ratio = if old > 0.0 { new / old } else { 1.0 };
+            (position * ratio, length * ratio)
+        }
+    }
+}
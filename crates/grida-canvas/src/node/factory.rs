@@ -1,19 +1,221 @@
 use super::schema::*;
 use math2::transform::AffineTransform;
-use uuid::Uuid;
+use std::cell::RefCell;
+use uuid::{Builder, Uuid};
+
+/// Overrides for [`NodeFactory::create_rectangle_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct RectangleOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub corner_radius: Option<RectangularCornerRadius>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_ellipse_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct EllipseOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_line_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct LineOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_text_span_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct TextSpanOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub text: Option<String>,
+    pub text_style: Option<TextStyle>,
+    pub fill: Option<Paint>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_container_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ContainerOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub corner_radius: Option<RectangularCornerRadius>,
+    pub fill: Option<Paint>,
+    /// Sets a stroke, containers have none by default.
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+    pub clip: Option<bool>,
+}
+
+/// Overrides for [`NodeFactory::create_path_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct PathOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub data: Option<String>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_regular_polygon_node_with`], `None` fields keep
+/// the factory defaults. The point count is set through
+/// [`RegularPolygonNode::with_point_count`], which rejects invalid counts.
+#[derive(Debug, Clone, Default)]
+pub struct RegularPolygonOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub corner_radius: Option<f32>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_regular_star_polygon_node_with`], `None` fields
+/// keep the factory defaults. The point count is set through
+/// [`RegularStarPolygonNode::with_point_count`], which rejects invalid counts.
+#[derive(Debug, Clone, Default)]
+pub struct RegularStarPolygonOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub inner_radius: Option<f32>,
+    pub corner_radius: Option<f32>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_polygon_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct PolygonOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub points: Option<Vec<Point>>,
+    pub corner_radius: Option<f32>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// Overrides for [`NodeFactory::create_image_node_with`], `None` fields keep the
+/// factory defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ImageOptions {
+    pub name: Option<String>,
+    pub transform: Option<AffineTransform>,
+    pub size: Option<Size>,
+    pub corner_radius: Option<RectangularCornerRadius>,
+    /// The image resource reference.
+    pub image_ref: Option<String>,
+    pub fill: Option<Paint>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+/// How a [`NodeFactory`] generates node ids.
+enum IdGenerator {
+    /// Random v4 uuids.
+    Random,
+    /// v4 uuids from a seeded generator, the value is the generator state.
+    Seeded(u64),
+    /// `{prefix}-{n}` with `n` counting up from `1`.
+    Prefixed { prefix: String, next: u64 },
+}
+
+impl IdGenerator {
+    fn next_id(&mut self) -> String {
+        match self {
+            IdGenerator::Random => Uuid::new_v4().to_string(),
+            IdGenerator::Seeded(state) => {
+                let mut bytes = [0u8; 16];
+                bytes[..8].copy_from_slice(&splitmix64(state).to_le_bytes());
+                bytes[8..].copy_from_slice(&splitmix64(state).to_le_bytes());
+                Builder::from_random_bytes(bytes).into_uuid().to_string()
+            }
+            IdGenerator::Prefixed { prefix, next } => {
+                let id = format!("{prefix}-{next}");
+                *next += 1;
+                id
+            }
+        }
+    }
+}
+
+/// Advances `state` and returns the next value of the SplitMix64 sequence.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
 
 /// Factory for creating nodes with default values
-pub struct NodeFactory;
+pub struct NodeFactory {
+    ids: RefCell<IdGenerator>,
+}
 
 impl NodeFactory {
+    /// Creates a factory giving nodes random ids.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            ids: RefCell::new(IdGenerator::Random),
+        }
+    }
+
+    /// Creates a factory giving nodes uuid ids from a generator seeded with `seed`, so
+    /// factories with the same seed produce the same id sequence.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            ids: RefCell::new(IdGenerator::Seeded(seed)),
+        }
+    }
+
+    /// Creates a factory giving nodes readable ids `{prefix}-1`, `{prefix}-2`, ...
+    pub fn with_id_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            ids: RefCell::new(IdGenerator::Prefixed {
+                prefix: prefix.into(),
+                next: 1,
+            }),
+        }
     }
 
     fn id(&self) -> String {
-        // random id
-        let id = Uuid::new_v4();
-        id.to_string()
+        self.ids.borrow_mut().next_id()
     }
 
     // Internal factory defaults
@@ -33,6 +235,8 @@ impl NodeFactory {
             id: self.id(),
             name: String::new(),
             active: true,
+            locked: false,
+            render_order: None,
         }
     }
 
@@ -50,17 +254,50 @@ impl NodeFactory {
             transform: AffineTransform::identity(),
             size: Self::DEFAULT_SIZE,
             corner_radius: RectangularCornerRadius::zero(),
+            corner_smoothing: 0.0,
             fill: Self::default_solid_paint(Self::DEFAULT_COLOR),
             stroke: Self::default_solid_paint(Self::DEFAULT_STROKE_COLOR),
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            trim: None,
         }
     }
 
+    /// Creates a new rectangle node, overriding the defaults with the fields set in `options`
+    pub fn create_rectangle_node_with(&self, options: RectangleOptions) -> RectangleNode {
+        let mut node = self.create_rectangle_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
     /// Creates a new ellipse node with default values
     pub fn create_ellipse_node(&self) -> EllipseNode {
         EllipseNode {
@@ -72,12 +309,44 @@ impl NodeFactory {
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            start_angle: 0.0,
+            end_angle: std::f32::consts::TAU,
+            inner_radius_ratio: 0.0,
+            trim: None,
         }
     }
 
+    /// Creates a new ellipse node, overriding the defaults with the fields set in `options`
+    pub fn create_ellipse_node_with(&self, options: EllipseOptions) -> EllipseNode {
+        let mut node = self.create_ellipse_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
     /// Creates a new line node with default values
     pub fn create_line_node(&self) -> LineNode {
         LineNode {
@@ -90,12 +359,39 @@ impl NodeFactory {
             stroke: Self::default_solid_paint(Self::DEFAULT_STROKE_COLOR),
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             _data_stroke_align: Self::DEFAULT_STROKE_ALIGN,
+            stroke_cap: StrokeCap::Butt,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
+            trim: None,
         }
     }
 
+    /// Creates a new line node, overriding the defaults with the fields set in `options`
+    pub fn create_line_node_with(&self, options: LineOptions) -> LineNode {
+        let mut node = self.create_line_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
     /// Creates a new text span node with default values
     pub fn create_text_span_node(&self) -> TextSpanNode {
         TextSpanNode {
@@ -105,19 +401,23 @@ impl NodeFactory {
                 width: Self::DEFAULT_SIZE.width,
                 height: 20.0,
             },
+            sizing: TextSizingMode::Fixed,
             text: String::new(),
             text_style: TextStyle {
                 text_decoration: TextDecoration::None,
-                font_family: String::from("Arial"),
+                font_families: vec![String::from("Arial")],
                 font_size: 16.0,
                 font_weight: FontWeight::default(),
                 italic: false,
-                letter_spacing: None,
-                line_height: None,
+                letter_spacing: LetterSpacing::default(),
+                line_height: LineHeight::Normal,
                 text_transform: TextTransform::None,
+                direction: TextDirection::Ltr,
             },
             text_align: TextAlign::Left,
             text_align_vertical: TextAlignVertical::Top,
+            max_lines: None,
+            overflow: TextOverflow::Clip,
             fill: Self::default_solid_paint(Self::DEFAULT_STROKE_COLOR),
             stroke: None,
             stroke_width: None,
@@ -127,6 +427,51 @@ impl NodeFactory {
         }
     }
 
+    /// Creates a new text span node, overriding the defaults with the fields set in `options`
+    pub fn create_text_span_node_with(&self, options: TextSpanOptions) -> TextSpanNode {
+        let mut node = self.create_text_span_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(text) = options.text {
+            node.text = text;
+        }
+        if let Some(text_style) = options.text_style {
+            node.text_style = text_style;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
+    /// Creates a new rich text node with default values and no spans
+    pub fn create_rich_text_node(&self) -> RichTextNode {
+        RichTextNode {
+            base: self.default_base_node(),
+            transform: AffineTransform::identity(),
+            size: Size {
+                width: Self::DEFAULT_SIZE.width,
+                height: 20.0,
+            },
+            spans: Vec::new(),
+            text_align: TextAlign::Left,
+            text_align_vertical: TextAlignVertical::Top,
+            fill: Self::default_solid_paint(Self::DEFAULT_STROKE_COLOR),
+            opacity: Self::DEFAULT_OPACITY,
+            blend_mode: BlendMode::Normal,
+        }
+    }
+
     /// Creates a new group node with default values
     pub fn create_group_node(&self) -> GroupNode {
         GroupNode {
@@ -145,19 +490,55 @@ impl NodeFactory {
             transform: AffineTransform::identity(),
             size: Self::DEFAULT_SIZE,
             corner_radius: RectangularCornerRadius::zero(),
+            corner_smoothing: 0.0,
             children: Vec::new(),
             fill: Self::default_solid_paint(Self::DEFAULT_COLOR),
             stroke: None,
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            padding: EdgeInsets::zero(),
             clip: true,
         }
     }
 
+    /// Creates a new container node, overriding the defaults with the fields set in `options`
+    pub fn create_container_node_with(&self, options: ContainerOptions) -> ContainerNode {
+        let mut node = self.create_container_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = Some(stroke);
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        if let Some(clip) = options.clip {
+            node.clip = clip;
+        }
+        node
+    }
+
     /// Creates a new path node with default values
     pub fn create_path_node(&self) -> PathNode {
         PathNode {
@@ -168,11 +549,42 @@ impl NodeFactory {
             stroke: Self::default_solid_paint(Self::DEFAULT_STROKE_COLOR),
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
+            stroke_cap: StrokeCap::Butt,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            trim: None,
+            fill_rule: FillRule::NonZero,
+        }
+    }
+
+    /// Creates a new path node, overriding the defaults with the fields set in `options`
+    pub fn create_path_node_with(&self, options: PathOptions) -> PathNode {
+        let mut node = self.create_path_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(data) = options.data {
+            node.data = data;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
         }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
     }
 
     /// Creates a new regular polygon node with default values
@@ -188,10 +600,45 @@ impl NodeFactory {
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            angle_offset: 0.0,
+        }
+    }
+
+    /// Creates a new regular polygon node, overriding the defaults with the fields set in `options`
+    pub fn create_regular_polygon_node_with(
+        &self,
+        options: RegularPolygonOptions,
+    ) -> RegularPolygonNode {
+        let mut node = self.create_regular_polygon_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
         }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
     }
 
     pub fn create_regular_star_polygon_node(&self) -> RegularStarPolygonNode {
@@ -207,12 +654,50 @@ impl NodeFactory {
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            angle_offset: 0.0,
         }
     }
 
+    /// Creates a new regular star polygon node, overriding the defaults with the fields set in `options`
+    pub fn create_regular_star_polygon_node_with(
+        &self,
+        options: RegularStarPolygonOptions,
+    ) -> RegularStarPolygonNode {
+        let mut node = self.create_regular_star_polygon_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(inner_radius) = options.inner_radius {
+            node.inner_radius = inner_radius;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
     pub fn create_polygon_node(&self) -> PolygonNode {
         PolygonNode {
             base: self.default_base_node(),
@@ -224,12 +709,44 @@ impl NodeFactory {
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
+            fill_rule: FillRule::NonZero,
         }
     }
 
+    /// Creates a new polygon node, overriding the defaults with the fields set in `options`
+    pub fn create_polygon_node_with(&self, options: PolygonOptions) -> PolygonNode {
+        let mut node = self.create_polygon_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(points) = options.points {
+            node.points = points;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
+        }
+        node
+    }
+
     /// Creates a new image node with default values
     pub fn create_image_node(&self) -> ImageNode {
         ImageNode {
@@ -242,10 +759,45 @@ impl NodeFactory {
             stroke_width: Self::DEFAULT_STROKE_WIDTH,
             stroke_align: Self::DEFAULT_STROKE_ALIGN,
             stroke_dash_array: None,
+            stroke_dash_offset: None,
             opacity: Self::DEFAULT_OPACITY,
             blend_mode: BlendMode::Normal,
-            effect: None,
+            effects: vec![],
             _ref: String::new(),
+            clip: true,
+        }
+    }
+
+    /// Creates a new image node, overriding the defaults with the fields set in `options`
+    pub fn create_image_node_with(&self, options: ImageOptions) -> ImageNode {
+        let mut node = self.create_image_node();
+        if let Some(name) = options.name {
+            node.base.name = name;
+        }
+        if let Some(transform) = options.transform {
+            node.transform = transform;
+        }
+        if let Some(size) = options.size {
+            node.size = size;
+        }
+        if let Some(corner_radius) = options.corner_radius {
+            node.corner_radius = corner_radius;
+        }
+        if let Some(image_ref) = options.image_ref {
+            node._ref = image_ref;
+        }
+        if let Some(fill) = options.fill {
+            node.fill = fill;
+        }
+        if let Some(stroke) = options.stroke {
+            node.stroke = stroke;
+        }
+        if let Some(stroke_width) = options.stroke_width {
+            node.stroke_width = stroke_width;
+        }
+        if let Some(opacity) = options.opacity {
+            node.opacity = opacity;
         }
+        node
     }
 }
@@ -1,4 +1,4 @@
-use crate::node::schema::{Node, NodeId};
+use crate::node::schema::{Node, NodeId, NodeTrait};
 use std::collections::HashMap;
 
 /// A repository for managing nodes with automatic ID indexing.
@@ -30,6 +30,7 @@ impl NodeRepository {
             Node::RegularStarPolygon(n) => n.base.id.clone(),
             Node::Line(n) => n.base.id.clone(),
             Node::TextSpan(n) => n.base.id.clone(),
+            Node::RichText(n) => n.base.id.clone(),
             Node::Path(n) => n.base.id.clone(),
             Node::BooleanOperation(n) => n.base.id.clone(),
             Node::Image(n) => n.base.id.clone(),
@@ -68,6 +69,31 @@ impl NodeRepository {
         self.nodes.is_empty()
     }
 
+    /// Returns all nodes matching the predicate, in no particular order
+    pub fn find(&self, pred: impl Fn(&Node) -> bool) -> Vec<&Node> {
+        self.nodes.values().filter(|node| pred(node)).collect()
+    }
+
+    /// Returns all nodes whose name is exactly `name`
+    pub fn find_by_name(&self, name: &str) -> Vec<&Node> {
+        self.find(|node| node.name() == name)
+    }
+
+    /// Returns `children` in the order they are drawn, back to front.
+    ///
+    /// Siblings are sorted by their [`Node::render_order`], where `None` counts as `0`.
+    /// Siblings with the same order keep their order in `children`.
+    pub fn in_render_order<'a>(&self, children: &'a [NodeId]) -> Vec<&'a NodeId> {
+        let mut ordered: Vec<&NodeId> = children.iter().collect();
+        if children.iter().any(|id| {
+            self.get(id)
+                .is_some_and(|node| node.render_order().is_some())
+        }) {
+            ordered.sort_by_key(|id| self.get(id).and_then(Node::render_order).unwrap_or(0));
+        }
+        ordered
+    }
+
     pub fn filter(&self, filter: impl Fn(&Node) -> bool) -> Self {
         NodeRepository {
             nodes: self
@@ -109,6 +135,8 @@ mod tests {
                 id: "1".to_string(),
                 name: "err".to_string(),
                 active: true,
+                locked: false,
+                render_order: None,
             },
             transform: math2::transform::AffineTransform::identity(),
             size: Size {
@@ -126,4 +154,45 @@ mod tests {
         repo.remove(&id);
         assert!(repo.is_empty());
     }
+
+    fn error_node(id: &str, name: &str) -> Node {
+        Node::Error(ErrorNode {
+            base: BaseNode {
+                id: id.to_string(),
+                name: name.to_string(),
+                active: true,
+                locked: false,
+                render_order: None,
+            },
+            transform: math2::transform::AffineTransform::identity(),
+            size: Size {
+                width: 10.0,
+                height: 10.0,
+            },
+            error: "err".to_string(),
+            opacity: 1.0,
+        })
+    }
+
+    #[test]
+    fn find_by_name_is_exact_and_returns_duplicates() {
+        let mut repo = NodeRepository::new();
+        repo.insert(error_node("1", "Button/Primary"));
+        repo.insert(error_node("2", "Button/Primary"));
+        repo.insert(error_node("3", "Button/Primary/Label"));
+        repo.insert(error_node("4", "button/primary"));
+
+        let mut ids: Vec<NodeId> = repo
+            .find_by_name("Button/Primary")
+            .into_iter()
+            .map(|n| n.id())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["1".to_string(), "2".to_string()]);
+        assert!(repo.find_by_name("Button").is_empty());
+
+        let found = repo.find(|n| n.name().starts_with("Button/"));
+        assert_eq!(found.len(), 3);
+        assert_eq!(repo.iter().count(), 4);
+    }
 }
@@ -1,10 +1,19 @@
+use crate::cache::geometry::GeometryCache;
 use crate::node::repository::NodeRepository;
 use crate::painter::cvt;
+use crate::painter::geometry::{
+    boolean_operation_path_with_options, build_arc_path, build_container_clip_shape, build_shape,
+    build_shape_from_node, build_smooth_rrect_path, BooleanOpOptions,
+};
+use crate::runtime::repository::FontRepository;
+use crate::text::measure::measure_text_lines;
 use core::str;
 use math2::box_fit::BoxFit;
 use math2::rect::Rectangle;
 use math2::transform::AffineTransform;
-use serde::Deserialize;
+pub use math2::vector2::Axis;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 pub type NodeId = String;
 
@@ -65,9 +74,101 @@ pub enum StrokeAlign {
     Outside,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Shape drawn at the open ends of a stroked path.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/stroke-linecap)
+/// - [Figma](https://www.figma.com/plugin-docs/api/properties/nodes-strokecap/)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrokeCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+/// Portion of a stroke to draw as `(start, end)` fractions of the outline length, in
+/// `[0, 1]`. Animating `end` from `0` to `1` draws the stroke on, see
+/// [`trim_path`](crate::painter::geometry::trim_path).
+pub type StrokeTrim = (f32, f32);
+
+impl From<StrokeCap> for skia_safe::PaintCap {
+    fn from(cap: StrokeCap) -> Self {
+        match cap {
+            StrokeCap::Butt => skia_safe::PaintCap::Butt,
+            StrokeCap::Round => skia_safe::PaintCap::Round,
+            StrokeCap::Square => skia_safe::PaintCap::Square,
+        }
+    }
+}
+
+/// Rule deciding which points are inside a self-intersecting or multi-contour path.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/fill-rule)
+/// - [Figma](https://www.figma.com/developers/api#path-type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+impl From<FillRule> for skia_safe::PathFillType {
+    fn from(rule: FillRule) -> Self {
+        match rule {
+            FillRule::NonZero => skia_safe::PathFillType::Winding,
+            FillRule::EvenOdd => skia_safe::PathFillType::EvenOdd,
+        }
+    }
+}
+
+/// An 8-bit sRGB color with straight (non premultiplied) alpha, in `(r, g, b, a)` order.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color(pub u8, pub u8, pub u8, pub u8);
 
+impl Color {
+    pub const fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self(r, g, b, a)
+    }
+
+    /// Creates a color from normalized channels, clamped to `0..=1` and rounded to the
+    /// nearest 8-bit value.
+    pub fn from_rgba_f32(r: f32, g: f32, b: f32, a: f32) -> Self {
+        let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self(channel(r), channel(g), channel(b), channel(a))
+    }
+
+    /// The channels normalized to `0..=1`, alpha is not premultiplied.
+    pub fn rgba_f32(&self) -> [f32; 4] {
+        let Self(r, g, b, a) = *self;
+        [r, g, b, a].map(|v| v as f32 / 255.0)
+    }
+
+    /// The same color with its alpha replaced by `a`.
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self(self.0, self.1, self.2, a)
+    }
+}
+
+impl From<Color> for skia_safe::Color {
+    fn from(color: Color) -> Self {
+        let Color(r, g, b, a) = color;
+        skia_safe::Color::from_argb(a, r, g, b)
+    }
+}
+
+impl From<Color> for skia_safe::Color4f {
+    fn from(color: Color) -> Self {
+        let [r, g, b, a] = color.rgba_f32();
+        skia_safe::Color4f::new(r, g, b, a)
+    }
+}
+
+impl From<skia_safe::Color4f> for Color {
+    fn from(color: skia_safe::Color4f) -> Self {
+        Color::from_rgba_f32(color.r, color.g, color.b, color.a)
+    }
+}
+
 /// Represents filter effects inspired by SVG `<filter>` primitives.
 ///
 /// See also:
@@ -78,7 +179,10 @@ pub enum FilterEffect {
     /// Drop shadow filter: offset + blur + color
     DropShadow(FeDropShadow),
 
-    /// Gaussian blur filter: blur only
+    /// Inner shadow filter: offset + blur + spread + color, drawn inside the shape
+    InnerShadow(FeInnerShadow),
+
+    /// Gaussian blur filter: blurs the node's own content, stroke included
     GaussianBlur(FeGaussianBlur),
 
     /// Background blur filter: blur only
@@ -104,6 +208,28 @@ pub struct FeDropShadow {
     /// Blur radius (`stdDeviation` in SVG)
     pub blur: f32,
 
+    /// Distance the shadow shape grows (or shrinks, if negative) before blurring, in px
+    pub spread: f32,
+
+    /// Shadow color (includes alpha)
+    pub color: Color,
+}
+
+/// An inner shadow filter effect, similar to CSS `box-shadow: inset ...`
+#[derive(Debug, Clone, Copy)]
+pub struct FeInnerShadow {
+    /// Horizontal shadow offset in px
+    pub dx: f32,
+
+    /// Vertical shadow offset in px
+    pub dy: f32,
+
+    /// Blur radius (`stdDeviation` in SVG)
+    pub blur: f32,
+
+    /// Distance the shadow grows inwards from the shape edge, in px
+    pub spread: f32,
+
     /// Shadow color (includes alpha)
     pub color: Color,
 }
@@ -111,7 +237,8 @@ pub struct FeDropShadow {
 /// A standalone blur filter effect (`<feGaussianBlur>`)
 #[derive(Debug, Clone, Copy)]
 pub struct FeGaussianBlur {
-    /// Blur radius (`stdDeviation` in SVG)
+    /// Blur radius (`stdDeviation` in SVG), used as the gaussian sigma as is. The blur
+    /// spreads about `3 * radius` past the content.
     pub radius: f32,
 }
 
@@ -156,8 +283,14 @@ pub enum BlendMode {
     // Skia: kLuminosity
     Luminosity,
 
-    /// Like `Normal`, but means no blending at all (pass-through).
-    /// This is Figma-specific, and typically treated the same as `Normal`.
+    /// Erases the backdrop where the node is opaque, e.g. to punch holes into the scene
+    /// background. Skia: kDstOut
+    DstOut,
+
+    /// Figma's pass-through: a group with this mode is not composited as a unit, its
+    /// children blend with whatever is behind the group. A `Normal` group is isolated
+    /// instead, so its children only blend with each other. Other nodes treat it as
+    /// `Normal`.
     PassThrough,
 }
 
@@ -181,6 +314,7 @@ impl From<BlendMode> for skia_safe::BlendMode {
             BlendMode::Saturation => Saturation,
             BlendMode::Color => Color,
             BlendMode::Luminosity => Luminosity,
+            BlendMode::DstOut => DstOut,
             BlendMode::PassThrough => SrcOver, // fallback
         }
     }
@@ -188,7 +322,7 @@ impl From<BlendMode> for skia_safe::BlendMode {
 
 /// Text Transform (Text Case)
 /// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/text-transform)
-#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum TextTransform {
     #[serde(rename = "none")]
     None,
@@ -200,13 +334,27 @@ pub enum TextTransform {
     Capitalize,
 }
 
+/// How text that does not fit its line limit is cut off.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/text-overflow)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, Default)]
+pub enum TextOverflow {
+    /// Lines past the limit are dropped.
+    #[serde(rename = "clip")]
+    #[default]
+    Clip,
+    /// The last visible line ends with an ellipsis (`…`).
+    #[serde(rename = "ellipsis")]
+    Ellipsis,
+}
+
 /// Supported text decoration modes.
 ///
 /// Only `Underline` and `None` are supported in the current version.
 ///
 /// - [Flutter](https://api.flutter.dev/flutter/dart-ui/TextDecoration-class.html)  
 /// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/text-decoration)
-#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum TextDecoration {
     #[serde(rename = "none")]
     None,
@@ -231,11 +379,11 @@ impl From<TextDecoration> for skia_safe::textlayout::TextDecoration {
 
 /// Supported horizontal text alignment.
 ///
-/// Does not include `Start` or `End`, as they are not supported currently.
+/// `Start` and `End` follow the [`TextDirection`] of the text, see [`TextAlign::resolve`].
 ///
 /// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/text-align)  
 /// - [Flutter](https://api.flutter.dev/flutter/dart-ui/TextAlign.html)
-#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum TextAlign {
     #[serde(rename = "left")]
     Left,
@@ -245,6 +393,27 @@ pub enum TextAlign {
     Center,
     #[serde(rename = "justify")]
     Justify,
+    #[serde(rename = "start")]
+    Start,
+    #[serde(rename = "end")]
+    End,
+}
+
+impl TextAlign {
+    /// Resolves `Start` and `End` to a physical alignment for text in `direction`.
+    ///
+    /// In right-to-left text `Start` is the right edge. Other alignments are returned as is.
+    pub fn resolve(self, direction: TextDirection) -> TextAlign {
+        match (self, direction) {
+            (TextAlign::Start, TextDirection::Ltr) | (TextAlign::End, TextDirection::Rtl) => {
+                TextAlign::Left
+            }
+            (TextAlign::Start, TextDirection::Rtl) | (TextAlign::End, TextDirection::Ltr) => {
+                TextAlign::Right
+            }
+            (align, _) => align,
+        }
+    }
 }
 
 impl From<TextAlign> for skia_safe::textlayout::TextAlign {
@@ -255,6 +424,29 @@ impl From<TextAlign> for skia_safe::textlayout::TextAlign {
             TextAlign::Right => Right,
             TextAlign::Center => Center,
             TextAlign::Justify => Justify,
+            TextAlign::Start => Start,
+            TextAlign::End => End,
+        }
+    }
+}
+
+/// Base direction of a paragraph.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/direction)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    #[serde(rename = "ltr")]
+    #[default]
+    Ltr,
+    #[serde(rename = "rtl")]
+    Rtl,
+}
+
+impl From<TextDirection> for skia_safe::textlayout::TextDirection {
+    fn from(direction: TextDirection) -> Self {
+        match direction {
+            TextDirection::Ltr => skia_safe::textlayout::TextDirection::LTR,
+            TextDirection::Rtl => skia_safe::textlayout::TextDirection::RTL,
         }
     }
 }
@@ -265,7 +457,7 @@ impl From<TextAlign> for skia_safe::textlayout::TextAlign {
 ///
 /// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/align-content)  
 /// - [Konva](https://konvajs.org/api/Konva.Text.html#verticalAlign)
-#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum TextAlignVertical {
     #[serde(rename = "top")]
     Top,
@@ -275,12 +467,26 @@ pub enum TextAlignVertical {
     Bottom,
 }
 
+impl TextAlignVertical {
+    /// The y offset of content `content_height` tall within a box `box_height` tall.
+    ///
+    /// Content taller than the box starts at the top, the offset is never negative.
+    pub fn offset(self, box_height: f32, content_height: f32) -> f32 {
+        let free = (box_height - content_height).max(0.0);
+        match self {
+            TextAlignVertical::Top => 0.0,
+            TextAlignVertical::Center => free / 2.0,
+            TextAlignVertical::Bottom => free,
+        }
+    }
+}
+
 /// Font weight value (1-1000).
 ///
 /// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/font-weight)  
 /// - [Flutter](https://api.flutter.dev/flutter/dart-ui/FontWeight-class.html)  
 /// - [OpenType spec](https://learn.microsoft.com/en-us/typography/opentype/spec/os2#usweightclass)
-#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub struct FontWeight(pub u32);
 
 impl FontWeight {
@@ -311,14 +517,69 @@ impl FontWeight {
     }
 }
 
+/// Height of a line of text.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/line-height)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineHeight {
+    /// The font's own line spacing, from its ascent, descent and line gap metrics.
+    #[default]
+    Normal,
+    /// A fixed height in logical pixels.
+    Px(f32),
+    /// A multiple of the font size, e.g. `1.5` for 24px lines at 16px.
+    Multiplier(f32),
+}
+
+impl LineHeight {
+    /// The line height as a multiple of `font_size`, `None` for [`LineHeight::Normal`].
+    pub fn multiplier(&self, font_size: f32) -> Option<f32> {
+        match *self {
+            LineHeight::Normal => None,
+            LineHeight::Px(px) if font_size > 0.0 => Some(px / font_size),
+            LineHeight::Px(_) => None,
+            LineHeight::Multiplier(multiplier) => Some(multiplier),
+        }
+    }
+}
+
+/// Additional spacing between characters.
+///
+/// - [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/letter-spacing)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LetterSpacing {
+    /// A fixed spacing in logical pixels.
+    Px(f32),
+    /// A fraction of the font size, e.g. `0.1` for 1.6px at 16px.
+    Em(f32),
+}
+
+impl Default for LetterSpacing {
+    fn default() -> Self {
+        LetterSpacing::Px(0.0)
+    }
+}
+
+impl LetterSpacing {
+    /// The spacing in logical pixels for text of `font_size`.
+    pub fn to_px(&self, font_size: f32) -> f32 {
+        match *self {
+            LetterSpacing::Px(px) => px,
+            LetterSpacing::Em(em) => em * font_size,
+        }
+    }
+}
+
 /// A set of style properties that can be applied to a text or text span.
 #[derive(Debug, Clone)]
 pub struct TextStyle {
     /// Text decoration (e.g. underline or none).
     pub text_decoration: TextDecoration,
 
-    /// Optional font family name (e.g. "Roboto").
-    pub font_family: String,
+    /// Font family names in fallback order (e.g. `["Roboto", "Noto Color Emoji"]`).
+    ///
+    /// Glyphs missing from the first family are looked up in the following ones.
+    pub font_families: Vec<String>,
 
     /// Font size in logical pixels.
     pub font_size: f32,
@@ -329,25 +590,88 @@ pub struct TextStyle {
     /// Font italic style.
     pub italic: bool,
 
-    /// Additional spacing between characters, in logical pixels.  
-    /// Default is `0.0`.
-    pub letter_spacing: Option<f32>,
+    /// Additional spacing between characters.  
+    /// Default is `LetterSpacing::Px(0.0)`.
+    pub letter_spacing: LetterSpacing,
 
-    /// Line height
-    pub line_height: Option<f32>,
+    /// Height of each line.  
+    /// Default is [`LineHeight::Normal`].
+    pub line_height: LineHeight,
 
     /// Text transform (e.g. uppercase, lowercase, capitalize)
     pub text_transform: TextTransform,
+
+    /// Base direction, used to resolve `Start` and `End` alignment.
+    pub direction: TextDirection,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl TextStyle {
+    /// The primary font family, i.e. the first entry of [`TextStyle::font_families`].
+    ///
+    /// Returns an empty string if no family is set.
+    pub fn font_family(&self) -> &str {
+        self.font_families.first().map_or("", String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GradientStop {
     /// 0.0 = start, 1.0 = end
     pub offset: f32,
     pub color: Color,
 }
 
-#[derive(Debug, Clone)]
+/// Clamps stop offsets to `[0, 1]`, stable-sorts the stops by offset and drops redundant stops.
+///
+/// Of several stops sharing an offset only the first and the last are kept, they form a hard
+/// edge and anything in between is never visible. Exact duplicates are dropped as well.
+/// Returns the number of malformed stops found: out of range, out of order or dropped.
+pub fn normalize_stops(stops: &mut Vec<GradientStop>) -> usize {
+    let mut malformed = 0;
+    for stop in stops.iter_mut() {
+        let offset = if stop.offset.is_nan() {
+            0.0
+        } else {
+            stop.offset.clamp(0.0, 1.0)
+        };
+        if offset != stop.offset {
+            stop.offset = offset;
+            malformed += 1;
+        }
+    }
+
+    malformed += stops
+        .windows(2)
+        .filter(|pair| pair[1].offset < pair[0].offset)
+        .count();
+    // `sort_by` is stable, stops at the same offset keep their order
+    stops.sort_by(|a, b| a.offset.partial_cmp(&b.offset).unwrap());
+
+    let sorted = std::mem::take(stops);
+    for (i, stop) in sorted.iter().enumerate() {
+        let first = i == 0 || sorted[i - 1].offset != stop.offset;
+        let last = i + 1 == sorted.len() || sorted[i + 1].offset != stop.offset;
+        if first || last {
+            stops.push(*stop);
+        }
+    }
+    stops.dedup();
+
+    malformed + sorted.len() - stops.len()
+}
+
+/// Paint of the strokes emitted for nodes without one, see [`ContainerNode::to_rectangle`].
+const TRANSPARENT_PAINT: Paint = Paint::Solid(SolidPaint {
+    color: Color(0, 0, 0, 0),
+    opacity: 1.0,
+});
+
+/// A fill or stroke paint.
+///
+/// Every paint carries its own `opacity`, which multiplies with the alpha of its colors
+/// and with the opacity of the node it is painted on. The effective alpha of a solid
+/// paint is `node.opacity * paint.opacity * color.a`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Paint {
     Solid(SolidPaint),
     LinearGradient(LinearGradientPaint),
@@ -355,32 +679,181 @@ pub enum Paint {
     Image(ImagePaint),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SolidPaint {
+    /// The color, including its own alpha.
     pub color: Color,
+    /// Multiplies the alpha of `color`.
     pub opacity: f32,
 }
 
-#[derive(Debug, Clone)]
+/// How a gradient continues past its first and last stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientSpread {
+    /// The end colors extend outwards.
+    #[default]
+    Pad,
+    /// The gradient starts over.
+    Repeat,
+    /// The gradient is mirrored back and forth.
+    Reflect,
+}
+
+impl From<GradientSpread> for skia_safe::TileMode {
+    fn from(spread: GradientSpread) -> Self {
+        match spread {
+            GradientSpread::Pad => skia_safe::TileMode::Clamp,
+            GradientSpread::Repeat => skia_safe::TileMode::Repeat,
+            GradientSpread::Reflect => skia_safe::TileMode::Mirror,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct LinearGradientPaint {
     pub transform: AffineTransform,
     pub stops: Vec<GradientStop>,
     pub opacity: f32,
+    pub spread: GradientSpread,
 }
 
-#[derive(Debug, Clone)]
+impl LinearGradientPaint {
+    /// Returns the start and end handles of the gradient in node space.
+    ///
+    /// The untransformed gradient runs from `(0, 0)` to `(width, 0)` of the node.
+    pub fn handles(&self, size: &Size) -> (Point, Point) {
+        (
+            transform_point(&self.transform, Point { x: 0.0, y: 0.0 }),
+            transform_point(
+                &self.transform,
+                Point {
+                    x: size.width,
+                    y: 0.0,
+                },
+            ),
+        )
+    }
+
+    /// Updates the transform so that the gradient runs from `start` to `end` in node space.
+    pub fn set_handles(&mut self, size: &Size, start: Point, end: Point) {
+        self.transform = handles_transform(
+            Point { x: 0.0, y: 0.0 },
+            size.width,
+            start,
+            Point {
+                x: end.x - start.x,
+                y: end.y - start.y,
+            },
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct RadialGradientPaint {
     pub transform: AffineTransform,
     pub stops: Vec<GradientStop>,
     pub opacity: f32,
+    pub spread: GradientSpread,
 }
 
-#[derive(Debug, Clone)]
+impl RadialGradientPaint {
+    /// Returns the center and radius handles of the gradient in node space.
+    ///
+    /// The untransformed gradient is centered in the node, with a radius of half the
+    /// shorter side. The radius handle is the point on the circle along the x axis.
+    pub fn handles(&self, size: &Size) -> (Point, Point) {
+        let (center, radius) = radial_geometry(size);
+        (
+            transform_point(&self.transform, center),
+            transform_point(
+                &self.transform,
+                Point {
+                    x: center.x + radius,
+                    y: center.y,
+                },
+            ),
+        )
+    }
+
+    /// Updates the transform so that the gradient is centered at `center` and reaches
+    /// `radius` (a point on the circle) in node space.
+    pub fn set_handles(&mut self, size: &Size, center: Point, radius: Point) {
+        let (origin, length) = radial_geometry(size);
+        self.transform = handles_transform(
+            origin,
+            length,
+            center,
+            Point {
+                x: radius.x - center.x,
+                y: radius.y - center.y,
+            },
+        );
+    }
+}
+
+fn radial_geometry(size: &Size) -> (Point, f32) {
+    (
+        Point {
+            x: size.width / 2.0,
+            y: size.height / 2.0,
+        },
+        size.width.min(size.height) / 2.0,
+    )
+}
+
+fn transform_point(transform: &AffineTransform, point: Point) -> Point {
+    let [[a, c, tx], [b, d, ty]] = transform.matrix;
+    Point {
+        x: a * point.x + c * point.y + tx,
+        y: b * point.x + d * point.y + ty,
+    }
+}
+
+/// Builds the similarity transform (rotation + uniform scale + translation) mapping
+/// `origin` to `target` and the x axis segment of `length` from `origin` to `direction`.
+fn handles_transform(
+    origin: Point,
+    length: f32,
+    target: Point,
+    direction: Point,
+) -> AffineTransform {
+    if length == 0.0 {
+        return AffineTransform::identity();
+    }
+    let a = direction.x / length;
+    let b = direction.y / length;
+    let (c, d) = (-b, a);
+    AffineTransform {
+        matrix: [
+            [a, c, target.x - (a * origin.x + c * origin.y)],
+            [b, d, target.y - (b * origin.x + d * origin.y)],
+        ],
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ImagePaint {
     pub transform: AffineTransform,
     pub _ref: String,
     pub fit: BoxFit,
     pub opacity: f32,
+    /// Tiles the image over the painted area. When repeating, `fit` is ignored and
+    /// `transform` places the first tile, at the natural size of the image.
+    pub repeat: ImageRepeat,
+}
+
+/// How an [`ImagePaint`] is repeated past the bounds of the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageRepeat {
+    /// The image is drawn once.
+    #[default]
+    NoRepeat,
+    /// The image is tiled in both directions.
+    Repeat,
+    /// The image is tiled horizontally only.
+    RepeatX,
+    /// The image is tiled vertically only.
+    RepeatY,
 }
 
 #[derive(Debug, Clone)]
@@ -420,6 +893,49 @@ impl RectangularCornerRadius {
     }
 }
 
+/// Insets from each edge of a box, e.g. the padding of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EdgeInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl EdgeInsets {
+    pub fn zero() -> Self {
+        Self::all(0.0)
+    }
+
+    pub fn all(value: f32) -> Self {
+        Self::symmetric(value, value)
+    }
+
+    /// Insets with `vertical` on the top and bottom and `horizontal` on the left and right.
+    pub fn symmetric(vertical: f32, horizontal: f32) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.top == 0.0 && self.right == 0.0 && self.bottom == 0.0 && self.left == 0.0
+    }
+
+    /// Shrinks `rect` by the insets, never below an empty size.
+    pub fn deflate(&self, rect: &Rectangle) -> Rectangle {
+        Rectangle {
+            x: rect.x + self.left,
+            y: rect.y + self.top,
+            width: (rect.width - self.left - self.right).max(0.0),
+            height: (rect.height - self.top - self.bottom).max(0.0),
+        }
+    }
+}
+
 // region: Scene
 #[derive(Debug, Clone)]
 pub struct Scene {
@@ -429,6 +945,682 @@ pub struct Scene {
     pub children: Vec<NodeId>,
     pub nodes: NodeRepository,
     pub background_color: Option<Color>,
+    /// Image drawn over the background color, fit to the viewport.
+    pub background_image: Option<ImagePaint>,
+    /// Ruler guides of the scene, drawn only when
+    /// [`RenderOptions::show_guides`](crate::painter::RenderOptions::show_guides) is set.
+    pub guides: Vec<Guide>,
+    /// How nodes follow their container when it is resized, see [`LayoutConstraint`].
+    pub constraints: LayoutConstraints,
+}
+
+/// A ruler guide, an infinite line perpendicular to `axis` at `offset` in scene space.
+///
+/// A guide on [`Axis::X`] is the vertical line `x = offset`, one on [`Axis::Y`] the
+/// horizontal line `y = offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guide {
+    pub axis: Axis,
+    pub offset: f32,
+}
+
+/// How a node follows its container along one axis when the container is resized.
+///
+/// `Start` is the left edge on the horizontal axis and the top edge on the vertical axis,
+/// `End` the right and bottom edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Constraint {
+    /// Keeps the distance to the start edge.
+    #[default]
+    Start,
+    /// Keeps the distance to the end edge.
+    End,
+    /// Keeps the offset of the node center from the container center.
+    Center,
+    /// Moves and resizes the node proportionally to the container.
+    Scale,
+    /// Keeps the distances to both edges, resizing the node.
+    Stretch,
+}
+
+/// The resizing constraints of a node inside its container, like Figma's constraints.
+///
+/// The default pins the node to the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LayoutConstraint {
+    pub horizontal: Constraint,
+    pub vertical: Constraint,
+}
+
+/// Layout constraints by node id, nodes without an entry use the default constraint.
+pub type LayoutConstraints = HashMap<NodeId, LayoutConstraint>;
+
+/// A draw operation of a flattened scene, see [`Scene::flatten`].
+#[derive(Debug, Clone)]
+pub struct FlatDrawOp {
+    /// Id of the scene node the operation comes from.
+    pub id: NodeId,
+    /// The node to draw. Its own transform is kept as authored, draw it with `transform`.
+    pub node: LeafNode,
+    /// Maps the local space of `node` into scene space, its own transform included.
+    pub transform: AffineTransform,
+    /// Product of the opacities of the ancestors, multiplied with the node's own opacity
+    /// when drawing.
+    pub opacity: f32,
+    /// Clip outlines of the clipping containers above the node in scene space, outermost
+    /// first. The node is drawn within their intersection.
+    pub clips: Vec<skia_safe::Path>,
+}
+
+impl Scene {
+    /// An empty scene at the origin, without background, guides or constraints.
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            transform: AffineTransform::identity(),
+            children: vec![],
+            nodes: NodeRepository::new(),
+            background_color: None,
+            background_image: None,
+            guides: vec![],
+            constraints: LayoutConstraints::new(),
+        }
+    }
+
+    /// Walks the scene tree depth-first in paint order, yielding each node id with its
+    /// depth (`0` for the scene's direct children).
+    ///
+    /// A node is visited at most once, so nodes referencing one of their ancestors
+    /// do not cause infinite loops.
+    pub fn walk(&self) -> SceneWalk<'_> {
+        SceneWalk::new(self, false)
+    }
+
+    /// Like [`Scene::walk`], but skips inactive nodes along with their subtrees.
+    pub fn walk_active(&self) -> SceneWalk<'_> {
+        SceneWalk::new(self, true)
+    }
+
+    /// Returns the union of the render bounds of every active node, in scene space.
+    ///
+    /// Render bounds include stroke extents and effect overflow (blurs and shadows), so the
+    /// result can size an export surface without clipping. Returns `None` for a scene
+    /// without active nodes.
+    pub fn content_bounds(&self) -> Option<Rectangle> {
+        let cache = GeometryCache::from_scene(self);
+        let bounds: Vec<Rectangle> = self
+            .walk_active()
+            .filter_map(|(id, _)| cache.get_render_bounds(&id))
+            .collect();
+        if bounds.is_empty() {
+            return None;
+        }
+        Some(math2::rect::union(&bounds))
+    }
+
+    /// Returns the active nodes that paint inside `viewport`, given in scene space, in
+    /// paint order.
+    ///
+    /// Leaves are kept when their [`Node::ink_bounds`] intersect the viewport. Groups are
+    /// kept when any of their children is, boolean operations when any of their operands
+    /// is (the operands themselves are not listed, they are drawn as part of the
+    /// operation). Clipping containers that are off-screen are culled with their whole
+    /// subtree.
+    pub fn visible_nodes(&self, viewport: Rectangle) -> Vec<NodeId> {
+        let mut visible = Vec::new();
+        let mut visited = HashSet::new();
+        self.collect_visible(
+            &self.children,
+            &self.transform,
+            &viewport,
+            &mut visited,
+            &mut visible,
+        );
+        visible
+    }
+
+    /// Flattens the active nodes into a list of draw operations in paint order, each with
+    /// its world transform, inherited opacity and clip resolved.
+    ///
+    /// Groups contribute their transform and opacity to their descendants and are not
+    /// emitted. Containers are emitted as their box (see [`ContainerNode::to_rectangle`])
+    /// before their children, which also inherit their clip. Boolean operations are
+    /// emitted as their resulting path (see [`BooleanPathOperationNode::to_path_node`]),
+    /// their operands are not.
+    pub fn flatten(&self) -> Vec<FlatDrawOp> {
+        let mut ops = Vec::new();
+        let mut visited = HashSet::new();
+        self.flatten_children(
+            &self.children,
+            &self.transform,
+            1.0,
+            &[],
+            &mut visited,
+            &mut ops,
+        );
+        ops
+    }
+
+    fn flatten_children<'a>(
+        &'a self,
+        children: &'a [NodeId],
+        parent_world: &AffineTransform,
+        opacity: f32,
+        clips: &[skia_safe::Path],
+        visited: &mut HashSet<&'a NodeId>,
+        ops: &mut Vec<FlatDrawOp>,
+    ) {
+        for id in self.nodes.in_render_order(children) {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            if !node.active() {
+                continue;
+            }
+
+            let world = parent_world.compose(&node.transform());
+            let op = |node: LeafNode| FlatDrawOp {
+                id: id.clone(),
+                node,
+                transform: world,
+                opacity,
+                clips: clips.to_vec(),
+            };
+            let leaf = match node {
+                Node::Group(n) => {
+                    let opacity = opacity * n.opacity;
+                    self.flatten_children(&n.children, &world, opacity, clips, visited, ops);
+                    continue;
+                }
+                Node::Container(n) => {
+                    ops.push(op(LeafNode::Rectangle(n.to_rectangle())));
+                    let mut clips = clips.to_vec();
+                    if n.clip {
+                        let mut clip = build_container_clip_shape(n).to_path();
+                        clip.transform(&cvt::sk_matrix(world.matrix));
+                        clips.push(clip);
+                    }
+                    let opacity = opacity * n.opacity;
+                    self.flatten_children(&n.children, &world, opacity, &clips, visited, ops);
+                    continue;
+                }
+                Node::BooleanOperation(n) => LeafNode::Path(n.to_path_node(&self.nodes)),
+                Node::Error(n) => LeafNode::Error(n.clone()),
+                Node::Rectangle(n) => LeafNode::Rectangle(n.clone()),
+                Node::Ellipse(n) => LeafNode::Ellipse(n.clone()),
+                Node::Polygon(n) => LeafNode::Polygon(n.clone()),
+                Node::RegularPolygon(n) => LeafNode::RegularPolygon(n.clone()),
+                Node::RegularStarPolygon(n) => LeafNode::RegularStarPolygon(n.clone()),
+                Node::Line(n) => LeafNode::Line(n.clone()),
+                Node::TextSpan(n) => LeafNode::TextSpan(n.clone()),
+                Node::RichText(n) => LeafNode::RichText(n.clone()),
+                Node::Path(n) => LeafNode::Path(n.clone()),
+                Node::Image(n) => LeafNode::Image(n.clone()),
+            };
+            ops.push(op(leaf));
+        }
+    }
+
+    /// Pushes the visible nodes of `children` into `visible`, returns whether any was.
+    fn collect_visible<'a>(
+        &'a self,
+        children: &'a [NodeId],
+        parent_world: &AffineTransform,
+        viewport: &Rectangle,
+        visited: &mut HashSet<&'a NodeId>,
+        visible: &mut Vec<NodeId>,
+    ) -> bool {
+        let mut any = false;
+        for id in self.nodes.in_render_order(children) {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            if !node.active() {
+                continue;
+            }
+
+            let world = parent_world.compose(&node.transform());
+            let ink_visible =
+                || math2::rect::transform(node.ink_bounds(), &world).intersects(viewport);
+
+            let start = visible.len();
+            let shown = match node {
+                Node::Group(n) => {
+                    self.collect_visible(&n.children, &world, viewport, visited, visible)
+                }
+                Node::Container(n) => {
+                    let own = ink_visible();
+                    // the clip is inside the ink bounds, nothing of the subtree shows
+                    if n.clip && !own {
+                        continue;
+                    }
+                    let children =
+                        self.collect_visible(&n.children, &world, viewport, visited, visible);
+                    own || children
+                }
+                Node::BooleanOperation(n) => {
+                    let mut operands = Vec::new();
+                    self.collect_visible(&n.children, &world, viewport, visited, &mut operands)
+                }
+                _ => ink_visible(),
+            };
+            if shown {
+                visible.insert(start, id.clone());
+                any = true;
+            }
+        }
+        any
+    }
+
+    /// Returns the transform that maps points in the local space of `from` into the
+    /// local space of `to`.
+    ///
+    /// The transform is composed up from `from` to the nearest common ancestor, then
+    /// down to `to`. Returns `None` if either node is not part of the scene, or if the
+    /// transform down to `to` is not invertible.
+    pub fn relative_transform(&self, from: &NodeId, to: &NodeId) -> Option<AffineTransform> {
+        let from_path = self.path_to(from)?;
+        let to_path = self.path_to(to)?;
+
+        let common = from_path
+            .iter()
+            .zip(to_path.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let up = self.compose_path(&from_path[common..])?;
+        let down = self.compose_path(&to_path[common..])?;
+
+        Some(down.inverse()?.compose(&up))
+    }
+
+    /// Returns the ids from a scene root down to `id` (inclusive).
+    fn path_to(&self, id: &NodeId) -> Option<Vec<NodeId>> {
+        fn visit(
+            nodes: &NodeRepository,
+            children: &[NodeId],
+            id: &NodeId,
+            path: &mut Vec<NodeId>,
+        ) -> bool {
+            for child in children {
+                path.push(child.clone());
+                if child == id {
+                    return true;
+                }
+                let grandchildren = nodes.get(child).map_or(&[][..], Node::children);
+                if visit(nodes, grandchildren, id, path) {
+                    return true;
+                }
+                path.pop();
+            }
+            false
+        }
+
+        let mut path = Vec::new();
+        if visit(&self.nodes, &self.children, id, &mut path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Composes the local transforms along `path`, parent first.
+    fn compose_path(&self, path: &[NodeId]) -> Option<AffineTransform> {
+        let mut transform = AffineTransform::identity();
+        for id in path {
+            transform = transform.compose(&self.nodes.get(id)?.transform());
+        }
+        Some(transform)
+    }
+
+    /// Removes all text nodes that are empty or whitespace only, see
+    /// [`TextSpanNode::is_effectively_empty`], and returns how many were removed.
+    ///
+    /// The nodes are dropped from the repository and from the children of their parents.
+    pub fn prune_empty_text(&mut self) -> usize {
+        let empty: HashSet<NodeId> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| matches!(node, Node::TextSpan(n) if n.is_effectively_empty()))
+            .map(|(id, _)| id.clone())
+            .collect();
+        if empty.is_empty() {
+            return 0;
+        }
+
+        for id in &empty {
+            self.nodes.remove(id);
+        }
+        self.children.retain(|id| !empty.contains(id));
+        let ids: Vec<NodeId> = self.nodes.iter().map(|(id, _)| id.clone()).collect();
+        for id in ids {
+            if let Some(children) = self.nodes.get_mut(&id).and_then(Node::children_mut) {
+                children.retain(|child| !empty.contains(child));
+            }
+        }
+
+        empty.len()
+    }
+
+    /// Resizes all auto sized text nodes to their content, see [`TextSpanNode::measure`],
+    /// and returns how many were measured.
+    pub fn fit_text_to_content(&mut self, fonts: &FontRepository) -> usize {
+        let ids: Vec<NodeId> = self
+            .nodes
+            .iter()
+            .filter(
+                |(_, node)| matches!(node, Node::TextSpan(n) if n.sizing != TextSizingMode::Fixed),
+            )
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &ids {
+            if let Some(Node::TextSpan(node)) = self.nodes.get_mut(id) {
+                node.size = node.measure(fonts);
+            }
+        }
+        ids.len()
+    }
+
+    /// Clones `root` and all its descendants with fresh ids, e.g. to paste them into
+    /// another document.
+    ///
+    /// Returns a scene fragment holding the cloned nodes, with the clone of `root` as its
+    /// only child, and the mapping from each original id to its new id. Children lists
+    /// within the fragment refer to the new ids, anything else keyed by the original ids
+    /// can be rewired by the caller through the mapping. The fragment transform places the
+    /// clone where the original is in this scene. Returns `None` if `root` is not in the
+    /// repository.
+    pub fn clone_subtree_remapped(
+        &self,
+        root: &NodeId,
+    ) -> Option<(Scene, HashMap<NodeId, NodeId>)> {
+        let root_node = self.nodes.get(root)?;
+
+        // assign new ids first, so children can be rewired in a single pass
+        let mut mapping = HashMap::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if mapping.contains_key(id) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            mapping.insert(id.clone(), uuid::Uuid::new_v4().to_string());
+            stack.extend(node.children());
+        }
+
+        let mut nodes = NodeRepository::new();
+        for (old_id, new_id) in &mapping {
+            let mut node = self.nodes.get(old_id).unwrap().clone();
+            node.base_mut().id = new_id.clone();
+            if let Some(children) = node.children_mut() {
+                // dangling children have no clone and are dropped
+                *children = children
+                    .iter()
+                    .filter_map(|child| mapping.get(child).cloned())
+                    .collect();
+            }
+            nodes.insert(node);
+        }
+
+        let ancestors = match self.path_to(root) {
+            Some(path) => self.compose_path(&path[..path.len() - 1])?,
+            None => AffineTransform::identity(),
+        };
+        let fragment = Scene {
+            transform: self.transform.compose(&ancestors),
+            children: vec![mapping[root].clone()],
+            nodes,
+            ..Scene::new(uuid::Uuid::new_v4().to_string(), root_node.name())
+        };
+        Some((fragment, mapping))
+    }
+
+    /// Returns the topmost node whose geometry contains `point`, given in scene space.
+    ///
+    /// Nodes are tested back-to-front, with the point mapped into each node's local
+    /// space. Inactive and locked nodes are skipped along with their children, and
+    /// children of clipping containers are only hit inside the container. No fonts are
+    /// available here, so text operands of boolean operations do not contribute to their
+    /// hit area.
+    pub fn node_at(&self, point: Point) -> Option<NodeId> {
+        self.node_at_among(point, None)
+    }
+
+    /// Same as [`Scene::node_at`], skipping nodes that are not in `candidates`.
+    ///
+    /// `candidates` must contain every node that could be hit along with its
+    /// ancestors, see [`crate::cache::scene::SceneCache::node_at`].
+    pub(crate) fn node_at_among(
+        &self,
+        point: Point,
+        candidates: Option<&HashSet<NodeId>>,
+    ) -> Option<NodeId> {
+        let mut visited = HashSet::new();
+        self.hit_children(
+            &self.children,
+            &self.transform,
+            point,
+            candidates,
+            &mut visited,
+        )
+    }
+
+    fn hit_children<'a>(
+        &'a self,
+        children: &'a [NodeId],
+        parent_world: &AffineTransform,
+        point: Point,
+        candidates: Option<&HashSet<NodeId>>,
+        visited: &mut HashSet<&'a NodeId>,
+    ) -> Option<NodeId> {
+        for id in self.nodes.in_render_order(children).into_iter().rev() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if candidates.is_some_and(|candidates| !candidates.contains(id)) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            if !node.active() || node.locked() {
+                continue;
+            }
+
+            let world = parent_world.compose(&node.transform());
+            let Some(inverse) = world.inverse() else {
+                continue;
+            };
+            let local = transform_point(&inverse, point);
+            let local = skia_safe::Point::new(local.x, local.y);
+
+            let hit = match node {
+                Node::Group(n) => {
+                    if let Some(hit) =
+                        self.hit_children(&n.children, &world, point, candidates, visited)
+                    {
+                        return Some(hit);
+                    }
+                    false
+                }
+                Node::Container(n) => {
+                    let inside = build_shape(&IntrinsicSizeNode::Container(n.clone()))
+                        .to_path()
+                        .contains(local);
+                    let inside_clip = if n.padding.is_zero() {
+                        inside
+                    } else {
+                        build_container_clip_shape(n).to_path().contains(local)
+                    };
+                    if inside_clip || !n.clip {
+                        if let Some(hit) =
+                            self.hit_children(&n.children, &world, point, candidates, visited)
+                        {
+                            return Some(hit);
+                        }
+                    }
+                    inside
+                }
+                Node::BooleanOperation(n) => n.resolve_path(&self.nodes).contains(local),
+                Node::TextSpan(n) => build_shape(&IntrinsicSizeNode::TextSpan(n.clone()))
+                    .to_path()
+                    .contains(local),
+                Node::RichText(n) => build_shape(&IntrinsicSizeNode::RichText(n.clone()))
+                    .to_path()
+                    .contains(local),
+                _ => build_shape_from_node(node)
+                    .map_or(false, |shape| shape.to_path().contains(local)),
+            };
+            if hit {
+                return Some(id.clone());
+            }
+        }
+        None
+    }
+}
+
+impl Scene {
+    /// Checks the node graph of the scene, returning every problem found.
+    ///
+    /// Reports child ids without a node, children that are one of their own ancestors and
+    /// nodes of the repository that are not reachable from the scene roots. The scene is
+    /// left untouched and an empty list means the scene is well formed. Problems are
+    /// reported in walk order, unreachable nodes sorted by id.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let mut done: HashSet<&NodeId> = HashSet::new();
+
+        for id in &self.children {
+            if self.nodes.get(id).is_none() {
+                errors.push(ValidationError::DanglingChild {
+                    parent: None,
+                    child: id.clone(),
+                });
+            } else {
+                self.validate_subtree(id, &mut done, &mut errors);
+            }
+        }
+
+        let mut unreachable: Vec<&NodeId> = self
+            .nodes
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| !done.contains(id))
+            .collect();
+        unreachable.sort();
+        for id in unreachable {
+            errors.push(ValidationError::Unreachable { node: id.clone() });
+            self.validate_subtree(id, &mut done, &mut errors);
+        }
+        errors
+    }
+
+    /// Depth-first search from `root`, skipping nodes in `done` and adding every visited
+    /// node to it.
+    fn validate_subtree<'a>(
+        &'a self,
+        root: &'a NodeId,
+        done: &mut HashSet<&'a NodeId>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if !done.insert(root) {
+            return;
+        }
+        // the current path from `root`, each node with the index of its next child
+        let mut path: Vec<(&NodeId, usize)> = vec![(root, 0)];
+        let mut on_path: HashSet<&NodeId> = HashSet::from([root]);
+
+        while let Some((parent, index)) = path.last_mut() {
+            let parent: &'a NodeId = *parent;
+            let children = self.nodes.get(parent).map_or(&[][..], Node::children);
+            let Some(child) = children.get(*index) else {
+                on_path.remove(parent);
+                path.pop();
+                continue;
+            };
+            *index += 1;
+
+            if self.nodes.get(child).is_none() {
+                errors.push(ValidationError::DanglingChild {
+                    parent: Some(parent.clone()),
+                    child: child.clone(),
+                });
+            } else if on_path.contains(child) {
+                errors.push(ValidationError::Cycle {
+                    parent: parent.clone(),
+                    child: child.clone(),
+                });
+            } else if done.insert(child) {
+                on_path.insert(child);
+                path.push((child, 0));
+            }
+        }
+    }
+}
+
+/// A problem in the node graph of a [`Scene`], see [`Scene::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// `parent` lists `child` as a child, but the repository has no node with that id.
+    /// `parent` is `None` for the scene roots.
+    DanglingChild {
+        parent: Option<NodeId>,
+        child: NodeId,
+    },
+    /// `parent` lists `child` as a child, while `child` is an ancestor of `parent`.
+    Cycle { parent: NodeId, child: NodeId },
+    /// `node` is in the repository but not reachable from the scene roots.
+    Unreachable { node: NodeId },
+}
+
+/// Depth-first iterator over the nodes of a [`Scene`], see [`Scene::walk`].
+pub struct SceneWalk<'a> {
+    nodes: &'a NodeRepository,
+    stack: Vec<(&'a NodeId, usize)>,
+    visited: HashSet<&'a NodeId>,
+    active_only: bool,
+}
+
+impl<'a> SceneWalk<'a> {
+    fn new(scene: &'a Scene, active_only: bool) -> Self {
+        Self {
+            nodes: &scene.nodes,
+            stack: scene.children.iter().rev().map(|id| (id, 0)).collect(),
+            visited: HashSet::new(),
+            active_only,
+        }
+    }
+}
+
+impl<'a> Iterator for SceneWalk<'a> {
+    type Item = (NodeId, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((id, depth)) = self.stack.pop() {
+            if !self.visited.insert(id) {
+                continue;
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            if self.active_only && !node.active() {
+                continue;
+            }
+            self.stack
+                .extend(node.children().iter().rev().map(|child| (child, depth + 1)));
+            return Some((id.clone(), depth));
+        }
+        None
+    }
 }
 
 // endregion
@@ -447,17 +1639,347 @@ pub enum Node {
     RegularStarPolygon(RegularStarPolygonNode),
     Line(LineNode),
     TextSpan(TextSpanNode),
+    RichText(RichTextNode),
     Path(PathNode),
     BooleanOperation(BooleanPathOperationNode),
     Image(ImageNode),
 }
 
+impl Node {
+    /// Returns the node's transform relative to its parent.
+    pub fn transform(&self) -> AffineTransform {
+        match self {
+            Node::Error(n) => n.transform,
+            Node::Group(n) => n.transform,
+            Node::Container(n) => n.transform,
+            Node::Rectangle(n) => n.transform,
+            Node::Ellipse(n) => n.transform,
+            Node::Polygon(n) => n.transform,
+            Node::RegularPolygon(n) => n.transform,
+            Node::RegularStarPolygon(n) => n.transform,
+            Node::Line(n) => n.transform,
+            Node::TextSpan(n) => n.transform,
+            Node::RichText(n) => n.transform,
+            Node::Path(n) => n.transform,
+            Node::BooleanOperation(n) => n.transform,
+            Node::Image(n) => n.transform,
+        }
+    }
+
+    /// Returns a mutable reference to the node's transform relative to its parent.
+    pub fn transform_mut(&mut self) -> &mut AffineTransform {
+        match self {
+            Node::Error(n) => &mut n.transform,
+            Node::Group(n) => &mut n.transform,
+            Node::Container(n) => &mut n.transform,
+            Node::Rectangle(n) => &mut n.transform,
+            Node::Ellipse(n) => &mut n.transform,
+            Node::Polygon(n) => &mut n.transform,
+            Node::RegularPolygon(n) => &mut n.transform,
+            Node::RegularStarPolygon(n) => &mut n.transform,
+            Node::Line(n) => &mut n.transform,
+            Node::TextSpan(n) => &mut n.transform,
+            Node::RichText(n) => &mut n.transform,
+            Node::Path(n) => &mut n.transform,
+            Node::BooleanOperation(n) => &mut n.transform,
+            Node::Image(n) => &mut n.transform,
+        }
+    }
+
+    /// Returns a mutable reference to the node's size, `None` for nodes whose size is
+    /// derived from their geometry or children, like paths and groups.
+    pub fn size_mut(&mut self) -> Option<&mut Size> {
+        match self {
+            Node::Error(n) => Some(&mut n.size),
+            Node::Container(n) => Some(&mut n.size),
+            Node::Rectangle(n) => Some(&mut n.size),
+            Node::Ellipse(n) => Some(&mut n.size),
+            Node::RegularPolygon(n) => Some(&mut n.size),
+            Node::RegularStarPolygon(n) => Some(&mut n.size),
+            Node::Line(n) => Some(&mut n.size),
+            Node::TextSpan(n) => Some(&mut n.size),
+            Node::RichText(n) => Some(&mut n.size),
+            Node::Image(n) => Some(&mut n.size),
+            Node::Group(_) | Node::Polygon(_) | Node::Path(_) | Node::BooleanOperation(_) => None,
+        }
+    }
+
+    /// Returns a copy of the node with its transform replaced by `transform`.
+    pub fn with_transform(&self, transform: AffineTransform) -> Node {
+        let mut node = self.clone();
+        *node.transform_mut() = transform;
+        node
+    }
+
+    /// Returns a copy of the node moved by `(dx, dy)` in its parent's space.
+    pub fn translated(&self, dx: f32, dy: f32) -> Node {
+        let mut transform = self.transform();
+        transform.translate(dx, dy);
+        self.with_transform(transform)
+    }
+
+    /// Returns a copy of the node rotated by `degrees` around `origin`, given in its
+    /// parent's space. Positive angles rotate clockwise on screen (y down).
+    pub fn rotated(&self, degrees: f32, origin: Point) -> Node {
+        self.transformed_around(AffineTransform::from_rotatation(degrees), origin)
+    }
+
+    /// Returns a copy of the node scaled by `(sx, sy)` around `origin`, given in its
+    /// parent's space.
+    pub fn scaled(&self, sx: f32, sy: f32, origin: Point) -> Node {
+        self.transformed_around(
+            AffineTransform::from_acebdf(sx, 0.0, 0.0, 0.0, sy, 0.0),
+            origin,
+        )
+    }
+
+    /// Applies `transform` after the node's own transform, with `origin` as the fixed point.
+    fn transformed_around(&self, transform: AffineTransform, origin: Point) -> Node {
+        let to_origin = AffineTransform::from_acebdf(1.0, 0.0, -origin.x, 0.0, 1.0, -origin.y);
+        let from_origin = AffineTransform::from_acebdf(1.0, 0.0, origin.x, 0.0, 1.0, origin.y);
+        let around = from_origin.compose(&transform).compose(&to_origin);
+        self.with_transform(around.compose(&self.transform()))
+    }
+
+    /// Returns the ids of the node's children, empty for leaf nodes.
+    pub fn children(&self) -> &[NodeId] {
+        match self {
+            Node::Group(n) => &n.children,
+            Node::Container(n) => &n.children,
+            Node::BooleanOperation(n) => &n.children,
+            _ => &[],
+        }
+    }
+
+    /// Mutable access to the ids of the node's children, `None` for leaf nodes.
+    fn children_mut(&mut self) -> Option<&mut Vec<NodeId>> {
+        match self {
+            Node::Group(n) => Some(&mut n.children),
+            Node::Container(n) => Some(&mut n.children),
+            Node::BooleanOperation(n) => Some(&mut n.children),
+            _ => None,
+        }
+    }
+
+    fn base_mut(&mut self) -> &mut BaseNode {
+        match self {
+            Node::Error(n) => &mut n.base,
+            Node::Group(n) => &mut n.base,
+            Node::Container(n) => &mut n.base,
+            Node::Rectangle(n) => &mut n.base,
+            Node::Ellipse(n) => &mut n.base,
+            Node::Polygon(n) => &mut n.base,
+            Node::RegularPolygon(n) => &mut n.base,
+            Node::RegularStarPolygon(n) => &mut n.base,
+            Node::Line(n) => &mut n.base,
+            Node::TextSpan(n) => &mut n.base,
+            Node::RichText(n) => &mut n.base,
+            Node::Path(n) => &mut n.base,
+            Node::BooleanOperation(n) => &mut n.base,
+            Node::Image(n) => &mut n.base,
+        }
+    }
+
+    /// Returns the bounds of everything the node paints, in its local space.
+    ///
+    /// Grows the geometry bounds the way the painter expands its draw regions: by the
+    /// stroke per its alignment (open paths are stroked centered), by three standard
+    /// deviations for layer blurs and by the offset, spread and blur of drop shadows.
+    /// Backdrop blurs and inner shadows stay inside the shape. The [`GeometryCache`] uses
+    /// the same expansion for its render bounds.
+    ///
+    /// Groups and boolean operations take their geometry from their children, which are
+    /// not reachable from the node alone, their ink bounds are empty.
+    pub fn ink_bounds(&self) -> Rectangle {
+        crate::cache::geometry::ink_bounds(self)
+    }
+
+    /// Returns whether the node is active (visible).
+    pub fn active(&self) -> bool {
+        match self {
+            Node::Error(n) => n.base.active,
+            Node::Group(n) => n.base.active,
+            Node::Container(n) => n.base.active,
+            Node::Rectangle(n) => n.base.active,
+            Node::Ellipse(n) => n.base.active,
+            Node::Polygon(n) => n.base.active,
+            Node::RegularPolygon(n) => n.base.active,
+            Node::RegularStarPolygon(n) => n.base.active,
+            Node::Line(n) => n.base.active,
+            Node::TextSpan(n) => n.base.active,
+            Node::RichText(n) => n.base.active,
+            Node::Path(n) => n.base.active,
+            Node::BooleanOperation(n) => n.base.active,
+            Node::Image(n) => n.base.active,
+        }
+    }
+
+    /// Returns whether the node is locked against interaction.
+    pub fn locked(&self) -> bool {
+        match self {
+            Node::Error(n) => n.base.locked,
+            Node::Group(n) => n.base.locked,
+            Node::Container(n) => n.base.locked,
+            Node::Rectangle(n) => n.base.locked,
+            Node::Ellipse(n) => n.base.locked,
+            Node::Polygon(n) => n.base.locked,
+            Node::RegularPolygon(n) => n.base.locked,
+            Node::RegularStarPolygon(n) => n.base.locked,
+            Node::Line(n) => n.base.locked,
+            Node::TextSpan(n) => n.base.locked,
+            Node::RichText(n) => n.base.locked,
+            Node::Path(n) => n.base.locked,
+            Node::BooleanOperation(n) => n.base.locked,
+            Node::Image(n) => n.base.locked,
+        }
+    }
+
+    /// Returns the blend mode of the node, [`BlendMode::Normal`] for error nodes.
+    pub fn blend_mode(&self) -> BlendMode {
+        match self {
+            Node::Error(_) => BlendMode::Normal,
+            Node::Group(n) => n.blend_mode,
+            Node::Container(n) => n.blend_mode,
+            Node::Rectangle(n) => n.blend_mode,
+            Node::Ellipse(n) => n.blend_mode,
+            Node::Polygon(n) => n.blend_mode,
+            Node::RegularPolygon(n) => n.blend_mode,
+            Node::RegularStarPolygon(n) => n.blend_mode,
+            Node::Line(n) => n.blend_mode,
+            Node::TextSpan(n) => n.blend_mode,
+            Node::RichText(n) => n.blend_mode,
+            Node::Path(n) => n.blend_mode,
+            Node::BooleanOperation(n) => n.blend_mode,
+            Node::Image(n) => n.blend_mode,
+        }
+    }
+
+    /// Returns the render order override of the node, if any.
+    pub fn render_order(&self) -> Option<i32> {
+        match self {
+            Node::Error(n) => n.base.render_order,
+            Node::Group(n) => n.base.render_order,
+            Node::Container(n) => n.base.render_order,
+            Node::Rectangle(n) => n.base.render_order,
+            Node::Ellipse(n) => n.base.render_order,
+            Node::Polygon(n) => n.base.render_order,
+            Node::RegularPolygon(n) => n.base.render_order,
+            Node::RegularStarPolygon(n) => n.base.render_order,
+            Node::Line(n) => n.base.render_order,
+            Node::TextSpan(n) => n.base.render_order,
+            Node::RichText(n) => n.base.render_order,
+            Node::Path(n) => n.base.render_order,
+            Node::BooleanOperation(n) => n.base.render_order,
+            Node::Image(n) => n.base.render_order,
+        }
+    }
+}
+
 // node trait
 pub trait NodeTrait {
     fn id(&self) -> NodeId;
     fn name(&self) -> String;
 }
 
+/// Produces the geometry of a shape node as a path in the node's local coordinate space.
+///
+/// The path describes the fill geometry, strokes are derived from it.
+pub trait ToPath {
+    fn to_path(&self) -> skia_safe::Path;
+}
+
+impl ToPath for RectangleNode {
+    fn to_path(&self) -> skia_safe::Path {
+        let rect = skia_safe::Rect::from_xywh(0.0, 0.0, self.size.width, self.size.height);
+        let r = self.corner_radius;
+        if !r.is_zero() && self.corner_smoothing > 0.0 {
+            return build_smooth_rrect_path(rect, &r, self.corner_smoothing);
+        }
+        let mut path = skia_safe::Path::new();
+        if r.is_zero() {
+            path.add_rect(rect, None);
+        } else {
+            let rrect = skia_safe::RRect::new_rect_radii(
+                rect,
+                &[
+                    skia_safe::Point::new(r.tl, r.tl),
+                    skia_safe::Point::new(r.tr, r.tr),
+                    skia_safe::Point::new(r.br, r.br),
+                    skia_safe::Point::new(r.bl, r.bl),
+                ],
+            );
+            path.add_rrect(rrect, None);
+        }
+        path
+    }
+}
+
+impl ToPath for EllipseNode {
+    fn to_path(&self) -> skia_safe::Path {
+        let rect = skia_safe::Rect::from_xywh(0.0, 0.0, self.size.width, self.size.height);
+        if self.is_full_ellipse() {
+            let mut path = skia_safe::Path::new();
+            path.add_oval(rect, None);
+            path
+        } else {
+            build_arc_path(self, rect)
+        }
+    }
+}
+
+impl ToPath for LineNode {
+    /// The line is an open path along the x axis. Its stroke is always center aligned,
+    /// so the path is the stroke's center line.
+    fn to_path(&self) -> skia_safe::Path {
+        let mut path = skia_safe::Path::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((self.size.width, 0.0));
+        path
+    }
+}
+
+impl ToPath for PolygonNode {
+    fn to_path(&self) -> skia_safe::Path {
+        let mut path = if self.corner_radius > 0.0 {
+            cvt::sk_polygon_path(&self.points, self.corner_radius)
+        } else {
+            let mut path = skia_safe::Path::new();
+            let mut iter = self.points.iter();
+            if let Some(&pt) = iter.next() {
+                path.move_to((pt.x, pt.y));
+                for &pt in iter {
+                    path.line_to((pt.x, pt.y));
+                }
+                path.close();
+            }
+            path
+        };
+        path.set_fill_type(self.fill_rule.into());
+        path
+    }
+}
+
+impl ToPath for RegularPolygonNode {
+    fn to_path(&self) -> skia_safe::Path {
+        self.to_polygon().to_path()
+    }
+}
+
+impl ToPath for RegularStarPolygonNode {
+    fn to_path(&self) -> skia_safe::Path {
+        self.to_polygon().to_path()
+    }
+}
+
+impl ToPath for PathNode {
+    /// Parses the SVG path data, returns an empty path if the data is invalid.
+    fn to_path(&self) -> skia_safe::Path {
+        let mut path = skia_safe::Path::from_svg(&self.data).unwrap_or_default();
+        path.set_fill_type(self.fill_rule.into());
+        path
+    }
+}
+
 impl NodeTrait for Node {
     fn id(&self) -> NodeId {
         match self {
@@ -471,6 +1993,7 @@ impl NodeTrait for Node {
             Node::RegularStarPolygon(n) => n.base.id.clone(),
             Node::Line(n) => n.base.id.clone(),
             Node::TextSpan(n) => n.base.id.clone(),
+            Node::RichText(n) => n.base.id.clone(),
             Node::Path(n) => n.base.id.clone(),
             Node::BooleanOperation(n) => n.base.id.clone(),
             Node::Image(n) => n.base.id.clone(),
@@ -489,6 +2012,7 @@ impl NodeTrait for Node {
             Node::RegularStarPolygon(n) => n.base.name.clone(),
             Node::Line(n) => n.base.name.clone(),
             Node::TextSpan(n) => n.base.name.clone(),
+            Node::RichText(n) => n.base.name.clone(),
             Node::Path(n) => n.base.name.clone(),
             Node::BooleanOperation(n) => n.base.name.clone(),
             Node::Image(n) => n.base.name.clone(),
@@ -508,6 +2032,7 @@ pub enum IntrinsicSizeNode {
     RegularStarPolygon(RegularStarPolygonNode),
     Line(LineNode),
     TextSpan(TextSpanNode),
+    RichText(RichTextNode),
     Path(PathNode),
     Image(ImageNode),
 }
@@ -522,6 +2047,7 @@ pub enum LeafNode {
     RegularStarPolygon(RegularStarPolygonNode),
     Line(LineNode),
     TextSpan(TextSpanNode),
+    RichText(RichTextNode),
     Path(PathNode),
     Image(ImageNode),
 }
@@ -531,6 +2057,11 @@ pub struct BaseNode {
     pub id: NodeId,
     pub name: String,
     pub active: bool,
+    /// Locked nodes are still drawn, but are ignored by hit testing.
+    pub locked: bool,
+    /// Draws the node above or below its siblings regardless of its position in the
+    /// parent's `children`, see [`NodeRepository::in_render_order`].
+    pub render_order: Option<i32>,
 }
 
 #[derive(Debug, Clone)]
@@ -568,15 +2099,21 @@ pub struct ContainerNode {
     pub transform: AffineTransform,
     pub size: Size,
     pub corner_radius: RectangularCornerRadius,
+    /// Corner smoothing (0..1) for continuous, squircle-like corners. `0.0` keeps circular corners.
+    pub corner_smoothing: f32,
     pub children: Vec<NodeId>,
     pub fill: Paint,
     pub stroke: Option<Paint>,
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
+    /// Space between the container edges and its content. Children are clipped to the
+    /// content box when `clip` is enabled.
+    pub padding: EdgeInsets,
     pub clip: bool,
 }
 
@@ -589,6 +2126,78 @@ impl ContainerNode {
             height: self.size.height,
         }
     }
+
+    /// The box children are laid out and clipped in, the container rect inset by `padding`.
+    pub fn content_rect(&self) -> Rectangle {
+        self.padding.deflate(&self.rect())
+    }
+
+    /// The container's own box as a rectangle node with the same id, paints and effects,
+    /// without its children.
+    pub fn to_rectangle(&self) -> RectangleNode {
+        RectangleNode {
+            base: self.base.clone(),
+            transform: self.transform,
+            size: self.size.clone(),
+            corner_radius: self.corner_radius,
+            corner_smoothing: self.corner_smoothing,
+            fill: self.fill.clone(),
+            stroke: self.stroke.clone().unwrap_or(TRANSPARENT_PAINT),
+            stroke_width: if self.stroke.is_some() {
+                self.stroke_width
+            } else {
+                0.0
+            },
+            stroke_align: self.stroke_align,
+            stroke_dash_array: self.stroke_dash_array.clone(),
+            stroke_dash_offset: self.stroke_dash_offset,
+            trim: None,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            effects: self.effects.clone(),
+        }
+    }
+
+    /// Returns true if the visual bounds of any active child exceed the container's content
+    /// box, see [`ContainerNode::content_rect`].
+    ///
+    /// Child bounds include effects and strokes (render bounds). Descendants of nested
+    /// containers with `clip` enabled are bounded by that container and do not count.
+    /// The result is independent of this container's own `clip` flag, so it can drive a
+    /// "content is clipped" indicator when `clip` is on.
+    pub fn content_overflows(&self, repo: &NodeRepository) -> bool {
+        const EPSILON: f32 = 0.001;
+        let bounds = self.content_rect();
+        self.children.iter().any(|child_id| {
+            let cache = GeometryCache::from_subtree(child_id, repo, &AffineTransform::identity());
+            visual_bounds(child_id, repo, &cache).is_some_and(|b| {
+                b.x < bounds.x - EPSILON
+                    || b.y < bounds.y - EPSILON
+                    || b.x + b.width > bounds.x + bounds.width + EPSILON
+                    || b.y + b.height > bounds.y + bounds.height + EPSILON
+            })
+        })
+    }
+}
+
+/// Union of the render bounds of an active node and its visible descendants.
+fn visual_bounds(id: &NodeId, repo: &NodeRepository, cache: &GeometryCache) -> Option<Rectangle> {
+    let node = repo.get(id)?;
+    if !node.active() {
+        return None;
+    }
+    let mut bounds = cache.get_render_bounds(id)?;
+    if let Node::Container(n) = node {
+        if n.clip {
+            return Some(bounds);
+        }
+    }
+    for child_id in node.children() {
+        if let Some(child) = visual_bounds(child_id, repo, cache) {
+            bounds = math2::rect::union(&[bounds, child]);
+        }
+    }
+    Some(bounds)
 }
 
 #[derive(Debug, Clone)]
@@ -597,14 +2206,19 @@ pub struct RectangleNode {
     pub transform: AffineTransform,
     pub size: Size,
     pub corner_radius: RectangularCornerRadius,
+    /// Corner smoothing (0..1) for continuous, squircle-like corners. `0.0` keeps circular corners.
+    pub corner_smoothing: f32,
     pub fill: Paint,
     pub stroke: Paint,
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
+    /// Portion of the stroke to draw, `None` draws the whole stroke.
+    pub trim: Option<StrokeTrim>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
 }
 
 impl RectangleNode {
@@ -626,7 +2240,11 @@ pub struct LineNode {
     pub stroke: Paint,
     pub stroke_width: f32,
     pub _data_stroke_align: StrokeAlign,
+    pub stroke_cap: StrokeCap,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
+    /// Portion of the stroke to draw, `None` draws the whole stroke.
+    pub trim: Option<StrokeTrim>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
 }
@@ -649,10 +2267,13 @@ pub struct ImageNode {
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
     pub _ref: String,
+    /// Whether the image is clipped to the node shape, images overflow their bounds when `false`.
+    pub clip: bool,
 }
 
 impl ImageNode {
@@ -680,12 +2301,29 @@ pub struct EllipseNode {
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
+    /// Portion of the stroke to draw, `None` draws the whole stroke.
+    pub trim: Option<StrokeTrim>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
+    /// Start of the arc sweep in radians. 0 is the positive x axis and angles
+    /// increase clockwise (same as Figma's `arcData.startingAngle`).
+    pub start_angle: f32,
+    /// End of the arc sweep in radians. A sweep of `2π` draws the full ellipse.
+    pub end_angle: f32,
+    /// Inner radius as a ratio of the outer radius, in `[0, 1)`.
+    /// `0` draws a pie (or a full ellipse), anything above draws a ring.
+    pub inner_radius_ratio: f32,
 }
 
 impl EllipseNode {
+    /// Returns true if the node describes a full, solid ellipse with no arc or inner radius.
+    pub fn is_full_ellipse(&self) -> bool {
+        (self.end_angle - self.start_angle).abs() >= std::f32::consts::TAU
+            && self.inner_radius_ratio <= 0.0
+    }
+
     pub fn rect(&self) -> Rectangle {
         Rectangle {
             x: 0.0,
@@ -707,9 +2345,64 @@ pub struct BooleanPathOperationNode {
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
+}
+
+impl BooleanPathOperationNode {
+    /// Resolves the children into a single path, in the local space of this node.
+    ///
+    /// Each child is placed with its own transform and the results are combined left to
+    /// right with `op`. A single child yields its own path, no children an empty path.
+    /// Nested operations are resolved recursively and groups contribute the union of their
+    /// descendants. Nodes that reference one of their ancestors are skipped. Text children
+    /// have no outline without fonts and are skipped, use
+    /// [`boolean_operation_path`](crate::painter::geometry::boolean_operation_path) to
+    /// include them.
+    pub fn resolve_path(&self, repo: &NodeRepository) -> skia_safe::Path {
+        boolean_operation_path_with_options(
+            self,
+            repo,
+            None,
+            BooleanOpOptions::default(),
+            &mut Vec::new(),
+        )
+        .unwrap_or_default()
+    }
+
+    /// The result of the operation as a path node with the same id, paints and effects,
+    /// see [`BooleanPathOperationNode::resolve_path`].
+    pub fn to_path_node(&self, repo: &NodeRepository) -> PathNode {
+        let path = self.resolve_path(repo);
+        PathNode {
+            base: self.base.clone(),
+            transform: self.transform,
+            fill: self.fill.clone(),
+            data: path.to_svg(),
+            fill_rule: match path.fill_type() {
+                skia_safe::PathFillType::EvenOdd | skia_safe::PathFillType::InverseEvenOdd => {
+                    FillRule::EvenOdd
+                }
+                _ => FillRule::NonZero,
+            },
+            stroke: self.stroke.clone().unwrap_or(TRANSPARENT_PAINT),
+            stroke_width: if self.stroke.is_some() {
+                self.stroke_width
+            } else {
+                0.0
+            },
+            stroke_align: self.stroke_align,
+            stroke_cap: StrokeCap::default(),
+            stroke_dash_array: self.stroke_dash_array.clone(),
+            stroke_dash_offset: self.stroke_dash_offset,
+            trim: None,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            effects: self.effects.clone(),
+        }
+    }
 }
 
 ///
@@ -721,13 +2414,18 @@ pub struct PathNode {
     pub transform: AffineTransform,
     pub fill: Paint,
     pub data: String,
+    pub fill_rule: FillRule,
     pub stroke: Paint,
     pub stroke_width: f32,
     pub stroke_align: StrokeAlign,
+    pub stroke_cap: StrokeCap,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
+    /// Portion of the stroke to draw, `None` draws the whole stroke.
+    pub trim: Option<StrokeTrim>,
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
 }
 
 /// A polygon shape defined by a list of absolute 2D points, following the SVG `<polygon>` model.
@@ -756,6 +2454,9 @@ pub struct PolygonNode {
     /// The paint used to fill the interior of the polygon.
     pub fill: Paint,
 
+    /// How the interior of a self-intersecting polygon is decided.
+    pub fill_rule: FillRule,
+
     /// The stroke paint used to outline the polygon.
     pub stroke: Paint,
 
@@ -766,14 +2467,78 @@ pub struct PolygonNode {
     /// Opacity applied to the polygon shape (`0.0` - transparent, `1.0` - opaque).
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
+}
+
+/// The direction in which polygon points are ordered, as seen on screen (y axis pointing down).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
 }
 
 impl PolygonNode {
-    pub fn to_path(&self) -> skia_safe::Path {
-        cvt::sk_polygon_path(&self.points, self.corner_radius)
+    /// Signed area of the polygon (shoelace formula) in local coordinates.
+    ///
+    /// Since the y axis points down, a positive area means the points run clockwise
+    /// on screen and a negative area means counter-clockwise.
+    pub fn signed_area(&self) -> f32 {
+        let n = self.points.len();
+        let mut sum = 0.0;
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            sum += a.x * b.y - b.x * a.y;
+        }
+        sum / 2.0
+    }
+
+    /// Returns the winding of the points, or `None` for degenerate (zero area) polygons.
+    pub fn winding(&self) -> Option<Winding> {
+        let area = self.signed_area();
+        if area > 0.0 {
+            Some(Winding::Clockwise)
+        } else if area < 0.0 {
+            Some(Winding::CounterClockwise)
+        } else {
+            None
+        }
+    }
+
+    /// Reorders the points to follow `winding`, keeping the first point in place.
+    pub fn ensure_winding(&mut self, winding: Winding) {
+        if self.winding().is_some_and(|w| w != winding) {
+            self.points[1..].reverse();
+        }
+    }
+}
+
+/// The fewest points a [`RegularPolygonNode`] or [`RegularStarPolygonNode`] is drawn with.
+pub const MIN_POLYGON_POINT_COUNT: usize = 3;
+
+/// A regular polygon or star `point_count` below [`MIN_POLYGON_POINT_COUNT`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPointCount(pub usize);
+
+/// Checks a regular polygon or star `point_count`, see [`MIN_POLYGON_POINT_COUNT`].
+pub fn validate_point_count(point_count: usize) -> Result<usize, InvalidPointCount> {
+    if point_count < MIN_POLYGON_POINT_COUNT {
+        return Err(InvalidPointCount(point_count));
     }
+    Ok(point_count)
+}
+
+/// Clamps a regular polygon or star `point_count` to at least [`MIN_POLYGON_POINT_COUNT`].
+///
+/// Fewer points can not enclose an area (and `0` would divide by zero), so smaller values
+/// are raised to the minimum instead of producing degenerate geometry. This is the
+/// backstop for counts assigned to the field directly, set them with
+/// [`RegularPolygonNode::with_point_count`] or
+/// [`RegularStarPolygonNode::with_point_count`] to have them rejected instead.
+pub fn clamp_point_count(point_count: usize) -> usize {
+    point_count.max(MIN_POLYGON_POINT_COUNT)
 }
 
 /// A node representing a regular polygon (triangle, square, pentagon, etc.)
@@ -799,9 +2564,16 @@ pub struct RegularPolygonNode {
     /// Bounding box size the polygon is fit into
     pub size: Size,
 
-    /// Number of equally spaced points (>= 3)
+    /// Number of equally spaced points (>= 3).
+    ///
+    /// Smaller values are drawn with [`MIN_POLYGON_POINT_COUNT`] points, see
+    /// [`clamp_point_count`]. [`Self::with_point_count`] rejects them.
     pub point_count: usize,
 
+    /// Rotation of the points around the center in radians, added to the default start
+    /// angle. `0.0` keeps the default orientation.
+    pub angle_offset: f32,
+
     /// The corner radius of the polygon.
     pub corner_radius: f32,
 
@@ -817,11 +2589,21 @@ pub struct RegularPolygonNode {
     /// Overall node opacity (0.0–1.0)
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
 }
 
 impl RegularPolygonNode {
+    /// Returns the node with `point_count` points, or an error below
+    /// [`MIN_POLYGON_POINT_COUNT`].
+    pub fn with_point_count(self, point_count: usize) -> Result<Self, InvalidPointCount> {
+        Ok(Self {
+            point_count: validate_point_count(point_count)?,
+            ..self
+        })
+    }
+
     pub fn rect(&self) -> Rectangle {
         Rectangle {
             x: 0.0,
@@ -831,29 +2613,32 @@ impl RegularPolygonNode {
         }
     }
 
+    /// Generates the polygon points, ordered [`Winding::CounterClockwise`].
     pub fn to_polygon(&self) -> PolygonNode {
         let w = self.size.width;
         let h = self.size.height;
         let cx = w / 2.0;
         let cy = h / 2.0;
         let r = w.min(h) / 2.0;
-        let angle_offset = if self.point_count % 2 == 0 {
-            std::f32::consts::PI / self.point_count as f32
+        let point_count = clamp_point_count(self.point_count);
+        let default_angle = if point_count % 2 == 0 {
+            std::f32::consts::PI / point_count as f32
         } else {
             -std::f32::consts::PI / 2.0
         };
+        let start_angle = default_angle + self.angle_offset;
 
-        let points: Vec<Point> = (0..self.point_count)
+        let points: Vec<Point> = (0..point_count)
             .map(|i| {
-                let theta = (i as f32 / self.point_count as f32) * 2.0 * std::f32::consts::PI
-                    + angle_offset;
+                let theta =
+                    (i as f32 / point_count as f32) * 2.0 * std::f32::consts::PI + start_angle;
                 let x = cx + r * theta.cos();
                 let y = cy + r * theta.sin();
                 Point { x, y }
             })
             .collect();
 
-        PolygonNode {
+        let mut polygon = PolygonNode {
             base: self.base.clone(),
             transform: self.transform,
             points,
@@ -864,9 +2649,13 @@ impl RegularPolygonNode {
             stroke_align: self.stroke_align,
             opacity: self.opacity,
             blend_mode: self.blend_mode,
-            effect: self.effect.clone(),
+            effects: self.effects.clone(),
             stroke_dash_array: self.stroke_dash_array.clone(),
-        }
+            stroke_dash_offset: self.stroke_dash_offset,
+            fill_rule: FillRule::NonZero,
+        };
+        polygon.ensure_winding(Winding::CounterClockwise);
+        polygon
     }
 }
 
@@ -888,9 +2677,16 @@ pub struct RegularStarPolygonNode {
     /// Bounding box size the polygon is fit into
     pub size: Size,
 
-    /// Number of equally spaced points (>= 3)
+    /// Number of equally spaced points (>= 3).
+    ///
+    /// Smaller values are drawn with [`MIN_POLYGON_POINT_COUNT`] points, see
+    /// [`clamp_point_count`]. [`Self::with_point_count`] rejects them.
     pub point_count: usize,
 
+    /// Rotation of the points around the center in radians, added to the default start
+    /// angle. `0.0` keeps the default orientation.
+    pub angle_offset: f32,
+
     /// The `inner_radius` defines the radius of the inner vertices of the star, relative to the center.
     ///
     /// It controls the sharpness of the star's angles:
@@ -899,6 +2695,8 @@ pub struct RegularStarPolygonNode {
     ///
     /// The outer radius is defined by the bounding box (`size`), while the `inner_radius` places the inner points on a second concentric circle.
     /// Unlike `corner_radius`, which affects the rounding of outer corners, `inner_radius` controls the depth of the inner angles between the points.
+    ///
+    /// Valid values are in `(0, 1]`, see [`RegularStarPolygonNode::clamp_inner_radius`].
     pub inner_radius: f32,
 
     /// The corner radius of the polygon.
@@ -916,11 +2714,42 @@ pub struct RegularStarPolygonNode {
     /// Overall node opacity (0.0–1.0)
     pub opacity: f32,
     pub blend_mode: BlendMode,
-    pub effect: Option<FilterEffect>,
+    pub effects: Vec<FilterEffect>,
     pub stroke_dash_array: Option<Vec<f32>>,
+    pub stroke_dash_offset: Option<f32>,
 }
 
 impl RegularStarPolygonNode {
+    /// Returns the node with `point_count` points, or an error below
+    /// [`MIN_POLYGON_POINT_COUNT`].
+    pub fn with_point_count(self, point_count: usize) -> Result<Self, InvalidPointCount> {
+        Ok(Self {
+            point_count: validate_point_count(point_count)?,
+            ..self
+        })
+    }
+
+    /// The smallest inner radius a star is drawn with. Anything smaller would collapse the
+    /// inner vertices into the center.
+    pub const MIN_INNER_RADIUS: f32 = 0.01;
+
+    /// Clamps an inner radius into `[MIN_INNER_RADIUS, 1]`.
+    ///
+    /// Values above `1` would place the inner vertices outside the points and turn the star
+    /// inside out, they are drawn as a regular polygon with `2 * point_count` edges instead.
+    /// `NaN` is treated as the minimum.
+    pub fn clamp_inner_radius(inner_radius: f32) -> f32 {
+        if inner_radius.is_nan() {
+            return Self::MIN_INNER_RADIUS;
+        }
+        inner_radius.clamp(Self::MIN_INNER_RADIUS, 1.0)
+    }
+
+    /// Sets the inner radius, clamped with [`Self::clamp_inner_radius`].
+    pub fn set_inner_radius(&mut self, inner_radius: f32) {
+        self.inner_radius = Self::clamp_inner_radius(inner_radius);
+    }
+
     pub fn rect(&self) -> Rectangle {
         Rectangle {
             x: 0.0,
@@ -930,18 +2759,21 @@ impl RegularStarPolygonNode {
         }
     }
 
+    /// Generates the polygon points, ordered [`Winding::CounterClockwise`].
     pub fn to_polygon(&self) -> PolygonNode {
         let w = self.size.width;
         let h = self.size.height;
         let cx = w / 2.0;
         let cy = h / 2.0;
         let outer_r = cx.min(cy);
-        let inner_r = outer_r * self.inner_radius;
-        let step = std::f32::consts::PI / self.point_count as f32;
-        let start_angle = -std::f32::consts::PI / 2.0;
-
-        let mut points = Vec::with_capacity(self.point_count * 2);
-        for i in 0..(self.point_count * 2) {
+        // the field is public, so it is clamped here as well
+        let inner_r = outer_r * Self::clamp_inner_radius(self.inner_radius);
+        let point_count = clamp_point_count(self.point_count);
+        let step = std::f32::consts::PI / point_count as f32;
+        let start_angle = -std::f32::consts::PI / 2.0 + self.angle_offset;
+
+        let mut points = Vec::with_capacity(point_count * 2);
+        for i in 0..(point_count * 2) {
             let angle = start_angle + i as f32 * step;
             let r = if i % 2 == 0 { outer_r } else { inner_r };
             let x = cx + r * angle.cos();
@@ -949,7 +2781,7 @@ impl RegularStarPolygonNode {
             points.push(Point { x, y });
         }
 
-        PolygonNode {
+        let mut polygon = PolygonNode {
             base: self.base.clone(),
             transform: self.transform,
             points,
@@ -960,14 +2792,18 @@ impl RegularStarPolygonNode {
             stroke_align: self.stroke_align,
             opacity: self.opacity,
             blend_mode: self.blend_mode,
-            effect: self.effect.clone(),
+            effects: self.effects.clone(),
             stroke_dash_array: self.stroke_dash_array.clone(),
-        }
+            stroke_dash_offset: self.stroke_dash_offset,
+            fill_rule: FillRule::NonZero,
+        };
+        polygon.ensure_winding(Winding::CounterClockwise);
+        polygon
     }
 }
 
 /// A node representing a plain text block (non-rich).
-/// For multi-style content, see `RichTextNode` (not implemented yet).
+/// For multi-style content, see [`RichTextNode`].
 #[derive(Debug, Clone)]
 pub struct TextSpanNode {
     /// Metadata and identity.
@@ -979,6 +2815,9 @@ pub struct TextSpanNode {
     /// Layout bounds (used for wrapping and alignment).
     pub size: Size,
 
+    /// Whether `size` is fixed or follows the content, see [`TextSpanNode::measure`].
+    pub sizing: TextSizingMode,
+
     /// Text content (plain UTF-8).
     pub text: String,
 
@@ -991,6 +2830,13 @@ pub struct TextSpanNode {
     /// Vertical alignment.
     pub text_align_vertical: TextAlignVertical,
 
+    /// Maximum number of laid out lines, `None` for no limit.
+    pub max_lines: Option<usize>,
+
+    /// How the text is cut off at `max_lines`. With [`TextOverflow::Ellipsis`] and no
+    /// `max_lines`, the text is cut to the lines fitting in the box height.
+    pub overflow: TextOverflow,
+
     /// Fill paint (solid or gradient)
     pub fill: Paint,
 
@@ -1005,6 +2851,119 @@ pub struct TextSpanNode {
     pub blend_mode: BlendMode,
 }
 
+/// How the size of a [`TextSpanNode`] is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextSizingMode {
+    /// The node keeps its `size`, the text wraps at its width.
+    #[default]
+    Fixed,
+    /// The node hugs its content in both directions, the text never wraps.
+    Auto,
+    /// The width is fixed and the text wraps at it, the height hugs the content.
+    AutoHeight,
+}
+
+/// A run of text with its own style, used by [`RichTextNode`].
+#[derive(Debug, Clone)]
+pub struct TextSpan {
+    /// Text content (plain UTF-8).
+    pub text: String,
+
+    /// Font appearance of this span.
+    pub text_style: TextStyle,
+
+    /// Fill paint of this span. Falls back to the node's `fill` when `None`.
+    pub fill: Option<Paint>,
+}
+
+/// A node representing a multi-style text block.
+///
+/// All spans are laid out together as a single paragraph, each with its own style.
+#[derive(Debug, Clone)]
+pub struct RichTextNode {
+    /// Metadata and identity.
+    pub base: BaseNode,
+
+    /// Transform applied to the text container.
+    pub transform: AffineTransform,
+
+    /// Layout bounds (used for wrapping and alignment).
+    pub size: Size,
+
+    /// Styled text runs, in reading order.
+    pub spans: Vec<TextSpan>,
+
+    /// Horizontal alignment.
+    pub text_align: TextAlign,
+
+    /// Vertical alignment.
+    pub text_align_vertical: TextAlignVertical,
+
+    /// Default fill paint for spans without their own fill.
+    pub fill: Paint,
+
+    /// Overall node opacity.
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+}
+
+impl TextSpanNode {
+    /// Returns `true` if the text is empty or whitespace only, so the node draws nothing.
+    pub fn is_effectively_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Returns the size of the node according to its [`TextSizingMode`].
+    ///
+    /// Fixed nodes return `size` as is, auto sized nodes lay out the paragraph with
+    /// `fonts` and return the size of the content, up to `max_lines` lines.
+    pub fn measure(&self, fonts: &FontRepository) -> Size {
+        match self.sizing {
+            TextSizingMode::Fixed => self.size.clone(),
+            TextSizingMode::Auto => {
+                let metrics = measure_text_lines(
+                    &self.text,
+                    &self.text_style,
+                    f32::INFINITY,
+                    self.max_lines,
+                    fonts,
+                );
+                Size {
+                    width: metrics.max_intrinsic_width,
+                    height: metrics.height,
+                }
+            }
+            TextSizingMode::AutoHeight => {
+                let metrics = measure_text_lines(
+                    &self.text,
+                    &self.text_style,
+                    self.size.width,
+                    self.max_lines,
+                    fonts,
+                );
+                Size {
+                    width: self.size.width,
+                    height: metrics.height,
+                }
+            }
+        }
+    }
+}
+
+impl RichTextNode {
+    /// Returns the concatenated text of all spans.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// The base direction of the paragraph, taken from the first span.
+    pub fn direction(&self) -> TextDirection {
+        self.spans
+            .first()
+            .map_or(TextDirection::Ltr, |span| span.text_style.direction)
+    }
+}
+
 #[derive(Debug, Clone)]
 #[deprecated(note = "Not implemented yet")]
 pub struct TextNode {
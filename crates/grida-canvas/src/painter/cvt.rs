@@ -1,50 +1,95 @@
 use crate::node::schema::*;
 use skia_safe;
+use skia_safe::gradient_shader::{Flags as GradientFlags, Interpolation};
 
+/// Builds the gradient colors as straight (non premultiplied) [`skia_safe::Color4f`] with
+/// `opacity` applied to the alpha.
 fn cg_build_gradient_stops(
     stops: &[GradientStop],
     opacity: f32,
-) -> (Vec<skia_safe::Color>, Vec<f32>) {
+) -> (Vec<skia_safe::Color4f>, Vec<f32>) {
     let mut colors = Vec::with_capacity(stops.len());
     let mut positions = Vec::with_capacity(stops.len());
 
     for stop in stops {
-        let Color(r, g, b, a) = stop.color;
-        let alpha = (a as f32 * opacity).round().clamp(0.0, 255.0) as u8;
-        colors.push(skia_safe::Color::from_argb(alpha, r, g, b));
+        let mut color: skia_safe::Color4f = stop.color.into();
+        color.a = (color.a * opacity).clamp(0.0, 1.0);
+        colors.push(color);
         positions.push(stop.offset);
     }
 
     (colors, positions)
 }
 
+/// Gradients interpolate in premultiplied space, so a transparent stop fades the
+/// neighbouring color out instead of blending towards its own (usually black) rgb.
+fn gradient_interpolation() -> Interpolation {
+    GradientFlags::INTERPOLATE_COLORS_IN_PREMUL.into()
+}
+
+/// Like [`sk_paint`], for a paint filling `rect` instead of a box at the origin.
+///
+/// Gradients and images are sized to `rect` and moved to its top-left corner.
+pub fn sk_paint_in_rect(paint: &Paint, opacity: f32, rect: skia_safe::Rect) -> skia_safe::Paint {
+    let mut skia_paint = sk_paint(paint, opacity, (rect.width(), rect.height()));
+    if let Some(shader) = skia_paint.shader() {
+        skia_paint.set_shader(
+            shader.with_local_matrix(&skia_safe::Matrix::translate((rect.left, rect.top))),
+        );
+    }
+    skia_paint
+}
+
+/// Builds the tiling shader of a repeating [`ImagePaint`], `None` if the paint does not
+/// repeat.
+///
+/// The first tile is the image at its natural size placed by the paint transform, the
+/// axes that do not repeat are left transparent past the image.
+pub fn sk_image_repeat_shader(
+    image: &skia_safe::Image,
+    paint: &ImagePaint,
+    sampling: skia_safe::SamplingOptions,
+) -> Option<skia_safe::Shader> {
+    use skia_safe::TileMode::{Decal, Repeat};
+    let tile_modes = match paint.repeat {
+        ImageRepeat::NoRepeat => return None,
+        ImageRepeat::Repeat => (Repeat, Repeat),
+        ImageRepeat::RepeatX => (Repeat, Decal),
+        ImageRepeat::RepeatY => (Decal, Repeat),
+    };
+    image.to_shader(tile_modes, sampling, &sk_matrix(paint.transform.matrix))
+}
+
 pub fn sk_matrix(m: [[f32; 3]; 2]) -> skia_safe::Matrix {
     let [[a, c, tx], [b, d, ty]] = m;
     skia_safe::Matrix::from_affine(&[a, b, c, d, tx, ty])
 }
 
+/// Converts `paint` into a skia paint for a box of `size` at the origin.
+///
+/// The alpha is `opacity * paint.opacity * color.a`. Painters draw nodes inside an
+/// opacity layer and pass `1.0` here, so the node opacity is never applied twice.
 pub fn sk_paint(paint: &Paint, opacity: f32, size: (f32, f32)) -> skia_safe::Paint {
     let mut skia_paint = skia_safe::Paint::default();
     skia_paint.set_anti_alias(true);
     let (width, height) = size;
     match paint {
         Paint::Solid(solid) => {
-            let Color(r, g, b, a) = solid.color;
-            let final_alpha = (a as f32 * opacity * solid.opacity) as u8;
-            skia_paint.set_color(skia_safe::Color::from_argb(final_alpha, r, g, b));
+            let final_alpha = (solid.color.3 as f32 * opacity * solid.opacity) as u8;
+            skia_paint.set_color(solid.color.with_alpha(final_alpha));
         }
         Paint::LinearGradient(gradient) => {
             let (colors, positions) =
                 cg_build_gradient_stops(&gradient.stops, opacity * gradient.opacity);
-            if let Some(shader) = skia_safe::Shader::linear_gradient(
+            if let Some(shader) = skia_safe::Shader::linear_gradient_with_interpolation(
                 (
                     skia_safe::Point::new(0.0, 0.0),
                     skia_safe::Point::new(width, 0.0),
                 ),
-                &colors[..],
+                (&colors[..], None::<skia_safe::ColorSpace>),
                 Some(&positions[..]),
-                skia_safe::TileMode::Clamp,
-                None,
+                gradient.spread.into(),
+                gradient_interpolation(),
                 Some(&sk_matrix(gradient.transform.matrix)),
             ) {
                 skia_paint.set_shader(shader);
@@ -55,13 +100,12 @@ pub fn sk_paint(paint: &Paint, opacity: f32, size: (f32, f32)) -> skia_safe::Pai
                 cg_build_gradient_stops(&gradient.stops, opacity * gradient.opacity);
             let center = skia_safe::Point::new(width / 2.0, height / 2.0);
             let radius = width.min(height) / 2.0;
-            if let Some(shader) = skia_safe::Shader::radial_gradient(
-                center,
-                radius,
-                &colors[..],
+            if let Some(shader) = skia_safe::Shader::radial_gradient_with_interpolation(
+                (center, radius),
+                (&colors[..], None::<skia_safe::ColorSpace>),
                 Some(&positions[..]),
-                skia_safe::TileMode::Clamp,
-                None,
+                gradient.spread.into(),
+                gradient_interpolation(),
                 Some(&sk_matrix(gradient.transform.matrix)),
             ) {
                 skia_paint.set_shader(shader);
@@ -99,69 +143,77 @@ pub fn sk_paint(paint: &Paint, opacity: f32, size: (f32, f32)) -> skia_safe::Pai
 //     paint
 // }
 
-// Given:
-//   - `pts`: Vec<Point> with your polygon's vertices in order
-//   - `r`: the corner‐radius
-//
-// Build a Path that walks each edge but rounds each "sharp" corner:
+/// Builds a closed path through the polygon `pts`, rounding each corner with a circular
+/// arc of radius `r`.
+///
+/// The arc of a corner with angle `θ` touches the adjacent edges `r / tan(θ / 2)` away
+/// from the vertex. That distance is clamped to half of the shorter adjacent edge, which
+/// shrinks the radius of the corner, so the arcs of neighbouring corners never overlap.
+/// The arc always lies within the angle of the corner, concave (reflex) corners such as
+/// the inner points of a star are rounded on the outside of the polygon.
 pub fn sk_polygon_path(pts: &[Point], r: f32) -> skia_safe::Path {
-    let n = pts.len();
-    assert!(n >= 3);
+    // repeated points have no direction to round
+    let mut points: Vec<Point> = Vec::with_capacity(pts.len());
+    for &pt in pts {
+        if !points
+            .last()
+            .is_some_and(|last| last.x == pt.x && last.y == pt.y)
+        {
+            points.push(pt);
+        }
+    }
+    while points.len() > 1
+        && points[0].x == points[points.len() - 1].x
+        && points[0].y == points[points.len() - 1].y
+    {
+        points.pop();
+    }
 
     let mut path = skia_safe::Path::new();
+    let n = points.len();
+    if n < 3 {
+        let pts: Vec<skia_safe::Point> = points.iter().map(|p| (p.x, p.y).into()).collect();
+        path.add_poly(&pts, true);
+        return path;
+    }
 
-    // Start at the first vertex, but moveTo a point
-    // that's `r` away from the first corner along the first edge.
-    // (We'll compute those "offset" points below.)
-
-    // Compute the "offset" point on the last edge that leads into pts[0]:
-    let last = pts[n - 1];
-    let first = pts[0];
-
-    // 1) Find direction from last→first, then move `r` along that:
-    let dir_a = Point {
-        x: (first.x - last.x) / ((first.x - last.x).powi(2) + (first.y - last.y).powi(2)).sqrt(),
-        y: (first.y - last.y) / ((first.x - last.x).powi(2) + (first.y - last.y).powi(2)).sqrt(),
-    };
-    let move_into_first = first.subtract_scaled(dir_a, r);
-
-    path.move_to(skia_safe::Point::new(move_into_first.x, move_into_first.y));
+    // start halfway along the closing edge, no corner reaches past it
+    let last = points[n - 1];
+    let first = points[0];
+    path.move_to(((last.x + first.x) / 2.0, (last.y + first.y) / 2.0));
 
     for i in 0..n {
-        // Current "corner" is pts[i],
-        // "incoming" edge is (pts[i−1] → pts[i]),
-        // "outgoing" edge is (pts[i] → pts[i+1]).
-        let curr = pts[i];
-        let prev = pts[(i + n - 1) % n];
-        let next = pts[(i + 1) % n];
-
-        // Compute offset along incoming edge (to where arc starts):
-        let dir_in = Point {
-            x: (curr.x - prev.x) / ((curr.x - prev.x).powi(2) + (curr.y - prev.y).powi(2)).sqrt(),
-            y: (curr.y - prev.y) / ((curr.x - prev.x).powi(2) + (curr.y - prev.y).powi(2)).sqrt(),
-        };
-        let start_arc = curr.subtract_scaled(dir_in, r);
-
-        // Compute offset along outgoing edge (to where arc ends):
-        let dir_out = Point {
-            x: (next.x - curr.x) / ((next.x - curr.x).powi(2) + (next.y - curr.y).powi(2)).sqrt(),
-            y: (next.y - curr.y) / ((next.x - curr.x).powi(2) + (next.y - curr.y).powi(2)).sqrt(),
-        };
-        let end_arc = Point {
-            x: curr.x + dir_out.x * r,
-            y: curr.y + dir_out.y * r,
-        };
-
-        // Line from previous offset → start_arc
-        path.line_to(skia_safe::Point::new(start_arc.x, start_arc.y));
-
-        // Add the rounded corner (arc) from start_arc → end_arc, tangent at curr:
-        path.quad_to(
-            skia_safe::Point::new(curr.x, curr.y),
-            skia_safe::Point::new(end_arc.x, end_arc.y),
+        let prev = points[(i + n - 1) % n];
+        let curr = points[i];
+        let next = points[(i + 1) % n];
+        path.arc_to_tangent(
+            (curr.x, curr.y),
+            (next.x, next.y),
+            corner_radius(prev, curr, next, r),
         );
     }
 
     path.close();
     path
 }
+
+/// The radius of the arc rounding the corner at `curr`, `r` clamped so the arc touches
+/// the adjacent edges no further than halfway along them.
+fn corner_radius(prev: Point, curr: Point, next: Point, r: f32) -> f32 {
+    let (ax, ay) = (prev.x - curr.x, prev.y - curr.y);
+    let (bx, by) = (next.x - curr.x, next.y - curr.y);
+    let (len_a, len_b) = ((ax * ax + ay * ay).sqrt(), (bx * bx + by * by).sqrt());
+
+    // angle between the two edges at the corner, in (0, π]
+    let cos = ((ax * bx + ay * by) / (len_a * len_b)).clamp(-1.0, 1.0);
+    let tan_half = (cos.acos() / 2.0).tan();
+
+    let tangent = (r / tan_half).min(len_a.min(len_b) / 2.0);
+    let radius = tangent * tan_half;
+    // a straight corner (θ = π) needs no rounding
+    if radius.is_finite() {
+        radius.min(r)
+    } else {
+        0.0
+    }
+}
@@ -1,28 +1,84 @@
-use crate::cache::geometry::GeometryCache;
 use crate::node::repository::NodeRepository;
 use crate::node::schema::*;
 use crate::painter::cvt;
-use math2::transform::AffineTransform;
+use crate::runtime::repository::FontRepository;
+use crate::text::outline::text_span_outline;
 use skia_safe::{
-    path_effect::PathEffect, stroke_rec::InitStyle, Path, PathOp, Point, RRect, Rect, StrokeRec,
+    path::Verb, path_effect::PathEffect, stroke_rec::InitStyle, ContourMeasureIter, Path, PathOp,
+    Point, RRect, Rect, StrokeRec,
 };
+use std::collections::HashSet;
+
+/// Normalizes a dash pattern into intervals accepted by skia's dash path effect.
+///
+/// Per the SVG spec, an odd-length dash array is repeated once to yield an even
+/// number of values, e.g. `[5, 3, 2]` becomes `[5, 3, 2, 5, 3, 2]`.
+///
+/// See [SVG `stroke-dasharray`](https://www.w3.org/TR/SVG2/painting.html#StrokeDashing)
+pub fn dash_intervals(dashes: &[f32]) -> Vec<f32> {
+    if dashes.len() % 2 == 1 {
+        dashes.iter().chain(dashes.iter()).copied().collect()
+    } else {
+        dashes.to_vec()
+    }
+}
+
+/// The stroke geometry of a [`TextSpanNode`] in its local space, following the glyph
+/// outlines (see [`text_span_outline`]). `None` without a stroke paint or width.
+pub fn text_span_stroke_path(node: &TextSpanNode, fonts: &FontRepository) -> Option<Path> {
+    let stroke_width = node.stroke_width.unwrap_or(0.0);
+    if node.stroke.is_none() || stroke_width <= 0.0 {
+        return None;
+    }
+    Some(stroke_geometry(
+        &text_span_outline(node, fonts),
+        &StrokeOptions::new(stroke_width, node.stroke_align),
+    ))
+}
+
+/// How [`stroke_geometry`] outlines a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeOptions<'a> {
+    /// The stroke width, in logical pixels.
+    pub width: f32,
+    /// Where the stroke lies relative to the path.
+    ///   - `StrokeAlign::Center`: Stroke is centered on the path (default Skia behavior).
+    ///   - `StrokeAlign::Inside`: Stroke lies entirely inside the path boundary.
+    ///   - `StrokeAlign::Outside`: Stroke lies entirely outside the path boundary.
+    pub align: StrokeAlign,
+    /// Drawn at the ends of open subpaths and dashes.
+    pub cap: StrokeCap,
+    /// Optional dash pattern (e.g., `[10.0, 4.0]` for 10 on, 4 off). Odd-length patterns
+    /// are repeated once, see [`dash_intervals`].
+    pub dash_array: Option<&'a [f32]>,
+    /// Optional dash phase, i.e. how far into the pattern the dash starts.
+    pub dash_offset: Option<f32>,
+    /// Only this portion of the path is stroked, see [`trim_path`]. The trim is applied
+    /// before the dash pattern, alignment still follows the whole path.
+    pub trim: Option<StrokeTrim>,
+}
+
+impl StrokeOptions<'_> {
+    /// A solid, untrimmed stroke with butt caps.
+    pub fn new(width: f32, align: StrokeAlign) -> Self {
+        Self {
+            width,
+            align,
+            cap: StrokeCap::Butt,
+            dash_array: None,
+            dash_offset: None,
+            trim: None,
+        }
+    }
+}
 
 /// Computes the stroke geometry path for a given input `Path`, enabling rich stroke
 /// rendering features such as image fills, gradients, and complex stroke alignment.
 ///
 /// This function generates a *filled path* that visually represents the stroke outline,
-/// based on stroke width, alignment, and optional dash pattern. The result can be used
-/// with any fill-based rendering pipeline, e.g. image shaders, gradients, or masking.
-///
-/// # Parameters
-///
-/// - `source_path`: The original vector path to be stroked.
-/// - `stroke_width`: The stroke width (measured in logical pixels).
-/// - `stroke_align`: Controls how the stroke is aligned relative to the path.
-///   - `StrokeAlign::Center`: Stroke is centered on the path (default Skia behavior).
-///   - `StrokeAlign::Inside`: Stroke lies entirely inside the path boundary.
-///   - `StrokeAlign::Outside`: Stroke lies entirely outside the path boundary.
-/// - `stroke_dash_array`: Optional dash pattern (e.g., `[10.0, 4.0]` for 10 on, 4 off).
+/// based on the stroke `options`: width, alignment, cap, trim and optional dash pattern.
+/// The result can be used with any fill-based rendering pipeline, e.g. image shaders,
+/// gradients, or masking.
 ///
 /// # Returns
 ///
@@ -31,8 +87,10 @@ use skia_safe::{
 ///
 /// # Behavior
 ///
-/// - If `stroke_align` is not `Center`, the result uses boolean path operations to clip or subtract
-///   the stroke geometry relative to the original path.
+/// - If the alignment is not `Center`, the stroke is drawn at twice the width and the result
+///   uses boolean path operations to clip or subtract it relative to the original path, so an
+///   inside stroke never leaves the shape, rounded corners included.
+/// - Open paths have no inside, `Inside` and `Outside` fall back to `Center` for them.
 /// - If a dash array is provided, it is applied before stroking.
 /// - If the path is empty or invalid, an empty `Path` is returned.
 ///
@@ -41,9 +99,10 @@ use skia_safe::{
 /// ```rust,ignore
 /// let stroke_path = stroke_geometry(
 ///     &original_path,
-///     4.0,
-///     StrokeAlign::Inside,
-///     Some(&vec![8.0, 4.0])
+///     &StrokeOptions {
+///         dash_array: Some(&[8.0, 4.0]),
+///         ..StrokeOptions::new(4.0, StrokeAlign::Inside)
+///     },
 /// );
 /// canvas.draw_path(&stroke_path, &image_paint);
 /// ```
@@ -53,30 +112,37 @@ use skia_safe::{
 /// - [`SkStrokeRec`](https://github.com/google/skia/blob/main/include/core/SkStrokeRec.h)
 /// - [`SkPath::op`](https://github.com/google/skia/blob/main/include/core/SkPath.h)
 /// - [`SkDashPathEffect`](https://github.com/google/skia/blob/main/include/effects/SkDashPathEffect.h)
-pub fn stroke_geometry(
-    source_path: &Path,
-    stroke_width: f32,
-    stroke_align: StrokeAlign,
-    stroke_dash_array: Option<&Vec<f32>>,
-) -> Path {
+pub fn stroke_geometry(source_path: &Path, options: &StrokeOptions) -> Path {
     use StrokeAlign::*;
 
+    let stroke_align = if !has_open_contour(source_path) {
+        options.align
+    } else {
+        Center
+    };
+
     let adjusted_width = match stroke_align {
-        Center => stroke_width,
-        Inside => stroke_width * 2.0,  // we'll clip it later
-        Outside => stroke_width * 2.0, // we'll subtract later
+        Center => options.width,
+        Inside => options.width * 2.0,  // we'll clip it later
+        Outside => options.width * 2.0, // we'll subtract later
     };
 
     // Create a stroke record with the adjusted width
     let mut stroke_rec = StrokeRec::new(InitStyle::Hairline);
     stroke_rec.set_stroke_style(adjusted_width, false);
+    stroke_rec.set_stroke_params(options.cap.into(), skia_safe::PaintJoin::Miter, 4.0);
+
+    let mut path_to_stroke = match options.trim {
+        Some(trim) => trim_path(source_path, trim),
+        None => source_path.clone(),
+    };
 
     // Apply dash effect if provided
-    let mut path_to_stroke = source_path.clone();
-    if let Some(dashes) = stroke_dash_array {
-        if let Some(pe) = PathEffect::dash(dashes, 0.0) {
+    if let Some(dashes) = options.dash_array {
+        let intervals = dash_intervals(dashes);
+        if let Some(pe) = PathEffect::dash(&intervals, options.dash_offset.unwrap_or(0.0)) {
             if let Some((dashed, _)) =
-                pe.filter_path(source_path, &stroke_rec, source_path.bounds())
+                pe.filter_path(&path_to_stroke, &stroke_rec, source_path.bounds())
             {
                 path_to_stroke = dashed;
             }
@@ -183,12 +249,165 @@ impl PainterShape {
     }
 }
 
+/// Builds the path of an ellipse arc, pie or donut within `rect`.
+///
+/// Angles follow [`EllipseNode::start_angle`] (radians, clockwise from the x axis).
+/// The inner ellipse is the outer one scaled by [`EllipseNode::inner_radius_ratio`]
+/// around the center.
+pub fn build_arc_path(node: &EllipseNode, rect: Rect) -> Path {
+    let tau = std::f32::consts::TAU;
+    let sweep = (node.end_angle - node.start_angle).clamp(-tau, tau);
+    let ratio = node.inner_radius_ratio.clamp(0.0, 1.0);
+    let start_deg = node.start_angle.to_degrees();
+    let sweep_deg = sweep.to_degrees();
+
+    let center = rect.center();
+    let inner = Rect::from_xywh(
+        center.x - rect.width() * ratio / 2.0,
+        center.y - rect.height() * ratio / 2.0,
+        rect.width() * ratio,
+        rect.height() * ratio,
+    );
+
+    let mut path = Path::new();
+    if sweep.abs() >= tau {
+        // full ring
+        path.add_oval(rect, None);
+        path.add_oval(inner, None);
+        path.set_fill_type(skia_safe::PathFillType::EvenOdd);
+    } else if ratio <= 0.0 {
+        // pie
+        path.move_to(center);
+        path.arc_to(rect, start_deg, sweep_deg, false);
+        path.close();
+    } else {
+        // partial donut: outer sweep forward, inner sweep back
+        path.arc_to(rect, start_deg, sweep_deg, true);
+        path.arc_to(inner, start_deg + sweep_deg, -sweep_deg, false);
+        path.close();
+    }
+    path
+}
+
+/// Per-corner parameters of a smoothed corner, see [`build_smooth_rrect_path`].
+struct SmoothCorner {
+    radius: f32,
+    /// Length along each edge taken by the corner.
+    p: f32,
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    /// Length of the circular arc section along each axis.
+    arc: f32,
+}
+
+impl SmoothCorner {
+    fn new(radius: f32, smoothing: f32, budget: f32) -> Self {
+        let radius = radius.clamp(0.0, budget);
+        if radius <= 0.0 {
+            return Self {
+                radius: 0.0,
+                p: 0.0,
+                a: 0.0,
+                b: 0.0,
+                c: 0.0,
+                d: 0.0,
+                arc: 0.0,
+            };
+        }
+
+        // the smoothed corner may not extend past the middle of the shorter side
+        let p = ((1.0 + smoothing) * radius).min(budget);
+        let smoothing = smoothing.min(budget / radius - 1.0);
+
+        let arc_measure = 90.0 * (1.0 - smoothing);
+        let arc = (arc_measure / 2.0).to_radians().sin() * radius * std::f32::consts::SQRT_2;
+        let angle_alpha = (90.0 - arc_measure) / 2.0;
+        let p3_to_p4 = radius * (angle_alpha / 2.0).to_radians().tan();
+        let angle_beta = (45.0 * smoothing).to_radians();
+        let c = p3_to_p4 * angle_beta.cos();
+        let d = c * angle_beta.tan();
+        let b = (p - arc - c - d) / 3.0;
+        let a = 2.0 * b;
+
+        Self {
+            radius,
+            p,
+            a,
+            b,
+            c,
+            d,
+            arc,
+        }
+    }
+}
+
+/// Builds a rounded rectangle with continuous ("squircle") corners.
+///
+/// `smoothing` (0..1) follows Figma's corner smoothing: each corner extends up to
+/// `(1 + smoothing) * radius` into its edges, and the circular arc shrinks towards the
+/// corner's diagonal while bezier curves ease the edges into it. At `0.0` the corners are
+/// plain circular arcs, but callers should use an [`RRect`] in that case.
+pub fn build_smooth_rrect_path(
+    rect: Rect,
+    radius: &RectangularCornerRadius,
+    smoothing: f32,
+) -> Path {
+    use skia_safe::{path::ArcSize, PathDirection};
+
+    let smoothing = smoothing.clamp(0.0, 1.0);
+    let budget = rect.width().min(rect.height()) / 2.0;
+    let tl = SmoothCorner::new(radius.tl, smoothing, budget);
+    let tr = SmoothCorner::new(radius.tr, smoothing, budget);
+    let br = SmoothCorner::new(radius.br, smoothing, budget);
+    let bl = SmoothCorner::new(radius.bl, smoothing, budget);
+
+    // each corner is drawn relative to where the previous edge ended, in clockwise order;
+    // `(sx, sy)` maps the top right corner onto the others.
+    let corner = |path: &mut Path, k: &SmoothCorner, rotate: bool, sx: f32, sy: f32| {
+        if k.radius <= 0.0 {
+            return;
+        }
+        let pt = |x: f32, y: f32| {
+            if rotate {
+                Point::new(-y * sx, x * sy)
+            } else {
+                Point::new(x * sx, y * sy)
+            }
+        };
+        path.r_cubic_to(pt(k.a, 0.0), pt(k.a + k.b, 0.0), pt(k.a + k.b + k.c, k.d));
+        path.r_arc_to_rotated(
+            (k.radius, k.radius),
+            0.0,
+            ArcSize::Small,
+            PathDirection::CW,
+            pt(k.arc, k.arc),
+        );
+        path.r_cubic_to(pt(k.d, k.c), pt(k.d, k.b + k.c), pt(k.d, k.a + k.b + k.c));
+    };
+
+    let mut path = Path::new();
+    path.move_to((rect.right - tr.p, rect.top));
+    corner(&mut path, &tr, false, 1.0, 1.0);
+    path.line_to((rect.right, rect.bottom - br.p));
+    corner(&mut path, &br, true, 1.0, 1.0);
+    path.line_to((rect.left + bl.p, rect.bottom));
+    corner(&mut path, &bl, false, -1.0, -1.0);
+    path.line_to((rect.left, rect.top + tl.p));
+    corner(&mut path, &tl, true, -1.0, -1.0);
+    path.close();
+    path
+}
+
 pub fn build_shape(node: &IntrinsicSizeNode) -> PainterShape {
     match node {
         IntrinsicSizeNode::Rectangle(n) => {
             let rect = Rect::from_xywh(0.0, 0.0, n.size.width, n.size.height);
             let r = n.corner_radius;
-            if !r.is_zero() {
+            if !r.is_zero() && n.corner_smoothing > 0.0 {
+                PainterShape::from_path(build_smooth_rrect_path(rect, &r, n.corner_smoothing))
+            } else if !r.is_zero() {
                 let rrect = RRect::new_rect_radii(
                     rect,
                     &[
@@ -205,41 +424,19 @@ pub fn build_shape(node: &IntrinsicSizeNode) -> PainterShape {
         }
         IntrinsicSizeNode::Ellipse(n) => {
             let rect = Rect::from_xywh(0.0, 0.0, n.size.width, n.size.height);
-            PainterShape::from_oval(rect)
-        }
-        IntrinsicSizeNode::Polygon(n) => {
-            let path = if n.corner_radius > 0.0 {
-                n.to_path()
+            if n.is_full_ellipse() {
+                PainterShape::from_oval(rect)
             } else {
-                let mut p = Path::new();
-                let mut iter = n.points.iter();
-                if let Some(&pt) = iter.next() {
-                    p.move_to((pt.x, pt.y));
-                    for &pt in iter {
-                        p.line_to((pt.x, pt.y));
-                    }
-                    p.close();
-                }
-                p
-            };
-            PainterShape::from_path(path)
-        }
-        IntrinsicSizeNode::RegularPolygon(n) => {
-            let poly = n.to_polygon();
-            build_shape(&IntrinsicSizeNode::Polygon(poly))
-        }
-        IntrinsicSizeNode::RegularStarPolygon(n) => {
-            let poly = n.to_polygon();
-            build_shape(&IntrinsicSizeNode::Polygon(poly))
-        }
-        IntrinsicSizeNode::Line(n) => {
-            let mut path = Path::new();
-            path.move_to((0.0, 0.0));
-            path.line_to((n.size.width, 0.0));
-            PainterShape::from_path(path)
+                PainterShape::from_path(build_arc_path(n, rect))
+            }
         }
+        IntrinsicSizeNode::Polygon(n) => PainterShape::from_path(n.to_path()),
+        IntrinsicSizeNode::RegularPolygon(n) => PainterShape::from_path(n.to_path()),
+        IntrinsicSizeNode::RegularStarPolygon(n) => PainterShape::from_path(n.to_path()),
+        IntrinsicSizeNode::Line(n) => PainterShape::from_path(n.to_path()),
         IntrinsicSizeNode::Path(n) => {
-            if let Some(path) = Path::from_svg(&n.data) {
+            if let Some(mut path) = Path::from_svg(&n.data) {
+                path.set_fill_type(n.fill_rule.into());
                 PainterShape::from_path(path)
             } else {
                 // Fallback to empty rect if path is invalid
@@ -249,7 +446,9 @@ pub fn build_shape(node: &IntrinsicSizeNode) -> PainterShape {
         IntrinsicSizeNode::Container(n) => {
             let rect = Rect::from_xywh(0.0, 0.0, n.size.width, n.size.height);
             let r = n.corner_radius;
-            if r.tl > 0.0 || r.tr > 0.0 || r.bl > 0.0 || r.br > 0.0 {
+            if !r.is_zero() && n.corner_smoothing > 0.0 {
+                PainterShape::from_path(build_smooth_rrect_path(rect, &r, n.corner_smoothing))
+            } else if r.tl > 0.0 || r.tr > 0.0 || r.bl > 0.0 || r.br > 0.0 {
                 let rrect = RRect::new_rect_radii(
                     rect,
                     &[
@@ -290,6 +489,9 @@ pub fn build_shape(node: &IntrinsicSizeNode) -> PainterShape {
             // Text spans don't have a shape
             PainterShape::from_rect(Rect::new(0.0, 0.0, n.size.width, n.size.height))
         }
+        IntrinsicSizeNode::RichText(n) => {
+            PainterShape::from_rect(Rect::new(0.0, 0.0, n.size.width, n.size.height))
+        }
     }
 }
 
@@ -336,6 +538,38 @@ pub fn merge_shapes(shapes: &[(PainterShape, BooleanPathOperation)]) -> Path {
     result
 }
 
+/// Build the shape the children of a container are clipped to, the container shape inset
+/// by its padding.
+///
+/// The corner radii shrink by the adjacent insets, like the inner edge of a CSS border.
+pub fn build_container_clip_shape(node: &ContainerNode) -> PainterShape {
+    if node.padding.is_zero() {
+        return build_shape(&IntrinsicSizeNode::Container(node.clone()));
+    }
+
+    let content = node.content_rect();
+    let (p, r) = (&node.padding, &node.corner_radius);
+    let mut inner = node.clone();
+    inner.size = Size {
+        width: content.width,
+        height: content.height,
+    };
+    inner.corner_radius = RectangularCornerRadius {
+        tl: (r.tl - p.top.max(p.left)).max(0.0),
+        tr: (r.tr - p.top.max(p.right)).max(0.0),
+        bl: (r.bl - p.bottom.max(p.left)).max(0.0),
+        br: (r.br - p.bottom.max(p.right)).max(0.0),
+    };
+
+    let shape = build_shape(&IntrinsicSizeNode::Container(inner));
+    if let Some(rect) = shape.rect_shape {
+        return PainterShape::from_rect(rect.with_offset((content.x, content.y)));
+    }
+    let mut path = shape.to_path();
+    path.offset((content.x, content.y));
+    PainterShape::from_path(path)
+}
+
 /// Build a [`PainterShape`] for a node if it has intrinsic geometry.
 pub fn build_shape_from_node(node: &Node) -> Option<PainterShape> {
     match node {
@@ -354,55 +588,218 @@ pub fn build_shape_from_node(node: &Node) -> Option<PainterShape> {
     }
 }
 
+/// Returns the part of `path` between the `(start, end)` fractions of its total length,
+/// measured across all contours in order.
+///
+/// Both ends are clamped into `[0, 1]`, an empty range yields an empty path and the full
+/// range the path itself.
+pub fn trim_path(path: &Path, (start, end): (f32, f32)) -> Path {
+    let start = start.clamp(0.0, 1.0);
+    let end = end.clamp(0.0, 1.0);
+    if start >= end {
+        return Path::new();
+    }
+    if start <= 0.0 && end >= 1.0 {
+        return path.clone();
+    }
+    let Some(effect) = PathEffect::trim(start, end, skia_safe::trim_path_effect::Mode::Normal)
+    else {
+        return path.clone();
+    };
+    let stroke_rec = StrokeRec::new(InitStyle::Hairline);
+    match effect.filter_path(path, &stroke_rec, path.bounds()) {
+        Some((trimmed, _)) => trimmed,
+        None => path.clone(),
+    }
+}
+
+/// Total length of the outline of `node` in its local space, summed over all contours.
+///
+/// Nodes without a shape of their own, see [`build_shape_from_node`], have a length of `0`.
+pub fn path_length(node: &Node) -> f32 {
+    let Some(shape) = build_shape_from_node(node) else {
+        return 0.0;
+    };
+    ContourMeasureIter::new(&shape.to_path(), false, None)
+        .map(|contour| contour.length())
+        .sum()
+}
+
+/// Options for resolving a [`BooleanPathOperationNode`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BooleanOpOptions {
+    /// Close open child contours with a straight line before the operation is applied.
+    /// When disabled, open children are used as is and reported as
+    /// [`BooleanOpDiagnostic::OpenPath`].
+    pub close_open_paths: bool,
+}
+
+impl Default for BooleanOpOptions {
+    fn default() -> Self {
+        Self {
+            close_open_paths: true,
+        }
+    }
+}
+
+/// A problem found while resolving a boolean operation that may make the result look wrong.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BooleanOpDiagnostic {
+    /// The child has at least one open contour, which path operations treat as implicitly
+    /// closed.
+    OpenPath { node_id: NodeId },
+}
+
+/// Returns `true` if any contour of `path` is not closed. Empty paths have none.
+pub(crate) fn has_open_contour(path: &Path) -> bool {
+    ContourMeasureIter::new(path, false, None).any(|contour| !contour.is_closed())
+}
+
+/// Returns a copy of `path` with every open contour closed by a straight line.
+fn close_open_contours(path: &Path) -> Path {
+    let mut closed = Path::new();
+    closed.set_fill_type(path.fill_type());
+    let mut iter = skia_safe::path::Iter::new(path, true);
+    while let Some((verb, points)) = iter.next() {
+        match verb {
+            Verb::Move => {
+                closed.move_to(points[0]);
+            }
+            Verb::Line => {
+                closed.line_to(points[1]);
+            }
+            Verb::Quad => {
+                closed.quad_to(points[1], points[2]);
+            }
+            Verb::Conic => {
+                closed.conic_to(points[1], points[2], iter.conic_weight().unwrap_or(1.0));
+            }
+            Verb::Cubic => {
+                closed.cubic_to(points[1], points[2], points[3]);
+            }
+            Verb::Close => {
+                closed.close();
+            }
+            Verb::Done => break,
+        }
+    }
+    closed
+}
+
 /// Compute the resulting path for a [`BooleanPathOperationNode`] in its local coordinate space.
+///
+/// Each child is placed with its own transform and the results are combined left to right
+/// with the node's `op`. Nested operations are resolved recursively and groups contribute
+/// the union of their descendants. [`TextSpanNode`] children are converted to glyph
+/// outlines using `fonts`. Open child paths are closed, see
+/// [`boolean_operation_path_with_options`]. `None` if no child has an outline.
 pub fn boolean_operation_path(
     node: &BooleanPathOperationNode,
     repo: &NodeRepository,
-    cache: &GeometryCache,
+    fonts: &FontRepository,
 ) -> Option<Path> {
-    let world = cache
-        .get_world_transform(&node.base.id)
-        .unwrap_or_else(AffineTransform::identity);
-    let inv = world.inverse().unwrap_or_else(AffineTransform::identity);
-
-    let mut shapes_with_ops = Vec::new();
-
-    for (i, child_id) in node.children.iter().enumerate() {
-        if let Some(child_node) = repo.get(child_id) {
-            let mut path = match child_node {
-                Node::BooleanOperation(child_bool) => {
-                    boolean_operation_path(child_bool, repo, cache)?
-                }
-                _ => build_shape_from_node(child_node)?.to_path(),
-            };
+    boolean_operation_path_with_options(
+        node,
+        repo,
+        Some(fonts),
+        BooleanOpOptions::default(),
+        &mut Vec::new(),
+    )
+}
 
-            let child_world = cache
-                .get_world_transform(child_id)
-                .unwrap_or_else(AffineTransform::identity);
-            let relative = inv.compose(&child_world);
-            path.transform(&cvt::sk_matrix(relative.matrix));
+/// Like [`boolean_operation_path`], with explicit `options`.
+///
+/// Without `fonts`, text children have no outline and are skipped. Nodes that reference
+/// one of their ancestors are skipped as well. Problems found in the children, including
+/// those of nested operations and groups, are appended to `diagnostics`.
+pub fn boolean_operation_path_with_options(
+    node: &BooleanPathOperationNode,
+    repo: &NodeRepository,
+    fonts: Option<&FontRepository>,
+    options: BooleanOpOptions,
+    diagnostics: &mut Vec<BooleanOpDiagnostic>,
+) -> Option<Path> {
+    BooleanOperands {
+        repo,
+        fonts,
+        options,
+        diagnostics,
+        ancestors: HashSet::from([node.base.id.clone()]),
+    }
+    .resolve(node)
+}
 
-            let op = if i == 0 {
-                BooleanPathOperation::Union
-            } else {
-                node.op
+/// State shared while resolving the operands of a boolean operation tree.
+struct BooleanOperands<'a> {
+    repo: &'a NodeRepository,
+    fonts: Option<&'a FontRepository>,
+    options: BooleanOpOptions,
+    diagnostics: &'a mut Vec<BooleanOpDiagnostic>,
+    /// The operations and groups being resolved, to break reference cycles.
+    ancestors: HashSet<NodeId>,
+}
+
+impl BooleanOperands<'_> {
+    fn resolve(&mut self, node: &BooleanPathOperationNode) -> Option<Path> {
+        let mut result: Option<Path> = None;
+        for child_id in &node.children {
+            let Some(path) = self.operand(child_id) else {
+                continue;
             };
-            shapes_with_ops.push((PainterShape::from_path(path), op));
+            result = Some(match result {
+                None => path,
+                Some(acc) => Path::op(&acc, &path, node.op.into()).unwrap_or(acc),
+            });
         }
+        result
     }
 
-    if shapes_with_ops.is_empty() {
-        return None;
+    /// The outline of `id` in its parent's space, `None` for missing nodes, nodes without
+    /// an outline and nodes already being resolved.
+    fn operand(&mut self, id: &NodeId) -> Option<Path> {
+        let repo = self.repo;
+        let node = repo.get(id)?;
+        if !self.ancestors.insert(id.clone()) {
+            return None;
+        }
+        let path = match node {
+            Node::BooleanOperation(n) => self.resolve(n),
+            Node::Group(n) => n
+                .children
+                .iter()
+                .filter_map(|child_id| self.operand(child_id))
+                .reduce(|acc, path| Path::op(&acc, &path, PathOp::Union).unwrap_or(acc)),
+            Node::TextSpan(n) => {
+                let fonts = self.fonts;
+                fonts.map(|fonts| self.close(id, text_span_outline(n, fonts)))
+            }
+            _ => build_shape_from_node(node).map(|shape| self.close(id, shape.to_path())),
+        };
+        self.ancestors.remove(id);
+        path.map(|path| path.with_transform(&cvt::sk_matrix(node.transform().matrix)))
     }
 
-    Some(merge_shapes(&shapes_with_ops))
+    /// Applies [`BooleanOpOptions::close_open_paths`] to the outline of a leaf operand.
+    fn close(&mut self, id: &NodeId, path: Path) -> Path {
+        if !has_open_contour(&path) {
+            return path;
+        }
+        if self.options.close_open_paths {
+            close_open_contours(&path)
+        } else {
+            self.diagnostics.push(BooleanOpDiagnostic::OpenPath {
+                node_id: id.clone(),
+            });
+            path
+        }
+    }
 }
 
 /// Convenience wrapper around [`boolean_operation_path`] returning a [`PainterShape`].
 pub fn boolean_operation_shape(
     node: &BooleanPathOperationNode,
     repo: &NodeRepository,
-    cache: &GeometryCache,
+    fonts: &FontRepository,
 ) -> Option<PainterShape> {
-    boolean_operation_path(node, repo, cache).map(PainterShape::from_path)
+    boolean_operation_path(node, repo, fonts).map(PainterShape::from_path)
 }
@@ -1,12 +1,15 @@
 use super::geometry::{
-    boolean_operation_path, boolean_operation_shape, build_shape, merge_shapes, stroke_geometry,
-    PainterShape,
+    boolean_operation_path, boolean_operation_shape, build_container_clip_shape, build_shape,
+    merge_shapes, stroke_geometry, text_span_stroke_path, PainterShape, StrokeOptions,
 };
 use crate::cache::geometry::GeometryCache;
 use crate::node::repository::NodeRepository;
 use crate::node::schema::*;
-use math2::transform::AffineTransform;
+use crate::runtime::repository::FontRepository;
+use math2::{rect::Rectangle, transform::AffineTransform};
 use skia_safe::Path;
+use std::collections::HashSet;
+use std::ops::Range;
 
 /// A Skia-friendly, cacheable picture layer for vector rendering.
 ///
@@ -70,6 +73,25 @@ use skia_safe::Path;
 pub enum PainterPictureLayer {
     Shape(PainterPictureShapeLayer),
     Text(PainterPictureTextLayer),
+    RichText(PainterPictureRichTextLayer),
+}
+
+impl PainterPictureLayer {
+    pub fn base(&self) -> &PainterPictureLayerBase {
+        match self {
+            PainterPictureLayer::Shape(layer) => &layer.base,
+            PainterPictureLayer::Text(layer) => &layer.base,
+            PainterPictureLayer::RichText(layer) => &layer.base,
+        }
+    }
+
+    pub fn base_mut(&mut self) -> &mut PainterPictureLayerBase {
+        match self {
+            PainterPictureLayer::Shape(layer) => &mut layer.base,
+            PainterPictureLayer::Text(layer) => &mut layer.base,
+            PainterPictureLayer::RichText(layer) => &mut layer.base,
+        }
+    }
 }
 
 pub trait Layer {
@@ -82,6 +104,7 @@ impl Layer for PainterPictureLayer {
         match self {
             PainterPictureLayer::Shape(layer) => &layer.base.id,
             PainterPictureLayer::Text(layer) => &layer.base.id,
+            PainterPictureLayer::RichText(layer) => &layer.base.id,
         }
     }
 
@@ -89,6 +112,7 @@ impl Layer for PainterPictureLayer {
         match self {
             PainterPictureLayer::Shape(layer) => layer.base.z_index,
             PainterPictureLayer::Text(layer) => layer.base.z_index,
+            PainterPictureLayer::RichText(layer) => layer.base.z_index,
         }
     }
 }
@@ -98,6 +122,7 @@ pub struct PainterPictureLayerBase {
     pub id: NodeId,
     pub z_index: usize,
     pub opacity: f32,
+    pub blend_mode: BlendMode,
     pub transform: AffineTransform,
     pub shape: PainterShape,
     pub effects: Vec<FilterEffect>,
@@ -105,11 +130,112 @@ pub struct PainterPictureLayerBase {
     pub fills: Vec<Paint>,
     pub stroke_path: Option<skia_safe::Path>,
     pub clip_path: Option<skia_safe::Path>,
+    /// Isolated ancestors, outermost first. The layer is drawn inside one
+    /// `save_layer` per entry, see [`LayerIsolation`].
+    pub isolation: Vec<LayerIsolation>,
+    /// Whether the node or one of its ancestors is locked. Locked layers are drawn but
+    /// not hit tested.
+    pub locked: bool,
+}
+
+/// A group or container whose descendants are composited as a unit.
+///
+/// Its opacity and blend mode are applied once to the whole subtree, instead of being
+/// multiplied into every descendant layer, so overlapping children do not show
+/// through each other. Groups are isolated as decided by [`group_isolates`], the
+/// painter draws them the same way. Containers treat `PassThrough` as `Normal` and are
+/// only isolated when they need to be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerIsolation {
+    pub id: NodeId,
+    pub opacity: f32,
+    pub blend_mode: BlendMode,
+}
+
+/// Whether a group is composited as a unit.
+///
+/// Groups with an opacity below 1 or a blend mode of their own always are, like CSS
+/// stacking contexts, so their opacity applies once to the whole group. A
+/// [`BlendMode::Normal`] group at full opacity only is when a descendant blends, keeping
+/// the blending inside the group, a [`BlendMode::PassThrough`] one never is and its
+/// descendants blend with the backdrop outside of it.
+pub(crate) fn group_isolates(node: &GroupNode, repo: &NodeRepository) -> bool {
+    match node.blend_mode {
+        _ if node.opacity < 1.0 => true,
+        BlendMode::PassThrough => false,
+        BlendMode::Normal => has_blending_descendant(&node.children, repo, &mut HashSet::new()),
+        _ => true,
+    }
+}
+
+/// Whether an active node below `children` has a blend mode other than `Normal`.
+fn has_blending_descendant<'a>(
+    children: &'a [NodeId],
+    repo: &'a NodeRepository,
+    visited: &mut HashSet<&'a NodeId>,
+) -> bool {
+    children.iter().any(|id| {
+        visited.insert(id)
+            && repo.get(id).is_some_and(|node| {
+                node.active()
+                    && (!matches!(
+                        node.blend_mode(),
+                        BlendMode::Normal | BlendMode::PassThrough
+                    ) || has_blending_descendant(node.children(), repo, visited))
+            })
+    })
+}
+
+impl LayerIsolation {
+    /// The isolation stack for the descendants of a group, or `None` when the group is
+    /// not isolated, see [`group_isolates`].
+    fn push_group(
+        stack: &[LayerIsolation],
+        node: &GroupNode,
+        opacity: f32,
+        repo: &NodeRepository,
+    ) -> Option<Vec<LayerIsolation>> {
+        if !group_isolates(node, repo) {
+            return None;
+        }
+        Some(Self::push(stack, &node.base.id, opacity, node.blend_mode))
+    }
+
+    /// The isolation stack for the descendants of a container, or `None` if the
+    /// container does not need to be composited as a unit.
+    fn push_container(
+        stack: &[LayerIsolation],
+        id: &NodeId,
+        opacity: f32,
+        blend_mode: BlendMode,
+    ) -> Option<Vec<LayerIsolation>> {
+        if opacity >= 1.0 && matches!(blend_mode, BlendMode::Normal | BlendMode::PassThrough) {
+            return None;
+        }
+        Some(Self::push(stack, id, opacity, blend_mode))
+    }
+
+    fn push(
+        stack: &[LayerIsolation],
+        id: &NodeId,
+        opacity: f32,
+        blend_mode: BlendMode,
+    ) -> Vec<LayerIsolation> {
+        let mut stack = stack.to_vec();
+        stack.push(LayerIsolation {
+            id: id.clone(),
+            opacity,
+            blend_mode,
+        });
+        stack
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PainterPictureShapeLayer {
     pub base: PainterPictureLayerBase,
+    /// Whether image fills are clipped to the shape
+    pub clip_fills: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -119,21 +245,92 @@ pub struct PainterPictureTextLayer {
     pub text_style: TextStyle,
     pub text_align: TextAlign,
     pub text_align_vertical: TextAlignVertical,
+    pub max_lines: Option<usize>,
+    pub overflow: TextOverflow,
+}
+
+/// A run of consecutive layers in a [`LayerList`] that can be drawn with shared paint state.
+///
+/// Layers in the same batch are plain shapes (no effects, no clip) with identical solid
+/// fills and strokes, the same opacity, and transforms that differ only by translation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawCommandBatch {
+    /// Range of indices into [`LayerList::layers`], in draw order.
+    pub range: Range<usize>,
+}
+
+impl DrawCommandBatch {
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PainterPictureRichTextLayer {
+    pub base: PainterPictureLayerBase,
+    pub spans: Vec<TextSpan>,
+    pub text_align: TextAlign,
+    pub text_align_vertical: TextAlignVertical,
+}
+
+/// What [`LayerList::flatten_node`] reads from, shared by the whole traversal.
+struct FlattenContext<'a> {
+    repo: &'a NodeRepository,
+    cache: &'a GeometryCache,
+    fonts: &'a FontRepository,
+    /// When set, nodes outside of it are skipped along with their subtree.
+    only: Option<&'a HashSet<NodeId>>,
 }
 
 /// Flat list of [`PainterPictureLayer`] entries.
 #[derive(Debug, Default, Clone)]
 pub struct LayerList {
     pub layers: Vec<PainterPictureLayer>,
+    /// Consecutive layers grouped by compatible paint, covering all `layers` in order.
+    pub batches: Vec<DrawCommandBatch>,
 }
 
 impl LayerList {
     /// Flatten an entire scene into a layer list using the provided geometry cache.
-    pub fn from_scene(scene: &Scene, cache: &GeometryCache) -> Self {
+    pub fn from_scene(scene: &Scene, cache: &GeometryCache, fonts: &FontRepository) -> Self {
+        Self::from_scene_nodes(scene, cache, fonts, None)
+    }
+
+    /// Flatten the part of `scene` that paints inside `viewport`, given in scene space.
+    ///
+    /// Only the nodes listed by [`Scene::visible_nodes`] are flattened, the layers of
+    /// culled nodes are never built.
+    pub fn from_scene_in_viewport(
+        scene: &Scene,
+        cache: &GeometryCache,
+        fonts: &FontRepository,
+        viewport: Rectangle,
+    ) -> Self {
+        let visible: HashSet<NodeId> = scene.visible_nodes(viewport).into_iter().collect();
+        Self::from_scene_nodes(scene, cache, fonts, Some(&visible))
+    }
+
+    fn from_scene_nodes(
+        scene: &Scene,
+        cache: &GeometryCache,
+        fonts: &FontRepository,
+        only: Option<&HashSet<NodeId>>,
+    ) -> Self {
+        let ctx = FlattenContext {
+            repo: &scene.nodes,
+            cache,
+            fonts,
+            only,
+        };
         let mut list = LayerList::default();
-        for id in &scene.children {
-            Self::flatten_node(id, &scene.nodes, cache, 1.0, &mut list.layers);
+        for id in scene.nodes.in_render_order(&scene.children) {
+            Self::flatten_node(id, &ctx, 1.0, &[], &mut list.layers);
         }
+        list.batches = Self::batch_layers(&list.layers);
         list
     }
 
@@ -142,10 +339,18 @@ impl LayerList {
         id: &NodeId,
         repo: &NodeRepository,
         cache: &GeometryCache,
+        fonts: &FontRepository,
         opacity: f32,
     ) -> Self {
+        let ctx = FlattenContext {
+            repo,
+            cache,
+            fonts,
+            only: None,
+        };
         let mut list = LayerList::default();
-        Self::flatten_node(id, repo, cache, opacity, &mut list.layers);
+        Self::flatten_node(id, &ctx, opacity, &[], &mut list.layers);
+        list.batches = Self::batch_layers(&list.layers);
         list
     }
 
@@ -155,31 +360,56 @@ impl LayerList {
 
     fn flatten_node(
         id: &NodeId,
-        repo: &NodeRepository,
-        cache: &GeometryCache,
+        ctx: &FlattenContext,
         parent_opacity: f32,
+        isolation: &[LayerIsolation],
         out: &mut Vec<PainterPictureLayer>,
     ) {
-        if let Some(node) = repo.get(id) {
+        if ctx.only.is_some_and(|only| !only.contains(id)) {
+            return;
+        }
+        let FlattenContext {
+            repo, cache, fonts, ..
+        } = *ctx;
+        if let Some(node) = repo.get(id).filter(|node| node.active()) {
             let transform = cache
                 .get_world_transform(id)
                 .unwrap_or_else(AffineTransform::identity);
+            let start = out.len();
             match node {
                 Node::Group(n) => {
                     let opacity = parent_opacity * n.opacity;
-                    for child in &n.children {
-                        Self::flatten_node(child, repo, cache, opacity, out);
+                    let isolated = LayerIsolation::push_group(isolation, n, opacity, repo);
+                    let (opacity, isolation) = match &isolated {
+                        Some(stack) => (1.0, stack.as_slice()),
+                        None => (opacity, isolation),
+                    };
+                    for child in repo.in_render_order(&n.children) {
+                        Self::flatten_node(child, ctx, opacity, isolation, out);
                     }
                 }
                 Node::Container(n) => {
                     let opacity = parent_opacity * n.opacity;
+                    let isolated = LayerIsolation::push_container(
+                        isolation,
+                        &n.base.id,
+                        opacity,
+                        n.blend_mode,
+                    );
+                    // an isolated container draws its own shape inside the isolation layer
+                    let (opacity, blend_mode, isolation) = match &isolated {
+                        Some(stack) => (1.0, BlendMode::Normal, stack.as_slice()),
+                        None => (opacity, n.blend_mode, isolation),
+                    };
                     let shape = build_shape(&IntrinsicSizeNode::Container(n.clone()));
                     let stroke_path = if n.stroke.is_some() && n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -189,28 +419,34 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity,
+                            blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: n.stroke.clone().into_iter().collect(),
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }));
-                    for child in &n.children {
-                        Self::flatten_node(child, repo, cache, opacity, out);
+                    for child in repo.in_render_order(&n.children) {
+                        Self::flatten_node(child, ctx, opacity, isolation, out);
                     }
                 }
                 Node::BooleanOperation(n) => {
                     let opacity = parent_opacity * n.opacity;
-                    if let Some(shape) = boolean_operation_shape(n, repo, cache) {
+                    if let Some(shape) = boolean_operation_shape(n, repo, fonts) {
                         let stroke_path = if n.stroke.is_some() && n.stroke_width > 0.0 {
                             Some(stroke_geometry(
                                 &shape.to_path(),
-                                n.stroke_width,
-                                n.stroke_align,
-                                n.stroke_dash_array.as_ref(),
+                                &StrokeOptions {
+                                    dash_array: n.stroke_dash_array.as_deref(),
+                                    dash_offset: n.stroke_dash_offset,
+                                    ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                                },
                             ))
                         } else {
                             None
@@ -220,18 +456,22 @@ impl LayerList {
                                 id: n.base.id.clone(),
                                 z_index: out.len(),
                                 opacity,
+                                blend_mode: n.blend_mode,
                                 transform,
                                 shape,
-                                effects: n.effect.clone().into_iter().collect(),
+                                effects: n.effects.clone(),
                                 strokes: n.stroke.clone().into_iter().collect(),
                                 fills: vec![n.fill.clone()],
                                 stroke_path,
-                                clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                                clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                                isolation: isolation.to_vec(),
+                                locked: false,
                             },
+                            clip_fills: true,
                         }));
                     } else {
-                        for child in &n.children {
-                            Self::flatten_node(child, repo, cache, opacity, out);
+                        for child in repo.in_render_order(&n.children) {
+                            Self::flatten_node(child, ctx, opacity, isolation, out);
                         }
                     }
                 }
@@ -240,9 +480,12 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                trim: n.trim,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -252,14 +495,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::Ellipse(n) => {
@@ -267,9 +514,12 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                trim: n.trim,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -279,14 +529,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::Polygon(n) => {
@@ -294,9 +548,11 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -306,14 +562,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::RegularPolygon(n) => {
@@ -321,9 +581,11 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -333,14 +595,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::RegularStarPolygon(n) => {
@@ -348,9 +614,11 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -360,14 +628,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::Line(n) => {
@@ -375,9 +647,13 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.get_stroke_align(),
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                cap: n.stroke_cap,
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                trim: n.trim,
+                                ..StrokeOptions::new(n.stroke_width, n.get_stroke_align())
+                            },
                         ))
                     } else {
                         None
@@ -387,14 +663,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
                             effects: vec![],
                             strokes: vec![n.stroke.clone()],
                             fills: vec![],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::TextSpan(n) => out.push(PainterPictureLayer::Text(PainterPictureTextLayer {
@@ -402,27 +682,58 @@ impl LayerList {
                         id: n.base.id.clone(),
                         z_index: out.len(),
                         opacity: parent_opacity * n.opacity,
+                        blend_mode: n.blend_mode,
                         transform,
                         shape: build_shape(&IntrinsicSizeNode::TextSpan(n.clone())),
                         effects: vec![],
                         strokes: n.stroke.clone().into_iter().collect(),
                         fills: vec![n.fill.clone()],
-                        stroke_path: None,
-                        clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                        stroke_path: text_span_stroke_path(n, fonts),
+                        clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                        isolation: isolation.to_vec(),
+                        locked: false,
                     },
                     text: n.text.clone(),
                     text_style: n.text_style.clone(),
                     text_align: n.text_align,
                     text_align_vertical: n.text_align_vertical,
+                    max_lines: n.max_lines,
+                    overflow: n.overflow,
                 })),
+                Node::RichText(n) => {
+                    out.push(PainterPictureLayer::RichText(PainterPictureRichTextLayer {
+                        base: PainterPictureLayerBase {
+                            id: n.base.id.clone(),
+                            z_index: out.len(),
+                            opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
+                            transform,
+                            shape: build_shape(&IntrinsicSizeNode::RichText(n.clone())),
+                            effects: vec![],
+                            strokes: vec![],
+                            fills: vec![n.fill.clone()],
+                            stroke_path: None,
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
+                        },
+                        spans: n.spans.clone(),
+                        text_align: n.text_align,
+                        text_align_vertical: n.text_align_vertical,
+                    }))
+                }
                 Node::Path(n) => {
                     let shape = build_shape(&IntrinsicSizeNode::Path(n.clone()));
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                cap: n.stroke_cap,
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                trim: n.trim,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -432,14 +743,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: true,
                     }))
                 }
                 Node::Image(n) => {
@@ -447,9 +762,11 @@ impl LayerList {
                     let stroke_path = if n.stroke_width > 0.0 {
                         Some(stroke_geometry(
                             &shape.to_path(),
-                            n.stroke_width,
-                            n.stroke_align,
-                            n.stroke_dash_array.as_ref(),
+                            &StrokeOptions {
+                                dash_array: n.stroke_dash_array.as_deref(),
+                                dash_offset: n.stroke_dash_offset,
+                                ..StrokeOptions::new(n.stroke_width, n.stroke_align)
+                            },
                         ))
                     } else {
                         None
@@ -459,14 +776,18 @@ impl LayerList {
                             id: n.base.id.clone(),
                             z_index: out.len(),
                             opacity: parent_opacity * n.opacity,
+                            blend_mode: n.blend_mode,
                             transform,
                             shape,
-                            effects: n.effect.clone().into_iter().collect(),
+                            effects: n.effects.clone(),
                             strokes: vec![n.stroke.clone()],
                             fills: vec![n.fill.clone()],
                             stroke_path,
-                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                            clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                            isolation: isolation.to_vec(),
+                            locked: false,
                         },
+                        clip_fills: n.clip,
                     }))
                 }
                 Node::Error(n) => out.push(PainterPictureLayer::Shape(PainterPictureShapeLayer {
@@ -474,16 +795,26 @@ impl LayerList {
                         id: n.base.id.clone(),
                         z_index: out.len(),
                         opacity: parent_opacity * n.opacity,
+                        blend_mode: BlendMode::Normal,
                         transform,
                         shape: build_shape(&IntrinsicSizeNode::Error(n.clone())),
                         effects: vec![],
                         strokes: vec![],
                         fills: vec![],
                         stroke_path: None,
-                        clip_path: Self::compute_clip_path(&n.base.id, repo, cache),
+                        clip_path: Self::compute_clip_path(&n.base.id, repo, cache, fonts),
+                        isolation: isolation.to_vec(),
+                        locked: false,
                     },
+                    clip_fills: true,
                 })),
             }
+            // locking a node locks its whole subtree
+            if node.locked() {
+                for layer in &mut out[start..] {
+                    layer.base_mut().locked = true;
+                }
+            }
         }
     }
 
@@ -494,9 +825,54 @@ impl LayerList {
                 list.layers.push(layer.clone());
             }
         }
+        list.batches = Self::batch_layers(&list.layers);
         list
     }
 
+    /// Groups consecutive layers that share identical paints and compatible transforms.
+    pub fn batch_layers(layers: &[PainterPictureLayer]) -> Vec<DrawCommandBatch> {
+        let mut batches: Vec<DrawCommandBatch> = Vec::new();
+        for (i, layer) in layers.iter().enumerate() {
+            if let Some(last) = batches.last_mut() {
+                let prev = &layers[last.range.end - 1];
+                if Self::can_batch(prev, layer) {
+                    last.range.end = i + 1;
+                    continue;
+                }
+            }
+            batches.push(DrawCommandBatch { range: i..i + 1 });
+        }
+        batches
+    }
+
+    fn can_batch(a: &PainterPictureLayer, b: &PainterPictureLayer) -> bool {
+        let (PainterPictureLayer::Shape(a), PainterPictureLayer::Shape(b)) = (a, b) else {
+            return false;
+        };
+        let (a, b) = (&a.base, &b.base);
+        let is_plain = |base: &PainterPictureLayerBase| {
+            base.effects.is_empty()
+                && base.clip_path.is_none()
+                && base
+                    .fills
+                    .iter()
+                    .chain(base.strokes.iter())
+                    .all(|p| matches!(p, Paint::Solid(_)))
+        };
+        let [[a00, a01, _], [a10, a11, _]] = a.transform.matrix;
+        let [[b00, b01, _], [b10, b11, _]] = b.transform.matrix;
+
+        is_plain(a)
+            && is_plain(b)
+            && a.opacity == b.opacity
+            && a.blend_mode == b.blend_mode
+            && a.isolation == b.isolation
+            && a.fills == b.fills
+            && a.strokes == b.strokes
+            && a.stroke_path.is_some() == b.stroke_path.is_some()
+            && [a00, a01, a10, a11] == [b00, b01, b10, b11]
+    }
+
     /// Computes the clip path for a node by traversing up the hierarchy
     /// and collecting all clip shapes from parent nodes.
     ///
@@ -509,6 +885,7 @@ impl LayerList {
     /// - `node_id`: The ID of the node to compute the clip path for
     /// - `repo`: The node repository containing all nodes
     /// - `cache`: The geometry cache for transforms
+    /// - `fonts`: The font repository used to outline text in boolean operations
     ///
     /// # Returns
     ///
@@ -517,6 +894,7 @@ impl LayerList {
         node_id: &NodeId,
         repo: &NodeRepository,
         cache: &GeometryCache,
+        fonts: &FontRepository,
     ) -> Option<Path> {
         let mut clip_shapes = Vec::new();
         let mut current_id = Some(node_id.clone());
@@ -533,14 +911,15 @@ impl LayerList {
             if let Some(node) = repo.get(&id) {
                 match node {
                     Node::Container(n) => {
-                        if n.clip {
+                        // a container clips its children, not its own stroke and effects
+                        if n.clip && id != *node_id {
                             // Get the world transform for this node
                             let world_transform = cache
                                 .get_world_transform(&id)
                                 .unwrap_or_else(AffineTransform::identity);
 
                             // Build the shape and transform it relative to the current node
-                            let shape = build_shape(&IntrinsicSizeNode::Container(n.clone()));
+                            let shape = build_container_clip_shape(n);
                             let mut path = shape.to_path();
                             let relative_transform = current_inv.compose(&world_transform);
                             path.transform(&crate::painter::cvt::sk_matrix(
@@ -554,7 +933,7 @@ impl LayerList {
                         }
                     }
                     Node::BooleanOperation(n) => {
-                        if let Some(mut path) = boolean_operation_path(n, repo, cache) {
+                        if let Some(mut path) = boolean_operation_path(n, repo, fonts) {
                             let world_transform = cache
                                 .get_world_transform(&id)
                                 .unwrap_or_else(AffineTransform::identity);
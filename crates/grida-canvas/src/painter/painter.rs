@@ -1,16 +1,80 @@
 use super::cvt;
 use super::geometry::*;
-use super::layer::{LayerList, PainterPictureLayer};
+use super::layer::{
+    group_isolates, DrawCommandBatch, Layer, LayerIsolation, LayerList, PainterPictureLayer,
+};
 use crate::cache::geometry::GeometryCache;
 use crate::cache::{paragraph::ParagraphCache, vector_path::VectorPathCache};
 use crate::node::repository::NodeRepository;
 use crate::node::schema::*;
 use crate::runtime::repository::{FontRepository, ImageRepository};
-use math2::{box_fit::BoxFit, transform::AffineTransform};
+use math2::{box_fit::BoxFit, rect::Rectangle, transform::AffineTransform};
 use skia_safe::{canvas::SaveLayerRec, textlayout, Paint as SkPaint, Path, Point};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+/// Blur sigma above which the raster backend blurs a downscaled copy of the content.
+const RASTER_BLUR_DOWNSAMPLE_THRESHOLD: f32 = 16.0;
+
+/// The blur implementation used by the painter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlurBackend {
+    /// Full resolution blur, executed on the GPU.
+    Gpu,
+    /// CPU blur, large radii are blurred on a downscaled copy and upscaled back.
+    Raster,
+}
+
+impl BlurBackend {
+    /// Detect the blur backend from the canvas, GPU-backed canvases have a recording context.
+    pub fn detect(canvas: &skia_safe::Canvas) -> Self {
+        if canvas.recording_context().is_some() {
+            BlurBackend::Gpu
+        } else {
+            BlurBackend::Raster
+        }
+    }
+}
+
+/// Options controlling how the painter rasterizes content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    /// Sample images from their mipmaps when drawn downscaled, so large images drawn
+    /// small don't alias. Enabled by default.
+    pub mipmaps: bool,
+    /// Film grain drawn over the whole frame, after the scene. Disabled by default.
+    pub grain: Option<GrainConfig>,
+    /// Draw the scene guides over the frame, see [`Painter::draw_guides`]. Disabled by default.
+    pub show_guides: bool,
+    /// Color of the guide lines.
+    pub guide_color: Color,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            mipmaps: true,
+            grain: None,
+            show_guides: false,
+            guide_color: Color(255, 0, 128, 255),
+        }
+    }
+}
+
+/// A monochrome noise overlay, see [`Painter::draw_grain`].
+///
+/// The noise is fully determined by the config, so the same seed always yields the same
+/// grain. Change the seed per frame for animated grain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrainConfig {
+    /// Strength of the overlay, from `0.0` (invisible) to `1.0`.
+    pub intensity: f32,
+    /// Size of the grain in pixels, larger values give coarser noise.
+    pub scale: f32,
+    /// Seed of the noise pattern.
+    pub seed: u32,
+}
+
 /// A painter that handles all drawing operations for nodes,
 /// with proper effect ordering and a layer‐blur/backdrop‐blur pipeline.
 pub struct Painter<'a> {
@@ -19,6 +83,8 @@ pub struct Painter<'a> {
     images: Rc<RefCell<ImageRepository>>,
     paragraph_cache: RefCell<ParagraphCache>,
     path_cache: RefCell<VectorPathCache>,
+    blur_backend: BlurBackend,
+    options: RenderOptions,
 }
 
 impl<'a> Painter<'a> {
@@ -34,9 +100,51 @@ impl<'a> Painter<'a> {
             images,
             paragraph_cache: RefCell::new(ParagraphCache::new()),
             path_cache: RefCell::new(VectorPathCache::new()),
+            blur_backend: BlurBackend::detect(canvas),
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Override the default [`RenderOptions`].
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The render options of this painter.
+    pub fn options(&self) -> RenderOptions {
+        self.options
+    }
+
+    /// Sampling used for image fills.
+    ///
+    /// With mipmaps enabled, skia picks (and lazily generates) the mip levels from the
+    /// device scale at draw time, so this also applies to pictures replayed under a zoomed
+    /// out camera. Filtering within a level stays nearest, leaving images drawn at or above
+    /// their size unchanged.
+    fn image_sampling(&self) -> skia_safe::SamplingOptions {
+        if self.options.mipmaps {
+            skia_safe::SamplingOptions::new(
+                skia_safe::FilterMode::Nearest,
+                skia_safe::MipmapMode::Linear,
+            )
+        } else {
+            skia_safe::SamplingOptions::default()
         }
     }
 
+    /// Override the detected blur backend, e.g. when recording pictures that are
+    /// later replayed on a GPU surface.
+    pub fn with_blur_backend(mut self, blur_backend: BlurBackend) -> Self {
+        self.blur_backend = blur_backend;
+        self
+    }
+
+    /// The blur backend selected for this painter.
+    pub fn blur_backend(&self) -> BlurBackend {
+        self.blur_backend
+    }
+
     #[cfg(test)]
     pub fn paragraph_cache(&self) -> &RefCell<ParagraphCache> {
         &self.paragraph_cache
@@ -72,17 +180,23 @@ impl<'a> Painter<'a> {
         }
     }
 
-    /// If blend mode is not Normal, wrap drawing in a save_layer with blend mode; else draw directly.
-    fn with_blendmode<F: FnOnce()>(&self, blend_mode: BlendMode, f: F) {
-        let canvas = self.canvas;
-        if blend_mode != BlendMode::Normal {
+    /// Applies `opacity` and `blend_mode` to everything drawn in `f`.
+    ///
+    /// Both are applied by a single layer. Nesting the blend layer inside an opacity layer
+    /// would blend the node against that empty layer instead of the backdrop, so modes
+    /// such as [`BlendMode::DstOut`] would have nothing to act on.
+    /// [`BlendMode::PassThrough`] only applies the opacity.
+    fn with_opacity_and_blendmode<F: FnOnce()>(&self, opacity: f32, blend_mode: BlendMode, f: F) {
+        if !matches!(blend_mode, BlendMode::Normal | BlendMode::PassThrough) {
+            let canvas = self.canvas;
             let mut paint = SkPaint::default();
+            paint.set_alpha_f(opacity);
             paint.set_blend_mode(blend_mode.into());
             canvas.save_layer(&SaveLayerRec::default().paint(&paint));
             f();
             canvas.restore();
         } else {
-            f();
+            self.with_opacity(opacity, f);
         }
     }
 
@@ -107,10 +221,43 @@ impl<'a> Painter<'a> {
         canvas.restore();
     }
 
+    /// Build a Gaussian blur image filter for the selected blur backend.
+    fn blur_filter(&self, radius: f32) -> Option<skia_safe::ImageFilter> {
+        if self.blur_backend == BlurBackend::Gpu || radius <= RASTER_BLUR_DOWNSAMPLE_THRESHOLD {
+            return skia_safe::image_filters::blur((radius, radius), None, None, None);
+        }
+
+        // blur a downscaled copy and scale it back up, the loss of detail is hidden by the blur
+        let scale = RASTER_BLUR_DOWNSAMPLE_THRESHOLD / radius;
+        let sampling = skia_safe::SamplingOptions::new(
+            skia_safe::FilterMode::Linear,
+            skia_safe::MipmapMode::None,
+        );
+        let downscaled = skia_safe::image_filters::matrix_transform(
+            &skia_safe::Matrix::scale((scale, scale)),
+            sampling,
+            None,
+        );
+        let blurred = skia_safe::image_filters::blur(
+            (
+                RASTER_BLUR_DOWNSAMPLE_THRESHOLD,
+                RASTER_BLUR_DOWNSAMPLE_THRESHOLD,
+            ),
+            None,
+            downscaled,
+            None,
+        );
+        skia_safe::image_filters::matrix_transform(
+            &skia_safe::Matrix::scale((1.0 / scale, 1.0 / scale)),
+            sampling,
+            blurred,
+        )
+    }
+
     /// Wrap a closure `f` in a layer that applies a Gaussian blur to everything drawn inside.
     fn with_layer_blur<F: FnOnce()>(&self, radius: f32, f: F) {
         let canvas = self.canvas;
-        let image_filter = skia_safe::image_filters::blur((radius, radius), None, None, None);
+        let image_filter = self.blur_filter(radius);
         let mut paint = SkPaint::default();
         paint.set_image_filter(image_filter);
         canvas.save_layer(&SaveLayerRec::default().paint(&paint));
@@ -121,16 +268,24 @@ impl<'a> Painter<'a> {
     /// Draw a drop shadow behind the content using a shape.
     fn draw_shadow(&self, shape: &PainterShape, shadow: &FeDropShadow) {
         let canvas = self.canvas;
-        let Color(r, g, b, a) = shadow.color;
-        let color = skia_safe::Color::from_argb(a, r, g, b);
+        let color: skia_safe::Color = shadow.color.into();
+
+        // Grow (or shrink) the source alpha by the spread before blurring
+        let spread = if shadow.spread > 0.0 {
+            skia_safe::image_filters::dilate((shadow.spread, shadow.spread), None, None)
+        } else if shadow.spread < 0.0 {
+            skia_safe::image_filters::erode((-shadow.spread, -shadow.spread), None, None)
+        } else {
+            None
+        };
 
-        // Create drop shadow filter
-        let image_filter = skia_safe::image_filters::drop_shadow(
+        // Create drop shadow filter, the source itself is drawn by the content
+        let image_filter = skia_safe::image_filters::drop_shadow_only(
             (shadow.dx, shadow.dy),     // offset as tuple
             (shadow.blur, shadow.blur), // sigma as tuple
             color,                      // color
             None,                       // color_space
-            None,                       // input
+            spread,                     // input
             None,                       // crop_rect
         );
 
@@ -143,22 +298,74 @@ impl<'a> Painter<'a> {
         canvas.draw_path(&shape.to_path(), &shadow_paint);
     }
 
+    /// Draw an inner shadow on top of the content, clipped to the shape.
+    ///
+    /// The shadow is cast by the area outside the shape: a frame around the shape is
+    /// dilated by the spread, blurred, offset and then clipped back to the shape.
+    fn draw_inner_shadow(&self, shape: &PainterShape, shadow: &FeInnerShadow) {
+        let canvas = self.canvas;
+        let color: skia_safe::Color = shadow.color.into();
+
+        let path = shape.to_path();
+        let outset = shadow.blur * 3.0 + shadow.spread + shadow.dx.abs().max(shadow.dy.abs());
+        let mut frame = Path::rect(path.bounds().with_outset((outset, outset)), None);
+        frame.add_path(&path, (0.0, 0.0), None);
+        frame.set_fill_type(skia_safe::PathFillType::EvenOdd);
+
+        let mut image_filter = None;
+        if shadow.spread > 0.0 {
+            image_filter = skia_safe::image_filters::dilate(
+                (shadow.spread, shadow.spread),
+                image_filter,
+                None,
+            );
+        }
+        if shadow.blur > 0.0 {
+            image_filter = skia_safe::image_filters::blur(
+                (shadow.blur, shadow.blur),
+                None,
+                image_filter,
+                None,
+            );
+        }
+        image_filter = skia_safe::image_filters::offset((shadow.dx, shadow.dy), image_filter, None);
+
+        let mut shadow_paint = SkPaint::default();
+        shadow_paint.set_color(color);
+        shadow_paint.set_image_filter(image_filter);
+        shadow_paint.set_anti_alias(true);
+
+        canvas.save();
+        canvas.clip_path(&path, None, true);
+        canvas.draw_path(&frame, &shadow_paint);
+        canvas.restore();
+    }
+
     /// Draw a backdrop blur: blur what's behind the shape.
     fn draw_backdrop_blur(&self, shape: &PainterShape, blur: &FeBackdropBlur) {
         let canvas = self.canvas;
         // 1) Build a Gaussian‐blur filter for the backdrop
-        let Some(image_filter) =
-            skia_safe::image_filters::blur((blur.radius, blur.radius), None, None, None)
-        else {
+        let Some(image_filter) = self.blur_filter(blur.radius) else {
             return;
         };
 
-        // 2) Clip to the shape
+        // 2) Clip to the shape, corner radius included
+        let path = shape.to_path();
         canvas.save();
-        canvas.clip_path(&shape.to_path(), None, true);
+        canvas.clip_path(&path, None, true);
 
-        // 3) Use a SaveLayerRec with a backdrop filter so that everything behind is blurred
-        let layer_rec = SaveLayerRec::default().backdrop(&image_filter);
+        // 3) Use a SaveLayerRec with a backdrop filter so that everything behind is blurred.
+        // The layer replaces the backdrop (`Src`) instead of being drawn over it, otherwise a
+        // translucent backdrop would show through sharp. Samples past the edges of the
+        // backdrop are clamped rather than fading out to transparent.
+        let mut paint = SkPaint::default();
+        paint.set_blend_mode(skia_safe::BlendMode::Src);
+        let bounds = *path.bounds();
+        let layer_rec = SaveLayerRec::default()
+            .bounds(&bounds)
+            .paint(&paint)
+            .backdrop(&image_filter)
+            .backdrop_tile_mode(skia_safe::TileMode::Clamp);
         canvas.save_layer(&layer_rec);
 
         // We don't draw any content here—just pushing and popping the layer
@@ -170,6 +377,7 @@ impl<'a> Painter<'a> {
         self.path_cache.borrow_mut().get_or_create(id, data)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn cached_paragraph(
         &self,
         id: &NodeId,
@@ -178,6 +386,8 @@ impl<'a> Painter<'a> {
         fill: &Paint,
         align: &TextAlign,
         valign: &TextAlignVertical,
+        max_lines: Option<usize>,
+        overflow: TextOverflow,
         style: &TextStyle,
     ) -> Rc<textlayout::Paragraph> {
         self.paragraph_cache.borrow_mut().get_or_create(
@@ -187,15 +397,38 @@ impl<'a> Painter<'a> {
             fill,
             align,
             valign,
+            max_lines,
+            overflow,
             style,
             &self.fonts.borrow(),
         )
     }
 
+    fn cached_rich_paragraph(
+        &self,
+        id: &NodeId,
+        spans: &[TextSpan],
+        size: &Size,
+        fill: &Paint,
+        align: &TextAlign,
+        valign: &TextAlignVertical,
+    ) -> Rc<textlayout::Paragraph> {
+        self.paragraph_cache.borrow_mut().get_or_create_rich(
+            id,
+            spans,
+            size,
+            fill,
+            align,
+            valign,
+            &self.fonts.borrow(),
+        )
+    }
+
     /// Determine the transformation matrix for an [`ImagePaint`].
     ///
     /// If the paint specifies a [`BoxFit`] other than `None`, the box-fit
-    /// transform is used. Otherwise, the paint's own transform is applied.
+    /// transform is used. Otherwise, or if the paint repeats, the paint's own transform
+    /// is applied.
     fn image_paint_matrix(
         &self,
         paint: &ImagePaint,
@@ -203,6 +436,7 @@ impl<'a> Painter<'a> {
         container_size: (f32, f32),
     ) -> [[f32; 3]; 2] {
         match paint.fit {
+            _ if paint.repeat != ImageRepeat::NoRepeat => paint.transform.matrix,
             BoxFit::None => paint.transform.matrix,
             _ => {
                 paint
@@ -213,18 +447,175 @@ impl<'a> Painter<'a> {
         }
     }
 
+    /// Draw an image covering the viewport, honoring the paint's [`BoxFit`] against
+    /// the viewport size.
+    pub fn draw_background_image(&self, paint: &ImagePaint, viewport: (f32, f32)) {
+        let canvas = self.canvas;
+        self.ensure_image(&paint._ref);
+        let images = self.images.borrow();
+        let Some(image) = images.get_by_size(&paint._ref, viewport.0, viewport.1) else {
+            // Image not ready - skip background
+            return;
+        };
+
+        let mut sk_paint = SkPaint::default();
+        sk_paint.set_anti_alias(true);
+        sk_paint.set_alpha_f(paint.opacity);
+
+        canvas.save();
+        canvas.clip_rect(skia_safe::Rect::from_wh(viewport.0, viewport.1), None, true);
+        let m = self.image_paint_matrix(
+            paint,
+            (image.width() as f32, image.height() as f32),
+            viewport,
+        );
+        canvas.concat(&cvt::sk_matrix(m));
+        canvas.draw_image_rect_with_sampling_options(
+            image,
+            None,
+            skia_safe::Rect::from_xywh(0.0, 0.0, image.width() as f32, image.height() as f32),
+            self.image_sampling(),
+            &sk_paint,
+        );
+        canvas.restore();
+    }
+
+    /// Draw film grain over the `viewport`, in device space.
+    ///
+    /// Grayscale fractal noise is blended over the content with `Overlay`, so it lightens
+    /// and darkens the colors beneath while keeping mid tones.
+    pub fn draw_grain(&self, grain: &GrainConfig, viewport: (f32, f32)) {
+        if grain.intensity <= 0.0 || grain.scale <= 0.0 {
+            return;
+        }
+        let frequency = 1.0 / grain.scale;
+        let Some(noise) =
+            skia_safe::shaders::fractal_noise((frequency, frequency), 2, grain.seed as f32, None)
+        else {
+            return;
+        };
+        // spread the red channel of the noise to gray, opaque
+        #[rustfmt::skip]
+        let gray = skia_safe::ColorMatrix::new(
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 1.0,
+        );
+
+        let mut paint = SkPaint::default();
+        paint.set_shader(noise);
+        paint.set_color_filter(skia_safe::color_filters::matrix(&gray, None));
+        paint.set_alpha_f(grain.intensity.min(1.0));
+        paint.set_blend_mode(skia_safe::BlendMode::Overlay);
+
+        self.canvas.save();
+        self.canvas.reset_matrix();
+        self.canvas
+            .draw_rect(skia_safe::Rect::from_wh(viewport.0, viewport.1), &paint);
+        self.canvas.restore();
+    }
+
+    /// Draw `guides` as hairlines across the `viewport`, in device space.
+    ///
+    /// `view` maps scene space to device space, it is expected to only scale and translate.
+    /// Only the guide offsets are transformed, so the lines stay one pixel wide at any zoom.
+    pub fn draw_guides(
+        &self,
+        guides: &[Guide],
+        view: &AffineTransform,
+        viewport: (f32, f32),
+        color: Color,
+    ) {
+        if guides.is_empty() {
+            return;
+        }
+        let mut paint = SkPaint::default();
+        paint.set_color(skia_safe::Color::from(color));
+        paint.set_style(skia_safe::PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+
+        // center the hairline on the device pixel, so it covers exactly one column or row
+        let snap = |v: f32| v.floor() + 0.5;
+
+        self.canvas.save();
+        self.canvas.reset_matrix();
+        for guide in guides {
+            let (start, end) = match guide.axis {
+                Axis::X => {
+                    let x = snap(view.matrix[0][0] * guide.offset + view.x());
+                    ((x, 0.0), (x, viewport.1))
+                }
+                Axis::Y => {
+                    let y = snap(view.matrix[1][1] * guide.offset + view.y());
+                    ((0.0, y), (viewport.0, y))
+                }
+            };
+            self.canvas.draw_line(start, end, &paint);
+        }
+        self.canvas.restore();
+    }
+
+    /// Picks the image to draw `paint` over `rect` with, the mip level matching the size
+    /// of `rect`, or the full size image for repeating paints that tile it at its natural
+    /// size.
+    fn image_for_paint<'a>(
+        &self,
+        images: &'a ImageRepository,
+        paint: &ImagePaint,
+        rect: &skia_safe::Rect,
+    ) -> Option<&'a skia_safe::Image> {
+        match paint.repeat {
+            ImageRepeat::NoRepeat => images.get_by_size(&paint._ref, rect.width(), rect.height()),
+            _ => images.get_full_size(&paint._ref),
+        }
+    }
+
+    /// Decodes the image `src` through the image store, if it is not loaded yet.
+    fn ensure_image(&self, src: &str) {
+        self.images.borrow_mut().ensure(src);
+    }
+
+    /// Draw the stand-in for an image fill whose image could not be resolved, a light gray
+    /// fill with a cross over the shape bounds.
+    fn draw_image_placeholder(&self, shape: &PainterShape) {
+        let canvas = self.canvas;
+        let mut paint = SkPaint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(skia_safe::Color::from_rgb(229, 229, 229));
+
+        canvas.save();
+        canvas.clip_path(&shape.to_path(), None, true);
+        canvas.draw_rect(shape.rect, &paint);
+        paint.set_color(skia_safe::Color::from_rgb(179, 179, 179));
+        paint.set_style(skia_safe::PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        let r = shape.rect;
+        canvas.draw_line((r.left, r.top), (r.right, r.bottom), &paint);
+        canvas.draw_line((r.right, r.top), (r.left, r.bottom), &paint);
+        canvas.restore();
+    }
+
     /// Draw fill for a shape using given paint.
     fn draw_fill(&self, shape: &PainterShape, fill: &Paint) {
+        self.draw_fill_with_clip(shape, fill, true);
+    }
+
+    /// Draw fill for a shape using given paint, `clip` controls whether image fills are
+    /// clipped to the shape.
+    fn draw_fill_with_clip(&self, shape: &PainterShape, fill: &Paint, clip: bool) {
         let canvas = self.canvas;
         let (fill_paint, image, image_params) = match fill {
             Paint::Image(image_paint) => {
+                self.ensure_image(&image_paint._ref);
                 let images = self.images.borrow();
-                if let Some(image) =
-                    images.get_by_size(&image_paint._ref, shape.rect.width(), shape.rect.height())
-                {
-                    let mut paint = SkPaint::default();
-                    paint.set_anti_alias(true);
+                if let Some(image) = self.image_for_paint(&images, image_paint, &shape.rect) {
+                    let paint = cvt::sk_paint(fill, 1.0, (shape.rect.width(), shape.rect.height()));
                     (paint, Some(image.clone()), Some(image_paint.clone()))
+                } else if images.is_missing(&image_paint._ref) {
+                    drop(images);
+                    self.draw_image_placeholder(shape);
+                    return;
                 } else {
                     // Image not ready - skip fill
                     return;
@@ -238,9 +629,21 @@ impl<'a> Painter<'a> {
         };
 
         if let (Some(image), Some(img_paint)) = (image, image_params) {
-            // For image fills, clip to the shape and apply transforms
+            if let Some(shader) =
+                cvt::sk_image_repeat_shader(&image, &img_paint, self.image_sampling())
+            {
+                // Repeating fills tile over the whole shape
+                let mut paint = fill_paint;
+                paint.set_shader(shader);
+                canvas.draw_path(&shape.to_path(), &paint);
+                return;
+            }
+
+            // For image fills, clip to the shape (unless disabled) and apply transforms
             canvas.save();
-            canvas.clip_path(&shape.to_path(), None, true);
+            if clip {
+                canvas.clip_path(&shape.to_path(), None, true);
+            }
 
             // Apply either the fit transform or the paint's custom transform
             let m = self.image_paint_matrix(
@@ -250,10 +653,11 @@ impl<'a> Painter<'a> {
             );
             canvas.concat(&cvt::sk_matrix(m));
 
-            canvas.draw_image_rect(
+            canvas.draw_image_rect_with_sampling_options(
                 &image,
                 None,
                 skia_safe::Rect::from_xywh(0.0, 0.0, image.width() as f32, image.height() as f32),
+                self.image_sampling(),
                 &fill_paint,
             );
             canvas.restore();
@@ -264,25 +668,13 @@ impl<'a> Painter<'a> {
     }
 
     /// Draw stroke for a shape using given paint.
-    fn draw_stroke(
-        &self,
-        shape: &PainterShape,
-        stroke: &Paint,
-        stroke_width: f32,
-        stroke_align: StrokeAlign,
-        stroke_dash_array: Option<&Vec<f32>>,
-    ) {
-        if stroke_width <= 0.0 {
+    fn draw_stroke(&self, shape: &PainterShape, stroke: &Paint, options: &StrokeOptions) {
+        if options.width <= 0.0 {
             return;
         }
 
         // Generate the stroke geometry
-        let stroke_path = stroke_geometry(
-            &shape.to_path(),
-            stroke_width,
-            stroke_align,
-            stroke_dash_array,
-        );
+        let stroke_path = stroke_geometry(&shape.to_path(), options);
 
         self.draw_stroke_path(shape, stroke, &stroke_path);
     }
@@ -299,12 +691,20 @@ impl<'a> Painter<'a> {
         // Draw the stroke using the generated geometry
         match stroke {
             Paint::Image(image_paint) => {
+                self.ensure_image(&image_paint._ref);
                 let images = self.images.borrow();
-                if let Some(image) =
-                    images.get_by_size(&image_paint._ref, shape.rect.width(), shape.rect.height())
-                {
-                    let mut paint = SkPaint::default();
-                    paint.set_anti_alias(true);
+                if let Some(image) = self.image_for_paint(&images, image_paint, &shape.rect) {
+                    let mut paint =
+                        cvt::sk_paint(stroke, 1.0, (shape.rect.width(), shape.rect.height()));
+
+                    if let Some(shader) =
+                        cvt::sk_image_repeat_shader(image, image_paint, self.image_sampling())
+                    {
+                        // Repeating strokes tile over the whole stroke
+                        paint.set_shader(shader);
+                        canvas.draw_path(stroke_path, &paint);
+                        return;
+                    }
 
                     // For image strokes, clip and apply transforms
                     canvas.save();
@@ -317,7 +717,7 @@ impl<'a> Painter<'a> {
                     );
                     canvas.concat(&cvt::sk_matrix(m));
 
-                    canvas.draw_image_rect(
+                    canvas.draw_image_rect_with_sampling_options(
                         &image,
                         None,
                         skia_safe::Rect::from_xywh(
@@ -326,6 +726,7 @@ impl<'a> Painter<'a> {
                             image.width() as f32,
                             image.height() as f32,
                         ),
+                        self.image_sampling(),
                         &paint,
                     );
                     canvas.restore();
@@ -339,27 +740,36 @@ impl<'a> Painter<'a> {
     }
 
     /// Shared utility to handle effect drawing for shapes
-    fn draw_shape_with_effect<F: Fn()>(
+    ///
+    /// Shadows and backdrop blurs are drawn behind the content in order, the content
+    /// itself is then drawn with its inner shadows on top and the layer blur applied, if any.
+    fn draw_shape_with_effects<F: Fn()>(
         &self,
-        effect: Option<&FilterEffect>,
+        effects: &[FilterEffect],
         shape: &PainterShape,
         draw_content: F,
     ) {
-        match effect {
-            Some(FilterEffect::DropShadow(shadow)) => {
-                self.draw_shadow(shape, shadow);
-                draw_content();
-            }
-            Some(FilterEffect::BackdropBlur(blur)) => {
-                self.draw_backdrop_blur(shape, blur);
-                draw_content();
-            }
-            Some(FilterEffect::GaussianBlur(blur)) => {
-                self.with_layer_blur(blur.radius, draw_content);
+        let mut layer_blur = None;
+        let mut inner_shadows = vec![];
+        for effect in effects {
+            match effect {
+                FilterEffect::DropShadow(shadow) => self.draw_shadow(shape, shadow),
+                FilterEffect::BackdropBlur(blur) => self.draw_backdrop_blur(shape, blur),
+                FilterEffect::GaussianBlur(blur) => layer_blur = Some(blur.radius),
+                FilterEffect::InnerShadow(shadow) => inner_shadows.push(shadow),
             }
-            None => {
-                draw_content();
+        }
+
+        let draw_content_with_inner_shadows = || {
+            draw_content();
+            for shadow in &inner_shadows {
+                self.draw_inner_shadow(shape, shadow);
             }
+        };
+
+        match layer_blur {
+            Some(radius) => self.with_layer_blur(radius, draw_content_with_inner_shadows),
+            None => draw_content_with_inner_shadows(),
         }
     }
 
@@ -371,18 +781,19 @@ impl<'a> Painter<'a> {
     fn draw_rect_node(&self, node: &RectangleNode) {
         self.with_transform(&node.transform.matrix, || {
             let shape = build_shape(&IntrinsicSizeNode::Rectangle(node.clone()));
-            self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                self.with_opacity(node.opacity, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        self.draw_fill(&shape, &node.fill);
-                        self.draw_stroke(
-                            &shape,
-                            &node.stroke,
-                            node.stroke_width,
-                            node.stroke_align,
-                            node.stroke_dash_array.as_ref(),
-                        );
-                    });
+            self.draw_shape_with_effects(&node.effects, &shape, || {
+                self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                    self.draw_fill(&shape, &node.fill);
+                    self.draw_stroke(
+                        &shape,
+                        &node.stroke,
+                        &StrokeOptions {
+                            dash_array: node.stroke_dash_array.as_deref(),
+                            dash_offset: node.stroke_dash_offset,
+                            trim: node.trim,
+                            ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                        },
+                    );
                 });
             });
         });
@@ -393,26 +804,28 @@ impl<'a> Painter<'a> {
         self.with_transform(&node.transform.matrix, || {
             let shape = build_shape(&IntrinsicSizeNode::Image(node.clone()));
 
-            self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                self.with_opacity(node.opacity, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        // convert the image itself to a paint
-                        let image_paint = Paint::Image(ImagePaint {
-                            _ref: node._ref.clone(),
-                            opacity: node.opacity,
-                            transform: AffineTransform::identity(),
-                            fit: math2::box_fit::BoxFit::Cover,
-                        });
-
-                        self.draw_fill(&shape, &image_paint);
-                        self.draw_stroke(
-                            &shape,
-                            &node.stroke,
-                            node.stroke_width,
-                            node.stroke_align,
-                            node.stroke_dash_array.as_ref(),
-                        );
+            self.draw_shape_with_effects(&node.effects, &shape, || {
+                self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                    // convert the image itself to a paint
+                    let image_paint = Paint::Image(ImagePaint {
+                        _ref: node._ref.clone(),
+                        // the node opacity is already applied by the layer
+                        opacity: 1.0,
+                        transform: AffineTransform::identity(),
+                        fit: math2::box_fit::BoxFit::Cover,
+                        repeat: ImageRepeat::NoRepeat,
                     });
+
+                    self.draw_fill_with_clip(&shape, &image_paint, node.clip);
+                    self.draw_stroke(
+                        &shape,
+                        &node.stroke,
+                        &StrokeOptions {
+                            dash_array: node.stroke_dash_array.as_deref(),
+                            dash_offset: node.stroke_dash_offset,
+                            ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                        },
+                    );
                 });
             });
         });
@@ -423,18 +836,19 @@ impl<'a> Painter<'a> {
     fn draw_ellipse_node(&self, node: &EllipseNode) {
         self.with_transform(&node.transform.matrix, || {
             let shape = build_shape(&IntrinsicSizeNode::Ellipse(node.clone()));
-            self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                self.with_opacity(node.opacity, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        self.draw_fill(&shape, &node.fill);
-                        self.draw_stroke(
-                            &shape,
-                            &node.stroke,
-                            node.stroke_width,
-                            node.stroke_align,
-                            node.stroke_dash_array.as_ref(),
-                        );
-                    });
+            self.draw_shape_with_effects(&node.effects, &shape, || {
+                self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                    self.draw_fill(&shape, &node.fill);
+                    self.draw_stroke(
+                        &shape,
+                        &node.stroke,
+                        &StrokeOptions {
+                            dash_array: node.stroke_dash_array.as_deref(),
+                            dash_offset: node.stroke_dash_offset,
+                            trim: node.trim,
+                            ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                        },
+                    );
                 });
             });
         });
@@ -445,17 +859,19 @@ impl<'a> Painter<'a> {
         self.with_transform(&node.transform.matrix, || {
             let shape = build_shape(&IntrinsicSizeNode::Line(node.clone()));
 
-            self.with_opacity(node.opacity, || {
-                self.with_blendmode(node.blend_mode, || {
-                    let paint = cvt::sk_paint(&node.stroke, node.opacity, (node.size.width, 0.0));
-                    let stroke_path = stroke_geometry(
-                        &shape.to_path(),
-                        node.stroke_width,
-                        node.get_stroke_align(),
-                        node.stroke_dash_array.as_ref(),
-                    );
-                    self.canvas.draw_path(&stroke_path, &paint);
-                });
+            self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                let paint = cvt::sk_paint(&node.stroke, 1.0, (node.size.width, 0.0));
+                let stroke_path = stroke_geometry(
+                    &shape.to_path(),
+                    &StrokeOptions {
+                        cap: node.stroke_cap,
+                        dash_array: node.stroke_dash_array.as_deref(),
+                        dash_offset: node.stroke_dash_offset,
+                        trim: node.trim,
+                        ..StrokeOptions::new(node.stroke_width, node.get_stroke_align())
+                    },
+                );
+                self.canvas.draw_path(&stroke_path, &paint);
             });
         });
     }
@@ -463,20 +879,23 @@ impl<'a> Painter<'a> {
     /// Draw a PathNode (SVG path data)
     fn draw_path_node(&self, node: &PathNode) {
         self.with_transform(&node.transform.matrix, || {
-            let path = self.cached_path(&node.base.id, &node.data);
-            let shape = PainterShape::from_path((*path).clone());
-            self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                self.with_opacity(node.opacity, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        self.draw_fill(&shape, &node.fill);
-                        self.draw_stroke(
-                            &shape,
-                            &node.stroke,
-                            node.stroke_width,
-                            node.stroke_align,
-                            node.stroke_dash_array.as_ref(),
-                        );
-                    });
+            let mut path = (*self.cached_path(&node.base.id, &node.data)).clone();
+            path.set_fill_type(node.fill_rule.into());
+            let shape = PainterShape::from_path(path);
+            self.draw_shape_with_effects(&node.effects, &shape, || {
+                self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                    self.draw_fill(&shape, &node.fill);
+                    self.draw_stroke(
+                        &shape,
+                        &node.stroke,
+                        &StrokeOptions {
+                            cap: node.stroke_cap,
+                            dash_array: node.stroke_dash_array.as_deref(),
+                            dash_offset: node.stroke_dash_offset,
+                            trim: node.trim,
+                            ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                        },
+                    );
                 });
             });
         });
@@ -487,18 +906,18 @@ impl<'a> Painter<'a> {
         self.with_transform(&node.transform.matrix, || {
             let path = node.to_path();
             let shape = PainterShape::from_path(path.clone());
-            self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                self.with_opacity(node.opacity, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        self.draw_fill(&shape, &node.fill);
-                        self.draw_stroke(
-                            &shape,
-                            &node.stroke,
-                            node.stroke_width,
-                            node.stroke_align,
-                            node.stroke_dash_array.as_ref(),
-                        );
-                    });
+            self.draw_shape_with_effects(&node.effects, &shape, || {
+                self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                    self.draw_fill(&shape, &node.fill);
+                    self.draw_stroke(
+                        &shape,
+                        &node.stroke,
+                        &StrokeOptions {
+                            dash_array: node.stroke_dash_array.as_deref(),
+                            dash_offset: node.stroke_dash_offset,
+                            ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                        },
+                    );
                 });
             });
         });
@@ -516,6 +935,7 @@ impl<'a> Painter<'a> {
         self.draw_polygon_node(&polygon);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn draw_text_span(
         &self,
         id: &NodeId,
@@ -524,6 +944,8 @@ impl<'a> Painter<'a> {
         fill: &Paint,
         text_align: &TextAlign,
         text_align_vertical: &TextAlignVertical,
+        max_lines: Option<usize>,
+        overflow: TextOverflow,
         text_style: &TextStyle,
     ) {
         let paragraph = self.cached_paragraph(
@@ -533,26 +955,71 @@ impl<'a> Painter<'a> {
             fill,
             text_align,
             text_align_vertical,
+            max_lines,
+            overflow,
             text_style,
         );
+        self.paint_paragraph(&paragraph, *text_align_vertical, size.height);
+    }
+
+    /// Paints `paragraph` offset by `valign` within a box `height` tall.
+    ///
+    /// The canvas is translated rather than the paragraph, so that fill shaders fitted to
+    /// the text block move along with the glyphs.
+    fn paint_paragraph(
+        &self,
+        paragraph: &textlayout::Paragraph,
+        valign: TextAlignVertical,
+        height: f32,
+    ) {
+        let y = valign.offset(height, paragraph.height());
+        self.canvas.save();
+        self.canvas.translate((0.0, y));
         paragraph.paint(self.canvas, Point::new(0.0, 0.0));
+        self.canvas.restore();
+    }
+
+    /// Draw a RichTextNode (multi-style text block)
+    fn draw_rich_text_node(&self, node: &RichTextNode) {
+        self.with_transform(&node.transform.matrix, || {
+            self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                let paragraph = self.cached_rich_paragraph(
+                    &node.base.id,
+                    &node.spans,
+                    &node.size,
+                    &node.fill,
+                    &node.text_align,
+                    &node.text_align_vertical,
+                );
+                self.paint_paragraph(&paragraph, node.text_align_vertical, node.size.height);
+            });
+        });
     }
 
     /// Draw a TextSpanNode (simple text block)
+    ///
+    /// The stroke follows the glyph outlines and is drawn over the fill, like shape
+    /// strokes. Outside strokes exclude the glyphs, so the fill is covered only once.
     fn draw_text_span_node(&self, node: &TextSpanNode) {
         self.with_transform(&node.transform.matrix, || {
-            self.with_opacity(node.opacity, || {
-                self.with_blendmode(node.blend_mode, || {
-                    self.draw_text_span(
-                        &node.base.id,
-                        &node.text,
-                        &node.size,
-                        &node.fill,
-                        &node.text_align,
-                        &node.text_align_vertical,
-                        &node.text_style,
-                    );
-                });
+            self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                self.draw_text_span(
+                    &node.base.id,
+                    &node.text,
+                    &node.size,
+                    &node.fill,
+                    &node.text_align,
+                    &node.text_align_vertical,
+                    node.max_lines,
+                    node.overflow,
+                    &node.text_style,
+                );
+                if let Some(stroke) = &node.stroke {
+                    if let Some(path) = text_span_stroke_path(node, &self.fonts.borrow()) {
+                        let shape = build_shape(&IntrinsicSizeNode::TextSpan(node.clone()));
+                        self.draw_stroke_path(&shape, stroke, &path);
+                    }
+                }
             });
         });
 
@@ -598,29 +1065,29 @@ impl<'a> Painter<'a> {
         cache: &GeometryCache,
     ) {
         self.with_transform(&node.transform.matrix, || {
-            self.with_opacity(node.opacity, || {
+            self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
                 let shape = build_shape(&IntrinsicSizeNode::Container(node.clone()));
 
                 // Draw effects first (if any) - these won't be clipped
-                self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                    self.with_blendmode(node.blend_mode, || {
-                        self.draw_fill(&shape, &node.fill);
-                        if let Some(stroke) = &node.stroke {
-                            self.draw_stroke(
-                                &shape,
-                                stroke,
-                                node.stroke_width,
-                                node.stroke_align,
-                                node.stroke_dash_array.as_ref(),
-                            );
-                        }
-                    });
+                self.draw_shape_with_effects(&node.effects, &shape, || {
+                    self.draw_fill(&shape, &node.fill);
+                    if let Some(stroke) = &node.stroke {
+                        self.draw_stroke(
+                            &shape,
+                            stroke,
+                            &StrokeOptions {
+                                dash_array: node.stroke_dash_array.as_deref(),
+                                dash_offset: node.stroke_dash_offset,
+                                ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                            },
+                        );
+                    }
                 });
 
                 // Draw children with clipping if enabled
                 if node.clip {
-                    self.with_clip(&shape, || {
-                        for child_id in &node.children {
+                    self.with_clip(&build_container_clip_shape(node), || {
+                        for child_id in repository.in_render_order(&node.children) {
                             if let Some(child) = repository.get(child_id) {
                                 self.draw_node_recursively(child, repository, cache);
                             }
@@ -628,7 +1095,7 @@ impl<'a> Painter<'a> {
                     });
                 } else {
                     // Draw children without clipping
-                    for child_id in &node.children {
+                    for child_id in repository.in_render_order(&node.children) {
                         if let Some(child) = repository.get(child_id) {
                             self.draw_node_recursively(child, repository, cache);
                         }
@@ -654,12 +1121,21 @@ impl<'a> Painter<'a> {
 
             self.with_opacity(node.opacity, || {
                 self.draw_fill(&shape, &fill);
-                self.draw_stroke(&shape, &stroke, 1.0, StrokeAlign::Inside, None);
+                self.draw_stroke(
+                    &shape,
+                    &stroke,
+                    &StrokeOptions::new(1.0, StrokeAlign::Inside),
+                );
             });
         });
     }
 
-    /// Draw a GroupNode: no shape of its own, only children, but apply transform + opacity
+    /// Draw a GroupNode: no shape of its own, only children, but apply transform, opacity
+    /// and blend mode to the children as a unit
+    ///
+    /// Isolated groups, see [`group_isolates`], are drawn into their own layer, so
+    /// blending children only see their siblings. The children of other groups are drawn
+    /// directly and blend with the backdrop outside of the group.
     fn draw_group_node_recursively(
         &self,
         node: &GroupNode,
@@ -667,13 +1143,24 @@ impl<'a> Painter<'a> {
         cache: &GeometryCache,
     ) {
         self.with_transform(&node.transform.matrix, || {
-            self.with_opacity(node.opacity, || {
-                for child_id in &node.children {
+            let draw_children = || {
+                for child_id in repository.in_render_order(&node.children) {
                     if let Some(child) = repository.get(child_id) {
                         self.draw_node_recursively(child, repository, cache);
                     }
                 }
-            });
+            };
+            if group_isolates(node, repository) {
+                let mut paint = SkPaint::default();
+                paint.set_alpha_f(node.opacity);
+                paint.set_blend_mode(node.blend_mode.into());
+                self.canvas
+                    .save_layer(&SaveLayerRec::default().paint(&paint));
+                draw_children();
+                self.canvas.restore();
+            } else {
+                draw_children();
+            }
         });
     }
 
@@ -684,25 +1171,25 @@ impl<'a> Painter<'a> {
         cache: &GeometryCache,
     ) {
         self.with_transform(&node.transform.matrix, || {
-            if let Some(shape) = boolean_operation_shape(node, repository, cache) {
-                self.draw_shape_with_effect(node.effect.as_ref(), &shape, || {
-                    self.with_opacity(node.opacity, || {
-                        self.with_blendmode(node.blend_mode, || {
-                            self.draw_fill(&shape, &node.fill);
-                            if let Some(stroke) = &node.stroke {
-                                self.draw_stroke(
-                                    &shape,
-                                    stroke,
-                                    node.stroke_width,
-                                    node.stroke_align,
-                                    node.stroke_dash_array.as_ref(),
-                                );
-                            }
-                        });
+            if let Some(shape) = boolean_operation_shape(node, repository, &self.fonts.borrow()) {
+                self.draw_shape_with_effects(&node.effects, &shape, || {
+                    self.with_opacity_and_blendmode(node.opacity, node.blend_mode, || {
+                        self.draw_fill(&shape, &node.fill);
+                        if let Some(stroke) = &node.stroke {
+                            self.draw_stroke(
+                                &shape,
+                                stroke,
+                                &StrokeOptions {
+                                    dash_array: node.stroke_dash_array.as_deref(),
+                                    dash_offset: node.stroke_dash_offset,
+                                    ..StrokeOptions::new(node.stroke_width, node.stroke_align)
+                                },
+                            );
+                        }
                     });
                 });
             } else {
-                for child_id in &node.children {
+                for child_id in repository.in_render_order(&node.children) {
                     if let Some(child) = repository.get(child_id) {
                         self.draw_node_recursively(child, repository, cache);
                     }
@@ -719,6 +1206,7 @@ impl<'a> Painter<'a> {
             LeafNode::Polygon(n) => self.draw_polygon_node(n),
             LeafNode::RegularPolygon(n) => self.draw_regular_polygon_node(n),
             LeafNode::TextSpan(n) => self.draw_text_span_node(n),
+            LeafNode::RichText(n) => self.draw_rich_text_node(n),
             LeafNode::Line(n) => self.draw_line_node(n),
             LeafNode::Image(n) => {
                 self.draw_image_node(n);
@@ -728,13 +1216,16 @@ impl<'a> Painter<'a> {
         }
     }
 
-    /// Dispatch to the correct node‐type draw method
+    /// Dispatch to the correct node‐type draw method. Inactive nodes are not drawn.
     pub fn draw_node_recursively(
         &self,
         node: &Node,
         repository: &NodeRepository,
         cache: &GeometryCache,
     ) {
+        if !node.active() {
+            return;
+        }
         match node {
             Node::Error(n) => self.draw_error_node(n),
             Node::Group(n) => self.draw_group_node_recursively(n, repository, cache),
@@ -744,6 +1235,7 @@ impl<'a> Painter<'a> {
             Node::Polygon(n) => self.draw_polygon_node(n),
             Node::RegularPolygon(n) => self.draw_regular_polygon_node(n),
             Node::TextSpan(n) => self.draw_text_span_node(n),
+            Node::RichText(n) => self.draw_rich_text_node(n),
             Node::Line(n) => self.draw_line_node(n),
             Node::Image(n) => {
                 self.draw_image_node(n);
@@ -762,71 +1254,227 @@ impl<'a> Painter<'a> {
             PainterPictureLayer::Shape(shape_layer) => {
                 self.with_transform(&shape_layer.base.transform.matrix, || {
                     let shape = &shape_layer.base.shape;
-                    let effect = shape_layer.base.effects.first();
+                    let effects = &shape_layer.base.effects;
                     let clip_path = &shape_layer.base.clip_path;
                     let draw_content = || {
-                        self.with_opacity(shape_layer.base.opacity, || {
-                            for fill in &shape_layer.base.fills {
-                                self.draw_fill(shape, fill);
-                            }
-                            for stroke in &shape_layer.base.strokes {
-                                if let Some(path) = &shape_layer.base.stroke_path {
-                                    self.draw_stroke_path(shape, stroke, path);
+                        self.with_opacity_and_blendmode(
+                            shape_layer.base.opacity,
+                            shape_layer.base.blend_mode,
+                            || {
+                                for fill in &shape_layer.base.fills {
+                                    self.draw_fill_with_clip(shape, fill, shape_layer.clip_fills);
                                 }
-                            }
-                        });
+                                for stroke in &shape_layer.base.strokes {
+                                    if let Some(path) = &shape_layer.base.stroke_path {
+                                        self.draw_stroke_path(shape, stroke, path);
+                                    }
+                                }
+                            },
+                        );
                     };
                     if let Some(clip) = clip_path {
                         self.canvas.save();
                         self.canvas.clip_path(clip, None, true);
-                        self.draw_shape_with_effect(effect, shape, draw_content);
+                        self.draw_shape_with_effects(effects, shape, draw_content);
                         self.canvas.restore();
                     } else {
-                        self.draw_shape_with_effect(effect, shape, draw_content);
+                        self.draw_shape_with_effects(effects, shape, draw_content);
                     }
                 });
             }
             PainterPictureLayer::Text(text_layer) => {
                 self.with_transform(&text_layer.base.transform.matrix, || {
                     let shape = &text_layer.base.shape;
-                    let effect = text_layer.base.effects.first();
+                    let effects = &text_layer.base.effects;
                     let clip_path = &text_layer.base.clip_path;
                     let draw_content = || {
-                        self.with_opacity(text_layer.base.opacity, || {
-                            self.draw_text_span(
-                                &text_layer.base.id,
-                                &text_layer.text,
-                                &Size {
-                                    width: shape.rect.width(),
-                                    height: shape.rect.height(),
-                                },
-                                match text_layer.base.fills.first() {
+                        self.with_opacity_and_blendmode(
+                            text_layer.base.opacity,
+                            text_layer.base.blend_mode,
+                            || {
+                                if let Some(fill) = text_layer.base.fills.first() {
+                                    self.draw_text_span(
+                                        &text_layer.base.id,
+                                        &text_layer.text,
+                                        &Size {
+                                            width: shape.rect.width(),
+                                            height: shape.rect.height(),
+                                        },
+                                        fill,
+                                        &text_layer.text_align,
+                                        &text_layer.text_align_vertical,
+                                        text_layer.max_lines,
+                                        text_layer.overflow,
+                                        &text_layer.text_style,
+                                    );
+                                }
+                                for stroke in &text_layer.base.strokes {
+                                    if let Some(path) = &text_layer.base.stroke_path {
+                                        self.draw_stroke_path(shape, stroke, path);
+                                    }
+                                }
+                            },
+                        );
+                    };
+                    if let Some(clip) = clip_path {
+                        self.canvas.save();
+                        self.canvas.clip_path(clip, None, true);
+                        self.draw_shape_with_effects(effects, shape, draw_content);
+                        self.canvas.restore();
+                    } else {
+                        self.draw_shape_with_effects(effects, shape, draw_content);
+                    }
+                });
+            }
+            PainterPictureLayer::RichText(text_layer) => {
+                self.with_transform(&text_layer.base.transform.matrix, || {
+                    let shape = &text_layer.base.shape;
+                    let effects = &text_layer.base.effects;
+                    let clip_path = &text_layer.base.clip_path;
+                    let draw_content = || {
+                        self.with_opacity_and_blendmode(
+                            text_layer.base.opacity,
+                            text_layer.base.blend_mode,
+                            || {
+                                let fill = match text_layer.base.fills.first() {
                                     Some(f) => f,
                                     None => return,
-                                },
-                                &text_layer.text_align,
-                                &text_layer.text_align_vertical,
-                                &text_layer.text_style,
-                            );
-                        });
+                                };
+                                let paragraph = self.cached_rich_paragraph(
+                                    &text_layer.base.id,
+                                    &text_layer.spans,
+                                    &Size {
+                                        width: shape.rect.width(),
+                                        height: shape.rect.height(),
+                                    },
+                                    fill,
+                                    &text_layer.text_align,
+                                    &text_layer.text_align_vertical,
+                                );
+                                self.paint_paragraph(
+                                    &paragraph,
+                                    text_layer.text_align_vertical,
+                                    shape.rect.height(),
+                                );
+                            },
+                        );
                     };
                     if let Some(clip) = clip_path {
                         self.canvas.save();
                         self.canvas.clip_path(clip, None, true);
-                        self.draw_shape_with_effect(effect, shape, draw_content);
+                        self.draw_shape_with_effects(effects, shape, draw_content);
                         self.canvas.restore();
                     } else {
-                        self.draw_shape_with_effect(effect, shape, draw_content);
+                        self.draw_shape_with_effects(effects, shape, draw_content);
                     }
                 });
             }
         }
     }
 
+    /// Draw a [`DrawCommandBatch`] of a [`LayerList`], building the shared paints once.
+    pub fn draw_layer_batch(&self, list: &LayerList, batch: &DrawCommandBatch) {
+        let layers = &list.layers[batch.range.clone()];
+        let [PainterPictureLayer::Shape(first), _, ..] = layers else {
+            for layer in layers {
+                self.draw_layer(layer);
+            }
+            return;
+        };
+
+        // batched layers only carry solid paints, which do not depend on the shape size
+        let fills: Vec<SkPaint> = first
+            .base
+            .fills
+            .iter()
+            .map(|fill| cvt::sk_paint(fill, 1.0, (0.0, 0.0)))
+            .collect();
+        let strokes: Vec<SkPaint> = first
+            .base
+            .strokes
+            .iter()
+            .map(|stroke| cvt::sk_paint(stroke, 1.0, (0.0, 0.0)))
+            .collect();
+
+        for layer in layers {
+            let PainterPictureLayer::Shape(shape_layer) = layer else {
+                continue;
+            };
+            self.with_transform(&shape_layer.base.transform.matrix, || {
+                self.with_opacity_and_blendmode(
+                    shape_layer.base.opacity,
+                    shape_layer.base.blend_mode,
+                    || {
+                        let path = shape_layer.base.shape.to_path();
+                        for paint in &fills {
+                            self.canvas.draw_path(&path, paint);
+                        }
+                        if let Some(stroke_path) = &shape_layer.base.stroke_path {
+                            for paint in &strokes {
+                                self.canvas.draw_path(stroke_path, paint);
+                            }
+                        }
+                    },
+                );
+            });
+        }
+    }
+
     /// Draw all layers in a [`LayerList`].
     pub fn draw_layer_list(&self, list: &LayerList) {
-        for layer in &list.layers {
-            self.draw_layer(layer);
+        let mut open = vec![];
+        if list.batches.is_empty() {
+            for layer in &list.layers {
+                self.sync_isolation(&mut open, &layer.base().isolation);
+                self.draw_layer(layer);
+            }
+        } else {
+            for batch in &list.batches {
+                // batched layers share the same isolation
+                let first = &list.layers[batch.range.start];
+                self.sync_isolation(&mut open, &first.base().isolation);
+                self.draw_layer_batch(list, batch);
+            }
+        }
+        self.sync_isolation(&mut open, &[]);
+    }
+
+    /// Draw the layers of `scene` that are visible in `viewport`, given in scene space.
+    ///
+    /// Nodes outside of the viewport are culled before their layers are built, see
+    /// [`LayerList::from_scene_in_viewport`] and [`Self::draw_layer_list`].
+    pub fn draw_scene_in_viewport(
+        &self,
+        scene: &Scene,
+        cache: &GeometryCache,
+        viewport: Rectangle,
+    ) {
+        let list = LayerList::from_scene_in_viewport(scene, cache, &self.fonts.borrow(), viewport);
+        self.draw_layer_list(&list);
+    }
+
+    /// Opens and closes isolation layers so that `isolation` becomes the `open` stack.
+    ///
+    /// Layers are drawn one by one from a flat list, so the isolation of consecutive
+    /// layers that share an isolated ancestor is kept open between them. Call with an
+    /// empty `isolation` once done to close the remaining layers.
+    pub fn sync_isolation(&self, open: &mut Vec<LayerIsolation>, isolation: &[LayerIsolation]) {
+        let shared = open
+            .iter()
+            .zip(isolation)
+            .take_while(|(a, b)| a.id == b.id)
+            .count();
+        while open.len() > shared {
+            self.canvas.restore();
+            open.pop();
+        }
+        for entry in &isolation[shared..] {
+            let mut paint = SkPaint::default();
+            paint.set_alpha_f(entry.opacity);
+            paint.set_blend_mode(entry.blend_mode.into());
+            self.canvas
+                .save_layer(&SaveLayerRec::default().paint(&paint));
+            open.push(entry.clone());
         }
     }
 }
@@ -834,16 +1482,20 @@ impl<'a> Painter<'a> {
 pub(crate) fn make_textstyle(text_style: &TextStyle) -> skia_safe::textlayout::TextStyle {
     let mut ts = skia_safe::textlayout::TextStyle::new();
     ts.set_font_size(text_style.font_size);
-    if let Some(letter_spacing) = text_style.letter_spacing {
+    let letter_spacing = text_style.letter_spacing.to_px(text_style.font_size);
+    if letter_spacing != 0.0 {
         ts.set_letter_spacing(letter_spacing);
     }
-    if let Some(line_height) = text_style.line_height {
-        ts.set_height(line_height);
+    // skia only applies the height multiplier with the override set, otherwise the
+    // font metrics are used
+    if let Some(height) = text_style.line_height.multiplier(text_style.font_size) {
+        ts.set_height(height);
+        ts.set_height_override(true);
     }
     let mut decor = skia_safe::textlayout::Decoration::default();
     decor.ty = text_style.text_decoration.into();
     ts.set_decoration(&decor);
-    ts.set_font_families(&[&text_style.font_family]);
+    ts.set_font_families(&text_style.font_families);
     let font_style = skia_safe::FontStyle::new(
         skia_safe::font_style::Weight::from(text_style.font_weight.value() as i32),
         skia_safe::font_style::Width::NORMAL,
@@ -866,6 +1518,38 @@ mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
 
+    #[test]
+    fn registers_the_font_fallback_chain_on_the_paragraph_builder() {
+        let mut fonts = FontRepository::new();
+        fonts.add(
+            include_bytes!("../../fonts/Geist/Geist-VariableFont_wght.ttf"),
+            "Geist",
+        );
+        let mut style = NodeFactory::new().create_text_span_node().text_style;
+        style.font_families = vec!["Geist".into(), "Noto Color Emoji".into()];
+
+        let mut builder = skia_safe::textlayout::ParagraphBuilder::new(
+            &skia_safe::textlayout::ParagraphStyle::new(),
+            &fonts.font_collection(),
+        );
+        builder.push_style(&make_textstyle(&style));
+        builder.add_text("Hello 👋");
+
+        let families: Vec<String> = builder
+            .peek_style()
+            .font_families()
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(families, vec!["Geist", "Noto Color Emoji"]);
+
+        // the emoji is not in Geist, both runs still lay out on a single line
+        let mut paragraph = builder.build();
+        paragraph.layout(1000.0);
+        assert_eq!(paragraph.line_number(), 1);
+        assert!(paragraph.longest_line() > 0.0);
+    }
+
     #[test]
     fn caches_reuse_paragraph_and_path() {
         let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
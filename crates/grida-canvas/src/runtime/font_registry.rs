@@ -0,0 +1,122 @@
+use skia_safe::{
+    textlayout::{FontCollection, TypefaceFontProvider},
+    FontMgr, FontStyle, Typeface,
+};
+use std::collections::HashMap;
+
+/// Custom fonts registered under family names, consulted by text layout before the
+/// system fonts.
+///
+/// A family can hold several faces, e.g. the regular, bold and italic files of a font,
+/// layout picks the face closest to the requested weight, width and slant. The family
+/// name used for lookup is the registered one, not the name stored in the font file.
+#[derive(Clone)]
+pub struct FontRegistry {
+    provider: TypefaceFontProvider,
+    families: HashMap<String, Vec<Typeface>>,
+    system_fonts: bool,
+}
+
+impl Default for FontRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self {
+            provider: TypefaceFontProvider::new(),
+            families: HashMap::new(),
+            system_fonts: true,
+        }
+    }
+
+    /// Loads a `.ttf` / `.otf` font from `bytes` and registers it under `family`.
+    ///
+    /// Returns the style of the loaded face, or `None` if `bytes` is not a font.
+    pub fn register(&mut self, family: &str, bytes: &[u8]) -> Option<FontStyle> {
+        let typeface = FontMgr::new().new_from_data(bytes, None)?;
+        let style = typeface.font_style();
+        self.register_typeface(family, typeface);
+        Some(style)
+    }
+
+    /// Registers an already loaded `typeface` under `family`.
+    pub fn register_typeface(&mut self, family: &str, typeface: Typeface) {
+        self.provider
+            .register_typeface(typeface.clone(), Some(family));
+        self.families
+            .entry(family.to_string())
+            .or_default()
+            .push(typeface);
+    }
+
+    /// Removes all faces of `family`, returns whether the family was registered.
+    pub fn unregister(&mut self, family: &str) -> bool {
+        if self.families.remove(family).is_none() {
+            return false;
+        }
+        // faces cannot be removed from a provider, register the remaining ones again
+        self.provider = TypefaceFontProvider::new();
+        for (family, typefaces) in &self.families {
+            for typeface in typefaces {
+                self.provider
+                    .register_typeface(typeface.clone(), Some(family.as_str()));
+            }
+        }
+        true
+    }
+
+    pub fn contains(&self, family: &str) -> bool {
+        self.families.contains_key(family)
+    }
+
+    /// The registered family names, unordered.
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        self.families.keys().map(|family| family.as_str())
+    }
+
+    /// The styles of the faces registered under `family`, in registration order.
+    pub fn styles(&self, family: &str) -> Vec<FontStyle> {
+        self.families
+            .get(family)
+            .map(|typefaces| typefaces.iter().map(|tf| tf.font_style()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The registered face of `family` closest to `style`.
+    pub fn match_typeface(&self, family: &str, style: FontStyle) -> Option<Typeface> {
+        if !self.contains(family) {
+            return None;
+        }
+        FontMgr::from(self.provider.clone()).match_family_style(family, style)
+    }
+
+    /// Whether layout finds `family` in `style`, as a registered family or, when enabled,
+    /// a system font.
+    pub fn resolves(&self, family: &str, style: FontStyle) -> bool {
+        self.contains(family)
+            || (self.system_fonts && FontMgr::new().match_family_style(family, style).is_some())
+    }
+
+    /// Whether layout falls back to the system fonts for families and glyphs that are
+    /// not registered. Enabled by default, disable it for reproducible output.
+    pub fn system_fonts(&self) -> bool {
+        self.system_fonts
+    }
+
+    pub fn set_system_fonts(&mut self, enabled: bool) {
+        self.system_fonts = enabled;
+    }
+
+    /// A font collection for paragraph layout, resolving the registered families first.
+    pub fn font_collection(&self) -> FontCollection {
+        let mut collection = FontCollection::new();
+        collection.set_asset_font_manager(Some(self.provider.clone().into()));
+        if self.system_fonts {
+            collection.set_default_font_manager(FontMgr::new(), None);
+        }
+        collection
+    }
+}
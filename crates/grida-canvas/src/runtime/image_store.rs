@@ -0,0 +1,103 @@
+use base64::Engine;
+use skia_safe::{Data, Image};
+use std::collections::HashMap;
+
+/// Resolves image refs, as used by [`ImagePaint::_ref`](crate::node::schema::ImagePaint)
+/// and [`ImageNode::_ref`](crate::node::schema::ImageNode), to decoded images.
+///
+/// A ref is looked up in the sources of the store, usually the `bitmaps` of a document,
+/// and falls back to the ref itself. Sources are either `data:` urls or paths to local
+/// files. Remote urls are left to the [`ImageLoader`](crate::resource::ImageLoader).
+///
+/// Decoded images are cached, as are refs that failed to resolve. Remote refs are neither
+/// cached nor reported as missing, they may still be loading.
+#[derive(Debug, Clone, Default)]
+pub struct ImageStore {
+    sources: HashMap<String, String>,
+    decoded: HashMap<String, Option<Image>>,
+}
+
+impl ImageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store from the `bitmaps` of a document.
+    ///
+    /// Each entry is either the source itself or an object with a `src`, `url` or `data`
+    /// string field. Other entries are ignored.
+    pub fn from_bitmaps(bitmaps: &HashMap<String, serde_json::Value>) -> Self {
+        let mut store = Self::new();
+        for (id, value) in bitmaps {
+            let src = value.as_str().or_else(|| {
+                ["src", "url", "data"]
+                    .iter()
+                    .find_map(|key| value.get(key).and_then(|v| v.as_str()))
+            });
+            if let Some(src) = src {
+                store.insert_source(id.clone(), src.to_string());
+            }
+        }
+        store
+    }
+
+    /// Registers `src` as the source of the image `id`, dropping any cached decode.
+    pub fn insert_source(&mut self, id: String, src: String) {
+        self.decoded.remove(&id);
+        self.sources.insert(id, src);
+    }
+
+    /// Returns the decoded image for `id`, decoding and caching it on first use.
+    pub fn resolve(&mut self, id: &str) -> Option<Image> {
+        if let Some(image) = self.decoded.get(id) {
+            return image.clone();
+        }
+        let src = self.sources.get(id).map_or(id, String::as_str);
+        if is_remote(src) {
+            return None;
+        }
+        let image = read_source(src).and_then(|bytes| Image::from_encoded(Data::new_copy(&bytes)));
+        self.decoded.insert(id.to_string(), image.clone());
+        image
+    }
+
+    /// Returns `true` if `id` was resolved before and could not be decoded.
+    pub fn is_missing(&self, id: &str) -> bool {
+        matches!(self.decoded.get(id), Some(None))
+    }
+}
+
+/// Whether `src` is fetched over the network by the
+/// [`ImageLoader`](crate::resource::ImageLoader).
+fn is_remote(src: &str) -> bool {
+    src.starts_with("http://") || src.starts_with("https://")
+}
+
+/// Reads the encoded bytes of `src`, a `data:` url or a local file path.
+fn read_source(src: &str) -> Option<Vec<u8>> {
+    if src.starts_with("data:") {
+        return decode_data_url(src);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::read(src).ok()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+/// Decodes the payload of a `data:` url, e.g. `data:image/png;base64,iVBORw0...`.
+///
+/// Returns `None` if `url` is not a data url or the base64 payload is invalid.
+pub fn decode_data_url(url: &str) -> Option<Vec<u8>> {
+    let (header, payload) = url.strip_prefix("data:")?.split_once(',')?;
+    if header.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload.trim())
+            .ok()
+    } else {
+        Some(payload.as_bytes().to_vec())
+    }
+}
@@ -1,4 +1,6 @@
 pub mod camera;
 pub mod counter;
+pub mod font_registry;
+pub mod image_store;
 pub mod repository;
 pub mod scene;
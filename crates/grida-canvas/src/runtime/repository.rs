@@ -1,8 +1,7 @@
-use skia_safe::{
-    textlayout::{FontCollection, TypefaceFontProvider},
-    FontMgr, Image,
-};
+use skia_safe::{textlayout::FontCollection, Image};
 
+use super::font_registry::FontRegistry;
+use super::image_store::ImageStore;
 use crate::cache::mipmap::{ImageMipmaps, MipmapConfig};
 use std::collections::HashMap;
 
@@ -43,6 +42,8 @@ pub struct ImageRepository {
     /// The map of all images indexed by their source URLs
     images: HashMap<String, ImageMipmaps>,
     config: MipmapConfig,
+    /// Fallback for images that were not inserted, see [`ImageRepository::ensure`].
+    store: Option<ImageStore>,
 }
 
 impl ImageRepository {
@@ -51,6 +52,7 @@ impl ImageRepository {
         Self {
             images: HashMap::new(),
             config: MipmapConfig::default(),
+            store: None,
         }
     }
 
@@ -59,6 +61,7 @@ impl ImageRepository {
         Self {
             images: HashMap::new(),
             config,
+            store: None,
         }
     }
 
@@ -75,6 +78,42 @@ impl ImageRepository {
             .and_then(|set| set.best_for_size(width, height))
     }
 
+    /// Sets the store used to decode images on demand, see [`ImageRepository::ensure`].
+    pub fn set_store(&mut self, store: ImageStore) {
+        self.store = Some(store);
+    }
+
+    /// Makes sure the image `src` is available, decoding it through the store if it was
+    /// not inserted. Returns `false` if the image is not available (yet).
+    pub fn ensure(&mut self, src: &str) -> bool {
+        if self.images.contains_key(src) {
+            return true;
+        }
+        let Some(image) = self.store.as_mut().and_then(|store| store.resolve(src)) else {
+            return false;
+        };
+        self.insert(src.to_string(), image);
+        true
+    }
+
+    /// Returns `true` if the store failed to resolve `src`, as opposed to an image that
+    /// may still be loading.
+    pub fn is_missing(&self, src: &str) -> bool {
+        !self.images.contains_key(src)
+            && self
+                .store
+                .as_ref()
+                .is_some_and(|store| store.is_missing(src))
+    }
+
+    /// Gets the full size image for a source URL, for paints that draw the image at its
+    /// natural size
+    pub fn get_full_size(&self, src: &str) -> Option<&Image> {
+        self.images
+            .get(src)
+            .and_then(ImageMipmaps::first_level_image)
+    }
+
     /// Removes an image from the repository by its source URL
     pub fn remove(&mut self, src: &str) -> Option<ImageMipmaps> {
         self.images.remove(src)
@@ -115,8 +154,11 @@ impl ResourceRepository<ImageMipmaps> for ImageRepository {
 }
 
 /// A repository for managing fonts.
+///
+/// Fonts added to a family are registered in a [`FontRegistry`], so text layout resolves
+/// them before the system fonts. Several weights and styles can be added to one family.
 pub struct FontRepository {
-    provider: TypefaceFontProvider,
+    registry: FontRegistry,
     fonts: HashMap<String, Vec<Vec<u8>>>,
     generation: usize,
 }
@@ -124,41 +166,34 @@ pub struct FontRepository {
 impl FontRepository {
     pub fn new() -> Self {
         Self {
-            provider: TypefaceFontProvider::new(),
+            registry: FontRegistry::new(),
             fonts: HashMap::new(),
             generation: 0,
         }
     }
 
     pub fn insert(&mut self, family: String, bytes: Vec<u8>) {
-        let family_fonts = self.fonts.entry(family.clone()).or_insert_with(Vec::new);
-
-        if let Some(tf) = FontMgr::new().new_from_data(&bytes, None) {
-            self.provider.register_typeface(tf, Some(family.as_str()));
-        }
-
-        family_fonts.push(bytes);
+        self.registry.register(&family, &bytes);
+        self.fonts.entry(family).or_default().push(bytes);
         self.generation += 1;
     }
 
     pub fn add(&mut self, bytes: &[u8], family: &str) {
-        let family_fonts = self
-            .fonts
-            .entry(family.to_string())
-            .or_insert_with(Vec::new);
+        self.insert(family.to_string(), bytes.to_vec());
+    }
 
-        if let Some(tf) = FontMgr::new().new_from_data(bytes, None) {
-            self.provider.register_typeface(tf, Some(family));
-        }
+    pub fn font_collection(&self) -> FontCollection {
+        self.registry.font_collection()
+    }
 
-        family_fonts.push(bytes.to_vec());
-        self.generation += 1;
+    pub fn registry(&self) -> &FontRegistry {
+        &self.registry
     }
 
-    pub fn font_collection(&self) -> FontCollection {
-        let mut collection = FontCollection::new();
-        collection.set_asset_font_manager(Some(self.provider.clone().into()));
-        collection
+    /// See [`FontRegistry::set_system_fonts`].
+    pub fn set_system_fonts(&mut self, enabled: bool) {
+        self.registry.set_system_fonts(enabled);
+        self.generation += 1;
     }
 
     pub fn family_count(&self) -> usize {
@@ -183,10 +218,9 @@ impl ResourceRepository<Vec<Vec<u8>>> for FontRepository {
     type Iter<'a> = std::collections::hash_map::Iter<'a, String, Vec<Vec<u8>>>;
 
     fn insert(&mut self, id: Self::Id, item: Vec<Vec<u8>>) {
+        self.registry.unregister(&id);
         for font_data in &item {
-            if let Some(tf) = FontMgr::new().new_from_data(font_data, None) {
-                self.provider.register_typeface(tf, Some(id.as_str()));
-            }
+            self.registry.register(&id, font_data);
         }
         self.fonts.insert(id, item);
         self.generation += 1;
@@ -203,6 +237,7 @@ impl ResourceRepository<Vec<Vec<u8>>> for FontRepository {
     fn remove(&mut self, id: &Self::Id) -> Option<Vec<Vec<u8>>> {
         let res = self.fonts.remove(id);
         if res.is_some() {
+            self.registry.unregister(id);
             self.generation += 1;
         }
         res
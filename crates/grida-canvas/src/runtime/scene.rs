@@ -1,7 +1,7 @@
 use crate::cache::tile::{ImageTileCacheResolutionStrategy, RegionTileInfo};
 use crate::node::schema::*;
 use crate::painter::layer::Layer;
-use crate::painter::{cvt, Painter};
+use crate::painter::{cvt, BlurBackend, Painter, RenderOptions};
 use crate::runtime::counter::FrameCounter;
 use crate::{
     cache,
@@ -43,6 +43,8 @@ pub struct DrawResult {
     pub cache_geometry_size: usize,
     pub tiles_total: usize,
     pub tiles_used: usize,
+    /// the blur implementation selected for the backend
+    pub blur_backend: BlurBackend,
 }
 
 pub enum FrameFlushResult {
@@ -74,6 +76,14 @@ impl Backend {
         }
     }
 
+    /// The blur implementation to use when painting for this backend.
+    pub fn blur_backend(&self) -> BlurBackend {
+        match self {
+            Backend::GL(_) => BlurBackend::Gpu,
+            Backend::Raster(_) => BlurBackend::Raster,
+        }
+    }
+
     pub fn new_from_raster(width: i32, height: i32) -> Self {
         let surface = Self::init_raster_surface(width, height);
         Self::Raster(surface)
@@ -96,6 +106,8 @@ pub struct Renderer {
     pub camera: Camera2D,
     pub images: Rc<RefCell<ImageRepository>>,
     pub fonts: Rc<RefCell<FontRepository>>,
+    /// Options passed to every painter created by the renderer.
+    pub options: RenderOptions,
     /// when called, the host will request a redraw in os-specific way
     request_redraw: Option<RequestRedrawCallback>,
     /// frame counter for managing render queue
@@ -120,6 +132,7 @@ impl Renderer {
             camera,
             images: image_repository,
             fonts: font_repository,
+            options: RenderOptions::default(),
             scene_cache: cache::scene::SceneCache::new(),
             request_redraw,
             fc: FrameCounter::new(),
@@ -180,7 +193,14 @@ impl Renderer {
         let width = surface.width() as f32;
         let height = surface.height() as f32;
         let mut canvas = surface.canvas();
-        let draw = self.draw(&mut canvas, &frame, scene.background_color, width, height);
+        let draw = self.draw(
+            &mut canvas,
+            &frame,
+            scene.background_color,
+            scene.background_image.as_ref(),
+            width,
+            height,
+        );
 
         if frame.stable {
             // if !self.camera.has_zoom_changed() {}
@@ -229,11 +249,13 @@ impl Renderer {
     }
 
     /// Load a scene into the renderer. Caching will be performed lazily during
-    /// rendering based on the configured caching strategy.
-    pub fn load_scene(&mut self, scene: Scene) {
+    /// rendering based on the configured caching strategy. Auto sized text nodes are
+    /// resized to their content with the fonts loaded so far.
+    pub fn load_scene(&mut self, mut scene: Scene) {
+        scene.fit_text_to_content(&self.fonts.borrow());
         self.scene_cache = cache::scene::SceneCache::new();
         self.scene_cache.update_geometry(&scene);
-        self.scene_cache.update_layers(&scene);
+        self.scene_cache.update_layers(&scene, &self.fonts.borrow());
         self.scene = Some(scene);
         self.queue_stable();
     }
@@ -284,7 +306,10 @@ impl Renderer {
             bounds.y + bounds.height,
         );
         let canvas = recorder.begin_recording(sk_bounds, None);
-        let painter = Painter::new(canvas, self.fonts.clone(), self.images.clone());
+        // pictures are replayed on the backend surface, so blur for the backend
+        let painter = Painter::new(canvas, self.fonts.clone(), self.images.clone())
+            .with_blur_backend(self.backend.blur_backend())
+            .with_options(self.options);
         draw(&painter);
         recorder.finish_recording_as_picture(None)
     }
@@ -374,6 +399,7 @@ impl Renderer {
         canvas: &Canvas,
         plan: &FramePlan,
         background_color: Option<Color>,
+        background_image: Option<&ImagePaint>,
         width: f32,
         height: f32,
     ) -> DrawResult {
@@ -384,14 +410,20 @@ impl Renderer {
 
         // Paint background color first if present
         if let Some(bg_color) = background_color {
-            let Color(r, g, b, a) = bg_color;
-            let color = skia_safe::Color::from_argb(a, r, g, b);
+            let color: skia_safe::Color = bg_color.into();
             let mut paint = SkPaint::default();
             paint.set_color(color);
             // Paint the entire canvas with the background color
             canvas.draw_rect(Rect::new(0.0, 0.0, width, height), &paint);
         }
 
+        // Paint the background image over it, fit to the viewport
+        if let Some(image) = background_image {
+            Painter::new(canvas, self.fonts.clone(), self.images.clone())
+                .with_options(self.options)
+                .draw_background_image(image, (width, height));
+        }
+
         canvas.save();
 
         // Apply camera transform
@@ -429,24 +461,26 @@ impl Renderer {
         }
 
         // draw picture regions
+        let isolation_painter = Painter::new(canvas, self.fonts.clone(), self.images.clone());
         for (region, indices) in &plan.regions {
+            // clip to region
+            canvas.save();
+            canvas.clip_rect(
+                Rect::from_xywh(region.x, region.y, region.width, region.height),
+                None,
+                false,
+            );
+            let mut isolation = vec![];
             for idx in indices {
                 if let Some(layer) = self.scene_cache.layers.layers.get(*idx) {
                     let layer = layer.clone();
+                    isolation_painter.sync_isolation(&mut isolation, &layer.base().isolation);
                     let picture = self.with_recording_cached(&layer.id(), |painter| {
                         painter.draw_layer(&layer);
                     });
 
                     if let Some(pic) = picture {
-                        // clip to region
-                        canvas.save();
-                        canvas.clip_rect(
-                            Rect::from_xywh(region.x, region.y, region.width, region.height),
-                            None,
-                            false,
-                        );
                         canvas.draw_picture(pic, None, None);
-                        canvas.restore();
                         cache_picture_used += 1;
                     }
                 } else {
@@ -454,12 +488,21 @@ impl Renderer {
                     println!("layer not found: {}", idx);
                 }
             }
+            isolation_painter.sync_isolation(&mut isolation, &[]);
+            canvas.restore();
         }
 
         let __painter_duration = __before_paint.elapsed();
 
         canvas.restore();
 
+        if let Some(grain) = &self.options.grain {
+            Painter::new(canvas, self.fonts.clone(), self.images.clone())
+                .draw_grain(grain, (width, height));
+        }
+
+        self.draw_guides(canvas, width, height);
+
         DrawResult {
             painter_duration: __painter_duration,
             cache_picture_used,
@@ -467,6 +510,7 @@ impl Renderer {
             cache_geometry_size: self.scene_cache.geometry.len(),
             tiles_total: self.scene_cache.tile.tiles().len(),
             tiles_used: plan.tiles.len(),
+            blur_backend: self.backend.blur_backend(),
         }
         //
     }
@@ -481,6 +525,7 @@ impl Renderer {
         canvas: &Canvas,
         plan: &FramePlan,
         background_color: Option<Color>,
+        background_image: Option<&ImagePaint>,
         width: f32,
         height: f32,
     ) -> DrawResult {
@@ -490,38 +535,56 @@ impl Renderer {
 
         // Paint background color first if present
         if let Some(bg_color) = background_color {
-            let Color(r, g, b, a) = bg_color;
-            let color = skia_safe::Color::from_argb(a, r, g, b);
+            let color: skia_safe::Color = bg_color.into();
             let mut paint = SkPaint::default();
             paint.set_color(color);
             // Paint the entire canvas with the background color
             canvas.draw_rect(Rect::new(0.0, 0.0, width, height), &paint);
         }
 
+        // Paint the background image over it, fit to the viewport
+        if let Some(image) = background_image {
+            Painter::new(canvas, self.fonts.clone(), self.images.clone())
+                .with_options(self.options)
+                .draw_background_image(image, (width, height));
+        }
+
         canvas.save();
 
         // Apply camera transform
         canvas.concat(&cvt::sk_matrix(self.camera.view_matrix().matrix));
 
         // draw picture regions
-        let painter = Painter::new(canvas, self.fonts.clone(), self.images.clone());
+        let painter = Painter::new(canvas, self.fonts.clone(), self.images.clone())
+            .with_options(self.options);
+        let blur_backend = painter.blur_backend();
         for (_region, indices) in &plan.regions {
+            let mut isolation = vec![];
             for idx in indices {
                 if let Some(layer) = self.scene_cache.layers.layers.get(*idx) {
                     let layer = layer.clone();
 
+                    painter.sync_isolation(&mut isolation, &layer.base().isolation);
                     painter.draw_layer(&layer);
                 } else {
                     // report error
                     println!("layer not found: {}", idx);
                 }
             }
+            painter.sync_isolation(&mut isolation, &[]);
         }
 
         let __painter_duration = __before_paint.elapsed();
 
         canvas.restore();
 
+        if let Some(grain) = &self.options.grain {
+            Painter::new(canvas, self.fonts.clone(), self.images.clone())
+                .draw_grain(grain, (width, height));
+        }
+
+        self.draw_guides(canvas, width, height);
+
         DrawResult {
             painter_duration: __painter_duration,
             cache_picture_used: 0,
@@ -529,6 +592,7 @@ impl Renderer {
             cache_geometry_size: 0,
             tiles_total: 0,
             tiles_used: 0,
+            blur_backend,
         }
         //
     }
@@ -540,17 +604,36 @@ impl Renderer {
         let height = surface.height() as f32;
         let mut canvas = surface.canvas();
         let frame = self.frame(self.camera.rect(), 1.0, true);
-        let _ = self.draw_nocache(&mut canvas, &frame, None, width, height);
+        let _ = self.draw_nocache(&mut canvas, &frame, None, None, width, height);
 
         surface.image_snapshot()
     }
 
+    /// Draw the guides of the current scene over the frame, if enabled in the options.
+    fn draw_guides(&self, canvas: &Canvas, width: f32, height: f32) {
+        if !self.options.show_guides {
+            return;
+        }
+        if let Some(scene) = &self.scene {
+            Painter::new(canvas, self.fonts.clone(), self.images.clone()).draw_guides(
+                &scene.guides,
+                &self.camera.view_matrix(),
+                (width, height),
+                self.options.guide_color,
+            );
+        }
+    }
+
     /// Render the current scene onto the provided canvas. This is useful for
     /// exporting the scene using alternate backends such as PDF.
     pub fn render_to_canvas(&self, canvas: &Canvas, width: f32, height: f32) {
         let frame = self.frame(self.camera.rect(), 1.0, true);
         let background = self.scene.as_ref().and_then(|s| s.background_color);
-        let _ = self.draw_nocache(canvas, &frame, background, width, height);
+        let background_image = self
+            .scene
+            .as_ref()
+            .and_then(|s| s.background_image.as_ref());
+        let _ = self.draw_nocache(canvas, &frame, background, background_image, width, height);
     }
 }
 
@@ -558,7 +641,6 @@ impl Renderer {
 mod tests {
     use super::*;
     use crate::node::{factory::NodeFactory, repository::NodeRepository, schema::Size};
-    use math2::transform::AffineTransform;
 
     #[test]
     fn picture_recorded_with_layer_bounds() {
@@ -574,12 +656,9 @@ mod tests {
         repo.insert(Node::Rectangle(rect));
 
         let scene = Scene {
-            id: "scene".into(),
-            name: "test".into(),
-            transform: AffineTransform::identity(),
             children: vec![rect_id.clone()],
             nodes: repo,
-            background_color: None,
+            ..Scene::new("scene", "test")
         };
 
         let mut renderer = Renderer::new(
@@ -631,4 +710,66 @@ mod tests {
 
         renderer.free();
     }
+
+    fn blurred_scene() -> Scene {
+        let nf = NodeFactory::new();
+        let mut repo = NodeRepository::new();
+
+        let mut rect = nf.create_rectangle_node();
+        rect.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur { radius: 40.0 })];
+        let rect_id = rect.base.id.clone();
+        repo.insert(Node::Rectangle(rect));
+
+        Scene {
+            children: vec![rect_id],
+            nodes: repo,
+            ..Scene::new("scene", "test")
+        }
+    }
+
+    #[test]
+    fn raster_backend_selects_raster_blur() {
+        let mut renderer = Renderer::new(
+            Backend::new_from_raster(100, 100),
+            None,
+            Camera2D::new(Size {
+                width: 100.0,
+                height: 100.0,
+            }),
+        );
+        renderer.load_scene(blurred_scene());
+
+        let FrameFlushResult::OK(stats) = renderer.flush() else {
+            panic!("expected a flushed frame");
+        };
+        assert_eq!(stats.draw.blur_backend, BlurBackend::Raster);
+
+        renderer.free();
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn gpu_backend_selects_gpu_blur() {
+        let mut winit = crate::os::winit::winit_window(100, 100);
+        assert_eq!(
+            BlurBackend::detect(winit.state.surface_mut().canvas()),
+            BlurBackend::Gpu
+        );
+
+        let mut renderer = Renderer::new(
+            Backend::GL(winit.state.surface_mut_ptr()),
+            None,
+            Camera2D::new(Size {
+                width: 100.0,
+                height: 100.0,
+            }),
+        );
+        renderer.load_scene(blurred_scene());
+
+        let FrameFlushResult::OK(stats) = renderer.flush() else {
+            panic!("expected a flushed frame");
+        };
+        assert_eq!(stats.draw.blur_backend, BlurBackend::Gpu);
+        // the surface is owned by the window state, so the renderer is not freed here
+    }
 }
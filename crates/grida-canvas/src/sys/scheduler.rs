@@ -1,6 +1,36 @@
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// How the [`FrameScheduler`] keeps up with its target frame rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FramePacing {
+    /// Sleeps away the remainder of each frame. Frames that overrun their budget delay
+    /// every following frame, the schedule drifts under load.
+    #[default]
+    Sleep,
+    /// Accumulates the time renders overrun their budget and skips frames once a whole
+    /// frame budget is owed, see [`FrameScheduler::record_frame`].
+    Accumulate,
+}
+
+/// Ticks arriving this much before the frame budget has passed still render, absorbing
+/// the jitter of `requestAnimationFrame` timestamps.
+const TICK_TOLERANCE_MS: f64 = 1.0;
+
+/// Frame timing over the rolling window of a [`FrameScheduler`], see
+/// [`FrameScheduler::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    /// Average frames per second, `0` without samples.
+    pub avg_fps: f32,
+    /// Shortest frame duration in milliseconds.
+    pub min_frame_ms: f32,
+    /// Longest frame duration in milliseconds.
+    pub max_frame_ms: f32,
+    /// Number of the recent frames followed by a skipped frame.
+    pub dropped: u32,
+}
+
 /// A module that controls frame pacing using target and max FPS limits,
 /// while maintaining frame duration statistics for FPS estimation.
 /// In WASM, the pacing logic is a no-op and the browser controls timing.
@@ -9,7 +39,17 @@ pub struct FrameScheduler {
     target_frame_time: Duration,
     max_frame_time: Option<Duration>,
     frame_durations: VecDeque<Duration>,
+    /// Whether each recent [`FrameScheduler::record_frame`] skipped the next frame.
+    frame_skips: VecDeque<bool>,
     max_samples: usize,
+    pacing: FramePacing,
+    /// Time owed to the schedule by renders that overran the frame budget.
+    lag: Duration,
+    skipped_frames: u64,
+    /// Scheduled time of the last rendered [`FrameScheduler::tick`], in milliseconds.
+    last_tick_ms: Option<f64>,
+    /// Timestamp of the last rendered [`FrameScheduler::tick`], in milliseconds.
+    last_rendered_tick_ms: f64,
 }
 
 impl FrameScheduler {
@@ -20,7 +60,13 @@ impl FrameScheduler {
             target_frame_time: Duration::from_micros(1_000_000 / target_fps as u64),
             max_frame_time: None,
             frame_durations: VecDeque::with_capacity(60),
+            frame_skips: VecDeque::with_capacity(60),
             max_samples: 60,
+            pacing: FramePacing::default(),
+            lag: Duration::ZERO,
+            skipped_frames: 0,
+            last_tick_ms: None,
+            last_rendered_tick_ms: 0.0,
         }
     }
 
@@ -30,8 +76,73 @@ impl FrameScheduler {
         self
     }
 
+    /// Sets the [`FramePacing`] mode, [`FramePacing::Sleep`] by default.
+    pub fn with_pacing(mut self, pacing: FramePacing) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// The time budget of a single frame, the target frame time limited by the max FPS.
+    pub fn frame_budget(&self) -> Duration {
+        match self.max_frame_time {
+            Some(max_time) => self.target_frame_time.max(max_time),
+            None => self.target_frame_time,
+        }
+    }
+
+    /// Whether the next frame should be skipped to catch up with the schedule, given the
+    /// duration of the last render.
+    ///
+    /// Always `false` with [`FramePacing::Sleep`]. With [`FramePacing::Accumulate`], a
+    /// frame is skipped once the accumulated overrun, including `last_render`, reaches a
+    /// whole frame budget.
+    pub fn should_skip_frame(&self, last_render: Duration) -> bool {
+        match self.pacing {
+            FramePacing::Sleep => false,
+            FramePacing::Accumulate => {
+                let budget = self.frame_budget();
+                (self.lag + last_render).saturating_sub(budget) >= budget
+            }
+        }
+    }
+
+    /// Accounts a rendered frame that took `last_render`, returns whether the next frame
+    /// should be skipped (see [`Self::should_skip_frame`]).
+    ///
+    /// A skipped frame pays back one frame budget of the accumulated overrun. Renders
+    /// faster than the budget pay back the time they leave over.
+    pub fn record_frame(&mut self, last_render: Duration) -> bool {
+        let skip = self.should_skip_frame(last_render);
+        if self.pacing == FramePacing::Accumulate {
+            let budget = self.frame_budget();
+            self.lag = (self.lag + last_render).saturating_sub(budget);
+            if skip {
+                self.lag -= budget;
+                self.skipped_frames += 1;
+            }
+        }
+        if self.frame_skips.len() == self.max_samples {
+            self.frame_skips.pop_front();
+        }
+        self.frame_skips.push_back(skip);
+        skip
+    }
+
+    /// The accumulated overrun not yet paid back by skipped frames.
+    pub fn lag(&self) -> Duration {
+        self.lag
+    }
+
+    /// The number of frames skipped so far.
+    pub fn skipped_frames(&self) -> u64 {
+        self.skipped_frames
+    }
+
     /// Records the most recent frame duration for smoothing.
-    fn record_frame_duration(&mut self, duration: Duration) {
+    ///
+    /// Called by [`Self::sleep_to_maintain_fps`], record durations directly when frames
+    /// are paced elsewhere (e.g. by the browser).
+    pub fn record_frame_duration(&mut self, duration: Duration) {
         if self.frame_durations.len() == self.max_samples {
             self.frame_durations.pop_front();
         }
@@ -49,7 +160,68 @@ impl FrameScheduler {
         1_000_000.0 / avg.as_micros() as f32
     }
 
-    /// No-op in WASM; browser controls frame rate via rAF.
+    /// Returns the frame timing over the last N recorded frames.
+    pub fn stats(&self) -> FrameStats {
+        let dropped = self.frame_skips.iter().filter(|skip| **skip).count() as u32;
+        let (Some(min), Some(max)) = (
+            self.frame_durations.iter().min(),
+            self.frame_durations.iter().max(),
+        ) else {
+            return FrameStats {
+                dropped,
+                ..Default::default()
+            };
+        };
+        FrameStats {
+            avg_fps: self.average_fps(),
+            min_frame_ms: min.as_secs_f32() * 1000.0,
+            max_frame_ms: max.as_secs_f32() * 1000.0,
+            dropped,
+        }
+    }
+
+    /// Paces frames driven by an external clock, such as a `requestAnimationFrame`
+    /// callback in the browser, without blocking.
+    ///
+    /// `now_ms` is the callback timestamp (`performance.now()`) in milliseconds. Returns
+    /// whether a frame should be rendered: `false` while less than the frame budget has
+    /// passed since the last scheduled frame, capping the frame rate on high-refresh
+    /// displays. The schedule advances by whole budgets, so ticks that do not divide the
+    /// budget still average the capped rate, and resyncs to `now_ms` once more than one
+    /// budget behind. The time between rendered ticks is recorded for [`Self::stats`]. The
+    /// first tick always renders, timestamps going backwards render and restart the
+    /// measurement.
+    pub fn tick(&mut self, now_ms: f64) -> bool {
+        let Some(last) = self.last_tick_ms else {
+            self.last_tick_ms = Some(now_ms);
+            self.last_rendered_tick_ms = now_ms;
+            return true;
+        };
+
+        let elapsed = now_ms - last;
+        if elapsed < 0.0 {
+            self.last_tick_ms = Some(now_ms);
+            self.last_rendered_tick_ms = now_ms;
+            return true;
+        }
+
+        let budget_ms = self.frame_budget().as_secs_f64() * 1000.0;
+        if elapsed + TICK_TOLERANCE_MS < budget_ms {
+            return false;
+        }
+
+        let interval_ms = now_ms - self.last_rendered_tick_ms;
+        self.record_frame_duration(Duration::from_secs_f64(interval_ms / 1000.0));
+        self.last_rendered_tick_ms = now_ms;
+        self.last_tick_ms = Some(if elapsed > 2.0 * budget_ms {
+            now_ms
+        } else {
+            last + budget_ms
+        });
+        true
+    }
+
+    /// No-op in WASM; browser controls frame rate via rAF, see [`Self::tick`].
     #[cfg(target_arch = "wasm32")]
     pub fn sleep_to_maintain_fps(&mut self) {
         // no-op
@@ -61,12 +233,10 @@ impl FrameScheduler {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_frame_time);
 
-        let target = match self.max_frame_time {
-            Some(max_time) => self.target_frame_time.max(max_time),
-            None => self.target_frame_time,
-        };
+        let target = self.frame_budget();
 
-        if elapsed < target {
+        // behind schedule, only `FramePacing::Accumulate` builds up lag
+        if self.lag.is_zero() && elapsed < target {
             std::thread::sleep(target - elapsed);
         }
 
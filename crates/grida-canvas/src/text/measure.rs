@@ -0,0 +1,191 @@
+use crate::node::schema::{Size, TextStyle};
+use crate::painter::make_textstyle;
+use crate::runtime::repository::FontRepository;
+use crate::text::text_transform::transform_text;
+use skia_safe::textlayout;
+
+/// Layout metrics of a measured text block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    /// Width of the longest line.
+    pub width: f32,
+    /// Total height of all lines.
+    pub height: f32,
+    /// Width of the text laid out on a single line.
+    pub max_intrinsic_width: f32,
+    /// Width of the widest unbreakable word.
+    pub min_intrinsic_width: f32,
+    /// Distance from the top to the alphabetic baseline of the first line.
+    pub alphabetic_baseline: f32,
+    pub line_count: usize,
+}
+
+impl TextMetrics {
+    /// The measured content size.
+    pub fn size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Lays out `text` with `style`, wrapped at `width`, and returns its metrics.
+///
+/// This always performs a full paragraph layout. For repeated measurements of the same
+/// content, use [`crate::cache::text_measure::TextMeasureCache`].
+pub fn measure_text(
+    text: &str,
+    style: &TextStyle,
+    width: f32,
+    fonts: &FontRepository,
+) -> TextMetrics {
+    measure_text_lines(text, style, width, None, fonts)
+}
+
+/// Same as [`measure_text`], keeping at most `max_lines` lines.
+pub fn measure_text_lines(
+    text: &str,
+    style: &TextStyle,
+    width: f32,
+    max_lines: Option<usize>,
+    fonts: &FontRepository,
+) -> TextMetrics {
+    let paragraph = layout_paragraph(
+        &transform_text(text, style.text_transform),
+        style,
+        width,
+        max_lines,
+        fonts,
+    );
+    paragraph_metrics(&paragraph)
+}
+
+/// A font family of a [`TextStyle`] that is neither registered nor a system font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontSubstitution {
+    /// The family requested by the style.
+    pub requested: String,
+    /// The family name of the face the text was drawn with instead, `None` when no font
+    /// was available at all.
+    pub used: Option<String>,
+}
+
+/// Metrics of a laid out text block, with the fonts and glyphs it lacked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayoutResult {
+    pub metrics: TextMetrics,
+    /// Requested families that are not available, in the order of the style.
+    pub substitutions: Vec<FontSubstitution>,
+    /// Characters without a glyph in any of the resolved fonts, drawn as tofu (or not at
+    /// all). Each character is listed once, in order of appearance.
+    pub missing_glyphs: Vec<char>,
+}
+
+impl TextLayoutResult {
+    /// Whether the text is drawn with the requested fonts and every character has a glyph.
+    pub fn is_complete(&self) -> bool {
+        self.substitutions.is_empty() && self.missing_glyphs.is_empty()
+    }
+}
+
+/// Same as [`measure_text`], also reporting missing font families and glyphs, so tooling
+/// can warn about text that does not render as designed.
+///
+/// Families are looked up in the [`FontRepository`] registry, then in the system fonts
+/// when enabled, see [`crate::runtime::font_registry::FontRegistry::set_system_fonts`].
+pub fn measure_text_report(
+    text: &str,
+    style: &TextStyle,
+    width: f32,
+    fonts: &FontRepository,
+) -> TextLayoutResult {
+    let text = transform_text(text, style.text_transform);
+    let paragraph = layout_paragraph(&text, style, width, None, fonts);
+    let used_fonts = paragraph.get_fonts();
+
+    let font_style = make_textstyle(style).font_style();
+    let used = used_fonts
+        .first()
+        .map(|info| info.font.typeface().family_name());
+    let substitutions = style
+        .font_families
+        .iter()
+        .filter(|family| !fonts.registry().resolves(family, font_style))
+        .map(|family| FontSubstitution {
+            requested: family.clone(),
+            used: used.clone(),
+        })
+        .collect();
+
+    let mut missing_glyphs: Vec<char> = Vec::new();
+    for (index, c) in text.char_indices() {
+        if c.is_whitespace() || c.is_control() || missing_glyphs.contains(&c) {
+            continue;
+        }
+        let has_glyph = used_fonts
+            .iter()
+            .find(|info| info.text_range.contains(&index))
+            .is_some_and(|info| info.font.unichar_to_glyph(c as i32) != 0);
+        if !has_glyph {
+            missing_glyphs.push(c);
+        }
+    }
+
+    TextLayoutResult {
+        metrics: paragraph_metrics(&paragraph),
+        substitutions,
+        missing_glyphs,
+    }
+}
+
+fn layout_paragraph(
+    text: &str,
+    style: &TextStyle,
+    width: f32,
+    max_lines: Option<usize>,
+    fonts: &FontRepository,
+) -> textlayout::Paragraph {
+    let mut paragraph_style = textlayout::ParagraphStyle::new();
+    paragraph_style.set_text_direction(style.direction.into());
+    paragraph_style.set_max_lines(max_lines.map(|lines| lines.max(1)));
+
+    let mut para_builder =
+        textlayout::ParagraphBuilder::new(&paragraph_style, &fonts.font_collection());
+    para_builder.push_style(&make_textstyle(style));
+    para_builder.add_text(text);
+    let mut paragraph = para_builder.build();
+    paragraph.layout(width);
+    paragraph
+}
+
+fn paragraph_metrics(paragraph: &textlayout::Paragraph) -> TextMetrics {
+    TextMetrics {
+        width: paragraph.longest_line(),
+        height: paragraph.height(),
+        max_intrinsic_width: paragraph.max_intrinsic_width(),
+        min_intrinsic_width: paragraph.min_intrinsic_width(),
+        alphabetic_baseline: paragraph.alphabetic_baseline(),
+        line_count: paragraph.line_number(),
+    }
+}
+
+/// The box covered by the lines of a laid out paragraph, in paragraph space.
+///
+/// Unlike the layout width, this follows the offset of each line, so centered or right
+/// aligned text yields the box around its glyphs. Empty for a paragraph without lines.
+pub fn text_block_bounds(paragraph: &textlayout::Paragraph) -> skia_safe::Rect {
+    let lines = paragraph.get_line_metrics();
+    if lines.is_empty() {
+        return skia_safe::Rect::new_empty();
+    }
+    let left = lines
+        .iter()
+        .map(|line| line.left as f32)
+        .fold(f32::INFINITY, f32::min);
+    let right = lines
+        .iter()
+        .map(|line| (line.left + line.width) as f32)
+        .fold(f32::NEG_INFINITY, f32::max);
+    skia_safe::Rect::new(left, 0.0, right, paragraph.height())
+}
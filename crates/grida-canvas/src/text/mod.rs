@@ -1 +1,5 @@
+pub mod measure;
+pub mod outline;
+pub mod overflow;
+pub mod rich_text;
 pub mod text_transform;
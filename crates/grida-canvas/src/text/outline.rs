@@ -0,0 +1,63 @@
+use crate::node::schema::{
+    Size, TextAlign, TextAlignVertical, TextOverflow, TextSpanNode, TextStyle,
+};
+use crate::painter::make_textstyle;
+use crate::runtime::repository::FontRepository;
+use crate::text::overflow::layout_within_line_limit;
+use crate::text::text_transform::transform_text;
+use skia_safe::{textlayout, Path};
+
+/// Lays out `text` wrapped at the width of `size` and returns the glyph outlines of all
+/// lines, in the local coordinate space of the text box.
+///
+/// The lines are offset within the height of `size` by `valign`, and cut off at
+/// `max_lines` as they are drawn, see [`layout_within_line_limit`]. Glyphs that cannot be
+/// converted to paths (e.g. bitmap emoji) are skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn text_outline(
+    text: &str,
+    style: &TextStyle,
+    align: TextAlign,
+    valign: TextAlignVertical,
+    size: &Size,
+    max_lines: Option<usize>,
+    overflow: TextOverflow,
+    fonts: &FontRepository,
+) -> Path {
+    let mut paragraph_style = textlayout::ParagraphStyle::new();
+    paragraph_style.set_text_direction(style.direction.into());
+    paragraph_style.set_text_align(align.resolve(style.direction).into());
+
+    let text = transform_text(text, style.text_transform);
+    let mut paragraph =
+        layout_within_line_limit(&mut paragraph_style, max_lines, overflow, size, |ps| {
+            let mut para_builder = textlayout::ParagraphBuilder::new(ps, &fonts.font_collection());
+            para_builder.push_style(&make_textstyle(style));
+            para_builder.add_text(&text);
+            let mut paragraph = para_builder.build();
+            paragraph.layout(size.width);
+            paragraph
+        });
+
+    let y = valign.offset(size.height, paragraph.height());
+    let mut path = Path::new();
+    for i in 0..paragraph.line_number() {
+        let (_, line_path) = paragraph.get_path_at(i);
+        path.add_path(&line_path, (0.0, y), None);
+    }
+    path
+}
+
+/// Glyph outlines of a [`TextSpanNode`], in the node's local space.
+pub fn text_span_outline(node: &TextSpanNode, fonts: &FontRepository) -> Path {
+    text_outline(
+        &node.text,
+        &node.text_style,
+        node.text_align,
+        node.text_align_vertical,
+        &node.size,
+        node.max_lines,
+        node.overflow,
+        fonts,
+    )
+}
@@ -0,0 +1,62 @@
+use crate::node::schema::{Size, TextOverflow};
+use skia_safe::textlayout::{Paragraph, ParagraphStyle};
+
+/// Appended to the last visible line of text cut off with [`TextOverflow::Ellipsis`].
+pub const ELLIPSIS: &str = "\u{2026}";
+
+/// Lays out a paragraph within the line limit of a text node.
+///
+/// `build` builds and lays out the paragraph with the given style. At most `max_lines`
+/// lines are kept (at least one), with [`TextOverflow::Ellipsis`] the last one ends with
+/// [`ELLIPSIS`]. Ellipsized text without `max_lines` is cut to the lines that fit in the
+/// height of `size`, which takes a second layout when it overflows.
+pub fn layout_within_line_limit(
+    paragraph_style: &mut ParagraphStyle,
+    max_lines: Option<usize>,
+    overflow: TextOverflow,
+    size: &Size,
+    build: impl Fn(&ParagraphStyle) -> Paragraph,
+) -> Paragraph {
+    let max_lines = max_lines.map(|lines| lines.max(1));
+    set_line_limit(paragraph_style, max_lines, overflow);
+    let paragraph = build(paragraph_style);
+    if max_lines.is_some()
+        || overflow != TextOverflow::Ellipsis
+        || paragraph.height() <= size.height
+    {
+        return paragraph;
+    }
+
+    let fitting = fitting_lines(&paragraph, size.height);
+    if fitting >= paragraph.line_number() {
+        return paragraph;
+    }
+    set_line_limit(paragraph_style, Some(fitting), overflow);
+    build(paragraph_style)
+}
+
+/// Number of lines of a laid out paragraph that end within `height`, at least one.
+pub fn fitting_lines(paragraph: &Paragraph, height: f32) -> usize {
+    let mut bottom = 0.0;
+    let fitting = paragraph
+        .get_line_metrics()
+        .iter()
+        .take_while(|line| {
+            bottom += line.height;
+            bottom <= height as f64
+        })
+        .count();
+    fitting.max(1)
+}
+
+fn set_line_limit(
+    paragraph_style: &mut ParagraphStyle,
+    max_lines: Option<usize>,
+    overflow: TextOverflow,
+) {
+    paragraph_style.set_max_lines(max_lines);
+    // skia only ellipsizes lines past a limit, an unlimited paragraph is never cut
+    if max_lines.is_some() && overflow == TextOverflow::Ellipsis {
+        paragraph_style.set_ellipsis(ELLIPSIS);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::node::schema::{Paint, Size, TextAlign, TextDirection, TextSpan};
+use crate::painter::{cvt, make_textstyle};
+use crate::runtime::repository::FontRepository;
+use crate::text::measure::text_block_bounds;
+use crate::text::text_transform::transform_text;
+use skia_safe::textlayout;
+
+/// Whether a text fill is stretched over the laid out text block rather than the node box.
+///
+/// Gradients span the measured lines, so every glyph samples the gradient at its position
+/// within the whole block, including across line breaks.
+pub fn spans_text_block(fill: &Paint) -> bool {
+    matches!(fill, Paint::LinearGradient(_) | Paint::RadialGradient(_))
+}
+
+/// Lays out styled text spans into a single paragraph.
+///
+/// Each span is pushed with its own text style and foreground paint. Spans without
+/// their own fill use the node-level `fill`. The paragraph direction is taken from the
+/// first span. Gradient fills span the laid out text block, see [`spans_text_block`].
+pub fn build_rich_paragraph(
+    spans: &[TextSpan],
+    fill: &Paint,
+    align: &TextAlign,
+    size: &Size,
+    fonts: &FontRepository,
+) -> textlayout::Paragraph {
+    let paragraph = layout_rich_paragraph(spans, fill, align, size, None, fonts);
+    if !spans
+        .iter()
+        .any(|span| spans_text_block(span.fill.as_ref().unwrap_or(fill)))
+    {
+        return paragraph;
+    }
+    let bounds = text_block_bounds(&paragraph);
+    layout_rich_paragraph(spans, fill, align, size, Some(bounds), fonts)
+}
+
+/// Builds the paragraph, with gradient fills sized to `block` when known.
+fn layout_rich_paragraph(
+    spans: &[TextSpan],
+    fill: &Paint,
+    align: &TextAlign,
+    size: &Size,
+    block: Option<skia_safe::Rect>,
+    fonts: &FontRepository,
+) -> textlayout::Paragraph {
+    let mut paragraph_style = textlayout::ParagraphStyle::new();
+    let direction = spans
+        .first()
+        .map_or(TextDirection::Ltr, |span| span.text_style.direction);
+    paragraph_style.set_text_direction(direction.into());
+    paragraph_style.set_text_align(align.resolve(direction).into());
+
+    let mut para_builder =
+        textlayout::ParagraphBuilder::new(&paragraph_style, &fonts.font_collection());
+    for span in spans {
+        let span_fill = span.fill.as_ref().unwrap_or(fill);
+        let fill_paint = match block {
+            Some(block) if spans_text_block(span_fill) => {
+                cvt::sk_paint_in_rect(span_fill, 1.0, block)
+            }
+            _ => cvt::sk_paint(span_fill, 1.0, (size.width, size.height)),
+        };
+        let mut ts = make_textstyle(&span.text_style);
+        ts.set_foreground_paint(&fill_paint);
+        para_builder.push_style(&ts);
+        para_builder.add_text(&transform_text(&span.text, span.text_style.text_transform));
+        para_builder.pop();
+    }
+    let mut paragraph = para_builder.build();
+    paragraph.layout(size.width);
+    paragraph
+}
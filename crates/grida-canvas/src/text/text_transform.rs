@@ -2,6 +2,10 @@ use crate::node::schema::TextTransform;
 
 /// Applies text transformation according to CSS text-transform property.
 ///
+/// Casing follows the Unicode mappings of [`char::to_uppercase`] and
+/// [`char::to_lowercase`], so a character may expand (`ß` uppercases to `SS`). The
+/// transform is applied to the laid out string only, the node text is kept as authored.
+///
 /// # Arguments
 ///
 /// * `text` - The input text to transform
@@ -13,9 +17,9 @@ use crate::node::schema::TextTransform;
 ///
 /// # Examples
 ///
-/// ```ignore
-/// use cg::schema::TextTransform;
-/// use cg::text_transform::transform_text;
+/// ```
+/// use cg::node::schema::TextTransform;
+/// use cg::text::text_transform::transform_text;
 ///
 /// let text = "Hello World";
 /// assert_eq!(transform_text(text, TextTransform::Uppercase), "HELLO WORLD");
@@ -34,12 +38,15 @@ pub fn transform_text(text: &str, transform: TextTransform) -> String {
 
             for c in text.chars() {
                 if capitalize_next && c.is_alphabetic() {
-                    result.push(c.to_uppercase().next().unwrap_or(c));
+                    push_titlecase(&mut result, c);
                     capitalize_next = false;
                 } else {
                     result.push(c);
-                    // Consider a word boundary to be any non-alphanumeric character
-                    capitalize_next = !c.is_alphanumeric();
+                    // Consider a word boundary to be any non-alphanumeric character,
+                    // apostrophes and combining marks continue the current word
+                    if !is_combining_mark(c) && !matches!(c, '\'' | '\u{2019}') {
+                        capitalize_next = !c.is_alphanumeric();
+                    }
                 }
             }
             result
@@ -47,6 +54,31 @@ pub fn transform_text(text: &str, transform: TextTransform) -> String {
     }
 }
 
+/// Pushes the titlecase form of `c`: the first character of its uppercase mapping,
+/// followed by the rest of the mapping lowercased (`ß` becomes `Ss`).
+fn push_titlecase(result: &mut String, c: char) {
+    let mut upper = c.to_uppercase();
+    if let Some(first) = upper.next() {
+        result.push(first);
+    }
+    for rest in upper {
+        result.extend(rest.to_lowercase());
+    }
+}
+
+/// Whether `c` is in one of the combining diacritical mark blocks, e.g. the acute accent
+/// of a decomposed `é`.
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +151,43 @@ mod tests {
             "Hello_World"
         );
     }
+
+    #[test]
+    fn test_unicode_uppercase_expands() {
+        assert_eq!(
+            transform_text("straße", TextTransform::Uppercase),
+            "STRASSE"
+        );
+        assert_eq!(transform_text("ﬁn", TextTransform::Uppercase), "FIN");
+        assert_eq!(
+            transform_text("crème brûlée", TextTransform::Uppercase),
+            "CRÈME BRÛLÉE"
+        );
+    }
+
+    #[test]
+    fn test_unicode_lowercase() {
+        assert_eq!(
+            transform_text("ÉCOLE ÀÇÑ ΣΟΦΙΑ", TextTransform::Lowercase),
+            "école àçñ σοφια"
+        );
+    }
+
+    #[test]
+    fn test_unicode_capitalize() {
+        assert_eq!(
+            transform_text("élan über ßtraße", TextTransform::Capitalize),
+            "Élan Über Sstraße"
+        );
+        assert_eq!(transform_text("ﬁne", TextTransform::Capitalize), "Fine");
+        // decomposed accents do not start a new word
+        assert_eq!(
+            transform_text("e\u{301}cole", TextTransform::Capitalize),
+            "E\u{301}cole"
+        );
+        assert_eq!(
+            transform_text("don't l’amour", TextTransform::Capitalize),
+            "Don't L’amour"
+        );
+    }
 }
@@ -267,7 +267,7 @@ impl ApplicationApi for UnknownTargetApplication {
 
     fn load_scene_json(&mut self, json: &str) {
         use crate::io::io_json;
-        use math2::transform::AffineTransform;
+        use crate::runtime::image_store::ImageStore;
 
         let Ok(file) = io_json::parse(json) else {
             let err = io_json::parse(json).unwrap_err();
@@ -275,32 +275,14 @@ impl ApplicationApi for UnknownTargetApplication {
             return;
         };
 
-        let nodes = file
-            .document
-            .nodes
-            .into_iter()
-            .map(|(id, node)| (id, node.into()))
-            .collect();
-
-        let scene_id = file.document.entry_scene_id.unwrap_or_else(|| {
-            file.document
-                .scenes
-                .keys()
-                .next()
-                .cloned()
-                .unwrap_or_else(|| "scene".to_string())
-        });
+        self.renderer
+            .images
+            .borrow_mut()
+            .set_store(ImageStore::from_bitmaps(&file.document.bitmaps));
 
-        if let Some(scene) = file.document.scenes.get(&scene_id) {
-            let scene = crate::node::schema::Scene {
-                id: scene_id,
-                name: scene.name.clone(),
-                transform: AffineTransform::identity(),
-                children: scene.children.clone(),
-                nodes,
-                background_color: scene.background_color.clone().map(Into::into),
-            };
-            self.renderer.load_scene(scene);
+        match file.document.into_entry_scene() {
+            Ok(scene) => self.renderer.load_scene(scene),
+            Err(err) => eprintln!("failed to load scene json: {:?}", err),
         }
     }
 
@@ -0,0 +1,112 @@
+use cg::export::export_as_image::render_scene_to_png;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+
+const SQUARE: i32 = 10;
+
+/// Whether `(x, y)` is on a black square of the checkerboard.
+fn is_black(x: i32, y: i32) -> bool {
+    (x / SQUARE + y / SQUARE) % 2 == 0
+}
+
+/// A 100x100 black and white checkerboard with a blurred rounded panel over x/y 40..80.
+fn scene() -> Scene {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let mut children = vec![];
+    for row in 0..100 / SQUARE {
+        for col in 0..100 / SQUARE {
+            if !is_black(col * SQUARE, row * SQUARE) {
+                continue;
+            }
+            let mut square = nf.create_rectangle_node();
+            square.transform =
+                AffineTransform::new((col * SQUARE) as f32, (row * SQUARE) as f32, 0.0);
+            square.size = Size {
+                width: SQUARE as f32,
+                height: SQUARE as f32,
+            };
+            square.fill = Paint::Solid(SolidPaint {
+                color: Color(0, 0, 0, 255),
+                opacity: 1.0,
+            });
+            square.stroke_width = 0.0;
+            children.push(repo.insert(Node::Rectangle(square)));
+        }
+    }
+
+    let mut panel = nf.create_rectangle_node();
+    panel.transform = AffineTransform::new(40.0, 40.0, 0.0);
+    panel.size = Size {
+        width: 40.0,
+        height: 40.0,
+    };
+    panel.corner_radius = RectangularCornerRadius::all(10.0);
+    panel.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 0),
+        opacity: 1.0,
+    });
+    panel.stroke_width = 0.0;
+    panel.effects = vec![FilterEffect::BackdropBlur(FeBackdropBlur { radius: 4.0 })];
+    children.push(repo.insert(Node::Rectangle(panel)));
+
+    Scene {
+        children,
+        nodes: repo,
+        background_color: Some(Color(255, 255, 255, 255)),
+        ..Scene::new("scene", "test")
+    }
+}
+
+fn render() -> skia_safe::Surface {
+    let png = render_scene_to_png(&scene(), 100, 100, 1.0);
+    let image = Image::from_encoded(Data::new_copy(&png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().draw_image(&image, (0, 0), None);
+    surface
+}
+
+fn gray(pixmap: &skia_safe::Pixmap, (x, y): (i32, i32)) -> u8 {
+    let color = pixmap.get_color((x, y));
+    assert_eq!(color.a(), 255, "({x}, {y}) {color:?}");
+    color.r()
+}
+
+fn expected((x, y): (i32, i32)) -> u8 {
+    if is_black(x, y) {
+        0
+    } else {
+        255
+    }
+}
+
+#[test]
+fn backdrop_is_averaged_behind_the_panel() {
+    let mut surface = render();
+    let pixmap = surface.peek_pixels().unwrap();
+    // both sides of a square edge inside the panel are pulled towards gray
+    for point in [(59, 55), (60, 55), (55, 69), (55, 70)] {
+        let value = gray(&pixmap, point);
+        assert!((40..=215).contains(&value), "{point:?} {value}");
+    }
+}
+
+#[test]
+fn backdrop_outside_the_panel_is_untouched() {
+    let mut surface = render();
+    let pixmap = surface.peek_pixels().unwrap();
+    for point in [(59, 15), (60, 15), (15, 59), (90, 90)] {
+        assert_eq!(gray(&pixmap, point), expected(point), "{point:?}");
+    }
+}
+
+#[test]
+fn backdrop_blur_respects_corner_radius() {
+    let mut surface = render();
+    let pixmap = surface.peek_pixels().unwrap();
+    // inside the bounding box of the panel, outside of its rounded corners
+    for point in [(40, 40), (41, 41), (79, 79), (40, 79)] {
+        assert_eq!(gray(&pixmap, point), expected(point), "{point:?}");
+    }
+}
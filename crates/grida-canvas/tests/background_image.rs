@@ -0,0 +1,67 @@
+use cg::node::schema::*;
+use cg::runtime::camera::Camera2D;
+use cg::runtime::scene::{Backend, Renderer};
+use math2::box_fit::BoxFit;
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Color as SkColor, Paint as SkPaint, Rect};
+
+/// A 100x100 white image with a 20x20 red square in the center.
+fn square_image() -> skia_safe::Image {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(SkColor::WHITE);
+    let mut paint = SkPaint::default();
+    paint.set_color(SkColor::RED);
+    canvas.draw_rect(Rect::from_xywh(40.0, 40.0, 20.0, 20.0), &paint);
+    surface.image_snapshot()
+}
+
+#[test]
+fn cover_background_image_fills_viewport_without_distortion() {
+    let (width, height) = (200, 100);
+    let mut renderer = Renderer::new(
+        Backend::new_from_raster(width, height),
+        None,
+        Camera2D::new(Size {
+            width: width as f32,
+            height: height as f32,
+        }),
+    );
+    renderer
+        .images
+        .borrow_mut()
+        .insert("square".to_string(), square_image());
+    renderer.load_scene(Scene {
+        background_color: Some(Color(0, 0, 255, 255)),
+        background_image: Some(ImagePaint {
+            transform: AffineTransform::identity(),
+            _ref: "square".to_string(),
+            fit: BoxFit::Cover,
+            opacity: 1.0,
+            repeat: ImageRepeat::NoRepeat,
+        }),
+        ..Scene::new("scene", "test")
+    });
+
+    let mut surface = surfaces::raster_n32_premul((width, height)).unwrap();
+    renderer.render_to_canvas(surface.canvas(), width as f32, height as f32);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // the image covers the whole viewport, the background color is not visible
+    for (x, y) in [(1, 1), (198, 1), (1, 98), (198, 98)] {
+        assert_eq!(
+            pixels.get_color((x, y)),
+            SkColor::WHITE,
+            "pixel at ({x}, {y})"
+        );
+    }
+
+    // uniformly scaled by 2: the square spans 80..120 horizontally and 30..70 vertically
+    assert_eq!(pixels.get_color((100, 50)), SkColor::RED);
+    assert_eq!(pixels.get_color((85, 50)), SkColor::RED);
+    assert_eq!(pixels.get_color((100, 35)), SkColor::RED);
+    assert_eq!(pixels.get_color((75, 50)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((100, 25)), SkColor::WHITE);
+
+    renderer.free();
+}
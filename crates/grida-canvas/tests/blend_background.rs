@@ -0,0 +1,80 @@
+use cg::cache::geometry::GeometryCache;
+use cg::export::export_as_image::render_scene_to_png;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// An opaque blue background with a `DstOut` rectangle over x/y 25..75.
+fn scene(opacity: f32) -> Scene {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(25.0, 25.0, 0.0);
+    rect.size = Size {
+        width: 50.0,
+        height: 50.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(255, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    rect.opacity = opacity;
+    rect.blend_mode = BlendMode::DstOut;
+
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::Rectangle(rect));
+    Scene {
+        children: vec![id],
+        nodes: repo,
+        background_color: Some(Color(0, 0, 255, 255)),
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// Decodes the png and returns the color at each of `points`.
+fn pixels(png: &[u8], points: &[(i32, i32)]) -> Vec<skia_safe::Color> {
+    let image = Image::from_encoded(Data::new_copy(png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((image.width(), image.height())).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.canvas().draw_image(&image, (0, 0), None);
+    let pixmap = surface.peek_pixels().unwrap();
+    points.iter().map(|p| pixmap.get_color(*p)).collect()
+}
+
+#[test]
+fn dst_out_erases_a_hole_in_the_background() {
+    let png = render_scene_to_png(&scene(1.0), 100, 100, 1.0);
+    let colors = pixels(&png, &[(50, 50), (10, 10), (90, 90)]);
+
+    assert_eq!(colors[0].a(), 0, "{:?}", colors[0]);
+    assert_eq!(colors[1], skia_safe::Color::BLUE);
+    assert_eq!(colors[2], skia_safe::Color::BLUE);
+}
+
+#[test]
+fn opacity_scales_the_erased_amount() {
+    let scene = scene(0.5);
+    let cache = GeometryCache::from_scene(&scene);
+
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::BLUE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node_recursively(
+        scene.nodes.get(&scene.children[0]).unwrap(),
+        &scene.nodes,
+        &cache,
+    );
+
+    let pixmap = surface.peek_pixels().unwrap();
+    let alpha = pixmap.get_color((50, 50)).a();
+    assert!((120..=136).contains(&alpha), "{alpha}");
+    assert_eq!(pixmap.get_color((10, 10)), skia_safe::Color::BLUE);
+}
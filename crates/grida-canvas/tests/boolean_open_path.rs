@@ -0,0 +1,98 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::geometry::{
+    boolean_operation_path_with_options, BooleanOpDiagnostic, BooleanOpOptions,
+};
+use math2::transform::AffineTransform;
+use skia_safe::Point;
+
+/// A 100x100 rectangle minus an open triangle path over its top-left half.
+fn difference_with_open_path() -> (Scene, BooleanPathOperationNode, NodeId) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    let rect_id = repo.insert(Node::Rectangle(rect));
+
+    let mut path = nf.create_path_node();
+    path.data = "M0 0 L100 0 L0 100".to_string();
+    let path_id = repo.insert(Node::Path(path));
+
+    let bool_node = BooleanPathOperationNode {
+        base: BaseNode {
+            id: "difference".to_string(),
+            name: "Difference".to_string(),
+            active: true,
+            locked: false,
+            render_order: None,
+        },
+        transform: AffineTransform::identity(),
+        op: BooleanPathOperation::Difference,
+        children: vec![rect_id, path_id.clone()],
+        fill: Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        stroke: None,
+        stroke_width: 0.0,
+        stroke_align: StrokeAlign::Center,
+        stroke_dash_array: None,
+        stroke_dash_offset: None,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        effects: vec![],
+    };
+    let bool_id = repo.insert(Node::BooleanOperation(bool_node.clone()));
+
+    let scene = Scene {
+        children: vec![bool_id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    (scene, bool_node, path_id)
+}
+
+#[test]
+fn open_path_is_reported_when_closing_is_disabled() {
+    let (scene, node, path_id) = difference_with_open_path();
+    let mut diagnostics = Vec::new();
+
+    let path = boolean_operation_path_with_options(
+        &node,
+        &scene.nodes,
+        None,
+        BooleanOpOptions {
+            close_open_paths: false,
+        },
+        &mut diagnostics,
+    );
+
+    assert!(path.is_some());
+    assert_eq!(
+        diagnostics,
+        vec![BooleanOpDiagnostic::OpenPath { node_id: path_id }]
+    );
+}
+
+#[test]
+fn open_path_is_closed_by_default() {
+    let (scene, node, _) = difference_with_open_path();
+    let mut diagnostics = Vec::new();
+
+    let path = boolean_operation_path_with_options(
+        &node,
+        &scene.nodes,
+        None,
+        BooleanOpOptions::default(),
+        &mut diagnostics,
+    )
+    .unwrap();
+
+    assert!(diagnostics.is_empty());
+    // the closed triangle is cut out, the opposite half of the rectangle is kept
+    assert!(!path.contains(Point::new(10.0, 10.0)));
+    assert!(path.contains(Point::new(90.0, 90.0)));
+}
@@ -0,0 +1,166 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::geometry::boolean_operation_path;
+use cg::painter::layer::{Layer, LayerList};
+use cg::runtime::repository::FontRepository;
+use math2::transform::AffineTransform;
+use skia_safe::{ContourMeasureIter, Point, Rect};
+
+fn rect(repo: &mut NodeRepository, x: f32, y: f32) -> NodeId {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(x, y, 0.0);
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    repo.insert(Node::Rectangle(rect))
+}
+
+fn boolean(op: BooleanPathOperation, children: Vec<NodeId>) -> BooleanPathOperationNode {
+    BooleanPathOperationNode {
+        base: BaseNode {
+            id: "boolean".to_string(),
+            name: "Boolean".to_string(),
+            active: true,
+            locked: false,
+            render_order: None,
+        },
+        transform: AffineTransform::identity(),
+        op,
+        children,
+        fill: Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        stroke: None,
+        stroke_width: 0.0,
+        stroke_align: StrokeAlign::Center,
+        stroke_dash_array: None,
+        stroke_dash_offset: None,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        effects: vec![],
+    }
+}
+
+fn contour_count(path: &skia_safe::Path) -> usize {
+    ContourMeasureIter::new(path, false, None).count()
+}
+
+#[test]
+fn union_of_overlapping_rects_is_one_contour() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let b = rect(&mut repo, 50.0, 50.0);
+
+    let path = boolean(BooleanPathOperation::Union, vec![a, b]).resolve_path(&repo);
+    assert_eq!(contour_count(&path), 1);
+    assert_eq!(*path.bounds(), Rect::from_xywh(0.0, 0.0, 150.0, 150.0));
+    assert!(path.contains(Point::new(125.0, 125.0)));
+    assert!(!path.contains(Point::new(125.0, 25.0)));
+}
+
+#[test]
+fn difference_folds_left_to_right() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let b = rect(&mut repo, 50.0, 50.0);
+
+    let path = boolean(BooleanPathOperation::Difference, vec![a, b]).resolve_path(&repo);
+    assert!(path.contains(Point::new(25.0, 25.0)));
+    assert!(!path.contains(Point::new(75.0, 75.0)));
+    assert!(!path.contains(Point::new(125.0, 125.0)));
+}
+
+#[test]
+fn single_and_empty_children() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 10.0, 20.0);
+
+    let path = boolean(BooleanPathOperation::Intersection, vec![a]).resolve_path(&repo);
+    assert_eq!(*path.bounds(), Rect::from_xywh(10.0, 20.0, 100.0, 100.0));
+
+    let path = boolean(BooleanPathOperation::Union, vec![]).resolve_path(&repo);
+    assert!(path.is_empty());
+}
+
+#[test]
+fn nested_union_is_resolved_before_the_difference() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let b = rect(&mut repo, 100.0, 0.0);
+    let mut union = boolean(BooleanPathOperation::Union, vec![a, b]);
+    union.base.id = "union".to_string();
+    let union = repo.insert(Node::BooleanOperation(union));
+    let hole = rect(&mut repo, 50.0, 50.0);
+
+    let path = boolean(BooleanPathOperation::Difference, vec![union, hole]).resolve_path(&repo);
+    assert_eq!(*path.bounds(), Rect::from_xywh(0.0, 0.0, 200.0, 100.0));
+    assert!(path.contains(Point::new(25.0, 75.0)));
+    assert!(path.contains(Point::new(175.0, 25.0)));
+    assert!(!path.contains(Point::new(75.0, 75.0)));
+    assert!(!path.contains(Point::new(125.0, 75.0)));
+}
+
+#[test]
+fn group_children_are_unioned_in_group_space() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let b = rect(&mut repo, 50.0, 50.0);
+    let mut group = NodeFactory::new().create_group_node();
+    group.transform = AffineTransform::new(10.0, 0.0, 0.0);
+    group.children = vec![a, b];
+    let group = repo.insert(Node::Group(group));
+
+    let path = boolean(BooleanPathOperation::Intersection, vec![group]).resolve_path(&repo);
+    assert_eq!(contour_count(&path), 1);
+    assert_eq!(*path.bounds(), Rect::from_xywh(10.0, 0.0, 150.0, 150.0));
+}
+
+#[test]
+fn cycles_are_skipped() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let mut inner = boolean(BooleanPathOperation::Union, vec![a, "outer".to_string()]);
+    inner.base.id = "inner".to_string();
+    let inner = repo.insert(Node::BooleanOperation(inner));
+    let mut outer = boolean(BooleanPathOperation::Union, vec![inner]);
+    outer.base.id = "outer".to_string();
+    repo.insert(Node::BooleanOperation(outer.clone()));
+
+    let path = outer.resolve_path(&repo);
+    assert_eq!(*path.bounds(), Rect::from_xywh(0.0, 0.0, 100.0, 100.0));
+}
+
+#[test]
+fn renderer_resolves_group_operands_and_cycles() {
+    let mut repo = NodeRepository::new();
+    let a = rect(&mut repo, 0.0, 0.0);
+    let b = rect(&mut repo, 50.0, 50.0);
+    let mut group = NodeFactory::new().create_group_node();
+    group.children = vec![a, b];
+    let group = repo.insert(Node::Group(group));
+    // the operation lists itself next to the group
+    let node = boolean(
+        BooleanPathOperation::Union,
+        vec![group, "boolean".to_string()],
+    );
+    let id = repo.insert(Node::BooleanOperation(node.clone()));
+
+    let path = boolean_operation_path(&node, &repo, &FontRepository::new()).unwrap();
+    assert_eq!(*path.bounds(), Rect::from_xywh(0.0, 0.0, 150.0, 150.0));
+
+    let scene = Scene {
+        children: vec![id.clone()],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.layers[0].id(), &id);
+    assert_eq!(
+        *list.layers[0].base().shape.to_path().bounds(),
+        Rect::from_xywh(0.0, 0.0, 150.0, 150.0)
+    );
+}
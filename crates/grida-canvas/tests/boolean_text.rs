@@ -0,0 +1,108 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::{cvt, geometry::boolean_operation_path};
+use cg::runtime::repository::FontRepository;
+use cg::text::outline::text_span_outline;
+use math2::transform::AffineTransform;
+use skia_safe::Point;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+#[test]
+fn difference_with_text_cuts_glyph_holes() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+    let rect_id = rect.base.id.clone();
+
+    let mut text = nf.create_text_span_node();
+    text.transform = AffineTransform::new(20.0, 0.0, 0.0);
+    text.size = Size {
+        width: 160.0,
+        height: 100.0,
+    };
+    text.text = "HI".to_string();
+    text.text_style.font_families = vec!["Geist".to_string()];
+    text.text_style.font_size = 80.0;
+    let text_id = text.base.id.clone();
+    let text_transform = text.transform;
+
+    let fonts = fonts();
+    let mut glyphs = text_span_outline(&text, &fonts);
+    glyphs.transform(&cvt::sk_matrix(text_transform.matrix));
+    assert!(!glyphs.is_empty());
+
+    let bool_node = BooleanPathOperationNode {
+        base: BaseNode {
+            id: "difference".to_string(),
+            name: "Difference".to_string(),
+            active: true,
+            locked: false,
+            render_order: None,
+        },
+        transform: AffineTransform::identity(),
+        op: BooleanPathOperation::Difference,
+        children: vec![rect_id, text_id],
+        fill: Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        stroke: None,
+        stroke_width: 0.0,
+        stroke_align: StrokeAlign::Center,
+        stroke_dash_array: None,
+        stroke_dash_offset: None,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        effects: vec![],
+    };
+
+    repo.insert(Node::Rectangle(rect));
+    repo.insert(Node::TextSpan(text));
+    let bool_id = repo.insert(Node::BooleanOperation(bool_node.clone()));
+
+    let scene = Scene {
+        children: vec![bool_id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+
+    let path = boolean_operation_path(&bool_node, &scene.nodes, &fonts).unwrap();
+
+    // the rectangle outside the text is kept
+    assert!(path.contains(Point::new(2.0, 2.0)));
+    assert!(path.contains(Point::new(198.0, 98.0)));
+    let bounds = path.bounds();
+    assert_eq!((bounds.left, bounds.top), (0.0, 0.0));
+    assert_eq!((bounds.right, bounds.bottom), (200.0, 100.0));
+
+    // every sampled point well inside a glyph is cut out
+    let glyph_bounds = glyphs.bounds();
+    let inside_glyph = |p: Point| {
+        [(0.0, 0.0), (-1.0, 0.0), (1.0, 0.0), (0.0, -1.0), (0.0, 1.0)]
+            .iter()
+            .all(|(dx, dy)| glyphs.contains(Point::new(p.x + dx, p.y + dy)))
+    };
+    let mut holes = 0;
+    for x in (glyph_bounds.left as i32)..(glyph_bounds.right as i32) {
+        for y in (glyph_bounds.top as i32)..(glyph_bounds.bottom as i32) {
+            let p = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+            if inside_glyph(p) {
+                assert!(!path.contains(p), "point {p:?} inside a glyph");
+                holes += 1;
+            }
+        }
+    }
+    assert!(holes > 0);
+}
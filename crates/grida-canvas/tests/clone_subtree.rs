@@ -0,0 +1,101 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use std::collections::HashSet;
+
+/// A group holding a rectangle and a container with an ellipse, inside an outer group.
+fn scene() -> (Scene, NodeId) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let rect = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+    let mut ellipse = nf.create_ellipse_node();
+    ellipse.transform = AffineTransform::new(5.0, 5.0, 0.0);
+    let ellipse = repo.insert(Node::Ellipse(ellipse));
+
+    let mut container = nf.create_container_node();
+    container.children = vec![ellipse];
+    let container = repo.insert(Node::Container(container));
+
+    let mut group = nf.create_group_node();
+    group.base.name = "copy me".into();
+    group.transform = AffineTransform::new(20.0, 0.0, 0.0);
+    group.children = vec![rect, container];
+    let group = repo.insert(Node::Group(group));
+
+    let mut outer = nf.create_group_node();
+    outer.transform = AffineTransform::new(100.0, 50.0, 0.0);
+    outer.children = vec![group.clone()];
+    let outer = repo.insert(Node::Group(outer));
+
+    let scene = Scene {
+        children: vec![outer],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    (scene, group)
+}
+
+#[test]
+fn mapping_covers_every_cloned_node() {
+    let (scene, group) = scene();
+    let (fragment, mapping) = scene.clone_subtree_remapped(&group).unwrap();
+
+    // the group, the rectangle, the container and the ellipse
+    assert_eq!(mapping.len(), 4);
+    assert_eq!(fragment.nodes.len(), 4);
+    for (old_id, new_id) in &mapping {
+        assert_ne!(old_id, new_id);
+        assert!(scene.nodes.get(old_id).is_some());
+        assert!(fragment.nodes.get(new_id).is_some());
+        assert!(scene.nodes.get(new_id).is_none());
+    }
+    let new_ids: HashSet<_> = mapping.values().collect();
+    assert_eq!(new_ids.len(), 4);
+
+    assert_eq!(fragment.children, vec![mapping[&group].clone()]);
+    assert_eq!(fragment.name, "copy me");
+}
+
+#[test]
+fn internal_references_use_new_ids() {
+    let (scene, group) = scene();
+    let (fragment, mapping) = scene.clone_subtree_remapped(&group).unwrap();
+
+    for (id, node) in fragment.nodes.iter() {
+        assert_eq!(&node.id(), id);
+        let original = mapping.iter().find(|(_, new)| *new == id).unwrap().0;
+        let expected: Vec<NodeId> = scene
+            .nodes
+            .get(original)
+            .unwrap()
+            .children()
+            .iter()
+            .map(|child| mapping[child].clone())
+            .collect();
+        assert_eq!(node.children(), expected.as_slice());
+    }
+}
+
+#[test]
+fn fragment_keeps_world_placement() {
+    let (scene, group) = scene();
+    let (fragment, mapping) = scene.clone_subtree_remapped(&group).unwrap();
+
+    let original = GeometryCache::from_scene(&scene);
+    let cloned = GeometryCache::from_scene(&fragment);
+    for (old_id, new_id) in &mapping {
+        assert_eq!(
+            original.get_world_transform(old_id),
+            cloned.get_world_transform(new_id)
+        );
+    }
+}
+
+#[test]
+fn missing_root() {
+    let (scene, _) = scene();
+    assert!(scene
+        .clone_subtree_remapped(&"missing".to_string())
+        .is_none());
+}
@@ -0,0 +1,71 @@
+use cg::node::schema::Color;
+use skia_safe::{surfaces, AlphaType, Color4f, ColorType, ImageInfo};
+
+#[test]
+fn skia_color_keeps_straight_alpha() {
+    let color = Color::from_rgba8(255, 128, 0, 128);
+    let sk: skia_safe::Color = color.into();
+
+    // SkColor is unpremultiplied, the channels are passed through untouched
+    assert_eq!((sk.r(), sk.g(), sk.b(), sk.a()), (255, 128, 0, 128));
+}
+
+#[test]
+fn color4f_is_straight_alpha() {
+    let c4f: Color4f = Color::from_rgba8(255, 0, 0, 128).into();
+
+    assert_eq!(c4f.r, 1.0);
+    assert!((c4f.a - 128.0 / 255.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn premultiplied_only_in_surface_storage() {
+    let color = Color::from_rgba8(255, 0, 0, 128);
+    let mut surface = surfaces::raster_n32_premul((1, 1)).unwrap();
+    surface.canvas().clear(skia_safe::Color::from(color));
+
+    // reading back premultiplied pixels scales the channels by alpha
+    let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Premul, None);
+    let mut premul = [0u8; 4];
+    assert!(surface.read_pixels(&info, &mut premul, 4, (0, 0)));
+    assert_eq!(premul, [128, 0, 0, 128]);
+
+    // while unpremultiplied reads give back the straight color
+    let pixels = surface.peek_pixels().unwrap();
+    let sk = pixels.get_color((0, 0));
+    assert_eq!((sk.r(), sk.g(), sk.b(), sk.a()), (255, 0, 0, 128));
+}
+
+#[test]
+fn round_trips_through_color4f() {
+    for color in [
+        Color(0, 0, 0, 0),
+        Color(255, 255, 255, 255),
+        Color(12, 34, 56, 78),
+        Color(1, 128, 254, 127),
+    ] {
+        let c4f: Color4f = color.into();
+        assert_eq!(Color::from(c4f), color);
+    }
+}
+
+#[test]
+fn rgba_f32_normalizes_channels() {
+    assert_eq!(
+        Color::from_rgba8(255, 0, 51, 255).rgba_f32(),
+        [1.0, 0.0, 0.2, 1.0]
+    );
+}
+
+#[test]
+fn from_rgba_f32_rounds_and_clamps() {
+    assert_eq!(
+        Color::from_rgba_f32(0.5, 1.5, -0.1, 0.25),
+        Color(128, 255, 0, 64)
+    );
+}
+
+#[test]
+fn with_alpha_replaces_only_alpha() {
+    assert_eq!(Color(10, 20, 30, 255).with_alpha(0), Color(10, 20, 30, 0));
+}
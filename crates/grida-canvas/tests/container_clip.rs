@@ -0,0 +1,115 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::runtime::camera::Camera2D;
+use cg::runtime::scene::{Backend, Renderer};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Color as SkColor};
+
+fn solid(color: Color) -> Paint {
+    Paint::Solid(SolidPaint {
+        color,
+        opacity: 1.0,
+    })
+}
+
+/// An 80x80 transparent container at (10, 10) with 30px corners, holding a red square
+/// that overflows it on every side.
+fn rounded_container(clip: bool) -> (ContainerNode, NodeRepository) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = AffineTransform::new(-10.0, -10.0, 0.0);
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    rect.fill = solid(Color(255, 0, 0, 255));
+    rect.stroke_width = 0.0;
+    let rect_id = repo.insert(Node::Rectangle(rect));
+
+    let mut container = nf.create_container_node();
+    container.transform = AffineTransform::new(10.0, 10.0, 0.0);
+    container.size = Size {
+        width: 80.0,
+        height: 80.0,
+    };
+    container.corner_radius = RectangularCornerRadius::all(30.0);
+    container.fill = solid(Color(0, 0, 0, 0));
+    container.clip = clip;
+    container.children = vec![rect_id];
+    (container, repo)
+}
+
+fn render(container: ContainerNode, mut repo: NodeRepository) -> skia_safe::Surface {
+    let container_id = repo.insert(Node::Container(container));
+    let mut camera = Camera2D::new(Size {
+        width: 100.0,
+        height: 100.0,
+    });
+    camera.set_center(50.0, 50.0);
+    let mut renderer = Renderer::new(Backend::new_from_raster(100, 100), None, camera);
+    renderer.load_scene(Scene {
+        children: vec![container_id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    });
+
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().clear(SkColor::TRANSPARENT);
+    renderer.render_to_canvas(surface.canvas(), 100.0, 100.0);
+    renderer.free();
+    surface
+}
+
+#[test]
+fn overflowing_child_is_clipped_along_the_rounded_edge() {
+    let (container, repo) = rounded_container(true);
+    let mut surface = render(container, repo);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // inside the container box
+    assert_eq!(pixels.get_color((50, 50)), SkColor::RED);
+    assert_eq!(pixels.get_color((12, 50)), SkColor::RED);
+    // inside the box but outside the corner arc, centered at (40, 40)
+    assert_eq!(pixels.get_color((13, 13)).a(), 0);
+    assert_eq!(pixels.get_color((86, 86)).a(), 0);
+    // just inside the arc along the diagonal
+    assert_eq!(pixels.get_color((22, 22)), SkColor::RED);
+    // outside the box
+    assert_eq!(pixels.get_color((5, 50)).a(), 0);
+}
+
+#[test]
+fn smoothed_corners_clip_children() {
+    let (mut container, repo) = rounded_container(true);
+    container.corner_smoothing = 0.6;
+    let mut surface = render(container, repo);
+    let pixels = surface.peek_pixels().unwrap();
+
+    assert_eq!(pixels.get_color((50, 50)), SkColor::RED);
+    assert_eq!(pixels.get_color((13, 13)).a(), 0);
+}
+
+#[test]
+fn unclipped_children_overflow() {
+    let (container, repo) = rounded_container(false);
+    let mut surface = render(container, repo);
+    let pixels = surface.peek_pixels().unwrap();
+
+    assert_eq!(pixels.get_color((13, 13)), SkColor::RED);
+    assert_eq!(pixels.get_color((5, 50)), SkColor::RED);
+}
+
+#[test]
+fn clip_does_not_cut_the_container_stroke() {
+    let (mut container, _) = rounded_container(true);
+    container.children.clear();
+    container.stroke = Some(solid(Color(0, 0, 255, 255)));
+    container.stroke_width = 4.0;
+    container.stroke_align = StrokeAlign::Outside;
+    let mut surface = render(container, NodeRepository::new());
+    let pixels = surface.peek_pixels().unwrap();
+
+    // the outside stroke spans 6..10 above the top edge
+    assert_eq!(pixels.get_color((50, 8)), SkColor::BLUE);
+}
@@ -0,0 +1,96 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+
+fn scene(children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// A 100x100 rectangle at `(x, y)`.
+fn rect(x: f32, y: f32) -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(x, y, 0.0);
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    rect.stroke_width = 0.0;
+    rect
+}
+
+#[test]
+fn empty_scene_has_no_content_bounds() {
+    assert!(scene(vec![], NodeRepository::new())
+        .content_bounds()
+        .is_none());
+}
+
+#[test]
+fn content_bounds_unions_root_nodes() {
+    let mut repo = NodeRepository::new();
+    let a = repo.insert(Node::Rectangle(rect(10.0, 10.0)));
+    let b = repo.insert(Node::Rectangle(rect(200.0, 50.0)));
+
+    let bounds = scene(vec![a, b], repo).content_bounds().unwrap();
+    assert_eq!(
+        (bounds.x, bounds.y, bounds.width, bounds.height),
+        (10.0, 10.0, 290.0, 140.0)
+    );
+}
+
+#[test]
+fn content_bounds_include_drop_shadow_overflow() {
+    let mut shadowed = rect(10.0, 10.0);
+    shadowed.effects = vec![FilterEffect::DropShadow(FeDropShadow {
+        dx: 20.0,
+        dy: 0.0,
+        blur: 5.0,
+        spread: 2.0,
+        color: Color(0, 0, 0, 128),
+    })];
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::Rectangle(shadowed));
+
+    let bounds = scene(vec![id], repo).content_bounds().unwrap();
+    // the shadow is offset by 20 and grows by the spread plus 3 * sigma
+    let overflow = 2.0 + 3.0 * 5.0;
+    assert_eq!(bounds.x, 10.0 + 20.0 - overflow);
+    assert_eq!(bounds.y, 10.0 - overflow);
+    assert_eq!(bounds.x + bounds.width, 10.0 + 20.0 + 100.0 + overflow);
+    assert_eq!(bounds.y + bounds.height, 10.0 + 100.0 + overflow);
+}
+
+#[test]
+fn content_bounds_include_strokes_of_nested_nodes() {
+    let mut stroked = rect(0.0, 0.0);
+    stroked.stroke_width = 10.0;
+    stroked.stroke_align = StrokeAlign::Outside;
+
+    let mut repo = NodeRepository::new();
+    let child = repo.insert(Node::Rectangle(stroked));
+    let mut group = NodeFactory::new().create_group_node();
+    group.transform = AffineTransform::new(50.0, 50.0, 0.0);
+    group.children = vec![child];
+    let group = repo.insert(Node::Group(group));
+
+    let bounds = scene(vec![group], repo).content_bounds().unwrap();
+    assert_eq!(
+        (bounds.x, bounds.y, bounds.width, bounds.height),
+        (40.0, 40.0, 120.0, 120.0)
+    );
+}
+
+#[test]
+fn inactive_nodes_are_ignored() {
+    let mut hidden = rect(500.0, 500.0);
+    hidden.base.active = false;
+    let mut repo = NodeRepository::new();
+    let a = repo.insert(Node::Rectangle(rect(0.0, 0.0)));
+    let b = repo.insert(Node::Rectangle(hidden));
+
+    let bounds = scene(vec![a, b], repo).content_bounds().unwrap();
+    assert_eq!((bounds.width, bounds.height), (100.0, 100.0));
+}
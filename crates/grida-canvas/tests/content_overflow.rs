@@ -0,0 +1,78 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+
+fn container_with_child(x: f32, y: f32, clip: bool) -> (ContainerNode, NodeRepository) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = AffineTransform::new(x, y, 0.0);
+    rect.size = Size {
+        width: 20.0,
+        height: 20.0,
+    };
+    rect.stroke_width = 0.0;
+    let rect_id = repo.insert(Node::Rectangle(rect));
+
+    let mut container = nf.create_container_node();
+    container.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    container.clip = clip;
+    container.children = vec![rect_id];
+    (container, repo)
+}
+
+#[test]
+fn contained_child_does_not_overflow() {
+    let (container, repo) = container_with_child(40.0, 40.0, true);
+    assert!(!container.content_overflows(&repo));
+}
+
+#[test]
+fn child_outside_bounds_overflows() {
+    let (container, repo) = container_with_child(90.0, 40.0, true);
+    assert!(container.content_overflows(&repo));
+
+    let (container, repo) = container_with_child(-5.0, 40.0, false);
+    assert!(container.content_overflows(&repo));
+}
+
+#[test]
+fn child_shadow_overflows() {
+    let (container, mut repo) = container_with_child(75.0, 40.0, true);
+    if let Some(Node::Rectangle(rect)) = repo.get_mut(&container.children[0]) {
+        rect.effects = vec![FilterEffect::DropShadow(FeDropShadow {
+            dx: 4.0,
+            dy: 4.0,
+            blur: 4.0,
+            spread: 0.0,
+            color: Color(0, 0, 0, 64),
+        })];
+    }
+    assert!(container.content_overflows(&repo));
+}
+
+#[test]
+fn nested_clip_container_bounds_its_children() {
+    let nf = NodeFactory::new();
+    let (mut inner, mut repo) = container_with_child(90.0, 40.0, true);
+    inner.size = Size {
+        width: 50.0,
+        height: 50.0,
+    };
+    let inner_id = repo.insert(Node::Container(inner.clone()));
+
+    let mut outer = nf.create_container_node();
+    outer.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    outer.children = vec![inner_id.clone()];
+    assert!(!outer.content_overflows(&repo));
+
+    inner.clip = false;
+    repo.insert(Node::Container(inner));
+    assert!(outer.content_overflows(&repo));
+}
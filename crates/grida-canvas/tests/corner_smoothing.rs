@@ -0,0 +1,71 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use skia_safe::{Path, Point, RRect, Rect};
+
+fn rectangle(radius: f32, smoothing: f32) -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+    rect.corner_radius = RectangularCornerRadius::all(radius);
+    rect.corner_smoothing = smoothing;
+    rect
+}
+
+#[test]
+fn zero_smoothing_matches_circular_corners() {
+    let path = rectangle(20.0, 0.0).to_path();
+
+    let mut expected = Path::new();
+    expected.add_rrect(
+        RRect::new_rect_xy(Rect::from_wh(200.0, 100.0), 20.0, 20.0),
+        None,
+    );
+    assert_eq!(path, expected);
+}
+
+#[test]
+fn smoothed_corners_add_curve_points() {
+    let circular = rectangle(20.0, 0.0).to_path();
+    let smoothed = rectangle(20.0, 0.6).to_path();
+
+    assert!(
+        smoothed.count_points() > circular.count_points(),
+        "{} <= {}",
+        smoothed.count_points(),
+        circular.count_points()
+    );
+}
+
+#[test]
+fn smoothed_corners_stay_within_bounds() {
+    let path = rectangle(20.0, 1.0).to_path();
+
+    let bounds = path.compute_tight_bounds();
+    assert!((bounds.left - 0.0).abs() < 0.01);
+    assert!((bounds.top - 0.0).abs() < 0.01);
+    assert!((bounds.right - 200.0).abs() < 0.01);
+    assert!((bounds.bottom - 100.0).abs() < 0.01);
+
+    assert!(path.contains(Point::new(100.0, 50.0)));
+    // the corners themselves are rounded off
+    for corner in [(1.0, 1.0), (199.0, 1.0), (199.0, 99.0), (1.0, 99.0)] {
+        assert!(!path.contains(Point::from(corner)), "{corner:?}");
+    }
+    // the edge midpoints are untouched
+    for edge in [(100.0, 0.5), (199.5, 50.0), (100.0, 99.5), (0.5, 50.0)] {
+        assert!(path.contains(Point::from(edge)), "{edge:?}");
+    }
+}
+
+#[test]
+fn smoothing_is_limited_by_the_shorter_side() {
+    // a radius of half the height leaves no room on the short sides for smoothing
+    let path = rectangle(50.0, 1.0).to_path();
+
+    let bounds = path.compute_tight_bounds();
+    assert!((bounds.right - 200.0).abs() < 0.01);
+    assert!((bounds.bottom - 100.0).abs() < 0.01);
+    assert!(path.contains(Point::new(100.0, 50.0)));
+    assert!(path.contains(Point::new(0.5, 50.0)));
+}
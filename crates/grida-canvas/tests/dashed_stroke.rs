@@ -1,5 +1,5 @@
 use cg::node::schema::StrokeAlign;
-use cg::painter::geometry::stroke_geometry;
+use cg::painter::geometry::{dash_intervals, stroke_geometry, StrokeOptions};
 use skia_safe::Path;
 
 #[test]
@@ -8,8 +8,67 @@ fn dashed_stroke_has_more_segments() {
     path.move_to((0.0, 0.0));
     path.line_to((100.0, 0.0));
 
-    let solid = stroke_geometry(&path, 10.0, StrokeAlign::Center, None);
-    let dashed = stroke_geometry(&path, 10.0, StrokeAlign::Center, Some(&vec![10.0, 10.0]));
+    let solid = stroke_geometry(&path, &StrokeOptions::new(10.0, StrokeAlign::Center));
+    let dashed = stroke_geometry(
+        &path,
+        &StrokeOptions {
+            dash_array: Some(&vec![10.0, 10.0]),
+            ..StrokeOptions::new(10.0, StrokeAlign::Center)
+        },
+    );
 
     assert!(dashed.count_verbs() > solid.count_verbs());
 }
+
+#[test]
+fn dash_offset_shifts_pattern() {
+    let mut path = Path::new();
+    path.move_to((0.0, 0.0));
+    path.line_to((100.0, 0.0));
+
+    let dashes = vec![10.0, 10.0];
+    let dashed = stroke_geometry(
+        &path,
+        &StrokeOptions {
+            dash_array: Some(&dashes),
+            ..StrokeOptions::new(10.0, StrokeAlign::Center)
+        },
+    );
+    let shifted = stroke_geometry(
+        &path,
+        &StrokeOptions {
+            dash_array: Some(&dashes),
+            dash_offset: Some(5.0),
+            ..StrokeOptions::new(10.0, StrokeAlign::Center)
+        },
+    );
+
+    assert_eq!(dashed.bounds().left(), 0.0);
+    assert_eq!(shifted.bounds().left(), 0.0);
+    // without a phase the last dash covers 80..90, with a phase of 5 it covers 95..100
+    assert_eq!(dashed.bounds().right(), 90.0);
+    assert_eq!(shifted.bounds().right(), 100.0);
+}
+
+#[test]
+fn odd_length_dash_array_is_doubled() {
+    assert_eq!(
+        dash_intervals(&[5.0, 3.0, 2.0]),
+        vec![5.0, 3.0, 2.0, 5.0, 3.0, 2.0]
+    );
+    assert_eq!(dash_intervals(&[4.0, 2.0]), vec![4.0, 2.0]);
+
+    let mut path = Path::new();
+    path.move_to((0.0, 0.0));
+    path.line_to((100.0, 0.0));
+
+    let solid = stroke_geometry(&path, &StrokeOptions::new(10.0, StrokeAlign::Center));
+    let dashed = stroke_geometry(
+        &path,
+        &StrokeOptions {
+            dash_array: Some(&vec![10.0]),
+            ..StrokeOptions::new(10.0, StrokeAlign::Center)
+        },
+    );
+    assert!(dashed.count_verbs() > solid.count_verbs());
+}
@@ -0,0 +1,68 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::geometry::build_shape;
+use skia_safe::Rect;
+use std::f32::consts::{FRAC_PI_2, PI};
+
+fn ellipse(start_angle: f32, end_angle: f32, inner_radius_ratio: f32) -> EllipseNode {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_ellipse_node();
+    node.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    node.start_angle = start_angle;
+    node.end_angle = end_angle;
+    node.inner_radius_ratio = inner_radius_ratio;
+    node
+}
+
+#[test]
+fn full_ellipse_is_unchanged() {
+    let node = ellipse(0.0, std::f32::consts::TAU, 0.0);
+    assert!(node.is_full_ellipse());
+
+    let shape = build_shape(&IntrinsicSizeNode::Ellipse(node));
+    assert_eq!(shape.oval, Some(Rect::from_xywh(0.0, 0.0, 100.0, 100.0)));
+    assert!(shape.path.is_none());
+}
+
+#[test]
+fn quarter_pie() {
+    let node = ellipse(0.0, FRAC_PI_2, 0.0);
+    assert!(!node.is_full_ellipse());
+
+    let path = build_shape(&IntrinsicSizeNode::Ellipse(node)).to_path();
+    let bounds = path.bounds();
+    assert!((bounds.left - 50.0).abs() < 0.01);
+    assert!((bounds.top - 50.0).abs() < 0.01);
+    assert!((bounds.right - 100.0).abs() < 0.01);
+    assert!((bounds.bottom - 100.0).abs() < 0.01);
+
+    // clockwise from the x axis covers the bottom-right quadrant
+    assert!(path.contains((75.0, 75.0)));
+    assert!(!path.contains((25.0, 25.0)));
+    assert!(!path.contains((75.0, 25.0)));
+}
+
+#[test]
+fn half_donut() {
+    let node = ellipse(0.0, PI, 0.5);
+    let path = build_shape(&IntrinsicSizeNode::Ellipse(node)).to_path();
+
+    // the ring in the bottom half is filled
+    assert!(path.contains((50.0, 90.0)));
+    assert!(path.contains((10.0, 55.0)));
+    // the hole and the top half are empty
+    assert!(!path.contains((50.0, 60.0)));
+    assert!(!path.contains((50.0, 10.0)));
+}
+
+#[test]
+fn full_donut() {
+    let node = ellipse(0.0, std::f32::consts::TAU, 0.5);
+    let path = build_shape(&IntrinsicSizeNode::Ellipse(node)).to_path();
+
+    assert!(path.contains((50.0, 10.0)));
+    assert!(path.contains((50.0, 90.0)));
+    assert!(!path.contains((50.0, 50.0)));
+}
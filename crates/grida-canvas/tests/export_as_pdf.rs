@@ -30,6 +30,9 @@ fn test_pdf_export() {
         children: vec![rect_id.clone()],
         nodes: repo,
         background_color: Some(Color(255, 255, 255, 255)), // White background
+        background_image: None,
+        guides: vec![],
+        constraints: LayoutConstraints::new(),
     };
 
     // Create a geometry cache to get the render bounds
@@ -0,0 +1,108 @@
+use cg::cache::geometry::GeometryCache;
+use cg::export::export_as_atlas::export_nodes_as_atlas;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+
+fn rectangle(x: f32, width: f32, height: f32, color: Color) -> Node {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(x, 0.0, 0.0);
+    rect.size = Size { width, height };
+    rect.fill = Paint::Solid(SolidPaint {
+        color,
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    Node::Rectangle(rect)
+}
+
+/// Three overlapping rectangles of different sizes and colors.
+fn scene() -> (Scene, Vec<(NodeId, Color)>) {
+    let mut repo = NodeRepository::new();
+    let nodes = vec![
+        (
+            rectangle(0.0, 40.0, 20.0, Color(255, 0, 0, 255)),
+            Color(255, 0, 0, 255),
+        ),
+        (
+            rectangle(10.0, 30.0, 50.0, Color(0, 255, 0, 255)),
+            Color(0, 255, 0, 255),
+        ),
+        (
+            rectangle(20.0, 16.0, 16.0, Color(0, 0, 255, 255)),
+            Color(0, 0, 255, 255),
+        ),
+    ];
+    let ids: Vec<(NodeId, Color)> = nodes
+        .into_iter()
+        .map(|(node, color)| (repo.insert(node), color))
+        .collect();
+
+    let scene = Scene {
+        children: ids.iter().map(|(id, _)| id.clone()).collect(),
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    (scene, ids)
+}
+
+#[test]
+fn packs_three_nodes_without_overlap() {
+    let (scene, nodes) = scene();
+    let geometry = GeometryCache::from_scene(&scene);
+    let ids: Vec<NodeId> = nodes.iter().map(|(id, _)| id.clone()).collect();
+
+    let atlas = export_nodes_as_atlas(&scene, &geometry, &ids, 1.0).expect("atlas");
+    assert_eq!(atlas.entries.len(), 3);
+
+    for (i, a) in atlas.entries.iter().enumerate() {
+        let r = &a.rect_in_atlas;
+        assert!(r.x >= 0.0 && r.y >= 0.0);
+        assert!(r.x + r.width <= atlas.width as f32);
+        assert!(r.y + r.height <= atlas.height as f32);
+        for b in &atlas.entries[i + 1..] {
+            // `intersects` counts touching edges, the padding keeps sprites apart
+            assert!(!r.intersects(&b.rect_in_atlas), "{a:?} overlaps {b:?}");
+        }
+    }
+
+    let image = Image::from_encoded(Data::new_copy(&atlas.png)).expect("valid png");
+    assert_eq!((image.width(), image.height()), (atlas.width, atlas.height));
+
+    let mut surface = surfaces::raster_n32_premul((image.width(), image.height())).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.canvas().draw_image(&image, (0, 0), None);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // each sprite holds only its own node, even though the nodes overlap in the scene
+    for (id, color) in &nodes {
+        let entry = atlas.entries.iter().find(|e| &e.node_id == id).unwrap();
+        let r = &entry.rect_in_atlas;
+        for point in [
+            (r.x + 1.0, r.y + 1.0),
+            (r.x + r.width / 2.0, r.y + r.height / 2.0),
+            (r.x + r.width - 1.0, r.y + r.height - 1.0),
+        ] {
+            let sk = pixels.get_color((point.0 as i32, point.1 as i32));
+            assert_eq!(sk, skia_safe::Color::from(*color), "{id} at {point:?}");
+        }
+    }
+}
+
+#[test]
+fn scale_multiplies_sprite_size() {
+    let (scene, nodes) = scene();
+    let geometry = GeometryCache::from_scene(&scene);
+
+    let atlas = export_nodes_as_atlas(&scene, &geometry, &[nodes[0].0.clone()], 2.0).unwrap();
+    let r = &atlas.entries[0].rect_in_atlas;
+    assert_eq!((r.width, r.height), (80.0, 40.0));
+    assert_eq!((atlas.width, atlas.height), (80, 40));
+}
+
+#[test]
+fn nothing_to_pack() {
+    let (scene, _) = scene();
+    let geometry = GeometryCache::from_scene(&scene);
+    assert!(export_nodes_as_atlas(&scene, &geometry, &[], 1.0).is_none());
+}
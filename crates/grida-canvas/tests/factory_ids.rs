@@ -0,0 +1,38 @@
+use cg::node::factory::NodeFactory;
+use std::collections::HashSet;
+
+fn ids(factory: &NodeFactory) -> Vec<String> {
+    vec![
+        factory.create_rectangle_node().base.id,
+        factory.create_ellipse_node().base.id,
+        factory.create_group_node().base.id,
+        factory.create_text_span_node().base.id,
+    ]
+}
+
+#[test]
+fn seeded_factories_produce_identical_ids() {
+    let a = ids(&NodeFactory::with_seed(42));
+    let b = ids(&NodeFactory::with_seed(42));
+    assert_eq!(a, b);
+
+    // unique within the sequence, and still valid uuids
+    let unique: HashSet<&String> = a.iter().collect();
+    assert_eq!(unique.len(), a.len());
+    for id in &a {
+        assert!(uuid::Uuid::parse_str(id).is_ok(), "{id}");
+    }
+
+    assert_ne!(a, ids(&NodeFactory::with_seed(7)));
+}
+
+#[test]
+fn random_factories_differ() {
+    assert_ne!(ids(&NodeFactory::new()), ids(&NodeFactory::new()));
+}
+
+#[test]
+fn prefixed_ids_count_up() {
+    let factory = NodeFactory::with_id_prefix("rect");
+    assert_eq!(ids(&factory), vec!["rect-1", "rect-2", "rect-3", "rect-4"]);
+}
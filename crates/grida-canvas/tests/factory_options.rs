@@ -0,0 +1,116 @@
+use cg::node::factory::{
+    ContainerOptions, EllipseOptions, NodeFactory, RectangleOptions, RegularStarPolygonOptions,
+    TextSpanOptions,
+};
+use cg::node::schema::*;
+use math2::transform::AffineTransform;
+
+fn red() -> Paint {
+    Paint::Solid(SolidPaint {
+        color: Color(255, 0, 0, 255),
+        opacity: 1.0,
+    })
+}
+
+#[test]
+fn rectangle_in_one_call() {
+    let rect = NodeFactory::new().create_rectangle_node_with(RectangleOptions {
+        size: Some(Size {
+            width: 40.0,
+            height: 20.0,
+        }),
+        fill: Some(red()),
+        ..Default::default()
+    });
+    assert_eq!(rect.size.width, 40.0);
+    assert_eq!(rect.size.height, 20.0);
+    assert!(matches!(
+        rect.fill,
+        Paint::Solid(SolidPaint {
+            color: Color(255, 0, 0, 255),
+            ..
+        })
+    ));
+
+    // everything else keeps the factory defaults
+    let default = NodeFactory::new().create_rectangle_node();
+    assert_eq!(rect.stroke_width, default.stroke_width);
+    assert_eq!(rect.opacity, default.opacity);
+    assert_eq!(rect.transform, default.transform);
+    assert_ne!(rect.base.id, default.base.id);
+}
+
+#[test]
+fn ellipse_in_one_call() {
+    let ellipse = NodeFactory::new().create_ellipse_node_with(EllipseOptions {
+        name: Some("dot".to_string()),
+        transform: Some(AffineTransform::new(10.0, 20.0, 0.0)),
+        stroke_width: Some(0.0),
+        ..Default::default()
+    });
+    assert_eq!(ellipse.base.name, "dot");
+    assert_eq!(ellipse.transform, AffineTransform::new(10.0, 20.0, 0.0));
+    assert_eq!(ellipse.stroke_width, 0.0);
+    assert!(ellipse.is_full_ellipse());
+}
+
+#[test]
+fn container_in_one_call() {
+    let container = NodeFactory::new().create_container_node_with(ContainerOptions {
+        name: Some("frame".to_string()),
+        stroke: Some(red()),
+        clip: Some(false),
+        ..Default::default()
+    });
+    assert_eq!(container.base.name, "frame");
+    assert!(matches!(
+        container.stroke,
+        Some(Paint::Solid(SolidPaint {
+            color: Color(255, 0, 0, 255),
+            ..
+        }))
+    ));
+    assert!(!container.clip);
+    assert!(container.children.is_empty());
+
+    let default = NodeFactory::new().create_container_node();
+    assert_eq!(container.size.width, default.size.width);
+    assert_eq!(container.stroke_width, default.stroke_width);
+}
+
+#[test]
+fn text_span_in_one_call() {
+    let text = NodeFactory::new().create_text_span_node_with(TextSpanOptions {
+        text: Some("Hello".to_string()),
+        fill: Some(red()),
+        ..Default::default()
+    });
+    assert_eq!(text.text, "Hello");
+    assert!(matches!(
+        text.fill,
+        Paint::Solid(SolidPaint {
+            color: Color(255, 0, 0, 255),
+            ..
+        })
+    ));
+
+    let default = NodeFactory::new().create_text_span_node();
+    assert_eq!(text.text_style.font_size, default.text_style.font_size);
+    assert_eq!(
+        text.text_style.font_families,
+        default.text_style.font_families
+    );
+}
+
+#[test]
+fn star_options_chain_with_point_count() {
+    let star = NodeFactory::new()
+        .create_regular_star_polygon_node_with(RegularStarPolygonOptions {
+            inner_radius: Some(0.5),
+            ..Default::default()
+        })
+        .with_point_count(8)
+        .expect("8 points is a valid star");
+    assert_eq!(star.inner_radius, 0.5);
+    assert_eq!(star.point_count, 8);
+}
@@ -0,0 +1,121 @@
+use cg::io::io_json::IONode;
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A self-overlapping five-pointed star, its center pentagon is wound twice.
+const STAR: &str = "M50 0 L79 90 L2 35 L98 35 L21 90 Z";
+
+fn black() -> Paint {
+    Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 255),
+        opacity: 1.0,
+    })
+}
+
+/// Draws `node` into a 100x100 surface, returns the number of opaque pixels and whether
+/// the center of the star is filled.
+fn render(node: LeafNode) -> (usize, bool) {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&node);
+
+    let pixels = surface.peek_pixels().unwrap();
+    let mut filled = 0;
+    for y in 0..100 {
+        for x in 0..100 {
+            if pixels.get_color((x, y)).a() > 128 {
+                filled += 1;
+            }
+        }
+    }
+    (filled, pixels.get_color((50, 50)).a() > 128)
+}
+
+fn star_path(fill_rule: FillRule) -> PathNode {
+    let mut path = NodeFactory::new().create_path_node();
+    path.data = STAR.to_string();
+    path.fill = black();
+    path.stroke_width = 0.0;
+    path.fill_rule = fill_rule;
+    path
+}
+
+#[test]
+fn even_odd_leaves_a_hole_in_a_self_overlapping_path() {
+    let (nonzero_area, nonzero_center) = render(LeafNode::Path(star_path(FillRule::NonZero)));
+    let (evenodd_area, evenodd_center) = render(LeafNode::Path(star_path(FillRule::EvenOdd)));
+
+    assert!(nonzero_center);
+    assert!(!evenodd_center);
+    // the center pentagon is about a fifth of the star
+    assert!(
+        evenodd_area < nonzero_area * 9 / 10,
+        "{evenodd_area} {nonzero_area}"
+    );
+    assert!(
+        evenodd_area > nonzero_area / 2,
+        "{evenodd_area} {nonzero_area}"
+    );
+}
+
+#[test]
+fn polygon_fill_rule() {
+    let mut polygon = NodeFactory::new().create_polygon_node();
+    polygon.points = [
+        (50.0, 0.0),
+        (79.0, 90.0),
+        (2.0, 35.0),
+        (98.0, 35.0),
+        (21.0, 90.0),
+    ]
+    .into_iter()
+    .map(|(x, y)| Point { x, y })
+    .collect();
+    polygon.fill = black();
+    polygon.stroke_width = 0.0;
+
+    assert!(render(LeafNode::Polygon(polygon.clone())).1);
+    polygon.fill_rule = FillRule::EvenOdd;
+    assert_eq!(
+        polygon.to_path().fill_type(),
+        skia_safe::PathFillType::EvenOdd
+    );
+    assert!(!render(LeafNode::Polygon(polygon)).1);
+}
+
+#[test]
+fn json_path_fill_rule() {
+    let import = |rule: &str| {
+        let node: IONode = serde_json::from_str(&format!(
+            r#"{{
+                "type": "path",
+                "id": "path",
+                "name": "path",
+                "left": 0,
+                "top": 0,
+                "width": 10,
+                "height": 10
+                {rule}
+            }}"#
+        ))
+        .expect("valid node json");
+        let Node::Path(path) = node.into() else {
+            panic!("expected a path node");
+        };
+        path.fill_rule
+    };
+
+    assert_eq!(import(r#", "fillRule": "evenodd""#), FillRule::EvenOdd);
+    assert_eq!(import(r#", "fillRule": "nonzero""#), FillRule::NonZero);
+    assert_eq!(import(""), FillRule::NonZero);
+}
@@ -0,0 +1,176 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+
+fn assert_transform_eq(actual: &AffineTransform, expected: &AffineTransform) {
+    for (a, e) in actual
+        .matrix
+        .iter()
+        .flatten()
+        .zip(expected.matrix.iter().flatten())
+    {
+        assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+    }
+}
+
+fn scene(transform: AffineTransform, children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        transform,
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// group (rotated) > container (clipping) > group > rectangle, and a hidden ellipse.
+struct Document {
+    scene: Scene,
+    group: AffineTransform,
+    container: AffineTransform,
+    inner: AffineTransform,
+    rect: AffineTransform,
+    rect_id: NodeId,
+    container_id: NodeId,
+}
+
+fn nested() -> Document {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let rect_t = AffineTransform::new(5.0, 7.0, 15.0);
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = rect_t;
+    let rect_id = repo.insert(Node::Rectangle(rect));
+
+    let mut hidden = nf.create_ellipse_node();
+    hidden.base.active = false;
+    let hidden_id = repo.insert(Node::Ellipse(hidden));
+
+    let inner_t = AffineTransform::new(-3.0, 2.0, 0.0);
+    let mut inner = nf.create_group_node();
+    inner.transform = inner_t;
+    inner.opacity = 0.8;
+    inner.children = vec![rect_id.clone(), hidden_id];
+    let inner_id = repo.insert(Node::Group(inner));
+
+    let container_t = AffineTransform::new(40.0, 10.0, -30.0);
+    let mut container = nf.create_container_node();
+    container.transform = container_t;
+    container.opacity = 0.5;
+    container.clip = true;
+    container.children = vec![inner_id];
+    let container_id = repo.insert(Node::Container(container));
+
+    let group_t = AffineTransform::new(100.0, 50.0, 45.0);
+    let mut group = nf.create_group_node();
+    group.transform = group_t;
+    group.opacity = 0.5;
+    group.children = vec![container_id.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    Document {
+        scene: scene(AffineTransform::new(10.0, 20.0, 0.0), vec![group_id], repo),
+        group: group_t,
+        container: container_t,
+        inner: inner_t,
+        rect: rect_t,
+        rect_id,
+        container_id,
+    }
+}
+
+#[test]
+fn leaf_world_transform_is_the_product_of_its_ancestors() {
+    let doc = nested();
+    let ops = doc.scene.flatten();
+
+    // groups and inactive nodes are not emitted, the container draws its box first
+    let ids: Vec<&NodeId> = ops.iter().map(|op| &op.id).collect();
+    assert_eq!(ids, vec![&doc.container_id, &doc.rect_id]);
+
+    let expected = doc
+        .scene
+        .transform
+        .compose(&doc.group)
+        .compose(&doc.container)
+        .compose(&doc.inner)
+        .compose(&doc.rect);
+    let rect = &ops[1];
+    assert!(matches!(rect.node, LeafNode::Rectangle(_)));
+    assert_transform_eq(&rect.transform, &expected);
+    assert!((rect.opacity - 0.5 * 0.5 * 0.8).abs() < 1e-6);
+
+    let container = &ops[0];
+    assert!(matches!(container.node, LeafNode::Rectangle(_)));
+    assert_transform_eq(
+        &container.transform,
+        &doc.scene
+            .transform
+            .compose(&doc.group)
+            .compose(&doc.container),
+    );
+    assert!((container.opacity - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn clipping_containers_clip_their_descendants() {
+    let doc = nested();
+    let ops = doc.scene.flatten();
+    assert!(ops[0].clips.is_empty());
+    assert_eq!(ops[1].clips.len(), 1);
+
+    // the clip is the container box, placed in scene space
+    let world = doc
+        .scene
+        .transform
+        .compose(&doc.group)
+        .compose(&doc.container);
+    let [x, y] = math2::vector2::transform([50.0, 50.0], &world);
+    let clip = &ops[1].clips[0];
+    assert!(clip.contains((x, y)));
+    assert!(!clip.contains((0.0, 0.0)));
+}
+
+#[test]
+fn boolean_operations_are_emitted_as_their_result() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let a = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+    let mut b = nf.create_rectangle_node();
+    b.transform = AffineTransform::new(50.0, 50.0, 0.0);
+    let b = repo.insert(Node::Rectangle(b));
+    let boolean_id = repo.insert(Node::BooleanOperation(BooleanPathOperationNode {
+        base: BaseNode {
+            id: "boolean".to_string(),
+            name: "boolean".to_string(),
+            active: true,
+            locked: false,
+            render_order: None,
+        },
+        transform: AffineTransform::new(10.0, 0.0, 0.0),
+        op: BooleanPathOperation::Union,
+        children: vec![a, b],
+        fill: Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        stroke: None,
+        stroke_width: 0.0,
+        stroke_align: StrokeAlign::Inside,
+        stroke_dash_array: None,
+        stroke_dash_offset: None,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        effects: vec![],
+    }));
+
+    let ops = scene(AffineTransform::identity(), vec![boolean_id.clone()], repo).flatten();
+    assert_eq!(ops.len(), 1);
+    assert_eq!(ops[0].id, boolean_id);
+    let LeafNode::Path(path) = &ops[0].node else {
+        panic!("expected a path");
+    };
+    let outline = skia_safe::Path::from_svg(&path.data).expect("valid path data");
+    assert!(outline.contains((25.0, 25.0)));
+    assert!(outline.contains((125.0, 125.0)));
+    assert_transform_eq(&ops[0].transform, &AffineTransform::new(10.0, 0.0, 0.0));
+}
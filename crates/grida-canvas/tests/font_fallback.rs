@@ -0,0 +1,13 @@
+use cg::node::{factory::NodeFactory, schema::*};
+
+fn style(families: &[&str]) -> TextStyle {
+    let mut style = NodeFactory::new().create_text_span_node().text_style;
+    style.font_families = families.iter().map(|f| f.to_string()).collect();
+    style
+}
+
+#[test]
+fn font_family_is_the_first_entry() {
+    assert_eq!(style(&["Geist", "Noto Color Emoji"]).font_family(), "Geist");
+    assert_eq!(style(&[]).font_family(), "");
+}
@@ -0,0 +1,109 @@
+use cg::cache::paragraph::ParagraphCache;
+use cg::node::schema::*;
+use cg::runtime::font_registry::FontRegistry;
+use cg::runtime::repository::FontRepository;
+use skia_safe::FontStyle;
+
+const REGULAR: &[u8] = include_bytes!("../fonts/Geist/static/Geist-Regular.ttf");
+const BOLD: &[u8] = include_bytes!("../fonts/Geist/static/Geist-Bold.ttf");
+
+fn style(family: &str, weight: u32) -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: vec![family.to_string()],
+        font_size: 16.0,
+        font_weight: FontWeight::new(weight),
+        italic: false,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
+    }
+}
+
+/// Lays out "Hello" and returns the weight of the face it resolved to.
+fn resolved_weight(fonts: &FontRepository, style: &TextStyle) -> Option<i32> {
+    let paragraph = ParagraphCache::new().get_or_create(
+        &"text".to_string(),
+        "Hello",
+        &Size {
+            width: 1000.0,
+            height: 100.0,
+        },
+        &Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        &TextAlign::Left,
+        &TextAlignVertical::Top,
+        None,
+        TextOverflow::Clip,
+        style,
+        fonts,
+    );
+    let font = &paragraph.get_fonts().first()?.font;
+    Some(*font.typeface().font_style().weight())
+}
+
+#[test]
+fn registers_several_weights_under_one_family() {
+    let mut registry = FontRegistry::new();
+    let regular = registry.register("Brand Sans", REGULAR).expect("a font");
+    let bold = registry.register("Brand Sans", BOLD).expect("a font");
+    assert_eq!(*regular.weight(), 400);
+    assert_eq!(*bold.weight(), 700);
+
+    assert!(registry.contains("Brand Sans"));
+    assert_eq!(registry.styles("Brand Sans").len(), 2);
+    // the family name stored in the files is not registered
+    assert!(!registry.contains("Geist"));
+
+    let matched = registry
+        .match_typeface("Brand Sans", FontStyle::bold())
+        .expect("a registered face");
+    assert_eq!(*matched.font_style().weight(), 700);
+    assert!(registry
+        .match_typeface("Missing", FontStyle::normal())
+        .is_none());
+}
+
+#[test]
+fn invalid_bytes_are_not_registered() {
+    let mut registry = FontRegistry::new();
+    assert!(registry.register("Broken", b"not a font").is_none());
+    assert!(!registry.contains("Broken"));
+}
+
+#[test]
+fn paragraph_resolves_to_the_registered_font() {
+    let mut fonts = FontRepository::new();
+    fonts.set_system_fonts(false);
+    fonts.add(REGULAR, "Brand Sans");
+    fonts.add(BOLD, "Brand Sans");
+
+    assert_eq!(
+        resolved_weight(&fonts, &style("Brand Sans", 400)),
+        Some(400)
+    );
+    assert_eq!(
+        resolved_weight(&fonts, &style("Brand Sans", 700)),
+        Some(700)
+    );
+    // the closest registered weight is used
+    assert_eq!(
+        resolved_weight(&fonts, &style("Brand Sans", 800)),
+        Some(700)
+    );
+}
+
+#[test]
+fn removed_family_is_no_longer_resolved() {
+    use cg::runtime::repository::ResourceRepository;
+
+    let mut fonts = FontRepository::new();
+    fonts.add(REGULAR, "Brand Sans");
+    let generation = fonts.generation();
+    assert!(fonts.remove(&"Brand Sans".to_string()).is_some());
+    assert!(fonts.generation() > generation);
+    assert!(!fonts.registry().contains("Brand Sans"));
+}
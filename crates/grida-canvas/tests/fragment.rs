@@ -0,0 +1,135 @@
+use cg::cache::geometry::GeometryCache;
+use cg::io::io_json::{
+    convert_nodes, parse, parse_fragment, serialize_fragment, IOCanvasFile, IONode,
+};
+use cg::node::schema::*;
+use std::collections::HashMap;
+
+/// A container with a nested rectangle, a rotated ellipse and a rectangle that is not copied.
+fn file() -> IOCanvasFile {
+    parse(
+        r#"{
+            "version": "0.0.1-beta.1+20250303",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {
+                    "container": {
+                        "type": "container",
+                        "id": "container",
+                        "name": "container",
+                        "left": 20.0,
+                        "top": 30.0,
+                        "width": 100,
+                        "height": 80,
+                        "children": ["inner"]
+                    },
+                    "inner": {
+                        "type": "rectangle",
+                        "id": "inner",
+                        "name": "inner",
+                        "left": 10.0,
+                        "top": 5.0,
+                        "width": 40.0,
+                        "height": 20.0
+                    },
+                    "ellipse": {
+                        "type": "ellipse",
+                        "id": "ellipse",
+                        "name": "ellipse",
+                        "left": 200.0,
+                        "top": 50.0,
+                        "width": 60.0,
+                        "height": 30.0,
+                        "rotation": 30.0
+                    },
+                    "other": {
+                        "type": "rectangle",
+                        "id": "other",
+                        "name": "other",
+                        "left": 400.0,
+                        "top": 0.0,
+                        "width": 10.0,
+                        "height": 10.0
+                    }
+                },
+                "scenes": {
+                    "main": {
+                        "id": "main",
+                        "name": "main",
+                        "type": "scene",
+                        "children": ["container", "ellipse", "other"]
+                    }
+                },
+                "entry_scene_id": "main"
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+fn scene(roots: Vec<String>, nodes: HashMap<String, IONode>) -> Scene {
+    Scene {
+        children: roots,
+        nodes: convert_nodes(nodes),
+        ..Scene::new("scene", "test")
+    }
+}
+
+#[test]
+fn fragment_contains_selection_and_descendants() {
+    let file = file();
+    let ids = vec!["container".to_string(), "ellipse".to_string()];
+    let json = serialize_fragment(&file.document, &ids, 3).unwrap();
+    let fragment = parse_fragment(&json).unwrap();
+
+    assert_eq!(fragment.roots, ids);
+    let mut keys: Vec<&String> = fragment.nodes.keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["container", "ellipse", "inner"]);
+}
+
+#[test]
+fn nested_selection_is_not_a_root() {
+    let file = file();
+    let ids = vec!["inner".to_string(), "container".to_string()];
+    let fragment = parse_fragment(&serialize_fragment(&file.document, &ids, 3).unwrap()).unwrap();
+    assert_eq!(fragment.roots, vec!["container"]);
+    assert_eq!(fragment.nodes.len(), 2);
+}
+
+#[test]
+fn pasted_fragment_has_identical_geometry() {
+    let file = file();
+    let ids = vec!["container".to_string(), "ellipse".to_string()];
+    let json = serialize_fragment(&file.document, &ids, 3).unwrap();
+
+    let mut fragment = parse_fragment(&json).unwrap();
+    let mapping = fragment.remap_ids();
+    assert_eq!(mapping.len(), 3);
+    assert!(mapping.iter().all(|(old, new)| old != new));
+
+    let IOCanvasFile { document, .. } = file;
+    let original = scene(ids.clone(), document.nodes);
+    let pasted = scene(fragment.roots, fragment.nodes);
+    let original_cache = GeometryCache::from_scene(&original);
+    let pasted_cache = GeometryCache::from_scene(&pasted);
+
+    for (old, new) in &mapping {
+        assert!(pasted.nodes.get(new).is_some(), "missing {new}");
+        assert_eq!(
+            original_cache.get_world_bounds(old),
+            pasted_cache.get_world_bounds(new),
+            "{old}"
+        );
+        assert_eq!(
+            original_cache
+                .get_world_transform(old)
+                .map(|transform| transform.matrix),
+            pasted_cache
+                .get_world_transform(new)
+                .map(|transform| transform.matrix),
+            "{old}"
+        );
+    }
+}
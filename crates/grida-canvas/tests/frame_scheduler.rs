@@ -0,0 +1,167 @@
+use cg::sys::scheduler::{FramePacing, FrameScheduler, FrameStats};
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+/// A 100 fps scheduler, 10ms per frame.
+fn accumulating() -> FrameScheduler {
+    FrameScheduler::new(100).with_pacing(FramePacing::Accumulate)
+}
+
+#[test]
+fn sleep_pacing_never_skips() {
+    let mut scheduler = FrameScheduler::new(100);
+    for _ in 0..10 {
+        assert!(!scheduler.should_skip_frame(ms(50)));
+        assert!(!scheduler.record_frame(ms(50)));
+    }
+    assert_eq!(scheduler.lag(), Duration::ZERO);
+    assert_eq!(scheduler.skipped_frames(), 0);
+}
+
+#[test]
+fn renders_within_budget_do_not_skip() {
+    let mut scheduler = accumulating();
+    for _ in 0..100 {
+        assert!(!scheduler.record_frame(ms(9)));
+    }
+    assert_eq!(scheduler.lag(), Duration::ZERO);
+}
+
+#[test]
+fn long_overrun_skips_until_caught_up() {
+    let mut scheduler = accumulating();
+    assert_eq!(scheduler.frame_budget(), ms(10));
+
+    // 25ms behind after the render, the next two frames are skipped
+    assert!(scheduler.should_skip_frame(ms(35)));
+    assert!(scheduler.record_frame(ms(35)));
+    assert_eq!(scheduler.lag(), ms(15));
+    assert!(scheduler.record_frame(ms(5)));
+    assert_eq!(scheduler.lag(), Duration::ZERO);
+    assert!(!scheduler.record_frame(ms(5)));
+    assert_eq!(scheduler.skipped_frames(), 2);
+}
+
+#[test]
+fn small_overruns_accumulate_into_a_skip() {
+    let mut scheduler = accumulating();
+    let skips: Vec<bool> = (0..10).map(|_| scheduler.record_frame(ms(12))).collect();
+    // every fifth 2ms overrun adds up to a whole frame
+    assert_eq!(
+        skips,
+        vec![false, false, false, false, true, false, false, false, false, true]
+    );
+    assert_eq!(scheduler.skipped_frames(), 2);
+}
+
+#[test]
+fn fast_frames_pay_back_the_lag() {
+    let mut scheduler = accumulating();
+    assert!(!scheduler.record_frame(ms(18)));
+    assert_eq!(scheduler.lag(), ms(8));
+    assert!(!scheduler.record_frame(ms(4)));
+    assert_eq!(scheduler.lag(), ms(2));
+    assert!(!scheduler.should_skip_frame(ms(17)));
+    assert!(scheduler.should_skip_frame(ms(18)));
+}
+
+#[test]
+fn stats_are_empty_without_frames() {
+    assert_eq!(FrameScheduler::new(60).stats(), FrameStats::default());
+}
+
+#[test]
+fn stats_summarize_recorded_frames() {
+    let mut scheduler = FrameScheduler::new(60);
+    for duration in [10, 20, 30] {
+        scheduler.record_frame_duration(ms(duration));
+    }
+
+    let stats = scheduler.stats();
+    // 20ms on average
+    assert!((stats.avg_fps - 50.0).abs() < 0.01, "{stats:?}");
+    assert!((stats.min_frame_ms - 10.0).abs() < 0.001, "{stats:?}");
+    assert!((stats.max_frame_ms - 30.0).abs() < 0.001, "{stats:?}");
+    assert_eq!(stats.dropped, 0);
+}
+
+#[test]
+fn stats_cover_a_rolling_window() {
+    let mut scheduler = accumulating();
+    scheduler.record_frame_duration(ms(100));
+    assert!(scheduler.record_frame(ms(100)));
+    assert_eq!(scheduler.stats().dropped, 1);
+
+    // the lag is paid back within a few frames, after which the slow frame and its
+    // skips leave the 60 frame window
+    for _ in 0..70 {
+        scheduler.record_frame_duration(ms(10));
+        scheduler.record_frame(ms(1));
+    }
+    let stats = scheduler.stats();
+    assert_eq!(stats.dropped, 0);
+    assert!((stats.max_frame_ms - 10.0).abs() < 0.001, "{stats:?}");
+    assert!((stats.avg_fps - 100.0).abs() < 0.01, "{stats:?}");
+}
+
+/// Feeds `count` ticks `interval_ms` apart, returns which rendered.
+fn ticks(scheduler: &mut FrameScheduler, interval_ms: f64, count: usize) -> Vec<bool> {
+    (0..count)
+        .map(|i| scheduler.tick(1000.0 + i as f64 * interval_ms))
+        .collect()
+}
+
+#[test]
+fn tick_caps_a_high_refresh_display() {
+    // a 120Hz display capped at 60 fps renders every other tick
+    let mut scheduler = FrameScheduler::new(60).with_max_fps(60);
+    let rendered = ticks(&mut scheduler, 1000.0 / 120.0, 9);
+    assert_eq!(
+        rendered,
+        vec![true, false, true, false, true, false, true, false, true]
+    );
+    assert!((scheduler.stats().avg_fps - 60.0).abs() < 0.5);
+}
+
+#[test]
+fn tick_caps_a_refresh_rate_that_does_not_divide_the_budget() {
+    // a 144Hz display capped at 60 fps renders every second or third tick
+    let mut scheduler = FrameScheduler::new(60).with_max_fps(60);
+    let rendered = ticks(&mut scheduler, 1000.0 / 144.0, 288);
+    let count = rendered.into_iter().filter(|r| *r).count();
+    assert!((119..=121).contains(&count), "{count}");
+    let stats = scheduler.stats();
+    assert!((stats.avg_fps - 60.0).abs() < 0.5, "{stats:?}");
+}
+
+#[test]
+fn tick_renders_every_frame_below_the_cap() {
+    let mut scheduler = FrameScheduler::new(60).with_max_fps(60);
+    assert!(ticks(&mut scheduler, 20.0, 10).into_iter().all(|r| r));
+    let stats = scheduler.stats();
+    assert!((stats.avg_fps - 50.0).abs() < 0.5, "{stats:?}");
+    assert!((stats.min_frame_ms - 20.0).abs() < 0.01, "{stats:?}");
+}
+
+#[test]
+fn tick_tolerates_timestamp_jitter() {
+    let mut scheduler = FrameScheduler::new(60).with_max_fps(60);
+    assert!(scheduler.tick(0.0));
+    // slightly early, still the next display frame
+    assert!(scheduler.tick(16.2));
+    assert!(!scheduler.tick(20.0));
+    assert!(scheduler.tick(32.9));
+}
+
+#[test]
+fn tick_restarts_when_time_goes_backwards() {
+    let mut scheduler = FrameScheduler::new(60);
+    assert!(scheduler.tick(500.0));
+    assert!(scheduler.tick(100.0));
+    assert!(!scheduler.tick(105.0));
+    // nothing recorded for the jump back
+    assert_eq!(scheduler.stats(), FrameStats::default());
+}
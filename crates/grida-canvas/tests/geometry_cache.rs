@@ -31,12 +31,9 @@ fn geometry_cache_builds_recursively() {
     repo.insert(Node::Container(container));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![container_id.clone()],
         nodes: repo.clone(),
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let cache = GeometryCache::from_scene(&scene);
@@ -76,12 +73,9 @@ fn container_world_bounds_include_children() {
     repo.insert(Node::Container(container));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![container_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let cache = GeometryCache::from_scene(&scene);
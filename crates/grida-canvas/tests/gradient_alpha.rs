@@ -0,0 +1,105 @@
+use cg::node::schema::*;
+use cg::painter::cvt;
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Rect};
+
+const WIDTH: i32 = 101;
+
+/// Draws `paint` into a transparent 101x1 surface and returns the unpremultiplied colors.
+fn render(paint: &Paint) -> Vec<skia_safe::Color> {
+    let mut surface = surfaces::raster_n32_premul((WIDTH, 1)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    canvas.draw_rect(
+        Rect::from_wh(WIDTH as f32, 1.0),
+        &cvt::sk_paint(paint, 1.0, (WIDTH as f32, 1.0)),
+    );
+    let pixels = surface.peek_pixels().unwrap();
+    (0..WIDTH).map(|x| pixels.get_color((x, 0))).collect()
+}
+
+fn white_to_transparent() -> Vec<GradientStop> {
+    vec![
+        GradientStop {
+            offset: 0.0,
+            color: Color(255, 255, 255, 255),
+        },
+        GradientStop {
+            offset: 1.0,
+            color: Color(0, 0, 0, 0),
+        },
+    ]
+}
+
+#[test]
+fn transparent_stop_does_not_darken_midpoint() {
+    let colors = render(&Paint::LinearGradient(LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: white_to_transparent(),
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    }));
+
+    let mid = colors[(WIDTH / 2) as usize];
+    assert!(
+        mid.r() >= 250 && mid.g() >= 250 && mid.b() >= 250,
+        "{mid:?}"
+    );
+    assert!((120..=136).contains(&mid.a()), "{mid:?}");
+
+    // the color stays white while the alpha fades out
+    for color in &colors[..(WIDTH - 4) as usize] {
+        assert!(color.r() >= 240, "{color:?}");
+    }
+    assert!(colors[0].a() >= 250);
+}
+
+#[test]
+fn transparent_stop_does_not_darken_radial_gradient() {
+    let size = 101.0;
+    let mut surface = surfaces::raster_n32_premul((101, 101)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let paint = Paint::RadialGradient(RadialGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: white_to_transparent(),
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    });
+    canvas.draw_rect(
+        Rect::from_wh(size, size),
+        &cvt::sk_paint(&paint, 1.0, (size, size)),
+    );
+
+    // halfway between the center and the edge of the radius
+    let pixels = surface.peek_pixels().unwrap();
+    let mid = pixels.get_color((75, 50));
+    assert!(
+        mid.r() >= 250 && mid.g() >= 250 && mid.b() >= 250,
+        "{mid:?}"
+    );
+    assert!((100..=156).contains(&mid.a()), "{mid:?}");
+}
+
+#[test]
+fn paint_opacity_scales_stop_alpha() {
+    let colors = render(&Paint::LinearGradient(LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![
+            GradientStop {
+                offset: 0.0,
+                color: Color(255, 0, 0, 255),
+            },
+            GradientStop {
+                offset: 1.0,
+                color: Color(255, 0, 0, 255),
+            },
+        ],
+        opacity: 0.5,
+        spread: GradientSpread::Pad,
+    }));
+
+    let mid = colors[(WIDTH / 2) as usize];
+    assert!(mid.r() >= 250 && mid.g() <= 4, "{mid:?}");
+    assert!((124..=132).contains(&mid.a()), "{mid:?}");
+}
@@ -0,0 +1,70 @@
+use cg::node::schema::*;
+use math2::transform::AffineTransform;
+
+const SIZE: Size = Size {
+    width: 200.0,
+    height: 100.0,
+};
+
+fn assert_point_eq(actual: Point, expected: Point) {
+    assert!(
+        (actual.x - expected.x).abs() < 1e-4 && (actual.y - expected.y).abs() < 1e-4,
+        "expected {:?}, got {:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn linear_gradient_default_handles_span_the_width() {
+    let gradient = LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![],
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    };
+
+    let (start, end) = gradient.handles(&SIZE);
+    assert_point_eq(start, Point { x: 0.0, y: 0.0 });
+    assert_point_eq(end, Point { x: 200.0, y: 0.0 });
+}
+
+#[test]
+fn linear_gradient_handles_round_trip() {
+    let mut gradient = LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![],
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    };
+
+    let start = Point { x: 20.0, y: 30.0 };
+    let end = Point { x: 120.0, y: 90.0 };
+    gradient.set_handles(&SIZE, start, end);
+
+    let (actual_start, actual_end) = gradient.handles(&SIZE);
+    assert_point_eq(actual_start, start);
+    assert_point_eq(actual_end, end);
+}
+
+#[test]
+fn radial_gradient_handles_round_trip() {
+    let mut gradient = RadialGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![],
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    };
+
+    let (center, radius) = gradient.handles(&SIZE);
+    assert_point_eq(center, Point { x: 100.0, y: 50.0 });
+    assert_point_eq(radius, Point { x: 150.0, y: 50.0 });
+
+    let center = Point { x: 40.0, y: 60.0 };
+    let radius = Point { x: 40.0, y: 140.0 };
+    gradient.set_handles(&SIZE, center, radius);
+
+    let (actual_center, actual_radius) = gradient.handles(&SIZE);
+    assert_point_eq(actual_center, center);
+    assert_point_eq(actual_radius, radius);
+}
@@ -0,0 +1,88 @@
+use cg::io::io_json::Fill;
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const WIDTH: i32 = 101;
+
+/// A white gradient whose alpha fades from 0.8 to 0.4, read from JSON with `opacity`.
+fn gradient(opacity: f32) -> Paint {
+    let json = format!(
+        r#"{{
+            "type": "linear_gradient",
+            "stops": [
+                {{ "offset": 0.0, "color": {{ "r": 255, "g": 255, "b": 255, "a": 0.8 }} }},
+                {{ "offset": 1.0, "color": {{ "r": 255, "g": 255, "b": 255, "a": 0.4 }} }}
+            ],
+            "opacity": {opacity}
+        }}"#
+    );
+    let fill: Fill = serde_json::from_str(&json).unwrap();
+    Some(fill).into()
+}
+
+/// Draws a rectangle filled with `paint` at `opacity` and returns the alpha of each column.
+fn render(paint: Paint, opacity: f32) -> Vec<u8> {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = Size {
+        width: WIDTH as f32,
+        height: 1.0,
+    };
+    rect.fill = paint;
+    rect.stroke_width = 0.0;
+    rect.opacity = opacity;
+
+    let mut surface = surfaces::raster_n32_premul((WIDTH, 1)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect));
+
+    let pixels = surface.peek_pixels().unwrap();
+    (0..WIDTH).map(|x| pixels.get_color((x, 0)).a()).collect()
+}
+
+#[test]
+fn opacity_defaults_to_one() {
+    let fill: Fill = serde_json::from_str(r#"{ "type": "radial_gradient", "stops": [] }"#).unwrap();
+    let Paint::RadialGradient(paint) = Paint::from(Some(fill)) else {
+        panic!("expected a radial gradient");
+    };
+    assert_eq!(paint.opacity, 1.0);
+}
+
+#[test]
+fn stop_alpha_multiplies_gradient_and_node_opacity() {
+    let alphas = render(gradient(0.5), 0.5);
+
+    // 0.8 * 0.5 * 0.5 at the first stop, 0.4 * 0.5 * 0.5 at the last
+    let expected = [
+        (0, 0.8 * 0.25 * 255.0),
+        ((WIDTH - 1) as usize, 0.4 * 0.25 * 255.0),
+    ];
+    for (x, alpha) in expected {
+        let actual = alphas[x] as f32;
+        assert!((actual - alpha).abs() <= 3.0, "x={x}: {actual} != {alpha}");
+    }
+}
+
+#[test]
+fn half_opacity_halves_every_stop() {
+    let full = render(gradient(1.0), 1.0);
+    let half = render(gradient(0.5), 1.0);
+
+    for (full, half) in full.iter().zip(&half) {
+        let expected = *full as f32 / 2.0;
+        assert!(
+            (*half as f32 - expected).abs() <= 2.0,
+            "{half} != {expected}"
+        );
+    }
+}
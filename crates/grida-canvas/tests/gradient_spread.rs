@@ -0,0 +1,100 @@
+use cg::io::io_json::Fill;
+use cg::node::schema::*;
+use cg::painter::cvt;
+use math2::transform::AffineTransform;
+use serde_json::json;
+use skia_safe::{surfaces, Rect};
+
+const WIDTH: i32 = 100;
+
+/// A red to blue linear gradient squeezed into the first quarter of the fill.
+fn quarter_gradient(spread: GradientSpread) -> Paint {
+    Paint::LinearGradient(LinearGradientPaint {
+        transform: AffineTransform::from_acebdf(0.25, 0.0, 0.0, 0.0, 1.0, 0.0),
+        stops: vec![
+            GradientStop {
+                offset: 0.0,
+                color: Color(255, 0, 0, 255),
+            },
+            GradientStop {
+                offset: 1.0,
+                color: Color(0, 0, 255, 255),
+            },
+        ],
+        opacity: 1.0,
+        spread,
+    })
+}
+
+/// Draws `paint` into a 100x1 surface and returns the colors of the row.
+fn render(paint: &Paint) -> Vec<skia_safe::Color> {
+    let mut surface = surfaces::raster_n32_premul((WIDTH, 1)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    canvas.draw_rect(
+        Rect::from_wh(WIDTH as f32, 1.0),
+        &cvt::sk_paint(paint, 1.0, (WIDTH as f32, 1.0)),
+    );
+    let pixels = surface.peek_pixels().unwrap();
+    (0..WIDTH).map(|x| pixels.get_color((x, 0))).collect()
+}
+
+/// Counts the places where the row jumps back from blue to red.
+fn band_restarts(colors: &[skia_safe::Color]) -> usize {
+    colors
+        .windows(2)
+        .filter(|w| w[1].r() as i32 - w[0].r() as i32 > 128)
+        .count()
+}
+
+#[test]
+fn pad_gradient_clamps_to_last_stop() {
+    let colors = render(&quarter_gradient(GradientSpread::Pad));
+    assert_eq!(band_restarts(&colors), 0);
+    for color in &colors[30..] {
+        assert!(color.b() >= 250 && color.r() <= 5, "{color:?}");
+    }
+}
+
+#[test]
+fn repeat_gradient_produces_multiple_bands() {
+    let colors = render(&quarter_gradient(GradientSpread::Repeat));
+    assert_eq!(band_restarts(&colors), 3);
+    for x in [1, 26, 51, 76] {
+        let color = colors[x];
+        assert!(color.r() >= 230 && color.b() <= 25, "x={x} {color:?}");
+    }
+}
+
+#[test]
+fn reflect_gradient_mirrors_every_other_band() {
+    let colors = render(&quarter_gradient(GradientSpread::Reflect));
+    assert_eq!(band_restarts(&colors), 0);
+    // blue at the end of the first band, red again at the end of the second
+    assert!(colors[26].b() >= 230, "{:?}", colors[26]);
+    assert!(colors[49].r() >= 230, "{:?}", colors[49]);
+}
+
+#[test]
+fn json_spread_method() {
+    let fill: Fill = serde_json::from_value(json!({
+        "type": "linear_gradient",
+        "stops": [],
+        "spreadMethod": "reflect"
+    }))
+    .unwrap();
+    match Paint::from(Some(fill)) {
+        Paint::LinearGradient(gradient) => assert_eq!(gradient.spread, GradientSpread::Reflect),
+        other => panic!("unexpected paint {other:?}"),
+    }
+
+    let fill: Fill = serde_json::from_value(json!({
+        "type": "radial_gradient",
+        "stops": []
+    }))
+    .unwrap();
+    match Paint::from(Some(fill)) {
+        Paint::RadialGradient(gradient) => assert_eq!(gradient.spread, GradientSpread::Pad),
+        other => panic!("unexpected paint {other:?}"),
+    }
+}
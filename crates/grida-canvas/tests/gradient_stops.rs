@@ -0,0 +1,99 @@
+use cg::io::io_json::Fill;
+use cg::node::schema::*;
+
+fn stop(offset: f32, color: Color) -> GradientStop {
+    GradientStop { offset, color }
+}
+
+const RED: Color = Color(255, 0, 0, 255);
+const GREEN: Color = Color(0, 255, 0, 255);
+const BLUE: Color = Color(0, 0, 255, 255);
+
+#[test]
+fn sorts_reversed_stops() {
+    let mut stops = vec![stop(1.0, BLUE), stop(0.5, GREEN), stop(0.0, RED)];
+    let malformed = normalize_stops(&mut stops);
+
+    assert_eq!(
+        stops,
+        vec![stop(0.0, RED), stop(0.5, GREEN), stop(1.0, BLUE)]
+    );
+    assert_eq!(malformed, 2);
+}
+
+#[test]
+fn clamps_out_of_range_offsets() {
+    let mut stops = vec![stop(-0.2, RED), stop(0.5, GREEN), stop(1.5, BLUE)];
+    let malformed = normalize_stops(&mut stops);
+
+    assert_eq!(
+        stops,
+        vec![stop(0.0, RED), stop(0.5, GREEN), stop(1.0, BLUE)]
+    );
+    assert_eq!(malformed, 2);
+}
+
+#[test]
+fn clamped_offsets_collapse_onto_the_ends() {
+    let mut stops = vec![
+        stop(-0.2, RED),
+        stop(-0.1, GREEN),
+        stop(0.0, BLUE),
+        stop(1.5, RED),
+    ];
+    normalize_stops(&mut stops);
+
+    // the stop in the middle of the run at 0.0 is never visible
+    assert_eq!(stops, vec![stop(0.0, RED), stop(0.0, BLUE), stop(1.0, RED)]);
+}
+
+#[test]
+fn keeps_hard_edges_and_drops_duplicates() {
+    let mut stops = vec![
+        stop(0.0, RED),
+        stop(0.5, RED),
+        stop(0.5, BLUE),
+        stop(1.0, BLUE),
+        stop(1.0, BLUE),
+    ];
+    let malformed = normalize_stops(&mut stops);
+
+    assert_eq!(
+        stops,
+        vec![
+            stop(0.0, RED),
+            stop(0.5, RED),
+            stop(0.5, BLUE),
+            stop(1.0, BLUE)
+        ]
+    );
+    assert_eq!(malformed, 1);
+}
+
+#[test]
+fn well_formed_stops_are_untouched() {
+    let expected = vec![stop(0.0, RED), stop(0.25, GREEN), stop(1.0, BLUE)];
+    let mut stops = expected.clone();
+
+    assert_eq!(normalize_stops(&mut stops), 0);
+    assert_eq!(stops, expected);
+}
+
+#[test]
+fn json_fill_is_normalized() {
+    let fill: Fill = serde_json::from_str(
+        r#"{
+            "type": "linear_gradient",
+            "stops": [
+                { "offset": 1.5, "color": { "r": 0, "g": 0, "b": 255, "a": 1.0 } },
+                { "offset": -0.2, "color": { "r": 255, "g": 0, "b": 0, "a": 1.0 } }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let Paint::LinearGradient(paint) = Paint::from(Some(fill)) else {
+        panic!("expected a linear gradient");
+    };
+    assert_eq!(paint.stops, vec![stop(0.0, RED), stop(1.0, BLUE)]);
+}
@@ -0,0 +1,59 @@
+use cg::node::schema::*;
+use cg::painter::GrainConfig;
+use cg::runtime::{
+    camera::Camera2D,
+    scene::{Backend, Renderer},
+};
+use skia_safe::surfaces;
+
+const SIZE: i32 = 64;
+
+/// Renders an empty mid gray scene with `grain` and returns the pixels.
+fn render(grain: Option<GrainConfig>) -> Vec<u8> {
+    let scene = Scene {
+        background_color: Some(Color(128, 128, 128, 255)),
+        ..Scene::new("scene", "test")
+    };
+
+    let mut camera = Camera2D::new(Size {
+        width: SIZE as f32,
+        height: SIZE as f32,
+    });
+    camera.set_center(SIZE as f32 / 2.0, SIZE as f32 / 2.0);
+    let mut renderer = Renderer::new(Backend::new_from_raster(SIZE, SIZE), None, camera);
+    renderer.options.grain = grain;
+    renderer.load_scene(scene);
+
+    let mut surface = surfaces::raster_n32_premul((SIZE, SIZE)).unwrap();
+    renderer.render_to_canvas(surface.canvas(), SIZE as f32, SIZE as f32);
+    renderer.free();
+
+    let pixels = surface.peek_pixels().unwrap();
+    pixels.bytes().unwrap().to_vec()
+}
+
+fn grain(seed: u32) -> Option<GrainConfig> {
+    Some(GrainConfig {
+        intensity: 0.5,
+        scale: 2.0,
+        seed,
+    })
+}
+
+#[test]
+fn same_seed_renders_identically() {
+    assert_eq!(render(grain(7)), render(grain(7)));
+}
+
+#[test]
+fn different_seeds_differ() {
+    assert_ne!(render(grain(7)), render(grain(8)));
+}
+
+#[test]
+fn grain_changes_the_frame() {
+    let plain = render(None);
+    // without grain the background is flat
+    assert!(plain.chunks(4).all(|px| px == &plain[..4]));
+    assert_ne!(render(grain(7)), plain);
+}
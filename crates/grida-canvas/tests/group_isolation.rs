@@ -0,0 +1,226 @@
+use cg::cache::geometry::GeometryCache;
+use cg::export::export_as_image::render_scene_to_png;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::layer::LayerList;
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A 50% group of two white 50% rectangles overlapping over x 40..60.
+fn scene(blend_mode: BlendMode) -> Scene {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let mut children = vec![];
+    for x in [20.0, 40.0] {
+        let mut rect = nf.create_rectangle_node();
+        rect.transform = AffineTransform::new(x, 20.0, 0.0);
+        rect.size = Size {
+            width: 40.0,
+            height: 40.0,
+        };
+        rect.fill = Paint::Solid(SolidPaint {
+            color: Color(255, 255, 255, 255),
+            opacity: 1.0,
+        });
+        rect.stroke_width = 0.0;
+        rect.opacity = 0.5;
+        children.push(repo.insert(Node::Rectangle(rect)));
+    }
+
+    let mut group = nf.create_group_node();
+    group.children = children;
+    group.opacity = 0.5;
+    group.blend_mode = blend_mode;
+    let group = repo.insert(Node::Group(group));
+
+    Scene {
+        children: vec![group],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// Alpha outside of the overlap and inside of it.
+fn alphas(pixmap: &skia_safe::Pixmap) -> (u8, u8) {
+    (
+        pixmap.get_color((30, 40)).a(),
+        pixmap.get_color((50, 40)).a(),
+    )
+}
+
+fn render_png(scene: &Scene) -> (u8, u8) {
+    let png = render_scene_to_png(scene, 100, 100, 1.0);
+    let image = Image::from_encoded(Data::new_copy(&png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.canvas().draw_image(&image, (0, 0), None);
+    alphas(&surface.peek_pixels().unwrap())
+}
+
+fn render_with(scene: &Scene, draw: impl FnOnce(&Painter, &GeometryCache)) -> (u8, u8) {
+    let cache = GeometryCache::from_scene(scene);
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    draw(&painter, &cache);
+    alphas(&surface.peek_pixels().unwrap())
+}
+
+fn assert_isolated((single, overlap): (u8, u8)) {
+    // 0.5 * 0.5 outside of the overlap, 0.5 * 0.75 inside of it
+    assert!((62..=66).contains(&single), "single {single}");
+    assert!((94..=98).contains(&overlap), "overlap {overlap}");
+}
+
+/// Renders `scene` with the painter, the layer list and the PNG export.
+fn render_all(scene: &Scene) -> [(u8, u8); 3] {
+    [
+        render_png(scene),
+        render_with(scene, |painter, cache| {
+            painter.draw_node_recursively(
+                scene.nodes.get(&scene.children[0]).unwrap(),
+                &scene.nodes,
+                cache,
+            );
+        }),
+        render_with(scene, |painter, cache| {
+            let list = LayerList::from_scene(scene, cache, &FontRepository::new());
+            painter.draw_layer_list(&list);
+        }),
+    ]
+}
+
+#[test]
+fn group_opacity_composites_children_as_a_unit() {
+    for alphas in render_all(&scene(BlendMode::Normal)) {
+        assert_isolated(alphas);
+    }
+}
+
+#[test]
+fn isolated_children_keep_their_own_opacity() {
+    let scene = scene(BlendMode::Normal);
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+
+    assert_eq!(list.len(), 2);
+    for layer in &list.layers {
+        let base = layer.base();
+        assert_eq!(base.opacity, 0.5);
+        assert_eq!(base.isolation.len(), 1);
+        assert_eq!(base.isolation[0].id, scene.children[0]);
+        assert_eq!(base.isolation[0].opacity, 0.5);
+    }
+}
+
+#[test]
+fn pass_through_group_opacity_composites_children_as_a_unit() {
+    let scene = scene(BlendMode::PassThrough);
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+    for layer in &list.layers {
+        assert_eq!(layer.base().isolation.len(), 1);
+        assert_eq!(layer.base().opacity, 0.5);
+    }
+
+    for alphas in render_all(&scene) {
+        assert_isolated(alphas);
+    }
+}
+
+#[test]
+fn opaque_group_without_blending_children_is_not_isolated() {
+    for blend_mode in [BlendMode::Normal, BlendMode::PassThrough] {
+        let mut scene = scene(blend_mode);
+        let Some(Node::Group(group)) = scene.nodes.get_mut(&scene.children[0]) else {
+            panic!("expected the group");
+        };
+        group.opacity = 1.0;
+
+        let cache = GeometryCache::from_scene(&scene);
+        let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+        for layer in &list.layers {
+            assert!(layer.base().isolation.is_empty());
+            assert_eq!(layer.base().opacity, 0.5);
+        }
+
+        // each child is drawn at 0.5 on its own, 1 - 0.5 * 0.5 where they overlap
+        for (single, overlap) in render_all(&scene) {
+            assert!((126..=130).contains(&single), "single {single}");
+            assert!((189..=193).contains(&overlap), "overlap {overlap}");
+        }
+    }
+}
+
+/// A yellow backdrop behind a group holding a cyan `Multiply` square at (25, 25).
+fn multiply_scene(group_blend_mode: BlendMode) -> Scene {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let mut rect = |x: f32, size: f32, color: Color, blend_mode: BlendMode| {
+        let mut rect = nf.create_rectangle_node();
+        rect.transform = AffineTransform::new(x, x, 0.0);
+        rect.size = Size {
+            width: size,
+            height: size,
+        };
+        rect.fill = Paint::Solid(SolidPaint {
+            color,
+            opacity: 1.0,
+        });
+        rect.stroke_width = 0.0;
+        rect.blend_mode = blend_mode;
+        repo.insert(Node::Rectangle(rect))
+    };
+    let backdrop = rect(0.0, 100.0, Color(255, 255, 0, 255), BlendMode::Normal);
+    let square = rect(25.0, 50.0, Color(0, 255, 255, 255), BlendMode::Multiply);
+
+    let mut group = nf.create_group_node();
+    group.children = vec![square];
+    group.blend_mode = group_blend_mode;
+    let group = repo.insert(Node::Group(group));
+
+    Scene {
+        children: vec![backdrop, group],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    }
+}
+
+fn center_color(scene: &Scene) -> skia_safe::Color {
+    let png = render_scene_to_png(scene, 100, 100, 1.0);
+    let image = Image::from_encoded(Data::new_copy(&png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().draw_image(&image, (0, 0), None);
+    surface.peek_pixels().unwrap().get_color((50, 50))
+}
+
+#[test]
+fn pass_through_group_children_blend_with_the_backdrop() {
+    // yellow multiplied by cyan
+    let scene = multiply_scene(BlendMode::PassThrough);
+    assert_eq!(center_color(&scene), skia_safe::Color::GREEN);
+
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+    assert!(list.layers.iter().all(|l| l.base().isolation.is_empty()));
+}
+
+#[test]
+fn normal_group_isolates_blending_children() {
+    // the square multiplies against the empty group layer only
+    let scene = multiply_scene(BlendMode::Normal);
+    assert_eq!(center_color(&scene), skia_safe::Color::CYAN);
+
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+    assert_eq!(list.layers[1].base().isolation.len(), 1);
+    assert_eq!(list.layers[1].base().isolation[0].id, scene.children[1]);
+}
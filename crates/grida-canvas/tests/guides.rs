@@ -0,0 +1,115 @@
+use cg::io::io_json::IOScene;
+use cg::node::schema::*;
+use cg::runtime::camera::Camera2D;
+use cg::runtime::scene::{Backend, Renderer};
+use math2::rect::Rectangle;
+use serde_json::json;
+use skia_safe::{surfaces, Color as SkColor};
+
+const SIZE: i32 = 100;
+
+/// Renders an empty white scene with a vertical guide at x = 20 and a horizontal one at
+/// y = 30, viewed at `zoom` from the scene origin.
+fn render(show_guides: bool, zoom: f32) -> skia_safe::Surface {
+    let mut renderer = Renderer::new(
+        Backend::new_from_raster(SIZE, SIZE),
+        None,
+        Camera2D::new_from_bounds(Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: SIZE as f32,
+            height: SIZE as f32,
+        }),
+    );
+    renderer.camera.set_zoom(zoom);
+    renderer.camera.set_center(50.0 / zoom, 50.0 / zoom);
+    renderer.options.show_guides = show_guides;
+    renderer.options.guide_color = Color(255, 0, 0, 255);
+    renderer.load_scene(Scene {
+        background_color: Some(Color(255, 255, 255, 255)),
+        guides: vec![
+            Guide {
+                axis: Axis::X,
+                offset: 20.0,
+            },
+            Guide {
+                axis: Axis::Y,
+                offset: 30.0,
+            },
+        ],
+        ..Scene::new("scene", "test")
+    });
+
+    let mut surface = surfaces::raster_n32_premul((SIZE, SIZE)).unwrap();
+    renderer.render_to_canvas(surface.canvas(), SIZE as f32, SIZE as f32);
+    surface
+}
+
+#[test]
+fn guides_are_drawn_at_their_offsets() {
+    let mut surface = render(true, 1.0);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // the lines span the whole viewport
+    for y in [0, 50, SIZE - 1] {
+        assert_eq!(pixels.get_color((20, y)), SkColor::RED, "x = 20, y = {y}");
+    }
+    for x in [0, 50, SIZE - 1] {
+        assert_eq!(pixels.get_color((x, 30)), SkColor::RED, "x = {x}, y = 30");
+    }
+    // one pixel wide
+    assert_eq!(pixels.get_color((19, 50)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((21, 50)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((50, 29)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((50, 31)), SkColor::WHITE);
+}
+
+#[test]
+fn guides_follow_the_camera_in_screen_space() {
+    let mut surface = render(true, 2.0);
+    let pixels = surface.peek_pixels().unwrap();
+
+    assert_eq!(pixels.get_color((40, 50)), SkColor::RED);
+    assert_eq!(pixels.get_color((50, 60)), SkColor::RED);
+    // still a hairline when zoomed in
+    assert_eq!(pixels.get_color((41, 50)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((20, 50)), SkColor::WHITE);
+}
+
+#[test]
+fn guides_are_hidden_by_default() {
+    let mut surface = render(false, 1.0);
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((20, 50)), SkColor::WHITE);
+    assert_eq!(pixels.get_color((50, 30)), SkColor::WHITE);
+}
+
+#[test]
+fn json_guides_are_parsed() {
+    let scene: IOScene = serde_json::from_value(json!({
+        "id": "scene",
+        "name": "scene",
+        "type": "scene",
+        "children": [],
+        "guides": [
+            { "axis": "x", "offset": 20 },
+            { "axis": "y", "offset": 30.5 },
+            { "axis": "z", "offset": 10 },
+            { "axis": "x" },
+        ],
+    }))
+    .unwrap();
+    assert_eq!(
+        scene.guide_list(),
+        vec![
+            Guide {
+                axis: Axis::X,
+                offset: 20.0,
+            },
+            Guide {
+                axis: Axis::Y,
+                offset: 30.5,
+            },
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 use cg::cache::scene::SceneCache;
 use cg::hittest::HitTester;
 use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::runtime::repository::FontRepository;
 use math2::{rect::Rectangle, transform::AffineTransform};
 
 #[test]
@@ -27,17 +28,14 @@ fn hit_first_returns_topmost() {
     repo.insert(Node::Container(container));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![container_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let mut cache = SceneCache::new();
     cache.update_geometry(&scene);
-    cache.update_layers(&scene);
+    cache.update_layers(&scene, &FontRepository::new());
 
     let tester = HitTester::new(&cache);
 
@@ -63,17 +61,14 @@ fn path_hit_testing_uses_contains() {
     repo.insert(Node::Path(path_node.clone()));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![path_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let mut cache = SceneCache::new();
     cache.update_geometry(&scene);
-    cache.update_layers(&scene);
+    cache.update_layers(&scene, &FontRepository::new());
     cache
         .path
         .borrow_mut()
@@ -112,17 +107,14 @@ fn intersects_returns_all_nodes_in_rect() {
     repo.insert(Node::Container(container));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![container_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let mut cache = SceneCache::new();
     cache.update_geometry(&scene);
-    cache.update_layers(&scene);
+    cache.update_layers(&scene, &FontRepository::new());
 
     let tester = HitTester::new(&cache);
 
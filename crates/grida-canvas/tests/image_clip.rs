@@ -0,0 +1,59 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Renders a 100x100 image node at (50, 50) showing a 200x100 red image with `BoxFit::Cover`.
+fn render(clip: bool) -> skia_safe::Surface {
+    let mut image_surface = surfaces::raster_n32_premul((200, 100)).unwrap();
+    image_surface.canvas().clear(skia_safe::Color::RED);
+    let mut images = ImageRepository::new();
+    images.insert("red".to_string(), image_surface.image_snapshot());
+
+    let nf = NodeFactory::new();
+    let mut node = nf.create_image_node();
+    node.transform = AffineTransform::new(50.0, 50.0, 0.0);
+    node.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    node.stroke_width = 0.0;
+    node._ref = "red".to_string();
+    node.clip = clip;
+
+    let mut surface = surfaces::raster_n32_premul((200, 200)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(images)),
+    );
+    painter.draw_node(&LeafNode::Image(node));
+    surface
+}
+
+#[test]
+fn clipped_image_stays_inside_bounds() {
+    let mut surface = render(true);
+    let pixels = surface.peek_pixels().unwrap();
+
+    assert_eq!(pixels.get_color((100, 100)), skia_safe::Color::RED);
+    assert_eq!(pixels.get_color((20, 100)), skia_safe::Color::WHITE);
+    assert_eq!(pixels.get_color((180, 100)), skia_safe::Color::WHITE);
+}
+
+#[test]
+fn unclipped_cover_image_overflows_bounds() {
+    let mut surface = render(false);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // the cover fit scales the image to the node height, overflowing horizontally
+    assert_eq!(pixels.get_color((100, 100)), skia_safe::Color::RED);
+    assert_eq!(pixels.get_color((20, 100)), skia_safe::Color::RED);
+    assert_eq!(pixels.get_color((180, 100)), skia_safe::Color::RED);
+    assert_eq!(pixels.get_color((100, 20)), skia_safe::Color::WHITE);
+}
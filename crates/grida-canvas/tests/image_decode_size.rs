@@ -0,0 +1,57 @@
+use cg::cache::mipmap::{paint_target_decode_size, target_decode_size};
+use cg::node::{factory::NodeFactory, schema::*};
+use math2::box_fit::BoxFit;
+use math2::transform::AffineTransform;
+
+fn image_node(width: f32, height: f32) -> ImageNode {
+    let mut node = NodeFactory::new().create_image_node();
+    node.size = Size { width, height };
+    node
+}
+
+#[test]
+fn small_node_at_1x_requests_downscaled_decode() {
+    let node = image_node(16.0, 16.0);
+    assert_eq!(target_decode_size(&node, 1.0, (256, 256)), (16, 16));
+    // a 2x display needs twice the pixels
+    assert_eq!(target_decode_size(&node, 2.0, (256, 256)), (32, 32));
+}
+
+#[test]
+fn decode_size_is_capped_at_source() {
+    let node = image_node(16.0, 16.0);
+    assert_eq!(target_decode_size(&node, 100.0, (256, 256)), (256, 256));
+}
+
+#[test]
+fn cover_keeps_aspect_ratio() {
+    // covering a 100x100 box scales a 400x200 image by 0.5
+    let node = image_node(100.0, 100.0);
+    assert_eq!(target_decode_size(&node, 1.0, (400, 200)), (200, 100));
+}
+
+#[test]
+fn paint_fit_is_respected() {
+    let paint = ImagePaint {
+        transform: AffineTransform::identity(),
+        _ref: "image".to_string(),
+        fit: BoxFit::Contain,
+        opacity: 1.0,
+        repeat: ImageRepeat::NoRepeat,
+    };
+    // containing a 400x200 image in a 100x100 box scales it by 0.25
+    assert_eq!(
+        paint_target_decode_size(&paint, (100.0, 100.0), 1.0, (400, 200)),
+        (100, 50)
+    );
+
+    let paint = ImagePaint {
+        fit: BoxFit::None,
+        transform: AffineTransform::from_acebdf(0.5, 0.0, 0.0, 0.0, 0.5, 0.0),
+        ..paint
+    };
+    assert_eq!(
+        paint_target_decode_size(&paint, (100.0, 100.0), 1.0, (400, 200)),
+        (200, 100)
+    );
+}
@@ -0,0 +1,92 @@
+use cg::cache::mipmap::{MipmapConfig, MipmapLevels};
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::{Painter, RenderOptions};
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::{images, surfaces, AlphaType, ColorType, Data, ImageInfo};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const IMAGE_SIZE: i32 = 256;
+const NODE_SIZE: i32 = 16;
+
+/// A black and white checkerboard with 1px cells.
+fn checkerboard() -> skia_safe::Image {
+    let mut pixels = Vec::with_capacity((IMAGE_SIZE * IMAGE_SIZE * 4) as usize);
+    for y in 0..IMAGE_SIZE {
+        for x in 0..IMAGE_SIZE {
+            let v = if (x + y) % 2 == 0 { 0 } else { 255 };
+            pixels.extend_from_slice(&[v, v, v, 255]);
+        }
+    }
+    let info = ImageInfo::new(
+        (IMAGE_SIZE, IMAGE_SIZE),
+        ColorType::RGBA8888,
+        AlphaType::Premul,
+        None,
+    );
+    images::raster_from_data(&info, Data::new_copy(&pixels), (IMAGE_SIZE * 4) as usize).unwrap()
+}
+
+/// Draws the checkerboard into a 16x16 image node and returns the mean distance of the
+/// output from mid gray, 0 for a perfectly smooth result.
+fn render(options: RenderOptions) -> f32 {
+    // keep only the full size image, so the downscale happens at draw time
+    let mut images = ImageRepository::with_config(MipmapConfig {
+        levels: MipmapLevels::Fixed(vec![1.0]),
+        chained: false,
+    });
+    images.insert("checker".to_string(), checkerboard());
+
+    let nf = NodeFactory::new();
+    let mut node = nf.create_image_node();
+    node.size = Size {
+        width: NODE_SIZE as f32,
+        height: NODE_SIZE as f32,
+    };
+    node.stroke_width = 0.0;
+    node._ref = "checker".to_string();
+
+    let mut surface = surfaces::raster_n32_premul((NODE_SIZE, NODE_SIZE)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(images)),
+    )
+    .with_options(options);
+    painter.draw_node(&LeafNode::Image(node));
+
+    let pixels = surface.peek_pixels().unwrap();
+    let mut distance = 0.0;
+    for y in 0..NODE_SIZE {
+        for x in 0..NODE_SIZE {
+            distance += (pixels.get_color((x, y)).r() as f32 - 127.5).abs();
+        }
+    }
+    distance / (NODE_SIZE * NODE_SIZE) as f32
+}
+
+#[test]
+fn mipmaps_are_enabled_by_default() {
+    assert!(RenderOptions::default().mipmaps);
+}
+
+#[test]
+fn mipmaps_smooth_downscaled_images() {
+    let with_mipmaps = render(RenderOptions {
+        mipmaps: true,
+        ..Default::default()
+    });
+    let without_mipmaps = render(RenderOptions {
+        mipmaps: false,
+        ..Default::default()
+    });
+
+    // the checkerboard averages to gray, point sampling picks single black or white cells
+    assert!(with_mipmaps < 32.0, "{with_mipmaps}");
+    assert!(
+        with_mipmaps < without_mipmaps,
+        "{with_mipmaps} >= {without_mipmaps}"
+    );
+}
@@ -0,0 +1,121 @@
+use cg::io::io_json::Fill;
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::{cvt, Painter};
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::box_fit::BoxFit;
+use math2::transform::AffineTransform;
+use serde_json::json;
+use skia_safe::{surfaces, Color as SkColor, Paint as SkPaint, Rect, SamplingOptions, TileMode};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A 10x10 blue image with a red 5x5 top-left quadrant.
+fn tile() -> skia_safe::Image {
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(SkColor::BLUE);
+    let mut paint = SkPaint::default();
+    paint.set_color(SkColor::RED);
+    canvas.draw_rect(Rect::from_wh(5.0, 5.0), &paint);
+    surface.image_snapshot()
+}
+
+fn paint(repeat: ImageRepeat, scale: f32) -> ImagePaint {
+    ImagePaint {
+        transform: AffineTransform::from_acebdf(scale, 0.0, 0.0, 0.0, scale, 0.0),
+        _ref: "tile".to_string(),
+        fit: BoxFit::Cover,
+        opacity: 1.0,
+        repeat,
+    }
+}
+
+#[test]
+fn repeat_shader_keeps_the_natural_tile_size() {
+    let image = tile();
+    assert!(cvt::sk_image_repeat_shader(
+        &image,
+        &paint(ImageRepeat::NoRepeat, 1.0),
+        SamplingOptions::default()
+    )
+    .is_none());
+
+    for scale in [1.0, 2.0] {
+        let shader = cvt::sk_image_repeat_shader(
+            &image,
+            &paint(ImageRepeat::Repeat, scale),
+            SamplingOptions::default(),
+        )
+        .unwrap();
+        let (_, matrix, tile_modes) = shader.image().unwrap();
+        // the fit is ignored, a 10px tile in a 100px box is not scaled up to cover it
+        assert_eq!(matrix.scale_x(), scale);
+        assert_eq!(matrix.scale_y(), scale);
+        assert_eq!(tile_modes, (TileMode::Repeat, TileMode::Repeat));
+    }
+
+    let shader = cvt::sk_image_repeat_shader(
+        &image,
+        &paint(ImageRepeat::RepeatX, 1.0),
+        SamplingOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        shader.image().unwrap().2,
+        (TileMode::Repeat, TileMode::Decal)
+    );
+}
+
+#[test]
+fn repeated_fill_tiles_the_box() {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    rect.stroke_width = 0.0;
+    rect.fill = Paint::Image(paint(ImageRepeat::Repeat, 1.0));
+
+    let mut images = ImageRepository::new();
+    images.insert("tile".to_string(), tile());
+
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(SkColor::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(images)),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect));
+
+    let pixels = surface.peek_pixels().unwrap();
+    for (x, y) in [(2, 2), (12, 12), (52, 2), (92, 92)] {
+        assert_eq!(pixels.get_color((x, y)), SkColor::RED, "({x}, {y})");
+    }
+    for (x, y) in [(7, 7), (17, 2), (97, 97)] {
+        assert_eq!(pixels.get_color((x, y)), SkColor::BLUE, "({x}, {y})");
+    }
+}
+
+#[test]
+fn json_image_fill_accepts_repeat() {
+    let fill: Fill = serde_json::from_value(json!({
+        "type": "image",
+        "src": "tile",
+        "repeat": "repeat-x",
+    }))
+    .unwrap();
+    let Paint::Image(paint) = Some(fill).into() else {
+        panic!("expected an image paint");
+    };
+    assert_eq!(paint._ref, "tile");
+    assert_eq!(paint.repeat, ImageRepeat::RepeatX);
+    assert_eq!(paint.fit, BoxFit::Cover);
+
+    let fill: Fill = serde_json::from_value(json!({ "type": "image", "src": "tile" })).unwrap();
+    let Paint::Image(paint) = Some(fill).into() else {
+        panic!("expected an image paint");
+    };
+    assert_eq!(paint.repeat, ImageRepeat::NoRepeat);
+}
@@ -0,0 +1,100 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::image_store::{decode_data_url, ImageStore};
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use serde_json::json;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A 2x2 opaque red png.
+const RED_PNG: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAACCAYAAABytg0kAAAAEUlEQVR4nGP4z8DwH4QZYAwAR8oH+WdZbrcAAAAASUVORK5CYII=";
+
+fn bitmaps() -> HashMap<String, serde_json::Value> {
+    HashMap::from([
+        ("red".to_string(), json!(RED_PNG)),
+        ("nested".to_string(), json!({ "src": RED_PNG })),
+        ("broken".to_string(), json!("data:image/png;base64,AAAA")),
+        ("remote".to_string(), json!("https://example.com/image.png")),
+    ])
+}
+
+#[test]
+fn decodes_base64_png_refs() {
+    assert!(decode_data_url(RED_PNG).unwrap().starts_with(b"\x89PNG"));
+
+    let mut store = ImageStore::from_bitmaps(&bitmaps());
+    for id in ["red", "nested"] {
+        let image = store.resolve(id).expect(id);
+        assert_eq!((image.width(), image.height()), (2, 2));
+    }
+    // a data url works as a ref on its own
+    assert!(store.resolve(RED_PNG).is_some());
+}
+
+#[test]
+fn unresolved_refs_are_missing() {
+    let mut store = ImageStore::from_bitmaps(&bitmaps());
+    assert!(!store.is_missing("broken"));
+    assert!(store.resolve("broken").is_none());
+    assert!(store.resolve("unknown").is_none());
+    assert!(store.is_missing("broken"));
+    assert!(store.is_missing("unknown"));
+}
+
+#[test]
+fn remote_refs_are_not_missing() {
+    let mut store = ImageStore::from_bitmaps(&bitmaps());
+    assert!(store.resolve("remote").is_none());
+    assert!(store.resolve("https://example.com/other.png").is_none());
+    assert!(!store.is_missing("remote"));
+    assert!(!store.is_missing("https://example.com/other.png"));
+}
+
+/// Draws a 10x10 rectangle filled with the image `_ref` and returns its center color.
+fn render(_ref: &str) -> skia_safe::Color {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = Size {
+        width: 10.0,
+        height: 10.0,
+    };
+    rect.stroke_width = 0.0;
+    rect.fill = Paint::Image(ImagePaint {
+        transform: math2::transform::AffineTransform::identity(),
+        _ref: _ref.to_string(),
+        fit: math2::box_fit::BoxFit::Cover,
+        opacity: 1.0,
+        repeat: ImageRepeat::NoRepeat,
+    });
+
+    let mut images = ImageRepository::new();
+    images.set_store(ImageStore::from_bitmaps(&bitmaps()));
+
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(images)),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect));
+    surface.peek_pixels().unwrap().get_color((2, 5))
+}
+
+#[test]
+fn painter_decodes_through_the_store() {
+    assert_eq!(render("red"), skia_safe::Color::RED);
+}
+
+#[test]
+fn missing_ref_draws_a_placeholder() {
+    let color = render("unknown");
+    assert_eq!(color, skia_safe::Color::from_rgb(229, 229, 229));
+}
+
+#[test]
+fn loading_remote_ref_draws_no_placeholder() {
+    assert_eq!(render("remote"), skia_safe::Color::TRANSPARENT);
+}
@@ -0,0 +1,145 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use math2::rect::Rectangle;
+use math2::transform::AffineTransform;
+
+/// A 100x100 rectangle at (50, 50) without a stroke. The transform does not affect the
+/// local ink bounds.
+fn rect() -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(50.0, 50.0, 0.0);
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    rect.stroke_width = 0.0;
+    rect
+}
+
+fn with_effect(effect: FilterEffect) -> Node {
+    let mut rect = rect();
+    rect.effects = vec![effect];
+    Node::Rectangle(rect)
+}
+
+fn ltrb(bounds: Rectangle) -> (f32, f32, f32, f32) {
+    (
+        bounds.x,
+        bounds.y,
+        bounds.x + bounds.width,
+        bounds.y + bounds.height,
+    )
+}
+
+#[test]
+fn plain_node_ink_bounds_are_its_geometry() {
+    assert_eq!(
+        ltrb(Node::Rectangle(rect()).ink_bounds()),
+        (0.0, 0.0, 100.0, 100.0)
+    );
+}
+
+#[test]
+fn stroke_expands_by_its_alignment() {
+    let bounds = |align: StrokeAlign| {
+        let mut rect = rect();
+        rect.stroke_width = 10.0;
+        rect.stroke_align = align;
+        ltrb(Node::Rectangle(rect).ink_bounds())
+    };
+    assert_eq!(bounds(StrokeAlign::Inside), (0.0, 0.0, 100.0, 100.0));
+    assert_eq!(bounds(StrokeAlign::Center), (-5.0, -5.0, 105.0, 105.0));
+    assert_eq!(bounds(StrokeAlign::Outside), (-10.0, -10.0, 110.0, 110.0));
+}
+
+#[test]
+fn open_path_strokes_expand_centered() {
+    let mut path = NodeFactory::new().create_path_node();
+    path.data = "M0 0 L100 0 L100 100".to_string();
+    path.stroke_width = 10.0;
+    path.stroke_align = StrokeAlign::Inside;
+    assert_eq!(
+        ltrb(Node::Path(path.clone()).ink_bounds()),
+        (-5.0, -5.0, 105.0, 105.0)
+    );
+
+    path.data = "M0 0 L100 0 L100 100 Z".to_string();
+    assert_eq!(
+        ltrb(Node::Path(path).ink_bounds()),
+        (0.0, 0.0, 100.0, 100.0)
+    );
+}
+
+#[test]
+fn layer_blur_expands_by_three_sigma() {
+    let node = with_effect(FilterEffect::GaussianBlur(FeGaussianBlur { radius: 4.0 }));
+    assert_eq!(ltrb(node.ink_bounds()), (-12.0, -12.0, 112.0, 112.0));
+}
+
+#[test]
+fn drop_shadow_expands_by_offset_spread_and_blur() {
+    let node = with_effect(FilterEffect::DropShadow(FeDropShadow {
+        dx: 10.0,
+        dy: -20.0,
+        blur: 2.0,
+        spread: 4.0,
+        color: Color(0, 0, 0, 255),
+    }));
+    // the shadow grows by 4 + 3 * 2 = 10 around the offset shape
+    assert_eq!(ltrb(node.ink_bounds()), (0.0, -30.0, 120.0, 100.0));
+}
+
+#[test]
+fn negative_spread_shrinks_the_shadow() {
+    let node = with_effect(FilterEffect::DropShadow(FeDropShadow {
+        dx: 0.0,
+        dy: 50.0,
+        blur: 0.0,
+        spread: -10.0,
+        color: Color(0, 0, 0, 255),
+    }));
+    // the shadow stays within the shape horizontally and extends 50 down
+    assert_eq!(ltrb(node.ink_bounds()), (0.0, 0.0, 100.0, 150.0));
+}
+
+#[test]
+fn inner_effects_do_not_expand() {
+    let backdrop = with_effect(FilterEffect::BackdropBlur(FeBackdropBlur { radius: 8.0 }));
+    assert_eq!(ltrb(backdrop.ink_bounds()), (0.0, 0.0, 100.0, 100.0));
+
+    let inner = with_effect(FilterEffect::InnerShadow(FeInnerShadow {
+        dx: 10.0,
+        dy: 10.0,
+        blur: 8.0,
+        spread: 4.0,
+        color: Color(0, 0, 0, 255),
+    }));
+    assert_eq!(ltrb(inner.ink_bounds()), (0.0, 0.0, 100.0, 100.0));
+}
+
+#[test]
+fn effects_accumulate() {
+    let mut rect = rect();
+    rect.stroke_width = 4.0;
+    rect.stroke_align = StrokeAlign::Outside;
+    rect.effects = vec![
+        FilterEffect::DropShadow(FeDropShadow {
+            dx: 0.0,
+            dy: 0.0,
+            blur: 1.0,
+            spread: 0.0,
+            color: Color(0, 0, 0, 255),
+        }),
+        FilterEffect::GaussianBlur(FeGaussianBlur { radius: 2.0 }),
+    ];
+    // stroke 4, then the blur by 6 around everything
+    assert_eq!(
+        ltrb(Node::Rectangle(rect).ink_bounds()),
+        (-10.0, -10.0, 110.0, 110.0)
+    );
+}
+
+#[test]
+fn groups_have_empty_ink_bounds() {
+    let group = NodeFactory::new().create_group_node();
+    assert_eq!(ltrb(Node::Group(group).ink_bounds()), (0.0, 0.0, 0.0, 0.0));
+}
@@ -0,0 +1,81 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const WHITE: skia_safe::Color = skia_safe::Color::WHITE;
+
+fn render(effects: Vec<FilterEffect>) -> skia_safe::Surface {
+    let nf = NodeFactory::new();
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = AffineTransform::new(20.0, 20.0, 0.0);
+    rect.size = Size {
+        width: 60.0,
+        height: 60.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(255, 255, 255, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    rect.effects = effects;
+
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect));
+    surface
+}
+
+fn inner_shadow() -> FilterEffect {
+    FilterEffect::InnerShadow(FeInnerShadow {
+        dx: 4.0,
+        dy: 4.0,
+        blur: 2.0,
+        spread: 2.0,
+        color: Color(0, 0, 0, 255),
+    })
+}
+
+#[test]
+fn inner_shadow_stays_inside_shape() {
+    let mut surface = render(vec![inner_shadow()]);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // shadow is cast along the top-left inner edge
+    assert_ne!(pixels.get_color((22, 50)), WHITE);
+    assert_ne!(pixels.get_color((50, 22)), WHITE);
+    assert_eq!(pixels.get_color((50, 50)), WHITE);
+
+    // nothing is drawn outside the shape bounds
+    for (x, y) in [(18, 50), (50, 18), (82, 50), (50, 82), (10, 10), (90, 90)] {
+        assert_eq!(pixels.get_color((x, y)), WHITE, "pixel at ({x}, {y})");
+    }
+}
+
+#[test]
+fn inner_shadow_composes_with_drop_shadow() {
+    let drop_shadow = FilterEffect::DropShadow(FeDropShadow {
+        dx: 8.0,
+        dy: 8.0,
+        blur: 0.0,
+        spread: 0.0,
+        color: Color(0, 0, 0, 255),
+    });
+    let mut surface = render(vec![drop_shadow, inner_shadow()]);
+    let pixels = surface.peek_pixels().unwrap();
+
+    // the drop shadow is visible outside, the inner shadow is drawn above the fill
+    assert_ne!(pixels.get_color((84, 84)), WHITE);
+    assert_ne!(pixels.get_color((22, 50)), WHITE);
+    assert_eq!(pixels.get_color((50, 50)), WHITE);
+    assert_eq!(pixels.get_color((10, 10)), WHITE);
+}
@@ -0,0 +1,95 @@
+use cg::io::io_svg::export_scene;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::runtime::repository::FontRepository;
+use math2::transform::AffineTransform;
+
+const BLACK: Paint = Paint::Solid(SolidPaint {
+    color: Color(0, 0, 0, 255),
+    opacity: 1.0,
+});
+
+fn document() -> Scene {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.base.id = "rect".into();
+    rect.size = Size {
+        width: 100.0,
+        height: 50.0,
+    };
+    rect.corner_radius = RectangularCornerRadius::all(8.0);
+    rect.fill = Paint::LinearGradient(LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![
+            GradientStop {
+                offset: 0.0,
+                color: Color(255, 0, 0, 255),
+            },
+            GradientStop {
+                offset: 1.0,
+                color: Color(0, 0, 255, 255),
+            },
+        ],
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    });
+    rect.stroke = BLACK;
+    rect.stroke_width = 2.0;
+    let rect_id = repo.insert(Node::Rectangle(rect));
+
+    let mut ellipse = nf.create_ellipse_node();
+    ellipse.base.id = "ellipse".into();
+    ellipse.transform = AffineTransform::new(120.0, 0.0, 0.0);
+    ellipse.size = Size {
+        width: 60.0,
+        height: 60.0,
+    };
+    ellipse.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 255, 0, 255),
+        opacity: 0.5,
+    });
+    ellipse.stroke_width = 0.0;
+    let ellipse_id = repo.insert(Node::Ellipse(ellipse));
+
+    let mut path = nf.create_path_node();
+    path.base.id = "path".into();
+    path.transform = AffineTransform::new(0.0, 60.0, 0.0);
+    path.data = "M0 0L10 0L10 10Z".into();
+    path.fill = BLACK;
+    path.stroke_width = 0.0;
+    let path_id = repo.insert(Node::Path(path));
+
+    let mut text = nf.create_text_span_node();
+    text.base.id = "text".into();
+    text.transform = AffineTransform::new(0.0, 80.0, 0.0);
+    text.size = Size {
+        width: 200.0,
+        height: 20.0,
+    };
+    text.text = "Hello & <world>".into();
+    text.text_style.font_families = vec!["Inter".into()];
+    text.fill = BLACK;
+    let text_id = repo.insert(Node::TextSpan(text));
+
+    let mut group = nf.create_group_node();
+    group.base.id = "group".into();
+    group.transform = AffineTransform::new(10.0, 10.0, 0.0);
+    group.opacity = 0.8;
+    group.blend_mode = BlendMode::Multiply;
+    group.children = vec![rect_id, ellipse_id, path_id, text_id];
+    let group_id = repo.insert(Node::Group(group));
+
+    Scene {
+        children: vec![group_id],
+        nodes: repo,
+        background_color: Some(Color(255, 255, 255, 255)),
+        ..Scene::new("scene", "document")
+    }
+}
+
+#[test]
+fn export_scene_matches_golden() {
+    let svg = export_scene(&document(), &FontRepository::new());
+    assert_eq!(svg, include_str!("../goldens/io_svg.svg"));
+}
@@ -0,0 +1,68 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::layer::LayerList;
+use cg::runtime::repository::FontRepository;
+use math2::transform::AffineTransform;
+
+fn scene_with(children: Vec<NodeId>, repo: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    }
+}
+
+#[test]
+fn same_fill_rectangles_flatten_into_one_batch() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let mut ids = Vec::new();
+
+    for i in 0..3 {
+        let mut rect = nf.create_rectangle_node();
+        rect.transform = AffineTransform::new(i as f32 * 120.0, 0.0, 0.0);
+        rect.fill = Paint::Solid(SolidPaint {
+            color: Color(255, 0, 0, 255),
+            opacity: 1.0,
+        });
+        ids.push(rect.base.id.clone());
+        repo.insert(Node::Rectangle(rect));
+    }
+
+    let scene = scene_with(ids, repo);
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+
+    assert_eq!(list.layers.len(), 3);
+    assert_eq!(list.batches.len(), 1);
+    assert_eq!(list.batches[0].range, 0..3);
+}
+
+#[test]
+fn different_fills_break_batches() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let mut ids = Vec::new();
+
+    for color in [
+        Color(255, 0, 0, 255),
+        Color(0, 255, 0, 255),
+        Color(0, 255, 0, 255),
+    ] {
+        let mut rect = nf.create_rectangle_node();
+        rect.fill = Paint::Solid(SolidPaint {
+            color,
+            opacity: 1.0,
+        });
+        ids.push(rect.base.id.clone());
+        repo.insert(Node::Rectangle(rect));
+    }
+
+    let scene = scene_with(ids, repo);
+    let cache = GeometryCache::from_scene(&scene);
+    let list = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+
+    assert_eq!(list.batches.len(), 2);
+    assert_eq!(list.batches[0].range, 0..1);
+    assert_eq!(list.batches[1].range, 1..3);
+}
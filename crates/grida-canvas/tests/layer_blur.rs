@@ -0,0 +1,81 @@
+use cg::export::export_as_image::render_scene_to_png;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+
+const SIGMA: f32 = 4.0;
+
+/// A black 40x40 rectangle at (30, 30) blurred by [`SIGMA`], with an outside stroke of
+/// `stroke_width`.
+fn scene(stroke_width: f32) -> Scene {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(30.0, 30.0, 0.0);
+    rect.size = Size {
+        width: 40.0,
+        height: 40.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = stroke_width;
+    rect.stroke_align = StrokeAlign::Outside;
+    rect.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur { radius: SIGMA })];
+
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::Rectangle(rect));
+    Scene {
+        children: vec![id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// The alpha of each pixel of the middle row.
+fn row(scene: &Scene) -> Vec<u8> {
+    let png = render_scene_to_png(scene, 100, 100, 1.0);
+    let image = Image::from_encoded(Data::new_copy(&png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.canvas().draw_image(&image, (0, 0), None);
+    let pixmap = surface.peek_pixels().unwrap();
+    (0..100).map(|x| pixmap.get_color((x, 50)).a()).collect()
+}
+
+/// The first and last columns with any coverage.
+fn extent(alphas: &[u8]) -> (usize, usize) {
+    let first = alphas.iter().position(|a| *a > 0).unwrap();
+    let last = alphas.iter().rposition(|a| *a > 0).unwrap();
+    (first, last)
+}
+
+#[test]
+fn blur_softens_edges_by_three_sigma() {
+    let alphas = row(&scene(0.0));
+
+    // the soft edge starts roughly 3 * sigma = 12px outside of the rect at 30..70
+    let (first, last) = extent(&alphas);
+    assert!((16..=21).contains(&first), "first {first}");
+    assert!((78..=83).contains(&last), "last {last}");
+
+    // the tail past the radius is not cut off by the render bounds
+    assert!(alphas[22] > 0 && alphas[77] > 0, "{alphas:?}");
+    // the edge is halfway, the center is untouched
+    assert!((100..=155).contains(&alphas[30]), "{}", alphas[30]);
+    assert_eq!(alphas[50], 255);
+}
+
+#[test]
+fn blur_includes_the_stroke() {
+    let alphas = row(&scene(4.0));
+
+    // the stroke grows the rect to 26..74 before blurring
+    let (first, last) = extent(&alphas);
+    assert!((12..=17).contains(&first), "first {first}");
+    assert!((82..=87).contains(&last), "last {last}");
+    assert!((100..=155).contains(&alphas[26]), "{}", alphas[26]);
+}
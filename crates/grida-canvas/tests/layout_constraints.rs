@@ -0,0 +1,229 @@
+use cg::io::io_json::{parse, IOScene};
+use cg::layout::apply_constraints;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use serde_json::json;
+
+#[test]
+fn scene_guides_and_constraints_are_parsed() {
+    let file = parse(
+        &json!({
+            "version": "0.0.1-beta.1+20250303",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {
+                    "banner": {
+                        "type": "rectangle",
+                        "id": "banner",
+                        "name": "banner",
+                        "left": 0.0,
+                        "top": 0.0,
+                        "width": 100.0,
+                        "height": 20.0
+                    }
+                },
+                "scenes": {
+                    "main": {
+                        "id": "main",
+                        "name": "main",
+                        "type": "scene",
+                        "children": ["banner"],
+                        "guides": [{ "axis": "x", "offset": 100 }],
+                        "constraints": {
+                            "children": "single",
+                            "banner": { "horizontal": "stretch" }
+                        }
+                    }
+                }
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let scene = file.into_scene("main").unwrap();
+    assert_eq!(
+        scene.guides,
+        vec![Guide {
+            axis: Axis::X,
+            offset: 100.0,
+        }]
+    );
+    // scene settings are not node constraints
+    assert_eq!(scene.constraints.len(), 1);
+    assert_eq!(
+        scene.constraints["banner"],
+        LayoutConstraint {
+            horizontal: Constraint::Stretch,
+            vertical: Constraint::Start,
+        }
+    );
+}
+
+#[test]
+fn constraint_values() {
+    let scene: IOScene = serde_json::from_value(json!({
+        "id": "scene",
+        "name": "scene",
+        "type": "scene",
+        "children": [],
+        "constraints": {
+            "a": { "horizontal": "right", "vertical": "bottom" },
+            "b": { "horizontal": "center", "vertical": "scale" },
+            "c": { "horizontal": "sideways", "vertical": "top" },
+        },
+    }))
+    .unwrap();
+    let constraints = scene.layout_constraints();
+
+    let constraint = |horizontal, vertical| LayoutConstraint {
+        horizontal,
+        vertical,
+    };
+    assert_eq!(
+        constraints["a"],
+        constraint(Constraint::End, Constraint::End)
+    );
+    assert_eq!(
+        constraints["b"],
+        constraint(Constraint::Center, Constraint::Scale)
+    );
+    assert_eq!(constraints["c"], LayoutConstraint::default());
+}
+
+/// A 200x100 container holding a 20x20 square at (150, 40) with `constraint`.
+fn scene(constraint: LayoutConstraint) -> (Scene, NodeId, NodeId) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut square = nf.create_rectangle_node();
+    square.transform = AffineTransform::new(150.0, 40.0, 0.0);
+    square.size = Size {
+        width: 20.0,
+        height: 20.0,
+    };
+    let square_id = repo.insert(Node::Rectangle(square));
+
+    let mut container = nf.create_container_node();
+    container.size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+    container.children = vec![square_id.clone()];
+    let container_id = repo.insert(Node::Container(container));
+
+    let scene = Scene {
+        children: vec![container_id.clone()],
+        nodes: repo,
+        constraints: LayoutConstraints::from([(square_id.clone(), constraint)]),
+        ..Scene::new("scene", "test")
+    };
+    (scene, container_id, square_id)
+}
+
+/// The `(x, y, width, height)` of the rectangle `id`.
+fn frame(scene: &Scene, id: &NodeId) -> (f32, f32, f32, f32) {
+    let Some(Node::Rectangle(rect)) = scene.nodes.get(id) else {
+        panic!("expected a rectangle");
+    };
+    (
+        rect.transform.x(),
+        rect.transform.y(),
+        rect.size.width,
+        rect.size.height,
+    )
+}
+
+fn resize(constraint: LayoutConstraint, width: f32, height: f32) -> (f32, f32, f32, f32) {
+    let (mut scene, container_id, square_id) = scene(constraint);
+    apply_constraints(&mut scene, &container_id, Size { width, height });
+    let Some(Node::Container(container)) = scene.nodes.get(&container_id) else {
+        panic!("expected a container");
+    };
+    assert_eq!(
+        (container.size.width, container.size.height),
+        (width, height)
+    );
+    frame(&scene, &square_id)
+}
+
+#[test]
+fn right_pinned_child_keeps_its_right_margin() {
+    let (x, y, width, height) = resize(
+        LayoutConstraint {
+            horizontal: Constraint::End,
+            vertical: Constraint::End,
+        },
+        300.0,
+        150.0,
+    );
+    // 30 to the right edge and 40 to the bottom edge, as before
+    assert_eq!(300.0 - (x + width), 30.0);
+    assert_eq!(150.0 - (y + height), 40.0);
+    assert_eq!((width, height), (20.0, 20.0));
+}
+
+#[test]
+fn left_pinned_child_does_not_move() {
+    let frame = resize(LayoutConstraint::default(), 300.0, 150.0);
+    assert_eq!(frame, (150.0, 40.0, 20.0, 20.0));
+}
+
+#[test]
+fn centered_stretched_and_scaled_children() {
+    let center = LayoutConstraint {
+        horizontal: Constraint::Center,
+        vertical: Constraint::Center,
+    };
+    assert_eq!(resize(center, 300.0, 150.0), (200.0, 65.0, 20.0, 20.0));
+
+    let stretch = LayoutConstraint {
+        horizontal: Constraint::Stretch,
+        vertical: Constraint::Stretch,
+    };
+    assert_eq!(resize(stretch, 300.0, 150.0), (150.0, 40.0, 120.0, 70.0));
+
+    let scale = LayoutConstraint {
+        horizontal: Constraint::Scale,
+        vertical: Constraint::Scale,
+    };
+    assert_eq!(resize(scale, 400.0, 50.0), (300.0, 20.0, 40.0, 10.0));
+}
+
+#[test]
+fn stretched_child_container_lays_out_its_children() {
+    let nf = NodeFactory::new();
+    let (mut scene, outer_id, square_id) = scene(LayoutConstraint::default());
+
+    // move the square into an inner container stretched with the outer one
+    let mut inner = nf.create_container_node();
+    inner.size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+    inner.children = vec![square_id.clone()];
+    let inner_id = scene.nodes.insert(Node::Container(inner));
+    if let Some(Node::Container(outer)) = scene.nodes.get_mut(&outer_id) {
+        outer.children = vec![inner_id.clone()];
+    }
+    let stretch = LayoutConstraint {
+        horizontal: Constraint::Stretch,
+        vertical: Constraint::Stretch,
+    };
+    let right = LayoutConstraint {
+        horizontal: Constraint::End,
+        vertical: Constraint::Start,
+    };
+    scene.constraints = LayoutConstraints::from([(inner_id, stretch), (square_id.clone(), right)]);
+
+    apply_constraints(
+        &mut scene,
+        &outer_id,
+        Size {
+            width: 250.0,
+            height: 100.0,
+        },
+    );
+    assert_eq!(frame(&scene, &square_id), (200.0, 40.0, 20.0, 20.0));
+}
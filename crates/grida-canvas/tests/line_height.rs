@@ -0,0 +1,105 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::runtime::repository::FontRepository;
+use cg::text::measure::measure_text;
+
+const TEXT: &str = "The quick brown fox jumps over the lazy dog";
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn style(line_height: LineHeight, letter_spacing: LetterSpacing) -> TextStyle {
+    let mut style = NodeFactory::new().create_text_span_node().text_style;
+    style.font_families = vec!["Geist".into()];
+    style.font_size = 16.0;
+    style.line_height = line_height;
+    style.letter_spacing = letter_spacing;
+    style
+}
+
+#[test]
+fn multiplier_matches_equivalent_px() {
+    let fonts = fonts();
+    let multiplier = measure_text(
+        TEXT,
+        &style(LineHeight::Multiplier(1.5), LetterSpacing::default()),
+        100.0,
+        &fonts,
+    );
+    let px = measure_text(
+        TEXT,
+        &style(LineHeight::Px(24.0), LetterSpacing::default()),
+        100.0,
+        &fonts,
+    );
+
+    assert!(multiplier.line_count > 1);
+    assert_eq!(multiplier.line_count, px.line_count);
+    assert!((multiplier.height - px.height).abs() < 0.01);
+    // every line is 1.5 x 16px tall
+    assert!(
+        (multiplier.height - 24.0 * multiplier.line_count as f32).abs() < 0.5,
+        "{multiplier:?}"
+    );
+}
+
+#[test]
+fn normal_uses_font_metrics() {
+    let fonts = fonts();
+    let normal = measure_text(
+        TEXT,
+        &style(LineHeight::Normal, LetterSpacing::default()),
+        1000.0,
+        &fonts,
+    );
+    let tall = measure_text(
+        TEXT,
+        &style(LineHeight::Multiplier(3.0), LetterSpacing::default()),
+        1000.0,
+        &fonts,
+    );
+
+    assert_eq!(normal.line_count, 1);
+    assert!(normal.height > 0.0 && normal.height < 48.0, "{normal:?}");
+    assert!((tall.height - 48.0).abs() < 0.5, "{tall:?}");
+}
+
+#[test]
+fn em_letter_spacing_matches_equivalent_px() {
+    let fonts = fonts();
+    let em = measure_text(
+        TEXT,
+        &style(LineHeight::Normal, LetterSpacing::Em(0.1)),
+        1000.0,
+        &fonts,
+    );
+    let px = measure_text(
+        TEXT,
+        &style(LineHeight::Normal, LetterSpacing::Px(1.6)),
+        1000.0,
+        &fonts,
+    );
+    let none = measure_text(
+        TEXT,
+        &style(LineHeight::Normal, LetterSpacing::default()),
+        1000.0,
+        &fonts,
+    );
+
+    assert!((em.width - px.width).abs() < 0.01);
+    assert!(em.width > none.width);
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(LineHeight::Normal.multiplier(16.0), None);
+    assert_eq!(LineHeight::Px(24.0).multiplier(16.0), Some(1.5));
+    assert_eq!(LineHeight::Multiplier(1.2).multiplier(16.0), Some(1.2));
+    assert_eq!(LetterSpacing::Em(0.5).to_px(20.0), 10.0);
+    assert_eq!(LetterSpacing::default().to_px(20.0), 0.0);
+}
@@ -0,0 +1,156 @@
+use cg::export::export_as_image::render_scene_to_png;
+use cg::io::io_json::{parse, IOCanvasFile, LoadError};
+use cg::node::schema::*;
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+
+/// A blue container holding a red square, a node outside any scene and two broken scenes.
+fn file() -> IOCanvasFile {
+    parse(
+        r#"{
+            "version": "0.0.1-beta.1+20250303",
+            "document": {
+                "bitmaps": {},
+                "properties": {},
+                "nodes": {
+                    "frame": {
+                        "type": "container",
+                        "id": "frame",
+                        "name": "frame",
+                        "left": 0.0,
+                        "top": 0.0,
+                        "width": 100,
+                        "height": 100,
+                        "fill": {
+                            "type": "solid",
+                            "color": { "r": 0, "g": 0, "b": 255, "a": 1.0 }
+                        },
+                        "children": ["dot"]
+                    },
+                    "dot": {
+                        "type": "rectangle",
+                        "id": "dot",
+                        "name": "dot",
+                        "left": 40.0,
+                        "top": 40.0,
+                        "width": 20.0,
+                        "height": 20.0,
+                        "fill": {
+                            "type": "solid",
+                            "color": { "r": 255, "g": 0, "b": 0, "a": 1.0 }
+                        }
+                    },
+                    "stray": {
+                        "type": "ellipse",
+                        "id": "stray",
+                        "name": "stray",
+                        "left": 0.0,
+                        "top": 0.0,
+                        "width": 10.0,
+                        "height": 10.0
+                    },
+                    "odd": {
+                        "type": "hologram",
+                        "id": "odd",
+                        "name": "odd"
+                    }
+                },
+                "scenes": {
+                    "main": {
+                        "id": "main",
+                        "name": "Main",
+                        "type": "scene",
+                        "children": ["frame"],
+                        "backgroundColor": { "r": 255, "g": 255, "b": 255, "a": 1.0 }
+                    },
+                    "broken": {
+                        "id": "broken",
+                        "name": "Broken",
+                        "type": "scene",
+                        "children": ["ghost"]
+                    },
+                    "mystery": {
+                        "id": "mystery",
+                        "name": "Mystery",
+                        "type": "scene",
+                        "children": ["odd"]
+                    }
+                },
+                "entry_scene_id": "main"
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn scene_holds_the_referenced_nodes() {
+    let scene = file().into_scene("main").unwrap();
+
+    assert_eq!(scene.id, "main");
+    assert_eq!(scene.name, "Main");
+    assert_eq!(scene.children, vec!["frame".to_string()]);
+    assert_eq!(scene.background_color, Some(Color(255, 255, 255, 255)));
+    assert_eq!(scene.transform, AffineTransform::identity());
+
+    let Some(Node::Container(frame)) = scene.nodes.get(&"frame".to_string()) else {
+        panic!("expected the frame container");
+    };
+    assert_eq!(frame.children, vec!["dot".to_string()]);
+    assert!(matches!(
+        scene.nodes.get(&"dot".to_string()),
+        Some(Node::Rectangle(_))
+    ));
+    // nodes of other scenes are left out
+    assert!(scene.nodes.get(&"stray".to_string()).is_none());
+    assert_eq!(scene.nodes.len(), 2);
+}
+
+#[test]
+fn scene_is_ready_to_render() {
+    let scene = file().into_scene("main").unwrap();
+    assert_eq!(scene.validate(), vec![]);
+
+    let png = render_scene_to_png(&scene, 100, 100, 1.0);
+    let image = Image::from_encoded(Data::new_copy(&png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().draw_image(&image, (0, 0), None);
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((50, 50)), skia_safe::Color::RED);
+    assert_eq!(pixels.get_color((10, 10)), skia_safe::Color::BLUE);
+}
+
+#[test]
+fn load_errors_are_reported() {
+    let file = file();
+    assert_eq!(
+        file.into_scene("nowhere").unwrap_err(),
+        LoadError::MissingScene("nowhere".to_string())
+    );
+    assert_eq!(
+        file.into_scene("broken").unwrap_err(),
+        LoadError::MissingNode {
+            parent: None,
+            id: "ghost".to_string(),
+        }
+    );
+    assert_eq!(
+        file.into_scene("mystery").unwrap_err(),
+        LoadError::UnknownNodeType("odd".to_string())
+    );
+}
+
+#[test]
+fn fixture_entry_scene_loads() {
+    let path = "../fixtures/local/document.json";
+    let Ok(data) = std::fs::read_to_string(path) else {
+        eprintln!("test resource not found: {}", path);
+        return;
+    };
+    let file = parse(&data).expect("failed to parse JSON");
+    let id = file.document.entry_scene_id().expect("a scene").to_string();
+    let scene = file.into_scene(&id).expect("loadable entry scene");
+    for child in &scene.children {
+        assert!(scene.nodes.get(child).is_some(), "missing node {child}");
+    }
+}
@@ -0,0 +1,121 @@
+use cg::cache::geometry::GeometryCache;
+use cg::cache::scene::SceneCache;
+use cg::hittest::HitTester;
+use cg::io::io_json::IORectangleNode;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::layer::LayerList;
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use serde_json::json;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A red square over a white background, covering the whole 10x10 scene.
+fn rect_scene(active: bool, locked: bool) -> (Scene, NodeId) {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.base.active = active;
+    rect.base.locked = locked;
+    rect.size = Size {
+        width: 10.0,
+        height: 10.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(255, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::Rectangle(rect));
+    let scene = Scene {
+        children: vec![id.clone()],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    (scene, id)
+}
+
+fn render(scene: &Scene, id: &NodeId) -> skia_safe::Color {
+    let cache = GeometryCache::from_scene(scene);
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node_recursively(scene.nodes.get(id).unwrap(), &scene.nodes, &cache);
+    surface.peek_pixels().unwrap().get_color((5, 5))
+}
+
+fn layer_count(scene: &Scene) -> usize {
+    let cache = GeometryCache::from_scene(scene);
+    LayerList::from_scene(scene, &cache, &FontRepository::new()).len()
+}
+
+#[test]
+fn locked_node_is_drawn_but_not_hit() {
+    let (scene, id) = rect_scene(true, true);
+    assert_eq!(render(&scene, &id), skia_safe::Color::RED);
+    assert_eq!(layer_count(&scene), 1);
+    assert_eq!(scene.node_at(Point { x: 5.0, y: 5.0 }), None);
+
+    let (scene, id) = rect_scene(true, false);
+    assert_eq!(scene.node_at(Point { x: 5.0, y: 5.0 }), Some(id));
+}
+
+#[test]
+fn inactive_node_is_neither_drawn_nor_hit() {
+    let (scene, id) = rect_scene(false, false);
+    assert_eq!(render(&scene, &id), skia_safe::Color::WHITE);
+    assert_eq!(layer_count(&scene), 0);
+    assert_eq!(scene.node_at(Point { x: 5.0, y: 5.0 }), None);
+}
+
+#[test]
+fn json_flags_are_kept() {
+    let node: IORectangleNode = serde_json::from_value(json!({
+        "id": "rect",
+        "name": "rect",
+        "active": false,
+        "locked": true,
+        "left": 0.0,
+        "top": 0.0,
+        "width": 10.0,
+        "height": 10.0,
+    }))
+    .unwrap();
+    let node: Node = node.into();
+    assert!(!node.active());
+    assert!(node.locked());
+}
+
+#[test]
+fn hit_tester_skips_locked_subtrees() {
+    let (mut scene, rect_id) = rect_scene(true, false);
+    let mut group = NodeFactory::new().create_group_node();
+    group.base.locked = true;
+    group.children = vec![rect_id.clone()];
+    let group_id = scene.nodes.insert(Node::Group(group));
+    scene.children = vec![group_id.clone()];
+
+    let hit = |scene: &Scene| {
+        let mut cache = SceneCache::new();
+        cache.update_geometry(scene);
+        cache.update_layers(scene, &FontRepository::new());
+        let tester = HitTester::new(&cache);
+        assert_eq!(tester.hits([5.0, 5.0]), tester.hits_fast([5.0, 5.0]));
+        (
+            tester.hit_first([5.0, 5.0]),
+            tester.hit_first_fast([5.0, 5.0]),
+        )
+    };
+    assert_eq!(hit(&scene), (None, None));
+
+    if let Some(Node::Group(group)) = scene.nodes.get_mut(&group_id) {
+        group.base.locked = false;
+    }
+    assert_eq!(hit(&scene), (Some(rect_id.clone()), Some(rect_id)));
+}
@@ -0,0 +1,83 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+
+fn scene(children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+fn rect(nf: &NodeFactory, x: f32, y: f32, rotation: f32, width: f32, height: f32) -> Node {
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = AffineTransform::new(x, y, rotation);
+    rect.size = Size { width, height };
+    Node::Rectangle(rect)
+}
+
+#[test]
+fn returns_topmost_of_overlapping_rectangles() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let bottom = repo.insert(rect(&nf, 0.0, 0.0, 0.0, 100.0, 100.0));
+    let top = repo.insert(rect(&nf, 50.0, 50.0, 0.0, 100.0, 100.0));
+    let scene = scene(vec![bottom.clone(), top.clone()], repo);
+
+    assert_eq!(scene.node_at(Point { x: 75.0, y: 75.0 }), Some(top.clone()));
+    assert_eq!(scene.node_at(Point { x: 25.0, y: 25.0 }), Some(bottom));
+    assert_eq!(scene.node_at(Point { x: 125.0, y: 125.0 }), Some(top));
+    assert_eq!(scene.node_at(Point { x: 200.0, y: 200.0 }), None);
+}
+
+#[test]
+fn hits_rotated_node_in_local_space() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    // rotated by 90° around its origin, covering x 80..100, y 100..200
+    let id = repo.insert(rect(
+        &nf,
+        100.0,
+        100.0,
+        std::f32::consts::FRAC_PI_2,
+        100.0,
+        20.0,
+    ));
+    let scene = scene(vec![id.clone()], repo);
+
+    assert_eq!(scene.node_at(Point { x: 90.0, y: 150.0 }), Some(id));
+    assert_eq!(scene.node_at(Point { x: 150.0, y: 110.0 }), None);
+}
+
+#[test]
+fn clipping_container_hides_children_outside() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let child = repo.insert(rect(&nf, 40.0, 40.0, 0.0, 50.0, 50.0));
+
+    let mut container = nf.create_container_node();
+    container.size = Size {
+        width: 50.0,
+        height: 50.0,
+    };
+    container.corner_radius = RectangularCornerRadius::zero();
+    container.children = vec![child.clone()];
+    container.clip = true;
+    let container_id = repo.insert(Node::Container(container));
+    let mut scene = scene(vec![container_id.clone()], repo);
+
+    assert_eq!(
+        scene.node_at(Point { x: 45.0, y: 45.0 }),
+        Some(child.clone())
+    );
+    assert_eq!(
+        scene.node_at(Point { x: 20.0, y: 20.0 }),
+        Some(container_id.clone())
+    );
+    assert_eq!(scene.node_at(Point { x: 80.0, y: 80.0 }), None);
+
+    if let Some(Node::Container(n)) = scene.nodes.get_mut(&container_id) {
+        n.clip = false;
+    }
+    assert_eq!(scene.node_at(Point { x: 80.0, y: 80.0 }), Some(child));
+}
@@ -0,0 +1,103 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+
+fn approx_eq(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+fn scene_with(children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// A rectangle at (5, 5) inside a group at (10, 10) rotated by 90 degrees.
+fn nested(rect: Node) -> (Scene, NodeId) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let rect_id = repo.insert(rect);
+
+    let mut group = nf.create_group_node();
+    group.transform = AffineTransform::new(10.0, 10.0, std::f32::consts::FRAC_PI_2);
+    group.children = vec![rect_id.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    (scene_with(vec![group_id], repo), rect_id)
+}
+
+fn rectangle() -> Node {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(5.0, 5.0, 0.0);
+    Node::Rectangle(rect)
+}
+
+#[test]
+fn translated_moves_world_position_by_delta() {
+    let original = rectangle();
+    let moved = original.translated(3.0, 4.0);
+
+    let (scene, id) = nested(original.clone());
+    let before = GeometryCache::from_scene(&scene)
+        .get_world_transform(&id)
+        .unwrap();
+    let (scene, id) = nested(moved);
+    let after = GeometryCache::from_scene(&scene)
+        .get_world_transform(&id)
+        .unwrap();
+
+    // the delta is in the parent's space, which is rotated by 90 degrees
+    assert!(approx_eq(after.x() - before.x(), -4.0));
+    assert!(approx_eq(after.y() - before.y(), 3.0));
+
+    // the original is left untouched
+    assert_eq!(original.transform(), AffineTransform::new(5.0, 5.0, 0.0));
+}
+
+#[test]
+fn translated_at_root_matches_delta() {
+    let original = rectangle();
+    let moved = original.translated(-2.0, 7.5);
+
+    assert!(approx_eq(moved.transform().x(), 3.0));
+    assert!(approx_eq(moved.transform().y(), 12.5));
+    assert_eq!(original.transform().x(), 5.0);
+}
+
+#[test]
+fn rotated_keeps_origin_fixed() {
+    let rect = rectangle();
+    // rotating around the node's own position keeps it in place
+    let rotated = rect.rotated(90.0, Point { x: 5.0, y: 5.0 });
+
+    let t = rotated.transform();
+    assert!(approx_eq(t.x(), 5.0));
+    assert!(approx_eq(t.y(), 5.0));
+    assert!(approx_eq(t.rotation(), std::f32::consts::FRAC_PI_2));
+
+    // rotating around the parent origin swings the position around it
+    let swung = rect.rotated(90.0, Point { x: 0.0, y: 0.0 });
+    assert!(approx_eq(swung.transform().x(), -5.0));
+    assert!(approx_eq(swung.transform().y(), 5.0));
+}
+
+#[test]
+fn scaled_around_origin() {
+    let rect = rectangle();
+    let scaled = rect.scaled(2.0, 3.0, Point { x: 1.0, y: 1.0 });
+
+    let t = scaled.transform();
+    assert!(approx_eq(t.x(), 9.0));
+    assert!(approx_eq(t.y(), 13.0));
+    assert!(approx_eq(t.get_scale_x(), 2.0));
+    assert!(approx_eq(t.get_scale_y(), 3.0));
+}
+
+#[test]
+fn transform_mut_edits_in_place() {
+    let mut rect = rectangle();
+    rect.transform_mut().translate(1.0, 1.0);
+    assert_eq!(rect.transform(), AffineTransform::new(6.0, 6.0, 0.0));
+}
@@ -0,0 +1,145 @@
+use cg::io::io_json::Fill;
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::box_fit::BoxFit;
+use math2::transform::AffineTransform;
+use serde_json::json;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const SIZE: i32 = 20;
+
+fn white(opacity: f32) -> Paint {
+    Paint::Solid(SolidPaint {
+        color: Color(255, 255, 255, 255),
+        opacity,
+    })
+}
+
+/// Draws `node` into a transparent 20x20 surface and returns the alpha at `(x, y)`.
+fn alpha_at(node: LeafNode, images: ImageRepository, (x, y): (i32, i32)) -> u8 {
+    let mut surface = surfaces::raster_n32_premul((SIZE, SIZE)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(images)),
+    );
+    painter.draw_node(&node);
+    surface.peek_pixels().unwrap().get_color((x, y)).a()
+}
+
+fn rectangle(fill: Paint, opacity: f32) -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = Size {
+        width: SIZE as f32,
+        height: SIZE as f32,
+    };
+    rect.fill = fill;
+    rect.stroke_width = 0.0;
+    rect.opacity = opacity;
+    rect
+}
+
+fn assert_quarter(alpha: u8) {
+    // 255 * 0.5 * 0.5
+    assert!((62..=66).contains(&alpha), "alpha {alpha}");
+}
+
+#[test]
+fn node_and_fill_opacity_multiply() {
+    let rect = rectangle(white(0.5), 0.5);
+    assert_quarter(alpha_at(
+        LeafNode::Rectangle(rect),
+        ImageRepository::new(),
+        (10, 10),
+    ));
+}
+
+#[test]
+fn node_and_stroke_opacity_multiply() {
+    let mut rect = rectangle(
+        Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 0),
+            opacity: 1.0,
+        }),
+        0.5,
+    );
+    rect.stroke = white(0.5);
+    rect.stroke_width = 4.0;
+    rect.stroke_align = StrokeAlign::Inside;
+    assert_quarter(alpha_at(
+        LeafNode::Rectangle(rect),
+        ImageRepository::new(),
+        (1, 10),
+    ));
+}
+
+#[test]
+fn line_opacity_is_applied_once() {
+    let mut line = NodeFactory::new().create_line_node();
+    line.transform = AffineTransform::from_acebdf(1.0, 0.0, 0.0, 0.0, 1.0, 10.0);
+    line.size = Size {
+        width: SIZE as f32,
+        height: 0.0,
+    };
+    line.stroke = white(0.5);
+    line.stroke_width = 4.0;
+    line.opacity = 0.5;
+    assert_quarter(alpha_at(
+        LeafNode::Line(line),
+        ImageRepository::new(),
+        (10, 10),
+    ));
+}
+
+#[test]
+fn node_and_image_fill_opacity_multiply() {
+    let mut surface = surfaces::raster_n32_premul((4, 4)).unwrap();
+    surface.canvas().clear(skia_safe::Color::WHITE);
+    let mut images = ImageRepository::new();
+    images.insert("white".to_string(), surface.image_snapshot());
+
+    let rect = rectangle(
+        Paint::Image(ImagePaint {
+            transform: AffineTransform::identity(),
+            _ref: "white".to_string(),
+            fit: BoxFit::Cover,
+            opacity: 0.5,
+            repeat: ImageRepeat::NoRepeat,
+        }),
+        0.5,
+    );
+    assert_quarter(alpha_at(LeafNode::Rectangle(rect), images, (10, 10)));
+}
+
+#[test]
+fn json_solid_fill_alpha_and_opacity_multiply() {
+    let fill: Fill = serde_json::from_value(json!({
+        "type": "solid",
+        "color": { "r": 255, "g": 255, "b": 255, "a": 0.5 },
+        "opacity": 0.5
+    }))
+    .unwrap();
+    let rect = rectangle(Some(fill).into(), 1.0);
+    assert_quarter(alpha_at(
+        LeafNode::Rectangle(rect),
+        ImageRepository::new(),
+        (10, 10),
+    ));
+
+    // without `opacity` the alpha of the color is kept as is
+    let fill: Fill = serde_json::from_value(json!({
+        "type": "solid",
+        "color": { "r": 255, "g": 255, "b": 255, "a": 0.5 }
+    }))
+    .unwrap();
+    let Paint::Solid(paint) = Some(fill).into() else {
+        panic!("expected a solid paint");
+    };
+    assert_eq!(paint.opacity, 1.0);
+    assert_eq!(paint.color.3, 128);
+}
@@ -0,0 +1,30 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::geometry::path_length;
+
+const SIZE: Size = Size {
+    width: 100.0,
+    height: 100.0,
+};
+
+#[test]
+fn rectangle_outline_length() {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.size = SIZE;
+    let length = path_length(&Node::Rectangle(rect));
+    assert!((length - 400.0).abs() < 1e-3, "{length}");
+}
+
+#[test]
+fn circle_outline_length() {
+    let mut ellipse = NodeFactory::new().create_ellipse_node();
+    ellipse.size = SIZE;
+    let length = path_length(&Node::Ellipse(ellipse));
+    let expected = std::f32::consts::TAU * 50.0;
+    assert!((length - expected).abs() < expected * 1e-3, "{length}");
+}
+
+#[test]
+fn nodes_without_outline_have_no_length() {
+    let group = NodeFactory::new().create_group_node();
+    assert_eq!(path_length(&Node::Group(group)), 0.0);
+}
@@ -0,0 +1,46 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use std::f32::consts::PI;
+
+const SIZE: Size = Size {
+    width: 100.0,
+    height: 100.0,
+};
+
+fn assert_near(point: Point, (x, y): (f32, f32)) {
+    assert!(
+        (point.x - x).abs() < 1e-3 && (point.y - y).abs() < 1e-3,
+        "({}, {}) != ({x}, {y})",
+        point.x,
+        point.y
+    );
+}
+
+#[test]
+fn star_points_up_by_default() {
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.size = SIZE;
+    assert_eq!(star.angle_offset, 0.0);
+    assert_near(star.to_polygon().points[0], (50.0, 0.0));
+}
+
+#[test]
+fn star_offset_by_pi_points_down() {
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.size = SIZE;
+    star.angle_offset = PI;
+    assert_near(star.to_polygon().points[0], (50.0, 100.0));
+}
+
+#[test]
+fn regular_polygon_offset_by_pi_points_down() {
+    let mut triangle = NodeFactory::new().create_regular_polygon_node();
+    triangle.size = SIZE;
+    triangle.point_count = 3;
+    assert_near(triangle.to_polygon().points[0], (50.0, 0.0));
+
+    triangle.angle_offset = PI;
+    let polygon = triangle.to_polygon();
+    assert_near(polygon.points[0], (50.0, 100.0));
+    // the winding is kept regardless of the rotation
+    assert_eq!(polygon.winding(), Some(Winding::CounterClockwise));
+}
@@ -0,0 +1,93 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use skia_safe::{surfaces, Paint as SkPaint, Path, PathFillType};
+
+/// A 100x100 five-pointed star with `corner_radius`.
+fn star(corner_radius: f32) -> PolygonNode {
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    star.point_count = 5;
+    star.inner_radius = 0.4;
+    star.corner_radius = corner_radius;
+    star.to_polygon()
+}
+
+/// Number of pixels covered by `path` filled with `fill_type`.
+fn coverage(path: &Path, fill_type: PathFillType) -> usize {
+    let mut path = path.clone();
+    path.set_fill_type(fill_type);
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    canvas.draw_path(&path, &SkPaint::default());
+    let pixels = surface.peek_pixels().unwrap();
+    (0..100)
+        .flat_map(|y| (0..100).map(move |x| (x, y)))
+        .filter(|p| pixels.get_color(*p).a() > 0)
+        .count()
+}
+
+/// The point `distance` away from `point`, away from the center of the star.
+fn outward(point: Point, distance: f32) -> (f32, f32) {
+    let (dx, dy) = (point.x - 50.0, point.y - 50.0);
+    let len = (dx * dx + dy * dy).sqrt();
+    (point.x + dx / len * distance, point.y + dy / len * distance)
+}
+
+#[test]
+fn clamped_corners_do_not_overlap() {
+    for radius in [5.0, 20.0, 1000.0] {
+        let path = star(radius).to_path();
+        let bounds = path.bounds();
+        assert!(
+            bounds.left >= 0.0 && bounds.top >= 0.0,
+            "{radius} {bounds:?}"
+        );
+        assert!(
+            bounds.right <= 100.0 && bounds.bottom <= 100.0,
+            "{radius} {bounds:?}"
+        );
+
+        // overlapping arcs would wind some areas twice
+        let nonzero = coverage(&path, PathFillType::Winding);
+        let evenodd = coverage(&path, PathFillType::EvenOdd);
+        assert!(nonzero > 0);
+        assert_eq!(nonzero, evenodd, "radius {radius}");
+    }
+}
+
+#[test]
+fn convex_points_are_rounded_inward() {
+    let sharp = star(0.0);
+    let tip = sharp.points[0];
+    let near_tip = outward(tip, -1.0);
+
+    assert!(sharp.to_path().contains(near_tip));
+    assert!(!star(20.0).to_path().contains(near_tip));
+}
+
+#[test]
+fn concave_points_are_rounded_outward() {
+    let sharp = star(0.0);
+    let inner = sharp.points[1];
+    let past_inner = outward(inner, 1.0);
+
+    assert!(!sharp.to_path().contains(past_inner));
+    // the arc fills the notch instead of cutting into the star
+    assert!(star(20.0).to_path().contains(past_inner));
+    assert!(star(20.0).to_path().contains(outward(inner, -1.0)));
+}
+
+#[test]
+fn larger_radius_is_clamped_to_half_the_edge() {
+    // past the clamp every corner is already as round as it can be
+    let clamped = star(1000.0).to_path();
+    let larger = star(5000.0).to_path();
+    assert_eq!(clamped.bounds(), larger.bounds());
+    assert_eq!(
+        coverage(&clamped, PathFillType::Winding),
+        coverage(&larger, PathFillType::Winding)
+    );
+}
@@ -0,0 +1,60 @@
+use cg::cache::polygon_path::PathCache;
+use cg::node::{factory::NodeFactory, schema::*};
+use std::rc::Rc;
+
+#[test]
+fn repeated_calls_reuse_the_path() {
+    let mut cache = PathCache::new();
+    let star = NodeFactory::new().create_regular_star_polygon_node();
+
+    let first = cache.get_or_create_regular_star(&star);
+    let second = cache.get_or_create_regular_star(&star);
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(first.bounds(), star.to_polygon().to_path().bounds());
+}
+
+#[test]
+fn changed_inputs_regenerate_the_path() {
+    let mut cache = PathCache::new();
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    let mut previous = cache.get_or_create_regular_star(&star);
+
+    let edits: [fn(&mut RegularStarPolygonNode); 5] = [
+        |n| n.size.width *= 2.0,
+        |n| n.point_count += 1,
+        |n| n.inner_radius /= 2.0,
+        |n| n.corner_radius += 4.0,
+        |n| n.angle_offset += 1.0,
+    ];
+    for edit in edits {
+        edit(&mut star);
+        let path = cache.get_or_create_regular_star(&star);
+        assert!(!Rc::ptr_eq(&previous, &path));
+        assert_eq!(path.bounds(), star.to_polygon().to_path().bounds());
+        previous = path;
+    }
+    // entries are replaced, not accumulated
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn regular_polygons_are_cached_per_node() {
+    let nf = NodeFactory::new();
+    let mut cache = PathCache::new();
+    let triangle = nf.create_regular_polygon_node();
+    let mut hexagon = nf.create_regular_polygon_node();
+    hexagon.point_count = 6;
+
+    let a = cache.get_or_create_regular_polygon(&triangle);
+    let b = cache.get_or_create_regular_polygon(&hexagon);
+    assert!(!Rc::ptr_eq(&a, &b));
+    assert_eq!(cache.len(), 2);
+    assert!(Rc::ptr_eq(
+        &a,
+        &cache.get_or_create_regular_polygon(&triangle)
+    ));
+
+    cache.invalidate();
+    assert!(cache.is_empty());
+}
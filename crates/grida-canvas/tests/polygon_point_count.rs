@@ -0,0 +1,75 @@
+use cg::node::{factory::NodeFactory, schema::*};
+
+fn assert_finite(polygon: &PolygonNode) {
+    for point in &polygon.points {
+        assert!(point.x.is_finite() && point.y.is_finite(), "{point:?}");
+    }
+    let bounds = polygon.to_path().bounds();
+    assert!(bounds.width() > 0.0 && bounds.height() > 0.0, "{bounds:?}");
+}
+
+#[test]
+fn factory_defaults_are_kept() {
+    let nf = NodeFactory::new();
+    assert_eq!(nf.create_regular_polygon_node().point_count, 3);
+    assert_eq!(nf.create_regular_star_polygon_node().point_count, 5);
+}
+
+#[test]
+fn clamp_point_count_raises_to_the_minimum() {
+    assert_eq!(clamp_point_count(0), MIN_POLYGON_POINT_COUNT);
+    assert_eq!(clamp_point_count(2), MIN_POLYGON_POINT_COUNT);
+    assert_eq!(clamp_point_count(3), 3);
+    assert_eq!(clamp_point_count(12), 12);
+}
+
+#[test]
+fn with_point_count_rejects_too_few_points() {
+    let nf = NodeFactory::new();
+    assert_eq!(
+        nf.create_regular_polygon_node()
+            .with_point_count(2)
+            .unwrap_err(),
+        InvalidPointCount(2)
+    );
+    assert_eq!(
+        nf.create_regular_star_polygon_node()
+            .with_point_count(0)
+            .unwrap_err(),
+        InvalidPointCount(0)
+    );
+
+    let polygon = nf
+        .create_regular_polygon_node()
+        .with_point_count(6)
+        .unwrap();
+    assert_eq!(polygon.point_count, 6);
+    assert_eq!(polygon.to_polygon().points.len(), 6);
+    let star = nf
+        .create_regular_star_polygon_node()
+        .with_point_count(3)
+        .unwrap();
+    assert_eq!(star.point_count, 3);
+}
+
+#[test]
+fn regular_polygon_with_too_few_points_is_drawn_as_a_triangle() {
+    for point_count in [0, 1, 2] {
+        let mut polygon = NodeFactory::new().create_regular_polygon_node();
+        polygon.point_count = point_count;
+        let polygon = polygon.to_polygon();
+        assert_eq!(polygon.points.len(), 3, "point_count {point_count}");
+        assert_finite(&polygon);
+    }
+}
+
+#[test]
+fn star_with_too_few_points_is_drawn_with_three_points() {
+    for point_count in [0, 1, 2] {
+        let mut star = NodeFactory::new().create_regular_star_polygon_node();
+        star.point_count = point_count;
+        let polygon = star.to_polygon();
+        assert_eq!(polygon.points.len(), 6, "point_count {point_count}");
+        assert_finite(&polygon);
+    }
+}
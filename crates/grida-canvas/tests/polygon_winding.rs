@@ -0,0 +1,42 @@
+use cg::node::{factory::NodeFactory, schema::*};
+
+#[test]
+fn generated_star_is_counter_clockwise() {
+    let nf = NodeFactory::new();
+    let star = nf.create_regular_star_polygon_node().to_polygon();
+    assert!(star.signed_area() < 0.0);
+    assert_eq!(star.winding(), Some(Winding::CounterClockwise));
+}
+
+#[test]
+fn generated_regular_polygon_is_counter_clockwise() {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_regular_polygon_node();
+    for point_count in [3, 4, 5, 6] {
+        node.point_count = point_count;
+        assert_eq!(node.to_polygon().winding(), Some(Winding::CounterClockwise));
+    }
+}
+
+#[test]
+fn ensure_winding_reverses_points() {
+    let nf = NodeFactory::new();
+    let mut polygon = nf.create_polygon_node();
+    // clockwise on screen (y axis pointing down)
+    polygon.points = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 0.0 },
+        Point { x: 10.0, y: 10.0 },
+    ];
+    assert_eq!(polygon.signed_area(), 50.0);
+    assert_eq!(polygon.winding(), Some(Winding::Clockwise));
+
+    polygon.ensure_winding(Winding::CounterClockwise);
+    assert_eq!(polygon.winding(), Some(Winding::CounterClockwise));
+    assert_eq!((polygon.points[0].x, polygon.points[0].y), (0.0, 0.0));
+    assert_eq!((polygon.points[1].x, polygon.points[1].y), (10.0, 10.0));
+
+    // already matching, untouched
+    polygon.ensure_winding(Winding::CounterClockwise);
+    assert_eq!((polygon.points[1].x, polygon.points[1].y), (10.0, 10.0));
+}
@@ -0,0 +1,50 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+
+fn text(content: &str) -> Node {
+    let mut node = NodeFactory::new().create_text_span_node();
+    node.text = content.to_string();
+    Node::TextSpan(node)
+}
+
+#[test]
+fn detects_whitespace_only_text() {
+    for content in ["", "   ", "\t\n "] {
+        let Node::TextSpan(node) = text(content) else {
+            unreachable!()
+        };
+        assert!(node.is_effectively_empty(), "{content:?}");
+    }
+
+    let Node::TextSpan(node) = text("  hi ") else {
+        unreachable!()
+    };
+    assert!(!node.is_effectively_empty());
+}
+
+#[test]
+fn prunes_empty_text_nodes() {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let spaces = repo.insert(text("    "));
+    let hello = repo.insert(text("hello"));
+    let nested = repo.insert(text(""));
+
+    let mut group = nf.create_group_node();
+    group.children = vec![nested.clone(), hello.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    let mut scene = Scene {
+        children: vec![spaces.clone(), group_id.clone()],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+
+    assert_eq!(scene.prune_empty_text(), 2);
+    assert!(scene.nodes.get(&spaces).is_none());
+    assert!(scene.nodes.get(&nested).is_none());
+    assert_eq!(scene.children, vec![group_id.clone()]);
+    assert_eq!(scene.nodes.get(&group_id).unwrap().children(), &[hello]);
+
+    // nothing left to prune
+    assert_eq!(scene.prune_empty_text(), 0);
+}
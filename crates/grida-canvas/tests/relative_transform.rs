@@ -0,0 +1,88 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use math2::vector2;
+
+fn assert_close(a: [f32; 2], b: [f32; 2]) {
+    assert!(
+        (a[0] - b[0]).abs() < 1e-3 && (a[1] - b[1]).abs() < 1e-3,
+        "{a:?} != {b:?}"
+    );
+}
+
+/// group (100, 0) > rect_a (10, 10)
+/// container (0, 200, rotation) > rect_b (5, 5)
+fn build_scene(rotation: f32) -> (Scene, NodeId, NodeId) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect_a = nf.create_rectangle_node();
+    rect_a.transform = AffineTransform::new(10.0, 10.0, 0.0);
+    let rect_a_id = repo.insert(Node::Rectangle(rect_a));
+
+    let mut rect_b = nf.create_rectangle_node();
+    rect_b.transform = AffineTransform::new(5.0, 5.0, 0.0);
+    let rect_b_id = repo.insert(Node::Rectangle(rect_b));
+
+    let mut group = nf.create_group_node();
+    group.transform = AffineTransform::new(100.0, 0.0, 0.0);
+    group.children = vec![rect_a_id.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    let mut container = nf.create_container_node();
+    container.transform = AffineTransform::new(0.0, 200.0, rotation);
+    container.children = vec![rect_b_id.clone()];
+    let container_id = repo.insert(Node::Container(container));
+
+    let scene = Scene {
+        children: vec![group_id, container_id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+
+    (scene, rect_a_id, rect_b_id)
+}
+
+#[test]
+fn maps_point_between_nodes_under_different_parents() {
+    let (scene, a, b) = build_scene(0.0);
+
+    let t = scene
+        .relative_transform(&a, &b)
+        .expect("relative transform");
+    // rect_a's origin is (110, 10) in world space, rect_b's origin is (5, 205)
+    assert_close(vector2::transform([0.0, 0.0], &t), [105.0, -195.0]);
+
+    let back = scene
+        .relative_transform(&b, &a)
+        .expect("relative transform");
+    assert_close(vector2::transform([105.0, -195.0], &back), [0.0, 0.0]);
+}
+
+#[test]
+fn relative_transform_matches_world_transforms() {
+    let (scene, a, b) = build_scene(std::f32::consts::FRAC_PI_2);
+    let cache = GeometryCache::from_scene(&scene);
+
+    let t = scene
+        .relative_transform(&a, &b)
+        .expect("relative transform");
+    let world_a = cache.get_world_transform(&a).unwrap();
+    let world_b = cache.get_world_transform(&b).unwrap();
+
+    let point = [3.0, 4.0];
+    let in_b = vector2::transform(point, &t);
+    assert_close(
+        vector2::transform(in_b, &world_b),
+        vector2::transform(point, &world_a),
+    );
+}
+
+#[test]
+fn relative_transform_missing_node_is_none() {
+    let (scene, a, _) = build_scene(0.0);
+    assert!(scene
+        .relative_transform(&a, &"missing".to_string())
+        .is_none());
+    assert!(scene.relative_transform(&a, &a).is_some());
+}
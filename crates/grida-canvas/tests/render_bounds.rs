@@ -1,6 +1,5 @@
 use cg::cache::geometry::GeometryCache;
 use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
-use math2::transform::AffineTransform;
 
 #[test]
 fn stroke_affects_render_bounds() {
@@ -14,12 +13,9 @@ fn stroke_affects_render_bounds() {
     repo.insert(Node::Rectangle(rect));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![rect_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let cache = GeometryCache::from_scene(&scene);
@@ -36,25 +32,23 @@ fn gaussian_blur_expands_render_bounds() {
     let mut repo = NodeRepository::new();
 
     let mut rect = nf.create_rectangle_node();
-    rect.effect = Some(FilterEffect::GaussianBlur(FeGaussianBlur { radius: 5.0 }));
+    rect.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur { radius: 5.0 })];
     let rect_id = rect.base.id.clone();
     repo.insert(Node::Rectangle(rect));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![rect_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let cache = GeometryCache::from_scene(&scene);
     let bounds = cache.get_render_bounds(&rect_id).unwrap();
-    assert_eq!(bounds.x, -5.0);
-    assert_eq!(bounds.y, -5.0);
-    assert_eq!(bounds.width, 110.0);
-    assert_eq!(bounds.height, 110.0);
+    // three standard deviations on each side
+    assert_eq!(bounds.x, -15.0);
+    assert_eq!(bounds.y, -15.0);
+    assert_eq!(bounds.width, 130.0);
+    assert_eq!(bounds.height, 130.0);
 }
 
 #[test]
@@ -63,28 +57,63 @@ fn drop_shadow_expands_render_bounds() {
     let mut repo = NodeRepository::new();
 
     let mut rect = nf.create_rectangle_node();
-    rect.effect = Some(FilterEffect::DropShadow(FeDropShadow {
+    rect.effects = vec![FilterEffect::DropShadow(FeDropShadow {
         dx: 5.0,
         dy: 5.0,
         blur: 10.0,
+        spread: 0.0,
         color: Color(0, 0, 0, 255),
-    }));
+    })];
     let rect_id = rect.base.id.clone();
     repo.insert(Node::Rectangle(rect));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![rect_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let cache = GeometryCache::from_scene(&scene);
     let bounds = cache.get_render_bounds(&rect_id).unwrap();
-    assert_eq!(bounds.x, -5.0);
-    assert_eq!(bounds.y, -5.0);
-    assert_eq!(bounds.width, 120.0);
-    assert_eq!(bounds.height, 120.0);
+    // the shadow is offset by 5 and blurred by 3 * sigma = 30
+    assert_eq!(bounds.x, -25.0);
+    assert_eq!(bounds.y, -25.0);
+    assert_eq!(bounds.width, 160.0);
+    assert_eq!(bounds.height, 160.0);
+}
+
+#[test]
+fn drop_shadow_spread_expands_render_bounds() {
+    let shadow_bounds = |spread: f32| {
+        let nf = NodeFactory::new();
+        let mut repo = NodeRepository::new();
+
+        let mut rect = nf.create_rectangle_node();
+        rect.effects = vec![FilterEffect::DropShadow(FeDropShadow {
+            dx: 0.0,
+            dy: 0.0,
+            blur: 0.0,
+            spread,
+            color: Color(0, 0, 0, 255),
+        })];
+        let rect_id = rect.base.id.clone();
+        repo.insert(Node::Rectangle(rect));
+
+        let scene = Scene {
+            children: vec![rect_id.clone()],
+            nodes: repo,
+            ..Scene::new("scene", "test")
+        };
+
+        let cache = GeometryCache::from_scene(&scene);
+        cache.get_render_bounds(&rect_id).unwrap()
+    };
+
+    let without_spread = shadow_bounds(0.0);
+    let with_spread = shadow_bounds(10.0);
+    assert_eq!(without_spread.width, 100.0);
+    assert_eq!(with_spread.x, -10.0);
+    assert_eq!(with_spread.y, -10.0);
+    assert_eq!(with_spread.width, 120.0);
+    assert_eq!(with_spread.height, 120.0);
 }
@@ -0,0 +1,79 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::layer::{Layer, LayerList};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn square(color: Color, render_order: Option<i32>) -> Node {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.base.render_order = render_order;
+    rect.size = Size {
+        width: 10.0,
+        height: 10.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color,
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    Node::Rectangle(rect)
+}
+
+/// A group with two overlapping squares, the red one first in `children` but raised with
+/// a render order.
+fn scene() -> (Scene, NodeId, NodeId, NodeId) {
+    let mut repo = NodeRepository::new();
+    let red = repo.insert(square(Color(255, 0, 0, 255), Some(1)));
+    let blue = repo.insert(square(Color(0, 0, 255, 255), None));
+
+    let mut group = NodeFactory::new().create_group_node();
+    group.children = vec![red.clone(), blue.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    let scene = Scene {
+        children: vec![group_id.clone()],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+    (scene, group_id, red, blue)
+}
+
+#[test]
+fn siblings_sort_by_render_order() {
+    let (scene, group_id, red, blue) = scene();
+    let children = scene.nodes.get(&group_id).unwrap().children();
+    assert_eq!(scene.nodes.in_render_order(children), vec![&blue, &red]);
+}
+
+#[test]
+fn high_render_order_draws_last() {
+    let (scene, group_id, red, blue) = scene();
+    let cache = GeometryCache::from_scene(&scene);
+
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node_recursively(scene.nodes.get(&group_id).unwrap(), &scene.nodes, &cache);
+
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((5, 5)), skia_safe::Color::RED);
+
+    // the layer list used by the cached renderer follows the same order
+    let layers = LayerList::from_scene(&scene, &cache, &FontRepository::new());
+    let ids: Vec<&NodeId> = layers.layers.iter().map(|layer| layer.id()).collect();
+    assert_eq!(ids, vec![&blue, &red]);
+}
+
+#[test]
+fn raised_sibling_wins_the_hit() {
+    let (scene, _, red, _) = scene();
+    assert_eq!(scene.node_at(Point { x: 5.0, y: 5.0 }), Some(red));
+}
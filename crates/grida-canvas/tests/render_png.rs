@@ -0,0 +1,91 @@
+use cg::export::export_as_image::render_scene_to_png;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Data, Image};
+
+const RED: skia_safe::Color = skia_safe::Color::RED;
+
+fn scene(background_color: Option<Color>, transform: AffineTransform) -> Scene {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let mut rect = nf.create_rectangle_node();
+    rect.transform = AffineTransform::new(10.0, 10.0, 0.0);
+    rect.size = Size {
+        width: 80.0,
+        height: 80.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(255, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    let id = repo.insert(Node::Rectangle(rect));
+
+    Scene {
+        transform,
+        children: vec![id],
+        nodes: repo,
+        background_color,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// Decodes the png and returns the color at each of `points`.
+fn pixels(png: &[u8], points: &[(i32, i32)]) -> Vec<skia_safe::Color> {
+    let image = Image::from_encoded(Data::new_copy(png)).expect("valid png");
+    let mut surface = surfaces::raster_n32_premul((image.width(), image.height())).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.canvas().draw_image(&image, (0, 0), None);
+    let pixmap = surface.peek_pixels().unwrap();
+    points.iter().map(|p| pixmap.get_color(*p)).collect()
+}
+
+#[test]
+fn renders_red_rectangle() {
+    let png = render_scene_to_png(&scene(None, AffineTransform::identity()), 100, 100, 1.0);
+    assert!(!png.is_empty());
+
+    let colors = pixels(&png, &[(50, 50), (5, 5)]);
+    assert_eq!(colors[0], RED);
+    // transparent without a background color
+    assert_eq!(colors[1].a(), 0);
+}
+
+#[test]
+fn renders_background_color() {
+    let png = render_scene_to_png(
+        &scene(Some(Color(255, 255, 255, 255)), AffineTransform::identity()),
+        100,
+        100,
+        1.0,
+    );
+    let colors = pixels(&png, &[(50, 50), (5, 5)]);
+    assert_eq!(colors[0], RED);
+    assert_eq!(colors[1], skia_safe::Color::WHITE);
+}
+
+#[test]
+fn renders_at_scale() {
+    let png = render_scene_to_png(&scene(None, AffineTransform::identity()), 200, 200, 2.0);
+    // the rectangle covers 20..180 at 2x
+    let colors = pixels(&png, &[(100, 100), (25, 25), (15, 15), (190, 190)]);
+    assert_eq!(colors[0], RED);
+    assert_eq!(colors[1], RED);
+    assert_eq!(colors[2].a(), 0);
+    assert_eq!(colors[3].a(), 0);
+}
+
+#[test]
+fn honors_scene_transform() {
+    let png = render_scene_to_png(
+        &scene(None, AffineTransform::new(50.0, 0.0, 0.0)),
+        200,
+        100,
+        1.0,
+    );
+    // the rectangle is moved to 60..140
+    let colors = pixels(&png, &[(100, 50), (30, 50)]);
+    assert_eq!(colors[0], RED);
+    assert_eq!(colors[1].a(), 0);
+}
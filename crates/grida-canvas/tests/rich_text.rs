@@ -0,0 +1,66 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::runtime::repository::FontRepository;
+use cg::text::rich_text::build_rich_paragraph;
+
+fn style(weight: u32, italic: bool) -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: vec!["Geist".to_string()],
+        font_size: 16.0,
+        font_weight: FontWeight::new(weight),
+        italic,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
+    }
+}
+
+#[test]
+fn rich_text_paragraph_has_one_run_per_span() {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+
+    let nf = NodeFactory::new();
+    let mut node = nf.create_rich_text_node();
+    node.size = Size {
+        width: 1000.0,
+        height: 40.0,
+    };
+    node.spans = vec![
+        TextSpan {
+            text: "Bold ".to_string(),
+            text_style: style(700, false),
+            fill: None,
+        },
+        TextSpan {
+            text: "Italic ".to_string(),
+            text_style: style(400, true),
+            fill: None,
+        },
+        TextSpan {
+            text: "Red".to_string(),
+            text_style: style(400, false),
+            fill: Some(Paint::Solid(SolidPaint {
+                color: Color(255, 0, 0, 255),
+                opacity: 1.0,
+            })),
+        },
+    ];
+    assert_eq!(node.text(), "Bold Italic Red");
+
+    let paragraph = build_rich_paragraph(
+        &node.spans,
+        &node.fill,
+        &node.text_align,
+        &node.size,
+        &fonts,
+    );
+
+    let lines = paragraph.get_line_metrics();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].get_style_metrics_count(0..node.text().len()), 3);
+}
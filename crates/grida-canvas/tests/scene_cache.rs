@@ -1,6 +1,7 @@
 use cg::cache::scene::SceneCache;
 use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
 use cg::painter::layer::Layer;
+use cg::runtime::repository::FontRepository;
 use math2::rect::Rectangle;
 use math2::transform::AffineTransform;
 
@@ -28,17 +29,14 @@ fn layers_in_rect_include_partially_visible_nested() {
     repo.insert(Node::Container(container));
 
     let scene = Scene {
-        id: "scene".into(),
-        name: "test".into(),
-        transform: AffineTransform::identity(),
         children: vec![container_id.clone()],
         nodes: repo,
-        background_color: None,
+        ..Scene::new("scene", "test")
     };
 
     let mut cache = SceneCache::new();
     cache.update_geometry(&scene);
-    cache.update_layers(&scene);
+    cache.update_layers(&scene, &FontRepository::new());
 
     // Query area partially overlapping the rectangle only
 
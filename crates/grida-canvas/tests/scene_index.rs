@@ -0,0 +1,283 @@
+use cg::cache::scene::SceneCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::runtime::repository::FontRepository;
+use math2::rect::Rectangle;
+use math2::transform::AffineTransform;
+
+/// A small deterministic xorshift generator, so failures can be reproduced.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A float in `[min, max)`.
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (self.next() % 10_000) as f32 / 10_000.0 * (max - min)
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+fn random_transform(rng: &mut Rng, extent: f32) -> AffineTransform {
+    let rotation = if rng.chance(30) {
+        rng.range(-3.0, 3.0)
+    } else {
+        0.0
+    };
+    AffineTransform::new(rng.range(0.0, extent), rng.range(0.0, extent), rotation)
+}
+
+fn random_size(rng: &mut Rng) -> Size {
+    Size {
+        width: rng.range(5.0, 60.0),
+        height: rng.range(5.0, 60.0),
+    }
+}
+
+fn random_effects(rng: &mut Rng) -> Vec<FilterEffect> {
+    match rng.next() % 4 {
+        0 => vec![FilterEffect::GaussianBlur(FeGaussianBlur {
+            radius: rng.range(0.0, 8.0),
+        })],
+        1 => vec![FilterEffect::DropShadow(FeDropShadow {
+            dx: rng.range(-20.0, 20.0),
+            dy: rng.range(-20.0, 20.0),
+            blur: rng.range(0.0, 6.0),
+            spread: rng.range(-4.0, 4.0),
+            color: Color(0, 0, 0, 128),
+        })],
+        _ => vec![],
+    }
+}
+
+fn random_leaf(rng: &mut Rng, nf: &NodeFactory, extent: f32) -> Node {
+    let transform = random_transform(rng, extent);
+    let mut node = match rng.next() % 3 {
+        0 => {
+            let mut n = nf.create_rectangle_node();
+            n.transform = transform;
+            n.size = random_size(rng);
+            n.stroke_width = rng.range(0.0, 6.0);
+            n.stroke_align = StrokeAlign::Outside;
+            n.effects = random_effects(rng);
+            Node::Rectangle(n)
+        }
+        1 => {
+            let mut n = nf.create_ellipse_node();
+            n.transform = transform;
+            n.size = random_size(rng);
+            n.effects = random_effects(rng);
+            Node::Ellipse(n)
+        }
+        _ => {
+            let mut n = nf.create_regular_star_polygon_node();
+            n.transform = transform;
+            n.size = random_size(rng);
+            Node::RegularStarPolygon(n)
+        }
+    };
+    set_flags(rng, &mut node);
+    node
+}
+
+fn set_flags(rng: &mut Rng, node: &mut Node) {
+    let base = match node {
+        Node::Rectangle(n) => &mut n.base,
+        Node::Ellipse(n) => &mut n.base,
+        Node::RegularStarPolygon(n) => &mut n.base,
+        Node::Group(n) => &mut n.base,
+        Node::Container(n) => &mut n.base,
+        Node::BooleanOperation(n) => &mut n.base,
+        _ => unreachable!(),
+    };
+    base.active = !rng.chance(5);
+    base.locked = rng.chance(5);
+}
+
+/// Inserts a random subtree into `repo` and returns its root.
+fn random_node(rng: &mut Rng, nf: &NodeFactory, repo: &mut NodeRepository, depth: usize) -> NodeId {
+    let roll = rng.next() % 10;
+    if depth >= 3 || roll < 6 {
+        return repo.insert(random_leaf(rng, nf, 200.0));
+    }
+
+    let count = 1 + (rng.next() % 4) as usize;
+    let children: Vec<NodeId> = (0..count)
+        .map(|_| random_node(rng, nf, repo, depth + 1))
+        .collect();
+    let mut node = match roll {
+        6 | 7 => {
+            let mut n = nf.create_group_node();
+            n.transform = random_transform(rng, 300.0);
+            n.children = children;
+            Node::Group(n)
+        }
+        8 => {
+            let mut n = nf.create_container_node();
+            n.transform = random_transform(rng, 300.0);
+            n.size = Size {
+                width: rng.range(50.0, 250.0),
+                height: rng.range(50.0, 250.0),
+            };
+            n.clip = rng.chance(50);
+            n.effects = random_effects(rng);
+            n.children = children;
+            Node::Container(n)
+        }
+        _ => Node::BooleanOperation(BooleanPathOperationNode {
+            base: BaseNode {
+                id: format!("boolean-{}", rng.next()),
+                name: "boolean".to_string(),
+                active: true,
+                locked: false,
+                render_order: None,
+            },
+            transform: random_transform(rng, 300.0),
+            op: BooleanPathOperation::Union,
+            children,
+            fill: Paint::Solid(SolidPaint {
+                color: Color(0, 0, 0, 255),
+                opacity: 1.0,
+            }),
+            stroke: None,
+            stroke_width: 0.0,
+            stroke_align: StrokeAlign::Inside,
+            stroke_dash_array: None,
+            stroke_dash_offset: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            effects: vec![],
+        }),
+    };
+    set_flags(rng, &mut node);
+    repo.insert(node)
+}
+
+fn random_scene(seed: u64) -> Scene {
+    let mut rng = Rng(seed);
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+    let children = (0..120)
+        .map(|_| random_node(&mut rng, &nf, &mut repo, 0))
+        .collect();
+    Scene {
+        children,
+        nodes: repo,
+        ..Scene::new("scene", "random")
+    }
+}
+
+fn indexed(scene: &Scene) -> SceneCache {
+    let mut cache = SceneCache::new();
+    cache.update_geometry(scene);
+    cache.update_layers(scene, &FontRepository::new());
+    cache
+}
+
+/// Compares indexed and linear queries over random viewports and points.
+fn assert_matches_linear(scene: &Scene, cache: &SceneCache, rng: &mut Rng) {
+    for _ in 0..40 {
+        let viewport = Rectangle {
+            x: rng.range(-100.0, 600.0),
+            y: rng.range(-100.0, 600.0),
+            width: rng.range(1.0, 200.0),
+            height: rng.range(1.0, 200.0),
+        };
+        assert_eq!(
+            cache.visible_nodes(viewport),
+            scene.visible_nodes(viewport),
+            "{viewport:?}"
+        );
+    }
+    for _ in 0..200 {
+        let point = Point {
+            x: rng.range(-50.0, 600.0),
+            y: rng.range(-50.0, 600.0),
+        };
+        assert_eq!(
+            cache.node_at(scene, point),
+            scene.node_at(point),
+            "{point:?}"
+        );
+    }
+}
+
+#[test]
+fn index_queries_match_linear_queries() {
+    for seed in [1, 42, 7_777] {
+        let scene = random_scene(seed);
+        let cache = indexed(&scene);
+        assert!(cache.layer_index.size() > 0);
+
+        let mut rng = Rng(seed ^ 0x9e37_79b9);
+        assert_matches_linear(&scene, &cache, &mut rng);
+    }
+}
+
+#[test]
+fn updated_index_matches_linear_queries() {
+    let mut scene = random_scene(3);
+    let mut cache = indexed(&scene);
+    let mut rng = Rng(99);
+
+    let ids: Vec<NodeId> = scene.walk().map(|(id, _)| id).collect();
+    for _ in 0..30 {
+        let id = &ids[(rng.next() % ids.len() as u64) as usize];
+        let node = scene.nodes.get_mut(id).unwrap();
+        if rng.chance(20) {
+            let active = node.active();
+            set_active(node, !active);
+        } else {
+            *node.transform_mut() = random_transform(&mut rng, 400.0);
+        }
+        cache.update_geometry(&scene);
+        cache.update_node(&scene, id, &FontRepository::new());
+    }
+
+    assert_matches_linear(&scene, &cache, &mut rng);
+    // the updated index ends up like a freshly built one
+    let viewport = Rectangle {
+        x: 0.0,
+        y: 0.0,
+        width: 300.0,
+        height: 300.0,
+    };
+    assert_eq!(
+        cache.visible_nodes(viewport),
+        indexed(&scene).visible_nodes(viewport)
+    );
+}
+
+fn set_active(node: &mut Node, active: bool) {
+    match node {
+        Node::Rectangle(n) => n.base.active = active,
+        Node::Ellipse(n) => n.base.active = active,
+        Node::RegularStarPolygon(n) => n.base.active = active,
+        Node::Group(n) => n.base.active = active,
+        Node::Container(n) => n.base.active = active,
+        Node::BooleanOperation(n) => n.base.active = active,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn empty_scene_has_an_empty_index() {
+    let scene = Scene::new("scene", "empty");
+    let cache = indexed(&scene);
+    assert_eq!(cache.layer_index.size(), 0);
+    assert!(cache
+        .visible_nodes(Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+        })
+        .is_empty());
+}
@@ -0,0 +1,84 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+
+fn scene(children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+fn group(id: &str, children: &[&str]) -> Node {
+    let mut group = NodeFactory::new().create_group_node();
+    group.base.id = id.to_string();
+    group.children = children.iter().map(|id| id.to_string()).collect();
+    Node::Group(group)
+}
+
+#[test]
+fn well_formed_scene_has_no_errors() {
+    let mut repo = NodeRepository::new();
+    let rect = repo.insert(Node::Rectangle(NodeFactory::new().create_rectangle_node()));
+    repo.insert(group("group", &[rect.as_str()]));
+    assert_eq!(scene(vec!["group".into()], repo).validate(), vec![]);
+}
+
+#[test]
+fn missing_child_id_is_reported() {
+    let mut repo = NodeRepository::new();
+    repo.insert(group("group", &["missing"]));
+    let scene = scene(vec!["group".into(), "gone".into()], repo);
+
+    assert_eq!(
+        scene.validate(),
+        vec![
+            ValidationError::DanglingChild {
+                parent: Some("group".into()),
+                child: "missing".into(),
+            },
+            ValidationError::DanglingChild {
+                parent: None,
+                child: "gone".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn two_node_cycle_is_reported() {
+    let mut repo = NodeRepository::new();
+    repo.insert(group("a", &["b"]));
+    repo.insert(group("b", &["a"]));
+    let scene = scene(vec!["a".into()], repo);
+
+    assert_eq!(
+        scene.validate(),
+        vec![ValidationError::Cycle {
+            parent: "b".into(),
+            child: "a".into(),
+        }]
+    );
+    // walking the invalid scene still terminates
+    assert_eq!(scene.walk().count(), 2);
+}
+
+#[test]
+fn unreachable_nodes_are_reported() {
+    let mut repo = NodeRepository::new();
+    repo.insert(group("root", &[]));
+    repo.insert(group("orphan", &["orphan-child"]));
+    repo.insert(group("orphan-child", &[]));
+    let scene = scene(vec!["root".into()], repo);
+
+    assert_eq!(
+        scene.validate(),
+        vec![
+            ValidationError::Unreachable {
+                node: "orphan".into(),
+            },
+            ValidationError::Unreachable {
+                node: "orphan-child".into(),
+            },
+        ]
+    );
+}
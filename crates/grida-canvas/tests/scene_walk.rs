@@ -0,0 +1,80 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+
+/// container > group > [rect_a, rect_b]
+///           > rect_c
+/// rect_d
+fn build_scene() -> (Scene, Vec<NodeId>) {
+    let nf = NodeFactory::new();
+    let mut repo = NodeRepository::new();
+
+    let rect_a = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+    let rect_b = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+    let rect_c = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+    let rect_d = repo.insert(Node::Rectangle(nf.create_rectangle_node()));
+
+    let mut group = nf.create_group_node();
+    group.children = vec![rect_a.clone(), rect_b.clone()];
+    let group_id = repo.insert(Node::Group(group));
+
+    let mut container = nf.create_container_node();
+    container.children = vec![group_id.clone(), rect_c.clone()];
+    let container_id = repo.insert(Node::Container(container));
+
+    let scene = Scene {
+        children: vec![container_id.clone(), rect_d.clone()],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+
+    (
+        scene,
+        vec![container_id, group_id, rect_a, rect_b, rect_c, rect_d],
+    )
+}
+
+#[test]
+fn walks_nested_scene_in_paint_order() {
+    let (scene, ids) = build_scene();
+    let [container, group, rect_a, rect_b, rect_c, rect_d] = ids.try_into().unwrap();
+
+    let walked: Vec<(NodeId, usize)> = scene.walk().collect();
+    assert_eq!(
+        walked,
+        vec![
+            (container, 0),
+            (group, 1),
+            (rect_a, 2),
+            (rect_b, 2),
+            (rect_c, 1),
+            (rect_d, 0),
+        ]
+    );
+}
+
+#[test]
+fn walk_active_skips_inactive_subtrees() {
+    let (mut scene, ids) = build_scene();
+    let [container, group, _, _, rect_c, rect_d] = ids.try_into().unwrap();
+
+    if let Some(Node::Group(n)) = scene.nodes.get_mut(&group) {
+        n.base.active = false;
+    }
+
+    let walked: Vec<NodeId> = scene.walk_active().map(|(id, _)| id).collect();
+    assert_eq!(walked, vec![container, rect_c, rect_d]);
+    assert_eq!(scene.walk().count(), 6);
+}
+
+#[test]
+fn walk_is_cycle_safe() {
+    let (mut scene, ids) = build_scene();
+    let container = ids[0].clone();
+    let group = ids[1].clone();
+
+    // the group references its own ancestor
+    if let Some(Node::Group(n)) = scene.nodes.get_mut(&group) {
+        n.children.push(container);
+    }
+
+    assert_eq!(scene.walk().count(), 6);
+}
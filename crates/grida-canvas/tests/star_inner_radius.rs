@@ -0,0 +1,65 @@
+use cg::node::{factory::NodeFactory, schema::*};
+
+/// Distances of the generated points from the center, in point order.
+fn radii(inner_radius: f32) -> Vec<f32> {
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    star.inner_radius = inner_radius;
+    star.to_polygon()
+        .points
+        .iter()
+        .map(|p| ((p.x - 50.0).powi(2) + (p.y - 50.0).powi(2)).sqrt())
+        .collect()
+}
+
+#[test]
+fn clamps_into_unit_range() {
+    assert_eq!(
+        RegularStarPolygonNode::clamp_inner_radius(0.0),
+        RegularStarPolygonNode::MIN_INNER_RADIUS
+    );
+    assert_eq!(
+        RegularStarPolygonNode::clamp_inner_radius(-1.0),
+        RegularStarPolygonNode::MIN_INNER_RADIUS
+    );
+    assert_eq!(
+        RegularStarPolygonNode::clamp_inner_radius(f32::NAN),
+        RegularStarPolygonNode::MIN_INNER_RADIUS
+    );
+    assert_eq!(RegularStarPolygonNode::clamp_inner_radius(0.4), 0.4);
+    assert_eq!(RegularStarPolygonNode::clamp_inner_radius(2.0), 1.0);
+
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.set_inner_radius(2.0);
+    assert_eq!(star.inner_radius, 1.0);
+}
+
+#[test]
+fn zero_inner_radius_keeps_inner_points_off_center() {
+    let radii = radii(0.0);
+    let point_count = NodeFactory::new()
+        .create_regular_star_polygon_node()
+        .point_count;
+    let outer = radii.iter().filter(|r| (*r - 50.0).abs() < 1e-3).count();
+    let inner = radii.iter().filter(|r| **r > 0.0 && **r < 1.0).count();
+    assert_eq!((outer, inner), (point_count, point_count), "{radii:?}");
+
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.inner_radius = 0.0;
+    assert!(star.to_polygon().signed_area().abs() > 0.0);
+}
+
+#[test]
+fn inner_radius_above_one_draws_a_regular_polygon() {
+    // all points on the outer circle, none pushed outside the bounds
+    for r in radii(2.0) {
+        assert!((r - 50.0).abs() < 1e-3, "{r}");
+    }
+
+    let mut star = NodeFactory::new().create_regular_star_polygon_node();
+    star.inner_radius = 2.0;
+    assert_eq!(star.to_polygon().winding(), Some(Winding::CounterClockwise));
+}
@@ -0,0 +1,125 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::geometry::{stroke_geometry, StrokeOptions};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, Path, RRect, Rect};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A 100x100 square with 20px corner radius.
+fn rounded_square() -> Path {
+    let mut path = Path::new();
+    path.add_rrect(
+        RRect::new_rect_xy(Rect::from_wh(100.0, 100.0), 20.0, 20.0),
+        None,
+    );
+    path
+}
+
+#[test]
+fn inside_stroke_stays_within_the_shape() {
+    let source = rounded_square();
+    let stroke = stroke_geometry(&source, &StrokeOptions::new(10.0, StrokeAlign::Inside));
+
+    let bounds = stroke.bounds();
+    assert!(bounds.left >= 0.0 && bounds.top >= 0.0, "{bounds:?}");
+    assert!(
+        bounds.right <= 100.0 && bounds.bottom <= 100.0,
+        "{bounds:?}"
+    );
+    assert!(stroke.contains((5.0, 50.0)));
+    assert!(!stroke.contains((-5.0, 50.0)));
+    assert!(!stroke.contains((50.0, 50.0)));
+    // the corner of the bounding box is outside of the rounded shape
+    assert!(!stroke.contains((1.0, 1.0)));
+}
+
+#[test]
+fn outside_stroke_extends_past_the_shape() {
+    let source = rounded_square();
+    let stroke = stroke_geometry(&source, &StrokeOptions::new(10.0, StrokeAlign::Outside));
+
+    let bounds = stroke.bounds();
+    assert!((bounds.left + 10.0).abs() < 0.01, "{bounds:?}");
+    assert!((bounds.right - 110.0).abs() < 0.01, "{bounds:?}");
+    assert!(stroke.contains((-5.0, 50.0)));
+    assert!(!stroke.contains((5.0, 50.0)));
+    // the rounded corner of the shape is followed, its bounding box corner is covered
+    assert!(stroke.contains((1.0, 1.0)));
+}
+
+#[test]
+fn center_stroke_straddles_the_outline() {
+    let stroke = stroke_geometry(
+        &rounded_square(),
+        &StrokeOptions::new(10.0, StrokeAlign::Center),
+    );
+    assert!(stroke.contains((-4.0, 50.0)));
+    assert!(stroke.contains((4.0, 50.0)));
+    assert!(!stroke.contains((50.0, 50.0)));
+}
+
+#[test]
+fn open_path_strokes_are_centered() {
+    let mut line = Path::new();
+    line.move_to((0.0, 0.0));
+    line.line_to((100.0, 0.0));
+
+    let center = stroke_geometry(&line, &StrokeOptions::new(10.0, StrokeAlign::Center));
+    for align in [StrokeAlign::Inside, StrokeAlign::Outside] {
+        let stroke = stroke_geometry(&line, &StrokeOptions::new(10.0, align));
+        assert_eq!(stroke.bounds(), center.bounds(), "{align:?}");
+    }
+}
+
+/// Draws a 100x100 rounded rectangle at (20, 20) with a 10px red stroke and returns the
+/// alpha at each of `points`.
+fn render(align: StrokeAlign, points: &[(i32, i32)]) -> Vec<u8> {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(20.0, 20.0, 0.0);
+    rect.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    rect.corner_radius = RectangularCornerRadius::all(20.0);
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 0),
+        opacity: 1.0,
+    });
+    rect.stroke = Paint::Solid(SolidPaint {
+        color: Color(255, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 10.0;
+    rect.stroke_align = align;
+
+    let mut surface = surfaces::raster_n32_premul((140, 140)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect));
+
+    let pixels = surface.peek_pixels().unwrap();
+    points
+        .iter()
+        .map(|point| pixels.get_color(*point).a())
+        .collect()
+}
+
+#[test]
+fn rendered_inside_stroke_does_not_overflow() {
+    // outside the edge, inside the edge, outside the rounded corner
+    let alphas = render(StrokeAlign::Inside, &[(15, 70), (25, 70), (21, 21)]);
+    assert_eq!(alphas, vec![0, 255, 0]);
+}
+
+#[test]
+fn rendered_outside_stroke_extends_past_the_edge() {
+    let alphas = render(StrokeAlign::Outside, &[(15, 70), (25, 70), (70, 70)]);
+    assert_eq!(alphas, vec![255, 0, 0]);
+}
@@ -0,0 +1,112 @@
+use cg::cache::geometry::GeometryCache;
+use cg::io::io_json::IONode;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::geometry::{stroke_geometry, StrokeOptions};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::{surfaces, Path};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn import(json: &str) -> Node {
+    let node: IONode = serde_json::from_str(json).expect("valid node json");
+    node.into()
+}
+
+#[test]
+fn imports_round_capped_line() {
+    let node = import(
+        r#"{
+            "type": "line",
+            "id": "line",
+            "name": "line",
+            "left": 10,
+            "top": 20,
+            "width": 100,
+            "strokeWidth": 4,
+            "strokeCap": "round"
+        }"#,
+    );
+
+    let Node::Line(line) = node else {
+        panic!("expected a line node");
+    };
+    assert_eq!(line.stroke_cap, StrokeCap::Round);
+    assert_eq!(line.stroke_width, 4.0);
+    assert_eq!(line.size.width, 100.0);
+}
+
+#[test]
+fn imports_path_stroke_cap() {
+    for (value, cap) in [
+        ("\"butt\"", StrokeCap::Butt),
+        ("\"round\"", StrokeCap::Round),
+        ("\"square\"", StrokeCap::Square),
+        ("\"unknown\"", StrokeCap::Butt),
+        ("null", StrokeCap::Butt),
+    ] {
+        let node = import(&format!(
+            r#"{{
+                "type": "path",
+                "id": "path",
+                "name": "path",
+                "left": 0,
+                "top": 0,
+                "width": 10,
+                "height": 10,
+                "strokeCap": {value}
+            }}"#
+        ));
+        let Node::Path(path) = node else {
+            panic!("expected a path node");
+        };
+        assert_eq!(path.stroke_cap, cap, "{value}");
+    }
+}
+
+#[test]
+fn caps_extend_open_strokes() {
+    let mut line = Path::new();
+    line.move_to((0.0, 0.0)).line_to((100.0, 0.0));
+
+    let width = |cap| {
+        stroke_geometry(
+            &line,
+            &StrokeOptions {
+                cap,
+                ..StrokeOptions::new(10.0, StrokeAlign::Center)
+            },
+        )
+        .compute_tight_bounds()
+        .width()
+    };
+    assert!((width(StrokeCap::Butt) - 100.0).abs() < 0.01);
+    // round and square caps both reach half the stroke width past each end
+    assert!((width(StrokeCap::Round) - 110.0).abs() < 0.01);
+    assert!((width(StrokeCap::Square) - 110.0).abs() < 0.01);
+}
+
+#[test]
+fn painter_draws_path_stroke_caps() {
+    // alpha just past the start of a 10px wide open path
+    let alpha = |cap| {
+        let mut node = NodeFactory::new().create_path_node();
+        node.data = "M20 50 L80 50".to_string();
+        node.stroke_width = 10.0;
+        node.stroke_cap = cap;
+        let node = Node::Path(node);
+
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(skia_safe::Color::TRANSPARENT);
+        let painter = Painter::new(
+            canvas,
+            Rc::new(RefCell::new(FontRepository::new())),
+            Rc::new(RefCell::new(ImageRepository::new())),
+        );
+        painter.draw_node_recursively(&node, &NodeRepository::new(), &GeometryCache::new());
+        surface.peek_pixels().unwrap().get_color((17, 50)).a()
+    };
+    assert_eq!(alpha(StrokeCap::Butt), 0);
+    assert_eq!(alpha(StrokeCap::Square), 255);
+}
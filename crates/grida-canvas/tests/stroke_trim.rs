@@ -0,0 +1,82 @@
+use cg::node::factory::{NodeFactory, RectangleOptions};
+use cg::node::schema::*;
+use cg::painter::geometry::{path_length, trim_path};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::{surfaces, ContourMeasureIter, Path};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A 100x100 rectangle at (50, 50) with a red 4px center stroke and no fill.
+fn rect(trim: Option<(f32, f32)>) -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node_with(RectangleOptions {
+        transform: Some(AffineTransform::new(50.0, 50.0, 0.0)),
+        fill: Some(Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 0),
+            opacity: 1.0,
+        })),
+        stroke: Some(Paint::Solid(SolidPaint {
+            color: Color(255, 0, 0, 255),
+            opacity: 1.0,
+        })),
+        stroke_width: Some(4.0),
+        ..Default::default()
+    });
+    rect.stroke_align = StrokeAlign::Center;
+    rect.trim = trim;
+    rect
+}
+
+/// Whether the stroke is drawn at the middle of the top, right, bottom and left edges.
+fn edges(trim: Option<(f32, f32)>) -> [bool; 4] {
+    let mut surface = surfaces::raster_n32_premul((200, 200)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::Rectangle(rect(trim)));
+    let pixels = surface.peek_pixels().unwrap();
+    [(100, 50), (150, 100), (100, 150), (50, 100)].map(|p| pixels.get_color(p).a() > 0)
+}
+
+fn length(path: &Path) -> f32 {
+    ContourMeasureIter::new(path, false, None)
+        .map(|contour| contour.length())
+        .sum()
+}
+
+#[test]
+fn half_trim_draws_half_the_perimeter() {
+    // the rectangle outline starts at the top left corner, clockwise
+    assert_eq!(edges(Some((0.0, 0.5))), [true, true, false, false]);
+    assert_eq!(edges(Some((0.5, 1.0))), [false, false, true, true]);
+    assert_eq!(edges(None), [true, true, true, true]);
+    assert_eq!(edges(Some((0.0, 0.0))), [false, false, false, false]);
+}
+
+#[test]
+fn trimmed_path_length() {
+    let node = Node::Rectangle(rect(None));
+    assert!((path_length(&node) - 400.0).abs() < 1e-3);
+
+    let mut outline = Path::new();
+    outline.add_rect(skia_safe::Rect::from_wh(100.0, 100.0), None);
+    let trimmed = trim_path(&outline, (0.0, 0.5));
+    assert!(
+        (length(&trimmed) - 200.0).abs() < 1e-2,
+        "{}",
+        length(&trimmed)
+    );
+
+    let trimmed = trim_path(&outline, (0.25, 2.0));
+    assert!(
+        (length(&trimmed) - 300.0).abs() < 1e-2,
+        "{}",
+        length(&trimmed)
+    );
+    assert!(trim_path(&outline, (0.6, 0.4)).is_empty());
+}
@@ -0,0 +1,111 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use cg::text::measure::measure_text;
+use cg::text::outline::text_span_outline;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+/// A single line of text in a 100x200 box.
+fn text(valign: TextAlignVertical) -> TextSpanNode {
+    let mut text = NodeFactory::new().create_text_span_node();
+    text.size = Size {
+        width: 100.0,
+        height: 200.0,
+    };
+    text.text = "Hi".to_string();
+    text.text_style.font_families = vec!["Geist".to_string()];
+    text.text_style.font_size = 20.0;
+    text.text_align_vertical = valign;
+    text.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 255),
+        opacity: 1.0,
+    });
+    text
+}
+
+/// The first and last rows with painted pixels.
+fn ink_rows(node: &TextSpanNode) -> (i32, i32) {
+    let mut surface = surfaces::raster_n32_premul((100, 200)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(fonts())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::TextSpan(node.clone()));
+
+    let pixels = surface.peek_pixels().unwrap();
+    let rows: Vec<i32> = (0..200)
+        .filter(|y| (0..100).any(|x| pixels.get_color((x, *y)).a() > 0))
+        .collect();
+    (*rows.first().unwrap(), *rows.last().unwrap())
+}
+
+#[test]
+fn offset_is_clamped_to_the_top() {
+    assert_eq!(TextAlignVertical::Top.offset(100.0, 20.0), 0.0);
+    assert_eq!(TextAlignVertical::Center.offset(100.0, 20.0), 40.0);
+    assert_eq!(TextAlignVertical::Bottom.offset(100.0, 20.0), 80.0);
+    // content taller than the box is not pushed above it
+    assert_eq!(TextAlignVertical::Center.offset(10.0, 20.0), 0.0);
+    assert_eq!(TextAlignVertical::Bottom.offset(10.0, 20.0), 0.0);
+}
+
+#[test]
+fn short_paragraph_is_centered_in_a_tall_box() {
+    let line_height = measure_text(
+        "Hi",
+        &text(TextAlignVertical::Top).text_style,
+        100.0,
+        &fonts(),
+    )
+    .height;
+    let offset = (200.0 - line_height) / 2.0;
+
+    let (top, bottom) = ink_rows(&text(TextAlignVertical::Top));
+    let (center_top, center_bottom) = ink_rows(&text(TextAlignVertical::Center));
+    assert!(top < 20, "{top}");
+    assert!(
+        ((center_top - top) as f32 - offset).abs() <= 1.0,
+        "{top} {center_top}"
+    );
+    assert!(
+        ((center_bottom - bottom) as f32 - offset).abs() <= 1.0,
+        "{bottom} {center_bottom}"
+    );
+
+    let (bottom_top, _) = ink_rows(&text(TextAlignVertical::Bottom));
+    assert!(
+        ((bottom_top - top) as f32 - offset * 2.0).abs() <= 1.0,
+        "{top} {bottom_top}"
+    );
+}
+
+#[test]
+fn outline_follows_the_vertical_alignment() {
+    let fonts = fonts();
+    let top = text_span_outline(&text(TextAlignVertical::Top), &fonts).bounds();
+    let center = text_span_outline(&text(TextAlignVertical::Center), &fonts).bounds();
+    let line_height = measure_text(
+        "Hi",
+        &text(TextAlignVertical::Top).text_style,
+        100.0,
+        &fonts,
+    )
+    .height;
+
+    assert!((center.top - top.top - (200.0 - line_height) / 2.0).abs() < 0.01);
+    assert_eq!(center.left, top.left);
+}
@@ -0,0 +1,76 @@
+use cg::io::io_json::IOTextNode;
+use cg::node::schema::*;
+use cg::runtime::repository::FontRepository;
+use cg::text::measure::measure_text;
+use serde_json::json;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn text_node(width: serde_json::Value, height: serde_json::Value) -> TextSpanNode {
+    let node: IOTextNode = serde_json::from_value(json!({
+        "id": "text",
+        "name": "text",
+        "left": 0.0,
+        "top": 0.0,
+        "width": width,
+        "height": height,
+        "text": "Hello auto sized world",
+        "fontFamily": "Geist",
+        "fontSize": 16.0,
+    }))
+    .unwrap();
+    node.into()
+}
+
+#[test]
+fn json_auto_sets_sizing_mode() {
+    assert_eq!(
+        text_node(json!("auto"), json!("auto")).sizing,
+        TextSizingMode::Auto
+    );
+    assert_eq!(
+        text_node(json!(120), json!("auto")).sizing,
+        TextSizingMode::AutoHeight
+    );
+    assert_eq!(
+        text_node(json!(120), json!(40)).sizing,
+        TextSizingMode::Fixed
+    );
+}
+
+#[test]
+fn auto_width_hugs_single_line() {
+    let fonts = fonts();
+    let node = text_node(json!("auto"), json!("auto"));
+    let metrics = measure_text(&node.text, &node.text_style, f32::INFINITY, &fonts);
+
+    let size = node.measure(&fonts);
+    assert!(size.width > 0.0);
+    assert!((size.width - metrics.width).abs() < 0.5, "{size:?}");
+    assert!((size.height - metrics.height).abs() < 0.5, "{size:?}");
+}
+
+#[test]
+fn auto_height_keeps_width_and_wraps() {
+    let fonts = fonts();
+    let single = text_node(json!("auto"), json!("auto")).measure(&fonts);
+    let node = text_node(json!(40), json!("auto"));
+
+    let size = node.measure(&fonts);
+    assert_eq!(size.width, 40.0);
+    assert!(size.height > single.height, "{size:?}");
+}
+
+#[test]
+fn fixed_size_is_returned_as_is() {
+    let node = text_node(json!(120), json!(40));
+    let size = node.measure(&fonts());
+    assert_eq!((size.width, size.height), (120.0, 40.0));
+}
@@ -0,0 +1,112 @@
+use cg::cache::paragraph::ParagraphCache;
+use cg::node::schema::*;
+use cg::runtime::repository::FontRepository;
+
+const WIDTH: f32 = 400.0;
+const ARABIC: &str = "مرحبا بالعالم";
+const HEBREW: &str = "שלום עולם";
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn style(direction: TextDirection) -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: vec!["Geist".to_string()],
+        font_size: 16.0,
+        font_weight: FontWeight::new(400),
+        italic: false,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction,
+    }
+}
+
+/// Lays out `text` in a wide box and returns the left edge and width of its single line.
+fn line(text: &str, align: TextAlign, direction: TextDirection) -> (f32, f32) {
+    let mut cache = ParagraphCache::new();
+    let paragraph = cache.get_or_create(
+        &"text".to_string(),
+        text,
+        &Size {
+            width: WIDTH,
+            height: 100.0,
+        },
+        &Paint::Solid(SolidPaint {
+            color: Color(0, 0, 0, 255),
+            opacity: 1.0,
+        }),
+        &align,
+        &TextAlignVertical::Top,
+        None,
+        TextOverflow::Clip,
+        &style(direction),
+        &fonts(),
+    );
+    let metrics = paragraph.get_line_metrics();
+    assert_eq!(metrics.len(), 1);
+    (metrics[0].left as f32, metrics[0].width as f32)
+}
+
+fn is_right_aligned((left, width): (f32, f32)) -> bool {
+    width > 0.0 && (left + width - WIDTH).abs() < 1.0
+}
+
+fn is_left_aligned((left, width): (f32, f32)) -> bool {
+    width > 0.0 && left.abs() < 1.0
+}
+
+#[test]
+fn resolves_start_and_end_by_direction() {
+    use TextAlign::*;
+    use TextDirection::*;
+
+    assert_eq!(Start.resolve(Ltr), Left);
+    assert_eq!(End.resolve(Ltr), Right);
+    assert_eq!(Start.resolve(Rtl), Right);
+    assert_eq!(End.resolve(Rtl), Left);
+    // physical alignments ignore the direction
+    assert_eq!(Left.resolve(Rtl), Left);
+    assert_eq!(Center.resolve(Rtl), Center);
+}
+
+#[test]
+fn rtl_start_aligns_right() {
+    for text in [ARABIC, HEBREW] {
+        let line = line(text, TextAlign::Start, TextDirection::Rtl);
+        assert!(is_right_aligned(line), "{text}: {line:?}");
+    }
+}
+
+#[test]
+fn rtl_end_aligns_left() {
+    for text in [ARABIC, HEBREW] {
+        let line = line(text, TextAlign::End, TextDirection::Rtl);
+        assert!(is_left_aligned(line), "{text}: {line:?}");
+    }
+}
+
+#[test]
+fn ltr_start_aligns_left() {
+    let line = line("Hello", TextAlign::Start, TextDirection::Ltr);
+    assert!(is_left_aligned(line), "{line:?}");
+
+    let line = line("Hello", TextAlign::End, TextDirection::Ltr);
+    assert!(is_right_aligned(line), "{line:?}");
+}
+
+#[test]
+fn direction_round_trips_through_serde() {
+    let align: TextAlign = serde_json::from_str("\"start\"").unwrap();
+    assert_eq!(align, TextAlign::Start);
+    let direction: TextDirection = serde_json::from_str("\"rtl\"").unwrap();
+    assert_eq!(direction, TextDirection::Rtl);
+    assert_eq!(TextDirection::default(), TextDirection::Ltr);
+}
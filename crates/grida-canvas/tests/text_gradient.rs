@@ -0,0 +1,127 @@
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::transform::AffineTransform;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const WIDTH: i32 = 400;
+const HEIGHT: i32 = 140;
+const FONT_SIZE: f32 = 48.0;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+/// Red to blue, left to right.
+fn gradient() -> Paint {
+    Paint::LinearGradient(LinearGradientPaint {
+        transform: AffineTransform::identity(),
+        stops: vec![
+            GradientStop {
+                offset: 0.0,
+                color: Color(255, 0, 0, 255),
+            },
+            GradientStop {
+                offset: 1.0,
+                color: Color(0, 0, 255, 255),
+            },
+        ],
+        opacity: 1.0,
+        spread: GradientSpread::Pad,
+    })
+}
+
+/// Draws `text` into a node much wider than the text.
+fn render(text: &str) -> skia_safe::Surface {
+    let mut node = NodeFactory::new().create_text_span_node();
+    node.size = Size {
+        width: WIDTH as f32,
+        height: HEIGHT as f32,
+    };
+    node.text = text.to_string();
+    node.text_style.font_families = vec!["Geist".to_string()];
+    node.text_style.font_size = FONT_SIZE;
+    node.text_style.font_weight = FontWeight::new(900);
+    node.fill = gradient();
+
+    let mut surface = surfaces::raster_n32_premul((WIDTH, HEIGHT)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(fonts())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::TextSpan(node));
+    surface
+}
+
+/// The fully covered glyph pixels on row `y`.
+fn covered(pixmap: &skia_safe::Pixmap, y: i32) -> Vec<skia_safe::Color> {
+    (0..WIDTH)
+        .map(|x| pixmap.get_color((x, y)))
+        .filter(|color| color.a() == 255)
+        .collect()
+}
+
+/// The middle row of each band of rows holding glyphs, top to bottom.
+fn line_rows(pixmap: &skia_safe::Pixmap) -> Vec<i32> {
+    let mut rows = Vec::new();
+    let mut band_start = None;
+    for y in 0..=HEIGHT {
+        let inked = y < HEIGHT && !covered(pixmap, y).is_empty();
+        match (inked, band_start) {
+            (true, None) => band_start = Some(y),
+            (false, Some(start)) => {
+                rows.push((start + y) / 2);
+                band_start = None;
+            }
+            _ => {}
+        }
+    }
+    rows
+}
+
+/// The leftmost and rightmost fully covered glyph pixels on row `y`.
+fn row_ends(pixmap: &skia_safe::Pixmap, y: i32) -> (skia_safe::Color, skia_safe::Color) {
+    let covered = covered(pixmap, y);
+    (*covered.first().unwrap(), *covered.last().unwrap())
+}
+
+#[test]
+fn gradient_spans_the_word_left_to_right() {
+    let mut surface = render("HHHH");
+    let pixmap = surface.peek_pixels().unwrap();
+    let rows = line_rows(&pixmap);
+    assert_eq!(rows.len(), 1);
+    let (first, last) = row_ends(&pixmap, rows[0]);
+
+    // the word is much narrower than the node, still the gradient runs across the word
+    assert!(first.r() > 200 && first.b() < 55, "{first:?}");
+    assert!(last.b() > 200 && last.r() < 55, "{last:?}");
+}
+
+#[test]
+fn gradient_spans_the_whole_block_across_lines() {
+    let mut surface = render("HHHH\nHH");
+    let pixmap = surface.peek_pixels().unwrap();
+    let rows = line_rows(&pixmap);
+    assert_eq!(rows.len(), 2);
+    let (first, last) = row_ends(&pixmap, rows[0]);
+    let (second_first, second_last) = row_ends(&pixmap, rows[1]);
+
+    assert!(last.b() > 200, "{last:?}");
+    // the shorter second line starts red and stops about halfway through the gradient
+    assert!(second_first.r() > 200, "{second_first:?}");
+    assert!(
+        second_last.b() > first.b() + 60 && second_last.b() + 60 < last.b(),
+        "{second_last:?}"
+    );
+}
@@ -0,0 +1,71 @@
+use cg::node::schema::*;
+use cg::runtime::repository::FontRepository;
+use cg::text::measure::{measure_text, measure_text_report, FontSubstitution};
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    // keep the report independent of the fonts installed on the machine
+    fonts.set_system_fonts(false);
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn style(families: &[&str]) -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: families.iter().map(|family| family.to_string()).collect(),
+        font_size: 16.0,
+        font_weight: FontWeight::new(400),
+        italic: false,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
+    }
+}
+
+#[test]
+fn available_font_reports_nothing() {
+    let fonts = fonts();
+    let report = measure_text_report("Hello world", &style(&["Geist"]), 1000.0, &fonts);
+    assert!(report.is_complete(), "{report:?}");
+    assert_eq!(
+        report.metrics,
+        measure_text("Hello world", &style(&["Geist"]), 1000.0, &fonts)
+    );
+}
+
+#[test]
+fn nonexistent_family_is_reported_as_substituted() {
+    let fonts = fonts();
+    let report = measure_text_report(
+        "Hello",
+        &style(&["Nonexistent Family", "Geist"]),
+        1000.0,
+        &fonts,
+    );
+    assert_eq!(
+        report.substitutions,
+        vec![FontSubstitution {
+            requested: "Nonexistent Family".to_string(),
+            used: Some("Geist".to_string()),
+        }]
+    );
+    assert!(report.missing_glyphs.is_empty());
+}
+
+#[test]
+fn characters_without_glyphs_are_reported_once() {
+    let fonts = fonts();
+    let report = measure_text_report(
+        "a\u{E000}b \u{E000}\u{10FFFD}",
+        &style(&["Geist"]),
+        1000.0,
+        &fonts,
+    );
+    assert!(report.substitutions.is_empty());
+    assert_eq!(report.missing_glyphs, vec!['\u{E000}', '\u{10FFFD}']);
+}
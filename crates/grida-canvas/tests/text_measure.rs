@@ -0,0 +1,108 @@
+use cg::cache::text_measure::TextMeasureCache;
+use cg::node::schema::*;
+use cg::runtime::repository::FontRepository;
+use cg::text::measure::measure_text;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn style(font_size: f32) -> TextStyle {
+    TextStyle {
+        text_decoration: TextDecoration::None,
+        font_families: vec!["Geist".to_string()],
+        font_size,
+        font_weight: FontWeight::new(400),
+        italic: false,
+        letter_spacing: LetterSpacing::default(),
+        line_height: LineHeight::Normal,
+        text_transform: TextTransform::None,
+        direction: TextDirection::Ltr,
+    }
+}
+
+#[test]
+fn cached_metrics_match_fresh_measurement() {
+    let fonts = fonts();
+    let mut cache = TextMeasureCache::new();
+    let text = "The quick brown fox jumps over the lazy dog";
+
+    for width in [50.0, 120.0, 1000.0] {
+        let fresh = measure_text(text, &style(16.0), width, &fonts);
+        let first = cache.measure(text, &style(16.0), width, &fonts);
+        let cached = cache.measure(text, &style(16.0), width, &fonts);
+        assert_eq!(first, fresh);
+        assert_eq!(cached, fresh);
+    }
+    assert_eq!(cache.misses(), 3);
+    assert_eq!(cache.hits(), 3);
+
+    // narrower widths wrap into more lines
+    let narrow = cache.measure(text, &style(16.0), 50.0, &fonts);
+    let wide = cache.measure(text, &style(16.0), 1000.0, &fonts);
+    assert!(narrow.line_count > wide.line_count);
+    assert!(narrow.height > wide.height);
+}
+
+#[test]
+fn repeated_strings_hit_the_cache() {
+    let fonts = fonts();
+    let mut cache = TextMeasureCache::new();
+
+    for _ in 0..10 {
+        cache.measure("Approved", &style(14.0), 100.0, &fonts);
+        cache.measure("Rejected", &style(14.0), 100.0, &fonts);
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(cache.hits(), 18);
+
+    // a different style is a different entry
+    let small = cache.measure("Approved", &style(14.0), 100.0, &fonts);
+    let large = cache.measure("Approved", &style(28.0), 100.0, &fonts);
+    assert_eq!(cache.len(), 3);
+    assert!(large.width > small.width);
+}
+
+#[test]
+fn font_changes_invalidate_the_cache() {
+    let mut fonts = fonts();
+    let mut cache = TextMeasureCache::new();
+    cache.measure("Approved", &style(14.0), 100.0, &fonts);
+    assert_eq!(cache.len(), 1);
+
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist Copy",
+    );
+    cache.measure("Rejected", &style(14.0), 100.0, &fonts);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn text_transform_is_applied_before_layout() {
+    let fonts = fonts();
+    let mut upper = style(16.0);
+    upper.text_transform = TextTransform::Uppercase;
+
+    // `ß` uppercases to two characters
+    let transformed = measure_text("straße", &upper, 1000.0, &fonts);
+    let expected = measure_text("STRASSE", &style(16.0), 1000.0, &fonts);
+    assert_eq!(transformed, expected);
+    assert_ne!(
+        transformed,
+        measure_text("straße", &style(16.0), 1000.0, &fonts)
+    );
+
+    let mut capitalize = style(16.0);
+    capitalize.text_transform = TextTransform::Capitalize;
+    assert_eq!(
+        measure_text("élan über", &capitalize, 1000.0, &fonts),
+        measure_text("Élan Über", &style(16.0), 1000.0, &fonts)
+    );
+}
@@ -0,0 +1,132 @@
+use cg::cache::paragraph::ParagraphCache;
+use cg::node::{factory::NodeFactory, schema::*};
+use cg::runtime::repository::FontRepository;
+use cg::text::outline::text_span_outline;
+
+const LONG: &str = "The quick brown fox jumps over the lazy dog, again and again and again";
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+/// A 120px wide text node that wraps `LONG` over several lines.
+fn text(height: f32, max_lines: Option<usize>, overflow: TextOverflow) -> TextSpanNode {
+    let mut text = NodeFactory::new().create_text_span_node();
+    text.size = Size {
+        width: 120.0,
+        height,
+    };
+    text.text = LONG.to_string();
+    text.text_style.font_families = vec!["Geist".to_string()];
+    text.text_style.font_size = 16.0;
+    text.max_lines = max_lines;
+    text.overflow = overflow;
+    text
+}
+
+fn line_count(node: &TextSpanNode, fonts: &FontRepository) -> usize {
+    let mut cache = ParagraphCache::new();
+    cache
+        .get_or_create(
+            &node.base.id,
+            &node.text,
+            &node.size,
+            &node.fill,
+            &node.text_align,
+            &node.text_align_vertical,
+            node.max_lines,
+            node.overflow,
+            &node.text_style,
+            fonts,
+        )
+        .line_number()
+}
+
+#[test]
+fn long_text_in_a_one_line_box_is_laid_out_on_one_line() {
+    let fonts = fonts();
+    assert!(line_count(&text(20.0, None, TextOverflow::Clip), &fonts) > 2);
+    assert_eq!(
+        line_count(&text(20.0, Some(1), TextOverflow::Ellipsis), &fonts),
+        1
+    );
+    assert_eq!(
+        line_count(&text(20.0, Some(1), TextOverflow::Clip), &fonts),
+        1
+    );
+    assert_eq!(
+        line_count(&text(1000.0, Some(2), TextOverflow::Ellipsis), &fonts),
+        2
+    );
+}
+
+#[test]
+fn ellipsis_without_a_limit_fits_the_box_height() {
+    let fonts = fonts();
+    assert_eq!(
+        line_count(&text(20.0, None, TextOverflow::Ellipsis), &fonts),
+        1
+    );
+    // the text fits, nothing is cut
+    assert_eq!(
+        line_count(&text(1000.0, None, TextOverflow::Ellipsis), &fonts),
+        line_count(&text(1000.0, None, TextOverflow::Clip), &fonts)
+    );
+}
+
+#[test]
+fn last_visible_line_ends_with_an_ellipsis() {
+    let fonts = fonts();
+    let clipped = text_span_outline(&text(20.0, Some(1), TextOverflow::Clip), &fonts);
+    let ellipsized = text_span_outline(&text(20.0, Some(1), TextOverflow::Ellipsis), &fonts);
+
+    // the ellipsis replaces the end of the line, the glyphs differ but stay within the box
+    assert_ne!(clipped.count_verbs(), ellipsized.count_verbs());
+    assert!(ellipsized.bounds().right <= 120.0);
+}
+
+#[test]
+fn auto_height_grows_up_to_max_lines() {
+    let fonts = fonts();
+    let mut one = text(0.0, Some(1), TextOverflow::Ellipsis);
+    one.sizing = TextSizingMode::AutoHeight;
+    let mut three = text(0.0, Some(3), TextOverflow::Ellipsis);
+    three.sizing = TextSizingMode::AutoHeight;
+    let mut unlimited = text(0.0, None, TextOverflow::Clip);
+    unlimited.sizing = TextSizingMode::AutoHeight;
+
+    let one = one.measure(&fonts).height;
+    let three = three.measure(&fonts).height;
+    let unlimited = unlimited.measure(&fonts).height;
+    assert!((three - one * 3.0).abs() < 1.0, "{one} {three}");
+    assert!(unlimited > three, "{three} {unlimited}");
+}
+
+#[test]
+fn json_text_overflow() {
+    let node: cg::io::io_json::IONode = serde_json::from_str(
+        r#"{
+            "type": "text",
+            "id": "text",
+            "name": "text",
+            "left": 0,
+            "top": 0,
+            "width": 120,
+            "height": 20,
+            "text": "hello",
+            "maxLines": 1,
+            "textOverflow": "ellipsis"
+        }"#,
+    )
+    .expect("valid node json");
+    let Node::TextSpan(text) = node.into() else {
+        panic!("expected a text node");
+    };
+    assert_eq!(text.max_lines, Some(1));
+    assert_eq!(text.overflow, TextOverflow::Ellipsis);
+}
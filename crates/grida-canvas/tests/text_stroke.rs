@@ -0,0 +1,135 @@
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::Painter;
+use cg::runtime::camera::Camera2D;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use cg::runtime::scene::{Backend, Renderer};
+use cg::text::outline::text_span_outline;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn fonts() -> FontRepository {
+    let mut fonts = FontRepository::new();
+    fonts.add(
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+        "Geist",
+    );
+    fonts
+}
+
+fn solid(r: u8, g: u8, b: u8, a: u8) -> Paint {
+    Paint::Solid(SolidPaint {
+        color: Color(r, g, b, a),
+        opacity: 1.0,
+    })
+}
+
+/// A large "I" with a blue fill and a 6px red stroke.
+fn text(fill_alpha: u8, align: StrokeAlign) -> TextSpanNode {
+    let mut text = NodeFactory::new().create_text_span_node();
+    text.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    text.text = "I".to_string();
+    text.text_style.font_families = vec!["Geist".to_string()];
+    text.text_style.font_size = 80.0;
+    text.text_align = TextAlign::Center;
+    text.fill = solid(0, 0, 255, fill_alpha);
+    text.stroke = Some(solid(255, 0, 0, 255));
+    text.stroke_width = Some(6.0);
+    text.stroke_align = align;
+    text
+}
+
+/// Pixel positions left of the glyph edge (inside an outside stroke) and at the center
+/// of the glyph.
+fn probes(node: &TextSpanNode) -> ((i32, i32), (i32, i32)) {
+    let bounds = text_span_outline(node, &fonts()).compute_tight_bounds();
+    let y = bounds.center_y() as i32;
+    ((bounds.left as i32 - 3, y), (bounds.center_x() as i32, y))
+}
+
+fn draw(node: &TextSpanNode) -> skia_safe::Surface {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(fonts())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node(&LeafNode::TextSpan(node.clone()));
+    surface
+}
+
+#[test]
+fn outside_stroke_paints_around_the_glyphs() {
+    let node = text(255, StrokeAlign::Outside);
+    let (outside, center) = probes(&node);
+    let mut surface = draw(&node);
+    let pixels = surface.peek_pixels().unwrap();
+
+    let stroke = pixels.get_color(outside);
+    assert_eq!(
+        (stroke.r(), stroke.b(), stroke.a()),
+        (255, 0, 255),
+        "{stroke:?}"
+    );
+    let fill = pixels.get_color(center);
+    assert_eq!((fill.r(), fill.b(), fill.a()), (0, 255, 255), "{fill:?}");
+}
+
+#[test]
+fn inside_stroke_stays_within_the_glyphs() {
+    let node = text(255, StrokeAlign::Inside);
+    let (outside, _) = probes(&node);
+    let mut surface = draw(&node);
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color(outside).a(), 0);
+}
+
+#[test]
+fn outside_stroke_does_not_cover_a_translucent_fill() {
+    let node = text(128, StrokeAlign::Outside);
+    let (_, center) = probes(&node);
+    let mut surface = draw(&node);
+    let pixels = surface.peek_pixels().unwrap();
+
+    let fill = pixels.get_color(center);
+    assert_eq!(fill.r(), 0, "{fill:?}");
+    assert!((126..=130).contains(&fill.a()), "{fill:?}");
+}
+
+#[test]
+fn layer_path_draws_the_stroke() {
+    let node = text(255, StrokeAlign::Outside);
+    let (outside, _) = probes(&node);
+
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::TextSpan(node));
+    let scene = Scene {
+        children: vec![id],
+        nodes: repo,
+        ..Scene::new("scene", "test")
+    };
+
+    let mut camera = Camera2D::new(Size {
+        width: 100.0,
+        height: 100.0,
+    });
+    camera.set_center(50.0, 50.0);
+    let mut renderer = Renderer::new(Backend::new_from_raster(100, 100), None, camera);
+    renderer.add_font(
+        "Geist",
+        include_bytes!("../fonts/Geist/Geist-VariableFont_wght.ttf"),
+    );
+    renderer.load_scene(scene);
+
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    renderer.render_to_canvas(surface.canvas(), 100.0, 100.0);
+    let pixels = surface.peek_pixels().unwrap();
+    let stroke = pixels.get_color(outside);
+    assert_eq!((stroke.r(), stroke.a()), (255, 255), "{stroke:?}");
+}
@@ -0,0 +1,84 @@
+use cg::node::{factory::NodeFactory, schema::*};
+
+fn assert_closed(path: &skia_safe::Path) {
+    assert!(!path.is_empty());
+    assert!(path.is_last_contour_closed());
+}
+
+#[test]
+fn rectangle_to_path() {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_rectangle_node();
+    assert_closed(&node.to_path());
+
+    // per-corner radius, only the top-left corner is rounded
+    node.size = Size {
+        width: 100.0,
+        height: 100.0,
+    };
+    node.corner_radius = RectangularCornerRadius {
+        tl: 40.0,
+        tr: 0.0,
+        bl: 0.0,
+        br: 0.0,
+    };
+    let path = node.to_path();
+    assert_closed(&path);
+    assert!(!path.contains((2.0, 2.0)));
+    assert!(path.contains((98.0, 2.0)));
+    assert!(path.contains((98.0, 98.0)));
+    assert!(path.contains((2.0, 98.0)));
+}
+
+#[test]
+fn ellipse_to_path() {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_ellipse_node();
+    assert_closed(&node.to_path());
+
+    node.end_angle = std::f32::consts::PI;
+    assert_closed(&node.to_path());
+}
+
+#[test]
+fn line_to_path_is_open() {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_line_node();
+    node.size = Size {
+        width: 100.0,
+        height: 0.0,
+    };
+    let path = node.to_path();
+    assert!(!path.is_empty());
+    assert!(!path.is_last_contour_closed());
+    assert_eq!(path.bounds().width(), 100.0);
+    assert_eq!(path.bounds().height(), 0.0);
+}
+
+#[test]
+fn polygons_to_path() {
+    let nf = NodeFactory::new();
+    let mut polygon = nf.create_polygon_node();
+    polygon.points = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 100.0, y: 0.0 },
+        Point { x: 50.0, y: 100.0 },
+    ];
+    assert_closed(&polygon.to_path());
+    polygon.corner_radius = 10.0;
+    assert_closed(&polygon.to_path());
+
+    assert_closed(&nf.create_regular_polygon_node().to_path());
+    assert_closed(&nf.create_regular_star_polygon_node().to_path());
+}
+
+#[test]
+fn path_node_to_path() {
+    let nf = NodeFactory::new();
+    let mut node = nf.create_path_node();
+    node.data = "M0 0 L10 0 L10 10 Z".to_string();
+    assert_closed(&node.to_path());
+
+    node.data = "not a path".to_string();
+    assert!(node.to_path().is_empty());
+}
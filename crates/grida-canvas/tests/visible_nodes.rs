@@ -0,0 +1,180 @@
+use cg::cache::geometry::GeometryCache;
+use cg::node::{factory::NodeFactory, repository::NodeRepository, schema::*};
+use cg::painter::layer::{Layer, LayerList};
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use math2::rect::Rectangle;
+use math2::transform::AffineTransform;
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn scene(children: Vec<NodeId>, nodes: NodeRepository) -> Scene {
+    Scene {
+        children,
+        nodes,
+        ..Scene::new("scene", "test")
+    }
+}
+
+/// A black 10x10 rectangle at `(x, y)`.
+fn square(x: f32, y: f32) -> RectangleNode {
+    let mut rect = NodeFactory::new().create_rectangle_node();
+    rect.transform = AffineTransform::new(x, y, 0.0);
+    rect.size = Size {
+        width: 10.0,
+        height: 10.0,
+    };
+    rect.fill = Paint::Solid(SolidPaint {
+        color: Color(0, 0, 0, 255),
+        opacity: 1.0,
+    });
+    rect.stroke_width = 0.0;
+    rect
+}
+
+/// A 10x10 grid of squares, 20px apart. Returns the scene and the ids by `[row][col]`.
+fn grid() -> (Scene, Vec<Vec<NodeId>>) {
+    let mut repo = NodeRepository::new();
+    let ids: Vec<Vec<NodeId>> = (0..10)
+        .map(|row| {
+            (0..10)
+                .map(|col| {
+                    let rect = square(col as f32 * 20.0, row as f32 * 20.0);
+                    repo.insert(Node::Rectangle(rect))
+                })
+                .collect()
+        })
+        .collect();
+    let children = ids.iter().flatten().cloned().collect();
+    (scene(children, repo), ids)
+}
+
+#[test]
+fn only_nodes_in_the_viewport_are_visible() {
+    let (scene, ids) = grid();
+    let viewport = Rectangle {
+        x: 25.0,
+        y: 25.0,
+        width: 30.0,
+        height: 30.0,
+    };
+
+    let visible = scene.visible_nodes(viewport);
+    let expected = vec![
+        ids[1][1].clone(),
+        ids[1][2].clone(),
+        ids[2][1].clone(),
+        ids[2][2].clone(),
+    ];
+    assert_eq!(visible, expected);
+}
+
+#[test]
+fn viewport_covering_everything_keeps_every_node() {
+    let (scene, _) = grid();
+    let viewport = Rectangle {
+        x: 0.0,
+        y: 0.0,
+        width: 200.0,
+        height: 200.0,
+    };
+    assert_eq!(scene.visible_nodes(viewport).len(), 100);
+}
+
+#[test]
+fn effects_extend_the_visible_area() {
+    let mut blurred = square(100.0, 0.0);
+    blurred.effects = vec![FilterEffect::GaussianBlur(FeGaussianBlur { radius: 5.0 })];
+    let mut repo = NodeRepository::new();
+    let id = repo.insert(Node::Rectangle(blurred));
+    let scene = scene(vec![id.clone()], repo);
+
+    // the blur reaches 15px to the left of the square
+    let viewport = Rectangle {
+        x: 80.0,
+        y: 0.0,
+        width: 10.0,
+        height: 10.0,
+    };
+    assert_eq!(scene.visible_nodes(viewport), vec![id]);
+}
+
+/// A 50x50 container at (500, 500) with a child placed back at the origin.
+fn container_scene(clip: bool) -> (Scene, NodeId, NodeId) {
+    let mut repo = NodeRepository::new();
+    let child = repo.insert(Node::Rectangle(square(-500.0, -500.0)));
+    let mut container = NodeFactory::new().create_container_node();
+    container.transform = AffineTransform::new(500.0, 500.0, 0.0);
+    container.size = Size {
+        width: 50.0,
+        height: 50.0,
+    };
+    container.clip = clip;
+    container.children = vec![child.clone()];
+    let container = repo.insert(Node::Container(container));
+    (scene(vec![container.clone()], repo), container, child)
+}
+
+#[test]
+fn off_screen_clipping_container_culls_its_subtree() {
+    let viewport = Rectangle {
+        x: 0.0,
+        y: 0.0,
+        width: 100.0,
+        height: 100.0,
+    };
+
+    let (clipped, _, _) = container_scene(true);
+    assert!(clipped.visible_nodes(viewport).is_empty());
+
+    // without the clip the child is still drawn, its container is listed before it
+    let (unclipped, container, child) = container_scene(false);
+    assert_eq!(unclipped.visible_nodes(viewport), vec![container, child]);
+}
+
+#[test]
+fn painter_skips_nodes_outside_the_viewport() {
+    let (scene, _) = grid();
+    let cache = GeometryCache::from_scene(&scene);
+    let mut surface = surfaces::raster_n32_premul((200, 200)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::TRANSPARENT);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_scene_in_viewport(
+        &scene,
+        &cache,
+        Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 50.0,
+            height: 50.0,
+        },
+    );
+
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((5, 5)).a(), 255);
+    assert_eq!(pixels.get_color((45, 45)).a(), 255);
+    assert_eq!(pixels.get_color((65, 5)).a(), 0);
+    assert_eq!(pixels.get_color((185, 185)).a(), 0);
+}
+
+#[test]
+fn layers_are_built_for_visible_nodes_only() {
+    let (scene, _) = grid();
+    let cache = GeometryCache::from_scene(&scene);
+    let viewport = Rectangle {
+        x: 25.0,
+        y: 25.0,
+        width: 30.0,
+        height: 30.0,
+    };
+
+    let list = LayerList::from_scene_in_viewport(&scene, &cache, &FontRepository::new(), viewport);
+    let ids: Vec<NodeId> = list.layers.iter().map(|layer| layer.id().clone()).collect();
+    assert_eq!(ids, scene.visible_nodes(viewport));
+}
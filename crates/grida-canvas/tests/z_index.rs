@@ -0,0 +1,94 @@
+use cg::cache::geometry::GeometryCache;
+use cg::io::io_json::{convert_nodes, sort_by_z_index, IONode};
+use cg::node::schema::*;
+use cg::painter::Painter;
+use cg::runtime::repository::{FontRepository, ImageRepository};
+use skia_safe::surfaces;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+fn square(id: &str, r: u8, b: u8, z_index: i32) -> String {
+    format!(
+        r#""{id}": {{
+            "type": "rectangle",
+            "id": "{id}",
+            "name": "{id}",
+            "zIndex": {z_index},
+            "left": 0.0,
+            "top": 0.0,
+            "width": 10.0,
+            "height": 10.0,
+            "fill": {{ "type": "solid", "color": {{ "r": {r}, "g": 0, "b": {b}, "a": 1.0 }} }}
+        }}"#
+    )
+}
+
+/// A container listing red before blue, with the z-indices putting red on top.
+fn nodes() -> HashMap<String, IONode> {
+    let json = format!(
+        r#"{{
+            "container": {{
+                "type": "container",
+                "id": "container",
+                "name": "container",
+                "left": 0.0,
+                "top": 0.0,
+                "width": 10,
+                "height": 10,
+                "children": ["red", "blue"]
+            }},
+            {},
+            {}
+        }}"#,
+        square("red", 255, 0, 2),
+        square("blue", 0, 255, 1),
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn sorts_siblings_by_z_index() {
+    let nodes = nodes();
+    let children = vec!["red".to_string(), "blue".to_string()];
+    assert_eq!(sort_by_z_index(&children, &nodes), vec!["blue", "red"]);
+
+    // ties keep the document order
+    let children = vec!["blue".to_string(), "container".to_string()];
+    assert_eq!(
+        sort_by_z_index(&children, &nodes),
+        vec!["container", "blue"]
+    );
+}
+
+#[test]
+fn higher_z_index_paints_on_top() {
+    let nodes = nodes();
+    let IONode::Container(raw) = &nodes["container"] else {
+        panic!("expected a container");
+    };
+    // the document keeps the raw order
+    assert_eq!(raw.children, vec!["red", "blue"]);
+
+    let scene = Scene {
+        children: vec!["container".to_string()],
+        nodes: convert_nodes(nodes),
+        ..Scene::new("scene", "test")
+    };
+    let container = scene.nodes.get(&"container".to_string()).unwrap();
+    assert_eq!(container.children(), ["blue", "red"]);
+
+    let cache = GeometryCache::from_scene(&scene);
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(skia_safe::Color::WHITE);
+    let painter = Painter::new(
+        canvas,
+        Rc::new(RefCell::new(FontRepository::new())),
+        Rc::new(RefCell::new(ImageRepository::new())),
+    );
+    painter.draw_node_recursively(container, &scene.nodes, &cache);
+
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((5, 5)), skia_safe::Color::RED);
+}
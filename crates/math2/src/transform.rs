@@ -1,3 +1,5 @@
+use crate::vector2::Vector2;
+
 /// Represents a 2D affine transformation matrix.
 ///
 /// The matrix is a 2x3 transformation:
@@ -144,4 +146,66 @@ impl AffineTransform {
     pub fn rotation(&self) -> f32 {
         self.matrix[1][0].atan2(self.matrix[0][0])
     }
+
+    /// Splits the transform into translation, rotation, skew and scale.
+    ///
+    /// The parts compose back as `translate * rotate * skew_x * scale`, see
+    /// [`TransformDecomposition::to_transform`]. Flipped transforms (negative determinant)
+    /// yield a negative y scale. A transform that collapses the x axis has no defined
+    /// rotation and decomposes to a rotation and skew of `0`.
+    pub fn decompose(&self) -> TransformDecomposition {
+        let [[a, c, tx], [b, d, ty]] = self.matrix;
+        let scale_x = (a * a + b * b).sqrt();
+        if scale_x == 0.0 {
+            return TransformDecomposition {
+                translation: [tx, ty],
+                rotation: 0.0,
+                scale: (0.0, (c * c + d * d).sqrt()),
+                skew: 0.0,
+            };
+        }
+
+        let det = a * d - b * c;
+        let scale_y = det / scale_x;
+        let skew = if scale_y == 0.0 {
+            0.0
+        } else {
+            ((a * c + b * d) / det).atan()
+        };
+        TransformDecomposition {
+            translation: [tx, ty],
+            rotation: b.atan2(a),
+            scale: (scale_x, scale_y),
+            skew,
+        }
+    }
+}
+
+/// The parts of an [`AffineTransform`], as returned by [`AffineTransform::decompose`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformDecomposition {
+    /// Translation `[tx, ty]`.
+    pub translation: Vector2,
+    /// Rotation in radians.
+    pub rotation: f32,
+    /// Scale along the local x and y axes. The y scale is negative for flipped transforms.
+    pub scale: (f32, f32),
+    /// Horizontal skew angle in radians.
+    pub skew: f32,
+}
+
+impl TransformDecomposition {
+    /// Composes the parts back into a transform.
+    pub fn to_transform(&self) -> AffineTransform {
+        let [tx, ty] = self.translation;
+        let (scale_x, scale_y) = self.scale;
+        AffineTransform::new(tx, ty, self.rotation).compose(&AffineTransform::from_acebdf(
+            scale_x,
+            self.skew.tan() * scale_y,
+            0.0,
+            0.0,
+            scale_y,
+            0.0,
+        ))
+    }
 }
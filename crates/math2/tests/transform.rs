@@ -1,4 +1,4 @@
-use math2::transform::AffineTransform;
+use math2::transform::{AffineTransform, TransformDecomposition};
 
 fn transforms_close(a: &AffineTransform, b: &AffineTransform) {
     for i in 0..2 {
@@ -23,3 +23,76 @@ fn invert_rotation() {
     let res = inv.compose(&t);
     transforms_close(&res, &AffineTransform::identity());
 }
+
+fn decompositions_close(a: &TransformDecomposition, b: &TransformDecomposition) {
+    let close = |x: f32, y: f32| (x - y).abs() < 1e-5;
+    assert!(
+        close(a.translation[0], b.translation[0])
+            && close(a.translation[1], b.translation[1])
+            && close(a.rotation, b.rotation)
+            && close(a.scale.0, b.scale.0)
+            && close(a.scale.1, b.scale.1)
+            && close(a.skew, b.skew),
+        "{a:?} != {b:?}"
+    );
+}
+
+#[test]
+fn decompose_recovers_translation_rotation_scale_and_skew() {
+    let parts = TransformDecomposition {
+        translation: [12.0, -7.5],
+        rotation: 0.6,
+        scale: (2.0, 0.5),
+        skew: 0.3,
+    };
+    let t = parts.to_transform();
+    decompositions_close(&t.decompose(), &parts);
+    transforms_close(&t.decompose().to_transform(), &t);
+}
+
+#[test]
+fn decompose_plain_rotation() {
+    let t = AffineTransform::new(3.0, 4.0, -1.2);
+    let parts = t.decompose();
+    decompositions_close(
+        &parts,
+        &TransformDecomposition {
+            translation: [3.0, 4.0],
+            rotation: -1.2,
+            scale: (1.0, 1.0),
+            skew: 0.0,
+        },
+    );
+}
+
+#[test]
+fn decompose_flip_as_negative_y_scale() {
+    // mirrored along the x axis
+    let t = AffineTransform::from_acebdf(1.0, 0.0, 0.0, 0.0, -2.0, 0.0);
+    let parts = t.decompose();
+    decompositions_close(
+        &parts,
+        &TransformDecomposition {
+            translation: [0.0, 0.0],
+            rotation: 0.0,
+            scale: (1.0, -2.0),
+            skew: 0.0,
+        },
+    );
+
+    // a horizontal flip is the same as a half turn with the y axis flipped
+    let t = AffineTransform::from_acebdf(-1.0, 0.0, 5.0, 0.0, 1.0, 0.0);
+    let parts = t.decompose();
+    assert!((parts.rotation.abs() - std::f32::consts::PI).abs() < 1e-5);
+    assert!((parts.scale.1 + 1.0).abs() < 1e-5);
+    transforms_close(&parts.to_transform(), &t);
+}
+
+#[test]
+fn decompose_degenerate_transform() {
+    let t = AffineTransform::from_acebdf(0.0, 0.0, 1.0, 0.0, 3.0, 2.0);
+    let parts = t.decompose();
+    assert_eq!(parts.scale, (0.0, 3.0));
+    assert_eq!(parts.rotation, 0.0);
+    assert_eq!(parts.translation, [1.0, 2.0]);
+}